@@ -0,0 +1,32 @@
+//! Setup helpers for tests of this contract and of crates built on it,
+//! enabled by the `testutils` feature.
+
+use crate::{BillPayments, BillPaymentsClient};
+use soroban_sdk::{Address, Env, String};
+
+/// Register the contract
+pub fn register(env: &Env) -> BillPaymentsClient<'_> {
+    let contract_id = env.register_contract(None, BillPayments);
+    BillPaymentsClient::new(env, &contract_id)
+}
+
+/// Create a one-off XLM bill of `amount` for `owner`, due in `due_in_days`.
+/// Auths must be mocked.
+pub fn bill_due_in(
+    env: &Env,
+    client: &BillPaymentsClient,
+    owner: &Address,
+    amount: i128,
+    due_in_days: u64,
+) -> u32 {
+    client.create_bill(
+        owner,
+        &String::from_str(env, "Bill"),
+        &amount,
+        &(env.ledger().timestamp() + due_in_days * 86400),
+        &false,
+        &0,
+        &None,
+        &String::from_str(env, "XLM"),
+    )
+}
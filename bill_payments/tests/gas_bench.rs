@@ -1,4 +1,4 @@
-use bill_payments::{BillPayments, BillPaymentsClient};
+use bill_payments::{BillPayments, BillPaymentsClient, CancellationReason};
 use soroban_sdk::testutils::{Address as AddressTrait, EnvTestConfig, Ledger, LedgerInfo};
 use soroban_sdk::{Address, Env, String};
 
@@ -52,13 +52,14 @@ fn bench_get_total_unpaid_worst_case() {
             &1_000_000u64,
             &false,
             &0u32,
+            &None,
             &String::from_str(&env, "XLM"),
         );
     }
 
     // Create gaps to simulate worst-case scan behavior in previous implementation.
     for id in (2u32..=100u32).step_by(2) {
-        client.cancel_bill(&owner, &id);
+        client.cancel_bill(&owner, &id, &CancellationReason::Mistake);
     }
 
     let expected_total = 50i128 * 100i128;
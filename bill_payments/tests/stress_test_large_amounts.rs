@@ -9,8 +9,8 @@
 //!
 //! ## Documented Limitations
 //! - Maximum safe bill amount: i128::MAX/2 (to allow for safe addition operations)
-//! - get_total_unpaid uses checked_add internally via += operator
-//! - No explicit caps are imposed by the contract, but overflow will panic
+//! - get_total_unpaid reads a running total kept with saturating arithmetic
+//! - No explicit caps are imposed by the contract; the running unpaid total saturates at i128::MAX
 
 use bill_payments::{BillPayments, BillPaymentsClient};
 use soroban_sdk::testutils::{Address as AddressTrait, Ledger, LedgerInfo};
@@ -49,11 +49,13 @@ fn test_create_bill_near_max_i128() {
         &1000000,
         &false,
         &0,
+        &None,
+        &String::from_str(&env, "XLM"),
     );
 
     let bill = client.get_bill(&bill_id).unwrap();
     assert_eq!(bill.amount, large_amount);
-    assert!(!bill.paid);
+    assert!(!bill.is_paid());
 }
 
 #[test]
@@ -74,13 +76,15 @@ fn test_pay_bill_with_large_amount() {
         &1000000,
         &false,
         &0,
+        &None,
+        &String::from_str(&env, "XLM"),
     );
 
     env.mock_all_auths();
     client.pay_bill(&owner, &bill_id);
 
     let bill = client.get_bill(&bill_id).unwrap();
-    assert!(bill.paid);
+    assert!(bill.is_paid());
     assert_eq!(bill.amount, large_amount);
 }
 
@@ -102,6 +106,8 @@ fn test_recurring_bill_with_large_amount() {
         &1000000,
         &true,
         &30,
+        &None,
+        &String::from_str(&env, "XLM"),
     );
 
     env.mock_all_auths();
@@ -109,12 +115,12 @@ fn test_recurring_bill_with_large_amount() {
 
     // Verify original bill is paid
     let bill = client.get_bill(&bill_id).unwrap();
-    assert!(bill.paid);
+    assert!(bill.is_paid());
     assert_eq!(bill.amount, large_amount);
 
     // Verify next recurring bill was created with same amount
     let bill2 = client.get_bill(&2).unwrap();
-    assert!(!bill2.paid);
+    assert!(!bill2.is_paid());
     assert_eq!(bill2.amount, large_amount);
 }
 
@@ -137,6 +143,8 @@ fn test_get_total_unpaid_with_two_large_bills() {
         &1000000,
         &false,
         &0,
+        &None,
+        &String::from_str(&env, "XLM"),
     );
 
     env.mock_all_auths();
@@ -147,6 +155,8 @@ fn test_get_total_unpaid_with_two_large_bills() {
         &1000000,
         &false,
         &0,
+        &None,
+        &String::from_str(&env, "XLM"),
     );
 
     let total = client.get_total_unpaid(&owner);
@@ -154,8 +164,7 @@ fn test_get_total_unpaid_with_two_large_bills() {
 }
 
 #[test]
-#[should_panic(expected = "overflow")]
-fn test_get_total_unpaid_overflow_panics() {
+fn test_get_total_unpaid_saturates_on_overflow() {
     let env = Env::default();
     let contract_id = env.register_contract(None, BillPayments);
     let client = BillPaymentsClient::new(&env, &contract_id);
@@ -173,6 +182,8 @@ fn test_get_total_unpaid_overflow_panics() {
         &1000000,
         &false,
         &0,
+        &None,
+        &String::from_str(&env, "XLM"),
     );
 
     env.mock_all_auths();
@@ -183,10 +194,12 @@ fn test_get_total_unpaid_overflow_panics() {
         &1000000,
         &false,
         &0,
+        &None,
+        &String::from_str(&env, "XLM"),
     );
 
-    // This should panic due to overflow
-    client.get_total_unpaid(&owner);
+    // The running unpaid total saturates rather than wrapping
+    assert_eq!(client.get_total_unpaid(&owner), i128::MAX);
 }
 
 #[test]
@@ -209,6 +222,8 @@ fn test_multiple_large_bills_different_owners() {
         &1000000,
         &false,
         &0,
+        &None,
+        &String::from_str(&env, "XLM"),
     );
 
     env.mock_all_auths();
@@ -219,6 +234,8 @@ fn test_multiple_large_bills_different_owners() {
         &1000000,
         &false,
         &0,
+        &None,
+        &String::from_str(&env, "XLM"),
     );
 
     let total1 = client.get_total_unpaid(&owner1);
@@ -248,13 +265,15 @@ fn test_archive_large_amount_bill() {
         &1000000,
         &false,
         &0,
+        &None,
+        &String::from_str(&env, "XLM"),
     );
 
     env.mock_all_auths();
     client.pay_bill(&owner, &bill_id);
 
     env.mock_all_auths();
-    client.archive_paid_bills(&owner);
+    client.archive_paid_bills(&owner, &u64::MAX);
 
     let archived = client.get_archived_bill(&bill_id).unwrap();
     assert_eq!(archived.amount, large_amount);
@@ -281,6 +300,8 @@ fn test_batch_pay_large_bills() {
             &1000000,
             &false,
             &0,
+            &None,
+            &String::from_str(&env, "XLM"),
         );
         bill_ids.push_back(bill_id);
         env.mock_all_auths();
@@ -294,7 +315,7 @@ fn test_batch_pay_large_bills() {
     // Verify all bills are paid
     for bill_id in bill_ids.iter() {
         let bill = client.get_bill(&bill_id).unwrap();
-        assert!(bill.paid);
+        assert!(bill.is_paid());
         assert_eq!(bill.amount, amount);
     }
 }
@@ -319,6 +340,8 @@ fn test_overdue_bills_with_large_amounts() {
         &1000000, // Past due
         &false,
         &0,
+        &None,
+        &String::from_str(&env, "XLM"),
     );
 
     let page = client.get_overdue_bills(&0, &10);
@@ -345,6 +368,8 @@ fn test_edge_case_i128_max_minus_one() {
         &1000000,
         &false,
         &0,
+        &None,
+        &String::from_str(&env, "XLM"),
     );
 
     let bill = client.get_bill(&bill_id).unwrap();
@@ -371,6 +396,8 @@ fn test_pagination_with_large_amounts() {
             &1000000,
             &false,
             &0,
+            &None,
+            &String::from_str(&env, "XLM"),
         );
         env.mock_all_auths();
     }
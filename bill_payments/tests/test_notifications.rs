@@ -26,6 +26,7 @@ fn test_notification_flow() {
         &1234567890,
         &false,
         &0,
+        &None,
         &soroban_sdk::String::from_str(&e, "XLM"),
     );
 
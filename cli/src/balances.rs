@@ -0,0 +1,198 @@
+//! Consolidated funds view for `remitwise-cli balances`.
+//!
+//! Parses the JSON printed by `soroban contract invoke` and renders token
+//! balances of the split destination accounts, the allowance the owner has
+//! granted the split contract, the amount owed on unpaid bills and savings
+//! goal balances as one table.
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+/// Balance of one savings goal
+pub struct GoalBalance {
+    pub id: u64,
+    pub name: String,
+    pub current_amount: i128,
+    pub target_amount: i128,
+}
+
+/// Token and destination accounts of an owner's inbound watch
+pub struct WatchedAccounts {
+    pub token: String,
+    /// (split category, account address)
+    pub accounts: Vec<(&'static str, String)>,
+}
+
+/// Everything shown by `balances`
+pub struct FundsView {
+    /// Token the account balances and allowance are in
+    pub token: String,
+    /// (split category, account address, token balance)
+    pub accounts: Vec<(&'static str, String, i128)>,
+    /// Allowance the owner has granted the split contract on `token`
+    pub split_allowance: i128,
+    /// Total of the owner's unpaid bills. Bill payments holds no escrow, so
+    /// this is owed rather than held.
+    pub unpaid_bills: i128,
+    pub goals: Vec<GoalBalance>,
+}
+
+fn as_i128(value: &Value) -> Option<i128> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        Value::Number(n) => n.as_i64().map(i128::from),
+        _ => None,
+    }
+}
+
+/// Parse an `i128` result, which soroban prints as a quoted string
+pub fn parse_amount(output: &str) -> Result<i128> {
+    let value: Value = serde_json::from_str(output.trim())?;
+    as_i128(&value).ok_or_else(|| anyhow!("Expected an amount, got {}", output.trim()))
+}
+
+/// Parse the result of remittance split `get_inbound_watch`, which is `null`
+/// when the owner has no watch
+pub fn parse_inbound_watch(output: &str) -> Result<Option<WatchedAccounts>> {
+    let value: Value = serde_json::from_str(output.trim())?;
+    if value.is_null() {
+        return Ok(None);
+    }
+    let token = value
+        .get("token")
+        .and_then(Value::as_str)
+        .ok_or_else(|| anyhow!("Inbound watch is missing `token`"))?
+        .to_string();
+    let group = value
+        .get("accounts")
+        .ok_or_else(|| anyhow!("Inbound watch is missing `accounts`"))?;
+    let accounts = ["spending", "savings", "bills", "insurance"]
+        .into_iter()
+        .map(|category| {
+            let address = group
+                .get(category)
+                .and_then(Value::as_str)
+                .ok_or_else(|| anyhow!("Inbound watch is missing the {} account", category))?;
+            Ok((category, address.to_string()))
+        })
+        .collect::<Result<_>>()?;
+    Ok(Some(WatchedAccounts { token, accounts }))
+}
+
+/// Parse the result of savings goals `get_all_goals`
+pub fn parse_goals(output: &str) -> Result<Vec<GoalBalance>> {
+    let value: Value = serde_json::from_str(output.trim())?;
+    let goals = value
+        .as_array()
+        .ok_or_else(|| anyhow!("Expected a list of goals"))?;
+    goals
+        .iter()
+        .map(|goal| {
+            let field = |name: &str| {
+                goal.get(name)
+                    .ok_or_else(|| anyhow!("Goal is missing `{}`", name))
+            };
+            Ok(GoalBalance {
+                id: field("id")?
+                    .as_u64()
+                    .ok_or_else(|| anyhow!("Invalid goal id"))?,
+                name: field("name")?.as_str().unwrap_or_default().to_string(),
+                current_amount: as_i128(field("current_amount")?)
+                    .ok_or_else(|| anyhow!("Invalid goal current_amount"))?,
+                target_amount: as_i128(field("target_amount")?)
+                    .ok_or_else(|| anyhow!("Invalid goal target_amount"))?,
+            })
+        })
+        .collect()
+}
+
+impl FundsView {
+    pub fn render(&self) -> String {
+        let mut out = format!("Accounts (balance of {})\n", self.token);
+        let mut account_total = 0i128;
+        for (category, address, balance) in &self.accounts {
+            out.push_str(&format!(
+                "  {:<10} {:>20}  {}\n",
+                category, balance, address
+            ));
+            account_total = account_total.saturating_add(*balance);
+        }
+        out.push_str(&format!("  {:<10} {:>20}\n", "total", account_total));
+
+        out.push_str(&format!(
+            "\nSplit contract allowance {:>20}\n",
+            self.split_allowance
+        ));
+
+        out.push_str(&format!(
+            "\nOwed (not held)\n  {:<10} {:>20}\n",
+            "bills", self.unpaid_bills
+        ));
+
+        out.push_str("\nSavings goals\n");
+        let mut saved_total = 0i128;
+        for goal in &self.goals {
+            out.push_str(&format!(
+                "  #{:<4} {:<20} {:>20} / {}\n",
+                goal.id, goal.name, goal.current_amount, goal.target_amount
+            ));
+            saved_total = saved_total.saturating_add(goal.current_amount);
+        }
+        out.push_str(&format!("  {:<26} {:>20}\n", "total", saved_total));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_amount_and_goals() {
+        assert_eq!(parse_amount("\"1500\"\n").unwrap(), 1500);
+        assert_eq!(parse_amount("42").unwrap(), 42);
+        assert!(parse_amount("null").is_err());
+
+        let goals = parse_goals(
+            r#"[{"id":1,"name":"School","current_amount":"250","target_amount":"1000","locked":true}]"#,
+        )
+        .unwrap();
+        assert_eq!(goals.len(), 1);
+        assert_eq!(goals[0].name, "School");
+        assert_eq!(goals[0].current_amount, 250);
+        assert_eq!(goals[0].target_amount, 1000);
+
+        assert!(parse_inbound_watch("null\n").unwrap().is_none());
+        let watch = parse_inbound_watch(
+            r#"{"token":"CT","min_amount":"10","accounts":{"spending":"GA","savings":"GB","bills":"GC","insurance":"GD"}}"#,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(watch.token, "CT");
+        assert_eq!(watch.accounts[2], ("bills", "GC".to_string()));
+        assert!(parse_inbound_watch(r#"{"token":"CT","accounts":{}}"#).is_err());
+    }
+
+    #[test]
+    fn test_render_totals() {
+        let view = FundsView {
+            token: "CT".into(),
+            accounts: vec![("spending", "GA".into(), 100), ("savings", "GB".into(), 50)],
+            split_allowance: 500,
+            unpaid_bills: 70,
+            goals: vec![GoalBalance {
+                id: 1,
+                name: "School".into(),
+                current_amount: 250,
+                target_amount: 1000,
+            }],
+        };
+        let out = view.render();
+        assert!(out.contains("total                       150"));
+        assert!(out.contains("Accounts (balance of CT)"));
+        assert!(out.contains("Split contract allowance                  500"));
+        assert!(out.contains("Owed (not held)\n  bills                        70"));
+        assert!(!out.contains("escrow"));
+        assert!(out.contains("250 / 1000"));
+    }
+}
@@ -0,0 +1,238 @@
+//! Deployment checks for `remitwise-cli doctor`.
+//!
+//! For each configured contract, compares the version it reports with the
+//! versions this CLI was built against and looks at its admins and pause
+//! state. Every problem comes with the command or setting that fixes it, so
+//! misconfiguration shows up here rather than as a failed transaction.
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+/// Contract versions this CLI can talk to, as (registry name, oldest, newest)
+pub const SUPPORTED_VERSIONS: [(&str, u32, u32); 4] = [
+    ("split", 1, 1),
+    ("goals", 1, 1),
+    ("bills", 1, 1),
+    ("insurance", 1, 1),
+];
+
+#[derive(Clone, Copy, Debug, PartialEq, Eq, PartialOrd, Ord)]
+pub enum Severity {
+    Ok,
+    Warning,
+    Error,
+}
+
+impl Severity {
+    fn label(self) -> &'static str {
+        match self {
+            Severity::Ok => "ok",
+            Severity::Warning => "warning",
+            Severity::Error => "error",
+        }
+    }
+}
+
+/// What the contract reported
+pub struct ContractState {
+    pub version: u32,
+    pub paused: bool,
+    pub pause_admin: Option<String>,
+    pub upgrade_admin: Option<String>,
+}
+
+/// Outcome of checking one contract
+pub struct ContractReport {
+    /// Registry name of the contract
+    pub name: &'static str,
+    pub findings: Vec<(Severity, String)>,
+}
+
+impl ContractReport {
+    pub fn severity(&self) -> Severity {
+        self.findings
+            .iter()
+            .map(|(severity, _)| *severity)
+            .max()
+            .unwrap_or(Severity::Ok)
+    }
+
+    /// A contract whose ID could not be resolved or that could not be queried
+    pub fn unreachable(name: &'static str, problem: String) -> Self {
+        ContractReport {
+            name,
+            findings: vec![(Severity::Error, problem)],
+        }
+    }
+}
+
+/// Parse a `u32` result
+pub fn parse_version(output: &str) -> Result<u32> {
+    let value: Value = serde_json::from_str(output.trim())?;
+    value
+        .as_u64()
+        .and_then(|version| u32::try_from(version).ok())
+        .ok_or_else(|| anyhow!("Expected a version, got {}", output.trim()))
+}
+
+/// Parse a `bool` result
+pub fn parse_flag(output: &str) -> Result<bool> {
+    let value: Value = serde_json::from_str(output.trim())?;
+    value
+        .as_bool()
+        .ok_or_else(|| anyhow!("Expected true or false, got {}", output.trim()))
+}
+
+/// Parse an `Option<Address>` result
+pub fn parse_admin(output: &str) -> Result<Option<String>> {
+    match serde_json::from_str(output.trim())? {
+        Value::Null => Ok(None),
+        Value::String(address) => Ok(Some(address)),
+        _ => Err(anyhow!("Expected an address, got {}", output.trim())),
+    }
+}
+
+/// Check a contract's state. `admin` is the `ADMIN_ADDRESS` admin commands
+/// are signed with, when set.
+pub fn check(name: &'static str, state: &ContractState, admin: Option<&str>) -> ContractReport {
+    let mut findings = Vec::new();
+    match SUPPORTED_VERSIONS.iter().find(|(contract, _, _)| *contract == name) {
+        Some((_, oldest, _)) if state.version < *oldest => findings.push((
+            Severity::Error,
+            format!(
+                "version {} is older than this CLI supports (from {}); upgrade the contract or use an older remitwise-cli",
+                state.version, oldest
+            ),
+        )),
+        Some((_, _, newest)) if state.version > *newest => findings.push((
+            Severity::Error,
+            format!(
+                "version {} is newer than this CLI supports (up to {}); update remitwise-cli",
+                state.version, newest
+            ),
+        )),
+        Some(_) => findings.push((Severity::Ok, format!("version {}", state.version))),
+        None => findings.push((
+            Severity::Warning,
+            format!("version {} (not in the supported matrix)", state.version),
+        )),
+    }
+
+    if state.paused {
+        findings.push((
+            Severity::Warning,
+            format!(
+                "paused; all writes fail until the pause admin runs `admin unpause {}`",
+                name
+            ),
+        ));
+    }
+
+    for (role, current, flag) in [
+        ("pause", &state.pause_admin, ""),
+        ("upgrade", &state.upgrade_admin, " --upgrade"),
+    ] {
+        match (current, admin) {
+            (None, _) => findings.push((
+                Severity::Warning,
+                format!(
+                    "no {} admin set; the first caller to claim it gets it. Run `admin set-admin {} <ADMIN_ADDRESS>{}`",
+                    role, name, flag
+                ),
+            )),
+            (Some(current), Some(admin)) if current != admin => findings.push((
+                Severity::Warning,
+                format!(
+                    "{} admin is {}, not ADMIN_ADDRESS; admin commands signed as {} will be rejected",
+                    role, current, admin
+                ),
+            )),
+            (Some(_), _) => {}
+        }
+    }
+
+    ContractReport { name, findings }
+}
+
+/// Render the reports, one block per contract
+pub fn render(reports: &[ContractReport]) -> String {
+    let mut out = String::new();
+    for report in reports {
+        out.push_str(&format!(
+            "{:<10} {}\n",
+            report.name,
+            report.severity().label()
+        ));
+        for (severity, message) in &report.findings {
+            out.push_str(&format!("  {:<8} {}\n", severity.label(), message));
+        }
+    }
+    let problems = reports
+        .iter()
+        .filter(|report| report.severity() == Severity::Error)
+        .count();
+    let warnings = reports
+        .iter()
+        .filter(|report| report.severity() == Severity::Warning)
+        .count();
+    out.push_str(&format!(
+        "\n{} contract(s) with errors, {} with warnings\n",
+        problems, warnings
+    ));
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_results() {
+        assert_eq!(parse_version("1\n").unwrap(), 1);
+        assert!(parse_version("\"x\"").is_err());
+        assert!(parse_flag("true").unwrap());
+        assert_eq!(parse_admin("null").unwrap(), None);
+        assert_eq!(
+            parse_admin("\"GADMIN\"").unwrap().as_deref(),
+            Some("GADMIN")
+        );
+    }
+
+    #[test]
+    fn test_check_and_render() {
+        let healthy = ContractState {
+            version: 1,
+            paused: false,
+            pause_admin: Some("GADMIN".into()),
+            upgrade_admin: Some("GADMIN".into()),
+        };
+        let report = check("split", &healthy, Some("GADMIN"));
+        assert_eq!(report.severity(), Severity::Ok);
+
+        let report = check(
+            "bills",
+            &ContractState {
+                version: 2,
+                paused: true,
+                pause_admin: Some("GOTHER".into()),
+                upgrade_admin: None,
+            },
+            Some("GADMIN"),
+        );
+        assert_eq!(report.severity(), Severity::Error);
+        let messages: Vec<&str> = report.findings.iter().map(|(_, m)| m.as_str()).collect();
+        assert!(messages[0].contains("newer than this CLI supports"));
+        assert!(messages[1].contains("`admin unpause bills`"));
+        assert!(messages[2].contains("pause admin is GOTHER, not ADMIN_ADDRESS"));
+        assert!(messages[3].contains("`admin set-admin bills <ADMIN_ADDRESS> --upgrade`"));
+
+        let out = render(&[
+            check("split", &healthy, None),
+            report,
+            ContractReport::unreachable("goals", "SAVINGS_GOALS_CONTRACT_ID not set".into()),
+        ]);
+        assert!(out.contains("split      ok\n"));
+        assert!(out.contains("  error    SAVINGS_GOALS_CONTRACT_ID not set\n"));
+        assert!(out.ends_with("2 contract(s) with errors, 0 with warnings\n"));
+    }
+}
@@ -0,0 +1,412 @@
+//! End-to-end check of a fresh deployment, for `remitwise-cli e2e`.
+//!
+//! Deploys the split, bills, goals and insurance contracts to the given
+//! network (normally a local quickstart node) and walks through one
+//! household flow, failing on the first unexpected result:
+//!
+//! 1. initialize a 50/30/15/5 split and distribute native XLM through it
+//! 2. create and pay a bill
+//! 3. create a policy and pay its premium, which must move its next
+//!    payment date and be recorded as paid
+//! 4. create a goal and contribute to it
+//!
+//! The wasm files must already be built with
+//! `cargo build --target wasm32-unknown-unknown --release`.
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+use std::path::PathBuf;
+use std::process::Command;
+use std::time::{SystemTime, UNIX_EPOCH};
+
+use crate::{balances, errors};
+
+/// Amount distributed through the split, in stroops (1 XLM)
+const DISTRIBUTION: i128 = 10_000_000;
+
+pub struct E2e {
+    pub network: String,
+    /// Funded identity that deploys the contracts and acts as the owner
+    pub source: String,
+    pub wasm_dir: PathBuf,
+}
+
+impl E2e {
+    pub fn run(&self) -> Result<()> {
+        let owner = self.identity(&self.source)?;
+        println!("owner: {}", owner);
+
+        let split = self.deploy("remittance_split")?;
+        let bills = self.deploy("bill_payments")?;
+        let goals = self.deploy("savings_goals")?;
+        let insurance = self.deploy("insurance")?;
+        let token = self.native_token()?;
+
+        self.split_flow(&split, &token, &owner)?;
+        self.bills_flow(&bills, &owner)?;
+        self.insurance_flow(&insurance, &owner)?;
+        self.goals_flow(&goals, &owner)?;
+
+        println!("e2e: all checks passed");
+        Ok(())
+    }
+
+    fn split_flow(&self, split: &str, token: &str, owner: &str) -> Result<()> {
+        self.send(
+            "split",
+            split,
+            "set_pause_admin",
+            &["--caller", owner, "--new_admin", owner],
+        )?;
+        self.send(
+            "split",
+            split,
+            "add_supported_token",
+            &["--caller", owner, "--token", token],
+        )?;
+        let nonce = self.view("split", split, "get_nonce", &["--address", owner])?;
+        self.send(
+            "split",
+            split,
+            "initialize_split",
+            &[
+                "--owner",
+                owner,
+                "--nonce",
+                nonce.trim_matches('"'),
+                "--spending_percent",
+                "50",
+                "--savings_percent",
+                "30",
+                "--bills_percent",
+                "15",
+                "--insurance_percent",
+                "5",
+            ],
+        )?;
+        pass("split initialized");
+
+        let mut accounts = Vec::new();
+        for category in ["spending", "savings", "bills", "insurance"] {
+            accounts.push(self.identity(&format!("{}-{}", self.source, category))?);
+        }
+        let before = self.balance(token, &accounts[0])?;
+        let nonce = self.view("split", split, "get_nonce", &["--address", owner])?;
+        let accounts_json = format!(
+            r#"{{"spending":"{}","savings":"{}","bills":"{}","insurance":"{}"}}"#,
+            accounts[0], accounts[1], accounts[2], accounts[3]
+        );
+        self.send(
+            "split",
+            split,
+            "distribute_usdc",
+            &[
+                "--usdc_contract",
+                token,
+                "--from",
+                owner,
+                "--nonce",
+                nonce.trim_matches('"'),
+                "--accounts",
+                &accounts_json,
+                "--total_amount",
+                &DISTRIBUTION.to_string(),
+                "--purpose",
+                "1",
+            ],
+        )?;
+        let received = self.balance(token, &accounts[0])? - before;
+        check(
+            "distribution reached spending account",
+            received == DISTRIBUTION / 2,
+            received,
+        )
+    }
+
+    fn bills_flow(&self, bills: &str, owner: &str) -> Result<()> {
+        let due = (now()? + 30 * 86400).to_string();
+        let bill_id = self.send(
+            "bills",
+            bills,
+            "create_bill",
+            &[
+                "--owner",
+                owner,
+                "--name",
+                "E2E Water",
+                "--amount",
+                "1000",
+                "--due_date",
+                &due,
+                "--recurring",
+                "false",
+                "--frequency_days",
+                "0",
+                "--currency",
+                "XLM",
+            ],
+        )?;
+        self.send(
+            "bills",
+            bills,
+            "pay_bill",
+            &["--caller", owner, "--bill_id", &bill_id],
+        )?;
+        let bill = self.view_json("bills", bills, "get_bill", &["--bill_id", &bill_id])?;
+        // BillStatus::Paid
+        check("bill paid", bill["status"] == 3, &bill["status"])
+    }
+
+    fn insurance_flow(&self, insurance: &str, owner: &str) -> Result<()> {
+        let policy_id = self.send(
+            "insurance",
+            insurance,
+            "create_policy",
+            &[
+                "--owner",
+                owner,
+                "--name",
+                "E2E Health",
+                "--coverage_type",
+                "1",
+                "--monthly_premium",
+                "100",
+                "--coverage_amount",
+                "10000",
+            ],
+        )?;
+        let next_due = |policy_id: &str| -> Result<u64> {
+            let policy = self.view_json(
+                "insurance",
+                insurance,
+                "get_policy",
+                &["--policy_id", policy_id],
+            )?;
+            field_u64(&policy, "next_payment_date")
+        };
+        let due_before = next_due(&policy_id)?;
+        self.send(
+            "insurance",
+            insurance,
+            "pay_premium",
+            &["--caller", owner, "--policy_id", &policy_id],
+        )?;
+        let due_after = next_due(&policy_id)?;
+        check(
+            "premium moves the next payment date",
+            due_after > due_before,
+            format!("{} -> {}", due_before, due_after),
+        )?;
+        let paid = self.view(
+            "insurance",
+            insurance,
+            "get_premiums_paid",
+            &["--policy_id", &policy_id],
+        )?;
+        let paid = balances::parse_amount(&paid)?;
+        check("premium paid", paid == 100, paid)
+    }
+
+    fn goals_flow(&self, goals: &str, owner: &str) -> Result<()> {
+        self.send("goals", goals, "init", &[])?;
+        let target_date = (now()? + 365 * 86400).to_string();
+        let goal_id = self.send(
+            "goals",
+            goals,
+            "create_goal",
+            &[
+                "--owner",
+                owner,
+                "--name",
+                "E2E Goal",
+                "--target_amount",
+                "5000",
+                "--target_date",
+                &target_date,
+            ],
+        )?;
+        let balance = self.send(
+            "goals",
+            goals,
+            "add_to_goal",
+            &["--caller", owner, "--goal_id", &goal_id, "--amount", "1000"],
+        )?;
+        let balance = balances::parse_amount(&balance)?;
+        check("goal contribution recorded", balance == 1000, balance)
+    }
+
+    /// Address of `name`, generating and funding the identity if needed
+    fn identity(&self, name: &str) -> Result<String> {
+        if let Ok(address) = self.soroban(&["keys", "address", name]) {
+            return Ok(address);
+        }
+        self.soroban(&[
+            "keys",
+            "generate",
+            name,
+            "--network",
+            &self.network,
+            "--fund",
+        ])?;
+        self.soroban(&["keys", "address", name])
+    }
+
+    fn deploy(&self, crate_name: &str) -> Result<String> {
+        let wasm = self.wasm_dir.join(format!("{}.wasm", crate_name));
+        if !wasm.exists() {
+            return Err(anyhow!(
+                "{} not found; run `cargo build --target wasm32-unknown-unknown --release` first",
+                wasm.display()
+            ));
+        }
+        let id = self.soroban(&[
+            "contract",
+            "deploy",
+            "--wasm",
+            &wasm.to_string_lossy(),
+            "--source",
+            &self.source,
+            "--network",
+            &self.network,
+        ])?;
+        println!("deployed {}: {}", crate_name, id);
+        Ok(id)
+    }
+
+    /// Stellar asset contract of native XLM, deploying it if needed
+    fn native_token(&self) -> Result<String> {
+        let _ = self.soroban(&[
+            "contract",
+            "asset",
+            "deploy",
+            "--asset",
+            "native",
+            "--source",
+            &self.source,
+            "--network",
+            &self.network,
+        ]);
+        self.soroban(&[
+            "contract",
+            "id",
+            "asset",
+            "--asset",
+            "native",
+            "--network",
+            &self.network,
+        ])
+    }
+
+    fn balance(&self, token: &str, address: &str) -> Result<i128> {
+        let balance = self.view("token", token, "balance", &["--id", address])?;
+        balances::parse_amount(&balance)
+    }
+
+    /// Submit a transaction invoking `function`
+    fn send(&self, contract: &str, id: &str, function: &str, args: &[&str]) -> Result<String> {
+        self.invoke(contract, id, function, args, true)
+    }
+
+    /// Simulate `function` without submitting a transaction
+    fn view(&self, contract: &str, id: &str, function: &str, args: &[&str]) -> Result<String> {
+        self.invoke(contract, id, function, args, false)
+    }
+
+    fn view_json(&self, contract: &str, id: &str, function: &str, args: &[&str]) -> Result<Value> {
+        let output = self.view(contract, id, function, args)?;
+        Ok(serde_json::from_str(&output)?)
+    }
+
+    fn invoke(
+        &self,
+        contract: &str,
+        id: &str,
+        function: &str,
+        args: &[&str],
+        send: bool,
+    ) -> Result<String> {
+        let mut cmd_args = vec![
+            "contract",
+            "invoke",
+            "--id",
+            id,
+            "--source",
+            &self.source,
+            "--network",
+            &self.network,
+        ];
+        if !send {
+            cmd_args.extend(["--send", "no"]);
+        }
+        cmd_args.push("--");
+        cmd_args.push(function);
+        cmd_args.extend(args);
+        self.soroban(&cmd_args)
+            .map_err(|err| match errors::describe(contract, &err.to_string()) {
+                Some(description) => anyhow!("{} failed: {}", function, description),
+                None => anyhow!("{} failed: {}", function, err),
+            })
+    }
+
+    fn soroban(&self, args: &[&str]) -> Result<String> {
+        let output = Command::new("soroban").args(args).output()?;
+        if !output.status.success() {
+            return Err(anyhow!(
+                "{}",
+                String::from_utf8_lossy(&output.stderr).trim()
+            ));
+        }
+        Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+    }
+}
+
+/// Read a `u64` field of a struct result, printed as a number or a string
+fn field_u64(value: &Value, field: &str) -> Result<u64> {
+    let raw = &value[field];
+    raw.as_u64()
+        .or_else(|| raw.as_str().and_then(|s| s.parse().ok()))
+        .ok_or_else(|| anyhow!("Expected {} to be a u64, got {}", field, raw))
+}
+
+fn now() -> Result<u64> {
+    Ok(SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs())
+}
+
+fn pass(step: &str) {
+    println!("ok   {}", step);
+}
+
+fn check(step: &str, ok: bool, actual: impl std::fmt::Display) -> Result<()> {
+    if !ok {
+        println!("FAIL {} (got {})", step, actual);
+        return Err(anyhow!("e2e check failed: {}", step));
+    }
+    pass(step);
+    Ok(())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_u64() {
+        let policy: Value =
+            serde_json::from_str(r#"{"active":true,"next_payment_date":1700000000}"#).unwrap();
+        assert_eq!(
+            field_u64(&policy, "next_payment_date").unwrap(),
+            1_700_000_000
+        );
+        let quoted: Value = serde_json::from_str(r#"{"next_payment_date":"42"}"#).unwrap();
+        assert_eq!(field_u64(&quoted, "next_payment_date").unwrap(), 42);
+        assert!(field_u64(&policy, "active").is_err());
+        assert!(field_u64(&policy, "missing").is_err());
+    }
+
+    #[test]
+    fn test_check() {
+        assert!(check("ok step", true, 1).is_ok());
+        let err = check("bad step", false, 0).unwrap_err();
+        assert_eq!(err.to_string(), "e2e check failed: bad step");
+    }
+}
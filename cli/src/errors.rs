@@ -0,0 +1,240 @@
+//! Decoding of contract error codes reported by failed invocations.
+//!
+//! The soroban CLI reports a `contracterror` as `Error(Contract, #<code>)`.
+//! The tables below mirror each contract's error enum so the code can be
+//! shown by name with a hint on how to recover. Keep them in sync when an
+//! error enum changes.
+
+/// A contract error code with its enum variant name and a recovery hint
+pub struct ErrorInfo {
+    pub code: u32,
+    pub name: &'static str,
+    pub hint: &'static str,
+}
+
+const fn info(code: u32, name: &'static str, hint: &'static str) -> ErrorInfo {
+    ErrorInfo { code, name, hint }
+}
+
+/// `RemittanceSplitError`
+#[rustfmt::skip]
+const SPLIT_ERRORS: &[ErrorInfo] = &[
+    info(1, "AlreadyInitialized", "a split is already configured for this owner; use update_split instead"),
+    info(2, "NotInitialized", "run initialize_split for OWNER_ADDRESS first"),
+    info(3, "PercentagesDoNotSumTo100", "the four percentages must add up to exactly 100"),
+    info(4, "InvalidAmount", "amounts must be greater than zero"),
+    info(5, "Overflow", "the amount is too large to split; try a smaller value"),
+    info(6, "Unauthorized", "sign with the owner (or admin) account for this operation"),
+    info(7, "InvalidNonce", "fetch the current nonce with get_nonce and retry"),
+    info(8, "UnsupportedVersion", "the snapshot was exported by an incompatible contract version"),
+    info(9, "ChecksumMismatch", "the snapshot was modified or corrupted; export it again"),
+    info(10, "InvalidDueDate", "the due date must be in the future"),
+    info(11, "ScheduleNotFound", "check the schedule ID with get_remittance_schedules"),
+    info(12, "FallbackNotConfigured", "call set_fallback_category before freezing a category"),
+    info(13, "InvalidFallback", "the fallback category cannot be frozen, nor a frozen one used as fallback"),
+    info(14, "CategoryNotFrozen", "the category is not frozen, so there is nothing to unfreeze"),
+    info(15, "UnsupportedToken", "ask the admin to add the token to the allow-list"),
+    info(16, "ProposalRequired", "this transfer exceeds the large-transfer threshold; propose it first"),
+    info(17, "ProposalNotFound", "check the proposal ID"),
+    info(18, "ProposalNotReady", "wait for the proposal delay to pass before executing"),
+    info(19, "ProposalNotPending", "the proposal was already executed or cancelled"),
+    info(20, "NoPendingChange", "there is no split change waiting for guardian approval"),
+    info(21, "BatchTooLarge", "split the batch into calls of at most 50 households"),
+    info(22, "InsufficientBalance", "the sender balance does not cover the batch total"),
+    info(23, "ComplianceRejected", "the compliance checker denied a transfer"),
+    info(24, "InboundWatchNotFound", "the owner has no inbound watch"),
+    info(25, "InboundNotEligible", "the payment does not match the inbound watch"),
+    info(26, "InboundAlreadySettled", "the inbound payment has already been split"),
+    info(27, "InvalidTopUpTargets", "top-up targets must not be negative and categories must not repeat in the priority"),
+    info(28, "RedirectNotActive", "the owner has no emergency redirect to deactivate"),
+    info(29, "InboundExceedsReceived", "the owner's balance has not grown by the reported amount; re-register the watch to reset its baseline"),
+];
+
+/// bill_payments `Error`
+#[rustfmt::skip]
+const BILLS_ERRORS: &[ErrorInfo] = &[
+    info(1, "BillNotFound", "check the bill ID with `bills list`; it may have been archived or cancelled"),
+    info(2, "BillAlreadyPaid", "nothing to do; the bill is already paid"),
+    info(3, "InvalidAmount", "bill amounts must be greater than zero"),
+    info(4, "InvalidFrequency", "recurring bills need a frequency of at least one day"),
+    info(5, "Unauthorized", "only the bill owner can do this; check OWNER_ADDRESS"),
+    info(6, "ContractPaused", "the contract is paused by its admin; try again later"),
+    info(7, "UnauthorizedPause", "only the pause admin can pause or unpause"),
+    info(8, "FunctionPaused", "this function is paused by the admin; try again later"),
+    info(9, "BatchTooLarge", "split the request into batches of at most 50 bills"),
+    info(10, "BatchValidationFailed", "remove duplicate or invalid bill IDs from the batch"),
+    info(11, "InvalidLimit", "use a page limit between 1 and the maximum page size"),
+    info(12, "InvalidTag", "tags must be non-empty and within the length limit"),
+    info(13, "EmptyTags", "provide at least one tag"),
+    info(14, "OracleNotConfigured", "fiat bills need a rate oracle; ask the admin to configure one"),
+    info(15, "RateUnavailable", "the oracle has no fresh rate for this currency; retry later"),
+    info(16, "InvalidEscalation", "bps_per_cycle is at most 10000 and stepped escalation needs step_every_cycles"),
+    info(17, "PayeeNotVerified", "pick a verified biller or turn off verified-only payees"),
+    info(18, "BillerNotFound", "check the biller ID with the biller directory"),
+    info(19, "InvalidThreshold", "the second overdue threshold must be greater than the first"),
+    info(20, "InvalidAnchor", "use a day between 1 and 31 and an epoch offset of at most 14 hours"),
+    info(21, "HouseholdNotFound", "the wallet owner must register the household first"),
+    info(22, "DebitNotAuthorized", "the owner has not granted this biller a debit authorization"),
+    info(23, "DebitLimitExceeded", "the biller has reached its debit cap for this period"),
+    info(24, "BillNotOverdue", "the bill has not passed the first overdue threshold"),
+    info(25, "BillNotPublic", "the bill does not accept contributions"),
+    info(26, "TooManyContributors", "the bill has reached its contributor limit"),
+    info(27, "InvalidInstallments", "installment count or interval is out of range for the bill"),
+    info(28, "InstallmentNotAllowed", "recurring, public and installment bills cannot be split"),
+    info(29, "ReassignmentPending", "the owner already has a reassignment pending"),
+    info(30, "ReassignmentNotFound", "no reassignment is pending for the owner"),
+    info(31, "ReassignmentNotReady", "the reassignment delay has not elapsed yet"),
+    info(32, "InvalidRecoveryConfig", "guardians must be distinct, exclude the owner, and cover the threshold"),
+    info(33, "RecoveryPending", "the owner already has a recovery in progress"),
+    info(34, "RecoveryNotFound", "no recovery is in progress for the owner"),
+    info(35, "RecoveryNotReady", "the recovery needs more approvals or its veto window is still open"),
+    info(36, "ApprovalNotFound", "no autopay payment is queued for this bill"),
+    info(37, "ApprovalExpired", "the approval window has closed; pay the bill with `pay_bill`"),
+    info(38, "BillDisputed", "withdraw the dispute with `set_bill_disputed` before paying"),
+    info(39, "BillNotPaid", "credit notes can only be issued against a paid bill with a payee"),
+    info(40, "InvalidExpiry", "the credit note must expire in the future"),
+    info(41, "BillAmountTooLarge", "the amount is above the admin's cap; check for extra zeros (`get_sanity_limits`)"),
+    info(42, "ContributionTooLarge", "the contribution is above the admin's cap; split it or check for extra zeros"),
+];
+
+/// `SavingsGoalsError`
+#[rustfmt::skip]
+const SAVINGS_ERRORS: &[ErrorInfo] = &[
+    info(1, "InvalidAmount", "amounts must be greater than zero"),
+    info(2, "GoalNotFound", "check the goal ID with `goals list`"),
+    info(3, "Unauthorized", "only the goal owner can do this; check OWNER_ADDRESS"),
+    info(4, "GoalLocked", "unlock the goal before withdrawing"),
+    info(5, "InsufficientBalance", "the goal balance does not cover the withdrawal"),
+    info(6, "Overflow", "the amount is too large; try a smaller value"),
+    info(7, "ContributionTooSmall", "the contribution is below the goal's minimum"),
+    info(8, "SecurityLocked", "withdrawals are locked after repeated failed attempts; wait for the lock to expire"),
+    info(9, "OracleNotConfigured", "fiat goals need a rate oracle; ask the admin to configure one"),
+    info(10, "RateUnavailable", "the oracle has no fresh rate for this currency; retry later"),
+    info(11, "BonusNotEligible", "the goal does not meet the completion bonus rules"),
+    info(12, "BonusAlreadyPaid", "the owner has already received a completion bonus"),
+    info(13, "InstitutionNotRegistered", "the admin must register the institution first"),
+    info(14, "EscrowRestricted", "escrow goals only pay out to their institution"),
+    info(15, "NotEscrowGoal", "the goal has no escrow institution"),
+    info(16, "TooManyViewers", "remove a viewer before adding another"),
+    info(17, "InvalidRecoveryConfig", "guardians must be distinct, exclude the owner, and cover the threshold"),
+    info(18, "RecoveryPending", "the owner already has a recovery in progress"),
+    info(19, "RecoveryNotFound", "no recovery is in progress for the owner"),
+    info(20, "RecoveryNotReady", "the recovery needs more approvals or its waiting period is still running"),
+    info(21, "QuotaExceeded", "the owner has as many goals or active schedules as their quota allows"),
+    info(22, "WithdrawalPending", "the goal already has a withdrawal waiting out its delay"),
+    info(23, "WithdrawalNotFound", "the goal has no pending withdrawal"),
+    info(24, "NoCompletionAction", "the goal has no pending completion action to run"),
+    info(25, "BillNotOpen", "the bill is paid, disputed or not public, or refused the contribution"),
+];
+
+/// `InsuranceError`
+#[rustfmt::skip]
+const INSURANCE_ERRORS: &[ErrorInfo] = &[
+    info(1, "PolicyNotFound", "check the policy ID with `insurance list`"),
+    info(2, "Unauthorized", "only the policy owner can do this; check OWNER_ADDRESS"),
+    info(3, "InvalidAmount", "premium and coverage amounts must be greater than zero"),
+    info(4, "PolicyInactive", "reactivate the policy or create a new one"),
+    info(5, "ContractPaused", "the contract is paused by its admin; try again later"),
+    info(6, "FunctionPaused", "this function is paused by the admin; try again later"),
+    info(7, "InvalidTimestamp", "the timestamp must be in the future"),
+    info(8, "BatchTooLarge", "split the request into smaller batches"),
+    info(9, "InvalidCoOwner", "a co-owner must differ from the owner and not already be listed"),
+    info(10, "MemberNotFound", "add the member to the family policy first"),
+    info(11, "InvalidMember", "the sub-limit must be positive and within coverage, and the member list not full"),
+    info(12, "SubLimitExceeded", "the claim exceeds the member's remaining sub-limit"),
+    info(13, "WaitingPeriodActive", "claims open after the waiting period; see get_claims_unlock_at"),
+    info(14, "InsufficientStake", "stake more or request a smaller withdrawal"),
+    info(15, "StakeLocked", "wait for the stake lock period to end"),
+    info(16, "OracleNotConfigured", "premium conversion needs an FX oracle; ask the admin to configure one"),
+    info(17, "RateUnavailable", "the oracle has no rate for this display currency"),
+    info(18, "ClaimNotFound", "check the claim ID with get_policy_claims"),
+    info(19, "ClaimNotPending", "the claim has already been approved or rejected"),
+    info(20, "TooManyEvidence", "the claim has the maximum number of evidence items"),
+    info(21, "HolidayActive", "the policy is already on a premium holiday"),
+    info(22, "TierRateNotSet", "no premium rate is set for this coverage type and risk tier"),
+    info(23, "SavingsNotConfigured", "no savings goals contract is configured"),
+    info(24, "NotLifePolicy", "the policy is not life coverage"),
+    info(25, "InvalidBeneficiaries", "beneficiary shares must be distinct and add up to 100%"),
+    info(26, "SurvivorClaimExists", "the policy already has an open or approved survivor claim"),
+    info(27, "InvalidRecoveryConfig", "guardians must be distinct, exclude the owner, and cover the threshold"),
+    info(28, "RecoveryPending", "the holder already has a recovery in progress"),
+    info(29, "RecoveryNotFound", "no recovery is in progress for the holder"),
+    info(30, "RecoveryNotReady", "the recovery needs more approvals or its veto window is still open"),
+    info(31, "DuplicateIncident", "the owner already has an approved claim for this incident; the admin must allow the duplicate first"),
+    info(32, "CoverageRatioExceeded", "coverage is too high for the premium; get_max_coverage_ratio gives the allowed coverage per unit of monthly premium"),
+    info(33, "PremiumBelowQuote", "the premium is below the pricing contract's Low-tier quote; estimate_premium gives the minimum"),
+    info(34, "StakeTokenNotSet", "the pause admin must set the stake token with set_stake_token before staking"),
+    info(35, "StakeTokenInUse", "the stake token can only be changed once every staker has withdrawn"),
+];
+
+/// Error table for a contract, by its registry name
+fn table_for(contract: &str) -> &'static [ErrorInfo] {
+    match contract {
+        "split" => SPLIT_ERRORS,
+        "bills" => BILLS_ERRORS,
+        "goals" => SAVINGS_ERRORS,
+        "insurance" => INSURANCE_ERRORS,
+        _ => &[],
+    }
+}
+
+/// Look up a contract error code
+pub fn lookup(contract: &str, code: u32) -> Option<&'static ErrorInfo> {
+    table_for(contract).iter().find(|e| e.code == code)
+}
+
+/// Extract the code from the first `Error(Contract, #<code>)` in `stderr`
+pub fn parse_contract_code(stderr: &str) -> Option<u32> {
+    let marker = "Error(Contract, #";
+    let start = stderr.find(marker)? + marker.len();
+    let digits: String = stderr[start..]
+        .chars()
+        .take_while(|c| c.is_ascii_digit())
+        .collect();
+    digits.parse().ok()
+}
+
+/// Describe the contract error in `stderr`, if it contains a known one
+pub fn describe(contract: &str, stderr: &str) -> Option<String> {
+    let code = parse_contract_code(stderr)?;
+    Some(match lookup(contract, code) {
+        Some(e) => format!("{} (#{}): {}", e.name, e.code, e.hint),
+        None => format!("unknown {} contract error #{}", contract, code),
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_describe_known_code() {
+        let stderr = "error: HostError: Error(Contract, #4)\n\nEvent log (newest first): ...";
+        assert_eq!(
+            describe("insurance", stderr).unwrap(),
+            "PolicyInactive (#4): reactivate the policy or create a new one"
+        );
+        assert!(describe("bills", stderr)
+            .unwrap()
+            .starts_with("InvalidFrequency"));
+        assert!(describe("goals", stderr).unwrap().starts_with("GoalLocked"));
+    }
+
+    #[test]
+    fn test_describe_unknown_or_missing_code() {
+        assert_eq!(
+            describe("split", "Error(Contract, #99)").unwrap(),
+            "unknown split contract error #99"
+        );
+        assert!(describe("split", "Error(WasmVm, InvalidAction)").is_none());
+    }
+
+    #[test]
+    fn test_tables_are_ordered_and_dense() {
+        for table in [SPLIT_ERRORS, BILLS_ERRORS, SAVINGS_ERRORS, INSURANCE_ERRORS] {
+            for (i, e) in table.iter().enumerate() {
+                assert_eq!(e.code, i as u32 + 1, "{}", e.name);
+            }
+        }
+    }
+}
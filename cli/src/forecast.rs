@@ -0,0 +1,306 @@
+//! Schedule inspection for `remitwise-cli schedules list`.
+//!
+//! Collects an owner's premium schedules (insurance), savings schedules
+//! (savings goals) and recurring unpaid bills (bill payments) into one list
+//! and forecasts, as of a given time, when each runs next and how many runs
+//! it has missed. Keepers execute a due schedule once and skip the periods
+//! that already passed, so the forecast adds those to the stored count.
+
+use crate::recovery::format_countdown;
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+/// One automated payment of an owner
+pub struct ScheduleEntry {
+    /// `premium`, `savings` or `bill`
+    pub kind: &'static str,
+    pub id: u64,
+    /// What the schedule pays into, e.g. `policy 3`
+    pub target: String,
+    /// Amount per run, when the schedule fixes one
+    pub amount: Option<i128>,
+    pub next_due: u64,
+    /// Seconds between runs; 0 for a one-off
+    pub interval: u64,
+    /// Runs missed so far, as recorded by the contract
+    pub missed_count: u64,
+}
+
+/// When a schedule runs next, as of some time
+#[derive(Debug, PartialEq, Eq)]
+pub struct Forecast {
+    /// Time of the next run; in the past when the schedule is overdue
+    pub next_run: u64,
+    pub overdue: bool,
+    /// Recorded misses plus the periods that passed since `next_due`
+    pub missed: u64,
+}
+
+impl ScheduleEntry {
+    pub fn forecast(&self, now: u64) -> Forecast {
+        let overdue = self.next_due <= now;
+        let pending = if overdue && self.interval > 0 {
+            (now - self.next_due) / self.interval
+        } else {
+            0
+        };
+        Forecast {
+            next_run: self.next_due,
+            overdue,
+            missed: self.missed_count + pending,
+        }
+    }
+}
+
+fn as_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        Value::Number(n) => n.as_u64(),
+        _ => None,
+    }
+}
+
+fn as_i128(value: &Value) -> Option<i128> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        Value::Number(n) => n.as_i64().map(i128::from),
+        _ => None,
+    }
+}
+
+fn field<'a>(item: &'a Value, name: &str) -> Result<&'a Value> {
+    item.get(name)
+        .ok_or_else(|| anyhow!("Schedule is missing `{}`", name))
+}
+
+fn u64_field(item: &Value, name: &str) -> Result<u64> {
+    as_u64(field(item, name)?).ok_or_else(|| anyhow!("Invalid schedule `{}`", name))
+}
+
+fn items(output: &str) -> Result<Vec<Value>> {
+    match serde_json::from_str(output.trim())? {
+        Value::Array(items) => Ok(items),
+        _ => Err(anyhow!("Expected a list of schedules")),
+    }
+}
+
+fn is_active(item: &Value) -> bool {
+    item.get("active").and_then(Value::as_bool).unwrap_or(true)
+}
+
+/// Parse the active entries of insurance `get_premium_schedules`
+pub fn parse_premium_schedules(output: &str) -> Result<Vec<ScheduleEntry>> {
+    items(output)?
+        .iter()
+        .filter(|item| is_active(item))
+        .map(|item| {
+            Ok(ScheduleEntry {
+                kind: "premium",
+                id: u64_field(item, "id")?,
+                target: format!("policy {}", u64_field(item, "policy_id")?),
+                amount: None,
+                next_due: u64_field(item, "next_due")?,
+                interval: u64_field(item, "interval")?,
+                missed_count: u64_field(item, "missed_count")?,
+            })
+        })
+        .collect()
+}
+
+/// Parse the active entries of savings goals `get_savings_schedules`
+pub fn parse_savings_schedules(output: &str) -> Result<Vec<ScheduleEntry>> {
+    items(output)?
+        .iter()
+        .filter(|item| is_active(item))
+        .map(|item| {
+            Ok(ScheduleEntry {
+                kind: "savings",
+                id: u64_field(item, "id")?,
+                target: format!("goal {}", u64_field(item, "goal_id")?),
+                amount: Some(
+                    as_i128(field(item, "amount")?)
+                        .ok_or_else(|| anyhow!("Invalid schedule `amount`"))?,
+                ),
+                next_due: u64_field(item, "next_due")?,
+                interval: u64_field(item, "interval")?,
+                missed_count: u64_field(item, "missed_count")?,
+            })
+        })
+        .collect()
+}
+
+/// Parse one page of bill payments `get_unpaid_bills`, keeping recurring
+/// bills. Returns them with the cursor of the next page (0 when done).
+///
+/// A bill records no misses; each whole billing period it is past due
+/// counts as missed in the forecast.
+pub fn parse_bill_page(output: &str) -> Result<(Vec<ScheduleEntry>, u32)> {
+    let page: Value = serde_json::from_str(output.trim())?;
+    let next_cursor = page
+        .get("next_cursor")
+        .and_then(as_u64)
+        .ok_or_else(|| anyhow!("Invalid bill page"))? as u32;
+    let bills = page
+        .get("items")
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow!("Invalid bill page"))?;
+    let entries = bills
+        .iter()
+        .filter(|bill| bill.get("recurring").and_then(Value::as_bool) == Some(true))
+        .map(|bill| {
+            let name = bill.get("name").and_then(Value::as_str).unwrap_or_default();
+            Ok(ScheduleEntry {
+                kind: "bill",
+                id: u64_field(bill, "id")?,
+                target: name.to_string(),
+                amount: Some(
+                    as_i128(field(bill, "amount")?)
+                        .ok_or_else(|| anyhow!("Invalid bill `amount`"))?,
+                ),
+                next_due: u64_field(bill, "due_date")?,
+                interval: u64_field(bill, "frequency_days")? * 86400,
+                missed_count: 0,
+            })
+        })
+        .collect::<Result<_>>()?;
+    Ok((entries, next_cursor))
+}
+
+/// Parse a window like `7d`, `12h`, `30m` or a number of seconds
+pub fn parse_window(window: &str) -> Result<u64> {
+    let window = window.trim();
+    let (number, unit) = match window.char_indices().last() {
+        Some((i, c)) if c.is_ascii_alphabetic() => (&window[..i], c),
+        _ => (window, 's'),
+    };
+    let number: u64 = number
+        .parse()
+        .map_err(|_| anyhow!("Invalid window `{}`; use e.g. 7d, 12h or 30m", window))?;
+    let unit = match unit {
+        'd' => 86400,
+        'h' => 3600,
+        'm' => 60,
+        's' => 1,
+        _ => return Err(anyhow!("Unknown window unit `{}`; use d, h, m or s", unit)),
+    };
+    Ok(number.saturating_mul(unit))
+}
+
+/// Sort `entries` by next run and keep those due by `now + due_within`
+pub fn upcoming(
+    mut entries: Vec<ScheduleEntry>,
+    now: u64,
+    due_within: Option<u64>,
+) -> Vec<ScheduleEntry> {
+    if let Some(window) = due_within {
+        let until = now.saturating_add(window);
+        entries.retain(|entry| entry.next_due <= until);
+    }
+    entries.sort_by_key(|entry| (entry.next_due, entry.kind, entry.id));
+    entries
+}
+
+/// Render `entries` as a table, as of `now` (unix seconds)
+pub fn render(entries: &[ScheduleEntry], now: u64) -> String {
+    let mut out = format!(
+        "{:<8} {:>5}  {:<20} {:>14}  {:<24} {:>6}\n",
+        "KIND", "ID", "TARGET", "AMOUNT", "NEXT RUN", "MISSED"
+    );
+    for entry in entries {
+        let forecast = entry.forecast(now);
+        let next_run = if forecast.overdue {
+            format!("overdue {}", format_countdown(now - forecast.next_run))
+        } else {
+            format!("in {}", format_countdown(forecast.next_run - now))
+        };
+        let amount = entry
+            .amount
+            .map(|amount| amount.to_string())
+            .unwrap_or_else(|| "-".to_string());
+        out.push_str(&format!(
+            "{:<8} {:>5}  {:<20} {:>14}  {:<24} {:>6}\n",
+            entry.kind, entry.id, entry.target, amount, next_run, forecast.missed
+        ));
+    }
+    if entries.is_empty() {
+        out.push_str("  no schedules\n");
+    }
+    out
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_and_forecast() {
+        let premiums = parse_premium_schedules(
+            r#"[{"id":1,"policy_id":3,"next_due":1000,"interval":100,"active":true,"missed_count":2},
+                {"id":2,"policy_id":4,"next_due":1000,"interval":100,"active":false,"missed_count":0}]"#,
+        )
+        .unwrap();
+        assert_eq!(premiums.len(), 1);
+        assert_eq!(premiums[0].target, "policy 3");
+        assert_eq!(
+            premiums[0].forecast(1_250),
+            Forecast {
+                next_run: 1_000,
+                overdue: true,
+                missed: 4
+            }
+        );
+
+        let savings = parse_savings_schedules(
+            r#"[{"id":5,"goal_id":2,"amount":"300","next_due":5000,"interval":86400,"active":true,"missed_count":0}]"#,
+        )
+        .unwrap();
+        assert_eq!(savings[0].amount, Some(300));
+        assert!(!savings[0].forecast(1_000).overdue);
+
+        let (bills, cursor) = parse_bill_page(
+            r#"{"items":[{"id":7,"name":"Rent","amount":"900","due_date":100,"recurring":true,"frequency_days":30},
+                         {"id":8,"name":"Once","amount":"10","due_date":100,"recurring":false,"frequency_days":0}],
+                "next_cursor":0,"count":2}"#,
+        )
+        .unwrap();
+        assert_eq!(cursor, 0);
+        assert_eq!(bills.len(), 1);
+        assert_eq!(bills[0].interval, 30 * 86400);
+        assert_eq!(bills[0].forecast(100 + 61 * 86400).missed, 2);
+    }
+
+    #[test]
+    fn test_window_filter_and_render() {
+        assert_eq!(parse_window("7d").unwrap(), 7 * 86400);
+        assert_eq!(parse_window("12h").unwrap(), 12 * 3600);
+        assert_eq!(parse_window("90").unwrap(), 90);
+        assert!(parse_window("7w").is_err());
+
+        let entry = |id, next_due| ScheduleEntry {
+            kind: "premium",
+            id,
+            target: "policy 1".into(),
+            amount: None,
+            next_due,
+            interval: 86400,
+            missed_count: 0,
+        };
+        let now = 1_000_000;
+        let entries = upcoming(
+            vec![
+                entry(2, now + 10 * 86400),
+                entry(1, now + 3600),
+                entry(3, now - 60),
+            ],
+            now,
+            Some(7 * 86400),
+        );
+        let ids: Vec<u64> = entries.iter().map(|entry| entry.id).collect();
+        assert_eq!(ids, vec![3, 1]);
+
+        let out = render(&entries, now);
+        assert!(out.contains("overdue 1m"));
+        assert!(out.contains("in 1h 00m"));
+        assert!(render(&[], now).contains("no schedules"));
+    }
+}
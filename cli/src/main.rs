@@ -1,7 +1,20 @@
 use anyhow::{anyhow, Result};
-use clap::{Parser, Subcommand};
+use clap::{ArgGroup, Parser, Subcommand, ValueEnum};
 use std::env;
-use std::process::Command;
+use std::fs;
+use std::io::Write;
+use std::path::{Path, PathBuf};
+use std::process::{Command, Stdio};
+use std::time::{SystemTime, UNIX_EPOCH};
+
+mod balances;
+mod doctor;
+mod e2e;
+mod errors;
+mod forecast;
+mod recovery;
+mod report;
+mod schedule;
 
 #[derive(Parser)]
 #[command(name = "remitwise-cli")]
@@ -33,12 +46,163 @@ enum Commands {
         #[command(subcommand)]
         subcommand: InsuranceCommands,
     },
+    /// Guardian recovery of an owner's goals, bills or policies
+    Recovery {
+        #[command(subcommand)]
+        subcommand: RecoveryCommands,
+    },
+    /// Inspect premium, savings and bill schedules across contracts
+    Schedules {
+        #[command(subcommand)]
+        subcommand: SchedulesCommands,
+    },
+    /// Summarize a month of remittances, bills, premiums and savings deposits
+    Report {
+        /// Owner to report on (defaults to OWNER_ADDRESS)
+        #[arg(long)]
+        owner: Option<String>,
+        /// Calendar month (UTC) as YYYY-MM
+        #[arg(long, value_parser = report::parse_month)]
+        month: report::Month,
+        #[arg(long, value_enum, default_value_t = report::ReportFormat::Table)]
+        format: report::ReportFormat,
+    },
+    /// Show token balances of the split accounts, the split contract's allowance,
+    /// the amount owed on unpaid bills and goal balances
+    Balances {
+        /// Owner to inspect (defaults to OWNER_ADDRESS)
+        #[arg(long)]
+        owner: Option<String>,
+    },
+    /// Check contract versions, admins and pause states for known problems
+    Doctor,
+    /// Deploy all contracts and run a scripted end-to-end flow against them
+    E2e {
+        #[arg(long, default_value = "local")]
+        network: String,
+        /// Identity that deploys and owns everything (created and funded if missing)
+        #[arg(long, default_value = "e2e")]
+        source: String,
+        /// Directory holding the built contract wasm files
+        #[arg(long, default_value = "target/wasm32-unknown-unknown/release")]
+        wasm_dir: PathBuf,
+    },
+    /// Admin operations, signed by `ADMIN_ADDRESS`
+    ///
+    /// With `--sign-only` the transaction is only assembled, simulated and
+    /// written to a file, so it can be signed offline (by one or more signers with
+    /// `admin sign`) and submitted later with `--submit-signed`.
+    Admin {
+        /// Write the unsigned transaction XDR to FILE instead of submitting it
+        #[arg(long, value_name = "FILE", global = true)]
+        sign_only: Option<PathBuf>,
+        /// Submit a previously signed transaction XDR from FILE
+        #[arg(long, value_name = "FILE")]
+        submit_signed: Option<PathBuf>,
+        #[command(subcommand)]
+        subcommand: Option<AdminCommands>,
+    },
+}
+
+/// Contract targeted by an admin operation
+#[derive(Clone, Copy, ValueEnum)]
+enum ContractName {
+    Split,
+    Goals,
+    Bills,
+    Insurance,
+}
+
+impl ContractName {
+    const ALL: [ContractName; 4] = [
+        ContractName::Split,
+        ContractName::Goals,
+        ContractName::Bills,
+        ContractName::Insurance,
+    ];
+
+    fn registry_name(self) -> &'static str {
+        match self {
+            ContractName::Split => "split",
+            ContractName::Goals => "goals",
+            ContractName::Bills => "bills",
+            ContractName::Insurance => "insurance",
+        }
+    }
+
+    fn contract_id(self) -> Result<String> {
+        match self {
+            ContractName::Split => get_contract_id("REMITTANCE_SPLIT_CONTRACT_ID", "split"),
+            ContractName::Goals => get_contract_id("SAVINGS_GOALS_CONTRACT_ID", "goals"),
+            ContractName::Bills => get_contract_id("BILL_PAYMENTS_CONTRACT_ID", "bills"),
+            ContractName::Insurance => get_contract_id("INSURANCE_CONTRACT_ID", "insurance"),
+        }
+    }
+}
+
+/// Contract whose holdings a guardian recovery moves
+#[derive(Clone, Copy, ValueEnum)]
+enum RecoveryContract {
+    Goals,
+    Bills,
+    Insurance,
+}
+
+impl RecoveryContract {
+    const ALL: [RecoveryContract; 3] = [
+        RecoveryContract::Goals,
+        RecoveryContract::Bills,
+        RecoveryContract::Insurance,
+    ];
+
+    fn registry_name(self) -> &'static str {
+        match self {
+            RecoveryContract::Goals => "goals",
+            RecoveryContract::Bills => "bills",
+            RecoveryContract::Insurance => "insurance",
+        }
+    }
+
+    fn contract_id(self) -> Result<String> {
+        match self {
+            RecoveryContract::Goals => ContractName::Goals.contract_id(),
+            RecoveryContract::Bills => ContractName::Bills.contract_id(),
+            RecoveryContract::Insurance => ContractName::Insurance.contract_id(),
+        }
+    }
 }
 
 #[derive(Subcommand)]
 enum SplitCommands {
     /// Get split configuration
     GetConfig,
+    /// Distribute USDC from OWNER_ADDRESS to the accounts set in the environment
+    Distribute {
+        amount: u64,
+        /// Purpose code (1 = family support, 2 = school fees, 3 = medical, 4 = other)
+        #[arg(long, default_value_t = 1)]
+        purpose: u32,
+    },
+    /// Print a cron entry or systemd units that run `split distribute` on a schedule
+    #[command(group(ArgGroup::new("frequency").required(true)))]
+    #[command(group(ArgGroup::new("format").required(true)))]
+    Schedule {
+        #[arg(long, group = "frequency")]
+        daily: bool,
+        #[arg(long, group = "frequency")]
+        weekly: bool,
+        #[arg(long, group = "frequency")]
+        monthly: bool,
+        #[arg(long)]
+        amount: u64,
+        /// Environment file to load, as ~/.config/remitwise/<PROFILE>.env
+        #[arg(long, default_value = "default")]
+        profile: String,
+        #[arg(long, group = "format")]
+        emit_systemd: bool,
+        #[arg(long, group = "format")]
+        emit_cron: bool,
+    },
 }
 
 #[derive(Subcommand)]
@@ -67,6 +231,92 @@ enum InsuranceCommands {
     List,
 }
 
+#[derive(Subcommand)]
+enum SchedulesCommands {
+    /// List active schedules with their next run and missed runs
+    List {
+        /// Owner to inspect (defaults to OWNER_ADDRESS)
+        #[arg(long)]
+        owner: Option<String>,
+        /// Only show schedules due within this window, e.g. 7d, 12h or 30m
+        #[arg(long, value_parser = forecast::parse_window)]
+        due_within: Option<u64>,
+    },
+}
+
+#[derive(Subcommand)]
+enum RecoveryCommands {
+    /// Register OWNER_ADDRESS's guardians; none turns recovery off
+    SetGuardians {
+        contract: RecoveryContract,
+        /// Guardian approvals needed to start the veto window
+        #[arg(long, default_value_t = 1)]
+        threshold: u32,
+        guardians: Vec<String>,
+    },
+    /// Start recovering an owner's holdings to a new address, as a guardian
+    Initiate {
+        contract: RecoveryContract,
+        owner: String,
+        new_owner: String,
+        /// Guardian signing the call (defaults to GUARDIAN_ADDRESS)
+        #[arg(long)]
+        guardian: Option<String>,
+    },
+    /// Approve an owner's recovery, as a guardian
+    Approve {
+        contract: RecoveryContract,
+        owner: String,
+        /// Guardian signing the call (defaults to GUARDIAN_ADDRESS)
+        #[arg(long)]
+        guardian: Option<String>,
+    },
+    /// Stop the recovery of OWNER_ADDRESS's holdings
+    Veto { contract: RecoveryContract },
+    /// Complete a recovery once its veto window is over, as the new owner
+    Execute {
+        contract: RecoveryContract,
+        owner: String,
+        /// The recovery's new owner, who signs the call
+        #[arg(long)]
+        new_owner: String,
+    },
+    /// Show guardians and any recovery in progress, on one or all contracts
+    Status {
+        contract: Option<RecoveryContract>,
+        /// Owner to inspect (defaults to OWNER_ADDRESS)
+        #[arg(long)]
+        owner: Option<String>,
+    },
+}
+
+#[derive(Subcommand)]
+enum AdminCommands {
+    /// Pause a contract
+    Pause { contract: ContractName },
+    /// Unpause a contract
+    Unpause { contract: ContractName },
+    /// Hand the pause admin (or, with --upgrade, the upgrade admin) role over
+    SetAdmin {
+        contract: ContractName,
+        new_admin: String,
+        #[arg(long)]
+        upgrade: bool,
+    },
+    /// Record a new contract version (upgrade admin only)
+    Upgrade {
+        contract: ContractName,
+        version: u32,
+    },
+    /// Add a signature to a transaction file written by --sign-only
+    Sign {
+        file: PathBuf,
+        /// Identity or secret key to sign with
+        #[arg(long)]
+        signer: String,
+    },
+}
+
 #[tokio::main]
 async fn main() -> Result<()> {
     let cli = Cli::parse();
@@ -76,26 +326,114 @@ async fn main() -> Result<()> {
         Commands::Goals { subcommand } => handle_goals(subcommand).await,
         Commands::Bills { subcommand } => handle_bills(subcommand).await,
         Commands::Insurance { subcommand } => handle_insurance(subcommand).await,
+        Commands::Schedules { subcommand } => handle_schedules(subcommand).await,
+        Commands::Recovery { subcommand } => handle_recovery(subcommand).await,
+        Commands::Report {
+            owner,
+            month,
+            format,
+        } => handle_report(owner, month, format).await,
+        Commands::Balances { owner } => handle_balances(owner).await,
+        Commands::Doctor => handle_doctor().await,
+        Commands::E2e {
+            network,
+            source,
+            wasm_dir,
+        } => e2e::E2e {
+            network,
+            source,
+            wasm_dir,
+        }
+        .run(),
+        Commands::Admin {
+            sign_only,
+            submit_signed,
+            subcommand,
+        } => handle_admin(sign_only, submit_signed, subcommand).await,
     }
 }
 
 async fn handle_split(subcommand: SplitCommands) -> Result<()> {
-    let contract_id = get_contract_id("REMITTANCE_SPLIT_CONTRACT_ID")?;
+    const SPLIT_ID_VAR: &str = "REMITTANCE_SPLIT_CONTRACT_ID";
     match subcommand {
         SplitCommands::GetConfig => {
-            run_soroban_invoke(&contract_id, "get_config", &[]).await?;
+            let contract_id = get_contract_id(SPLIT_ID_VAR, "split")?;
+            let owner = get_env("OWNER_ADDRESS")?;
+            run_soroban_invoke("split", &contract_id, "get_config", &[&owner]).await?;
+        }
+        SplitCommands::Distribute { amount, purpose } => {
+            let contract_id = get_contract_id(SPLIT_ID_VAR, "split")?;
+            let owner = get_env("OWNER_ADDRESS")?;
+            let usdc = get_env("USDC_CONTRACT_ID")?;
+            let accounts = format!(
+                r#"{{"spending":"{}","savings":"{}","bills":"{}","insurance":"{}"}}"#,
+                get_env("SPENDING_ACCOUNT")?,
+                get_env("SAVINGS_ACCOUNT")?,
+                get_env("BILLS_ACCOUNT")?,
+                get_env("INSURANCE_ACCOUNT")?
+            );
+            let nonce =
+                soroban_invoke_output("split", &contract_id, "get_nonce", &["--address", &owner])?;
+            run_soroban_invoke(
+                "split",
+                &contract_id,
+                "distribute_usdc",
+                &[
+                    "--usdc_contract",
+                    &usdc,
+                    "--from",
+                    &owner,
+                    "--nonce",
+                    nonce.trim_matches('"'),
+                    "--accounts",
+                    &accounts,
+                    "--total_amount",
+                    &amount.to_string(),
+                    "--purpose",
+                    &purpose.to_string(),
+                ],
+            )
+            .await?;
+        }
+        SplitCommands::Schedule {
+            daily,
+            weekly,
+            amount,
+            profile,
+            emit_systemd,
+            ..
+        } => {
+            let frequency = if daily {
+                schedule::Frequency::Daily
+            } else if weekly {
+                schedule::Frequency::Weekly
+            } else {
+                schedule::Frequency::Monthly
+            };
+            let cli_path = env::current_exe()?;
+            let schedule = schedule::Schedule {
+                frequency,
+                amount,
+                profile: &profile,
+                cli_path: &cli_path.to_string_lossy(),
+            };
+            if emit_systemd {
+                print!("{}", schedule.to_systemd());
+            } else {
+                print!("{}", schedule.to_cron());
+            }
         }
     }
     Ok(())
 }
 
 async fn handle_goals(subcommand: GoalsCommands) -> Result<()> {
-    let contract_id = get_contract_id("SAVINGS_GOALS_CONTRACT_ID")?;
+    let contract_id = get_contract_id("SAVINGS_GOALS_CONTRACT_ID", "goals")?;
     match subcommand {
         GoalsCommands::List => {
             // Need owner address
             let owner = get_env("OWNER_ADDRESS")?;
-            run_soroban_invoke(&contract_id, "get_all_goals", &[&owner]).await?;
+            run_soroban_invoke("goals", &contract_id, "get_all_goals", &[&owner]).await?;
         }
         GoalsCommands::Create {
             name,
@@ -104,6 +442,7 @@ async fn handle_goals(subcommand: GoalsCommands) -> Result<()> {
         } => {
             let owner = get_env("OWNER_ADDRESS")?;
             run_soroban_invoke(
+                "goals",
                 &contract_id,
                 "create_goal",
                 &[
@@ -120,56 +459,666 @@ async fn handle_goals(subcommand: GoalsCommands) -> Result<()> {
 }
 
 async fn handle_bills(subcommand: BillsCommands) -> Result<()> {
-    let contract_id = get_contract_id("BILL_PAYMENTS_CONTRACT_ID")?;
+    let contract_id = get_contract_id("BILL_PAYMENTS_CONTRACT_ID", "bills")?;
     match subcommand {
         BillsCommands::List => {
             let owner = get_env("OWNER_ADDRESS")?;
-            run_soroban_invoke(&contract_id, "get_unpaid_bills", &[&owner, "0", "10"]).await?;
+            run_soroban_invoke(
+                "bills",
+                &contract_id,
+                "get_unpaid_bills",
+                &[&owner, "0", "10"],
+            )
+            .await?;
         }
         BillsCommands::Pay { bill_id } => {
             let owner = get_env("OWNER_ADDRESS")?;
-            run_soroban_invoke(&contract_id, "pay_bill", &[&owner, &bill_id.to_string()]).await?;
+            run_soroban_invoke(
+                "bills",
+                &contract_id,
+                "pay_bill",
+                &[&owner, &bill_id.to_string()],
+            )
+            .await?;
         }
     }
     Ok(())
 }
 
 async fn handle_insurance(subcommand: InsuranceCommands) -> Result<()> {
-    let contract_id = get_contract_id("INSURANCE_CONTRACT_ID")?;
+    let contract_id = get_contract_id("INSURANCE_CONTRACT_ID", "insurance")?;
     match subcommand {
         InsuranceCommands::List => {
             let owner = get_env("OWNER_ADDRESS")?;
-            run_soroban_invoke(&contract_id, "get_active_policies", &[&owner, "0", "10"]).await?;
+            run_soroban_invoke(
+                "insurance",
+                &contract_id,
+                "get_active_policies",
+                &[&owner, "0", "10"],
+            )
+            .await?;
         }
     }
     Ok(())
 }
 
-fn get_contract_id(env_var: &str) -> Result<String> {
-    env::var(env_var).map_err(|_| anyhow!("Environment variable {} not set", env_var))
+async fn handle_schedules(subcommand: SchedulesCommands) -> Result<()> {
+    let SchedulesCommands::List { owner, due_within } = subcommand;
+    let owner = match owner {
+        Some(owner) => owner,
+        None => get_env("OWNER_ADDRESS")?,
+    };
+    let insurance_id = get_contract_id("INSURANCE_CONTRACT_ID", "insurance")?;
+    let premiums = soroban_invoke_output(
+        "insurance",
+        &insurance_id,
+        "get_premium_schedules",
+        &["--owner", &owner],
+    )?;
+    let mut entries = forecast::parse_premium_schedules(&premiums)?;
+
+    let goals_id = get_contract_id("SAVINGS_GOALS_CONTRACT_ID", "goals")?;
+    let savings = soroban_invoke_output(
+        "goals",
+        &goals_id,
+        "get_savings_schedules",
+        &["--owner", &owner],
+    )?;
+    entries.extend(forecast::parse_savings_schedules(&savings)?);
+
+    let bills_id = get_contract_id("BILL_PAYMENTS_CONTRACT_ID", "bills")?;
+    let mut cursor = 0u32;
+    loop {
+        let page = soroban_invoke_output(
+            "bills",
+            &bills_id,
+            "get_unpaid_bills",
+            &[
+                "--owner",
+                &owner,
+                "--cursor",
+                &cursor.to_string(),
+                "--limit",
+                "50",
+            ],
+        )?;
+        let (bills, next_cursor) = forecast::parse_bill_page(&page)?;
+        entries.extend(bills);
+        if next_cursor == 0 {
+            break;
+        }
+        cursor = next_cursor;
+    }
+
+    let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+    let entries = forecast::upcoming(entries, now, due_within);
+    print!("{}", forecast::render(&entries, now));
+    Ok(())
+}
+
+async fn handle_recovery(subcommand: RecoveryCommands) -> Result<()> {
+    let guardian_or_env = |guardian: Option<String>| match guardian {
+        Some(guardian) => Ok(guardian),
+        None => get_env("GUARDIAN_ADDRESS"),
+    };
+    let (contract, signer, function, args) = match subcommand {
+        RecoveryCommands::SetGuardians {
+            contract,
+            threshold,
+            guardians,
+        } => {
+            let owner = get_env("OWNER_ADDRESS")?;
+            let guardians = serde_json::to_string(&guardians)?;
+            let args = vec![
+                "--owner".to_string(),
+                owner.clone(),
+                "--guardians".into(),
+                guardians,
+                "--threshold".into(),
+                threshold.to_string(),
+            ];
+            (contract, owner, "set_recovery_guardians", args)
+        }
+        RecoveryCommands::Initiate {
+            contract,
+            owner,
+            new_owner,
+            guardian,
+        } => {
+            let guardian = guardian_or_env(guardian)?;
+            let args = vec![
+                "--guardian".to_string(),
+                guardian.clone(),
+                "--owner".into(),
+                owner,
+                "--new_owner".into(),
+                new_owner,
+            ];
+            (contract, guardian, "initiate_recovery", args)
+        }
+        RecoveryCommands::Approve {
+            contract,
+            owner,
+            guardian,
+        } => {
+            let guardian = guardian_or_env(guardian)?;
+            let args = vec![
+                "--guardian".to_string(),
+                guardian.clone(),
+                "--owner".into(),
+                owner,
+            ];
+            (contract, guardian, "approve_recovery", args)
+        }
+        RecoveryCommands::Veto { contract } => {
+            let owner = get_env("OWNER_ADDRESS")?;
+            let args = vec!["--owner".to_string(), owner.clone()];
+            (contract, owner, "veto_recovery", args)
+        }
+        RecoveryCommands::Execute {
+            contract,
+            owner,
+            new_owner,
+        } => {
+            let args = vec!["--owner".to_string(), owner];
+            (contract, new_owner, "execute_recovery", args)
+        }
+        RecoveryCommands::Status { contract, owner } => {
+            let owner = match owner {
+                Some(owner) => owner,
+                None => get_env("OWNER_ADDRESS")?,
+            };
+            let contracts = match contract {
+                Some(contract) => vec![contract],
+                None => RecoveryContract::ALL.to_vec(),
+            };
+            let now = SystemTime::now().duration_since(UNIX_EPOCH)?.as_secs();
+            for contract in contracts {
+                let name = contract.registry_name();
+                let contract_id = contract.contract_id()?;
+                let args = ["--owner", owner.as_str()];
+                let config =
+                    soroban_invoke_output(name, &contract_id, "get_recovery_config", &args)?;
+                let request =
+                    soroban_invoke_output(name, &contract_id, "get_recovery_request", &args)?;
+                let status = recovery::RecoveryStatus {
+                    contract: name,
+                    config: recovery::parse_config(&config)?,
+                    request: recovery::parse_request(&request)?,
+                };
+                print!("{}", status.render(now));
+            }
+            return Ok(());
+        }
+    };
+
+    let args: Vec<&str> = args.iter().map(String::as_str).collect();
+    let output = soroban_invoke_signed(
+        contract.registry_name(),
+        &contract.contract_id()?,
+        Some(&signer),
+        function,
+        &args,
+    )?;
+    println!("{}", output);
+    Ok(())
+}
+
+async fn handle_balances(owner: Option<String>) -> Result<()> {
+    let owner = match owner {
+        Some(owner) => owner,
+        None => get_env("OWNER_ADDRESS")?,
+    };
+    // The split config stores percentages only; the owner's inbound watch is
+    // where the contract keeps destination accounts and a token.
+    let split_id = get_contract_id("REMITTANCE_SPLIT_CONTRACT_ID", "split")?;
+    let watch = soroban_invoke_output(
+        "split",
+        &split_id,
+        "get_inbound_watch",
+        &["--owner", &owner],
+    )?;
+    let watched = match balances::parse_inbound_watch(&watch)? {
+        Some(watched) => watched,
+        None => balances::WatchedAccounts {
+            token: get_env("USDC_CONTRACT_ID")?,
+            accounts: vec![
+                ("spending", get_env("SPENDING_ACCOUNT")?),
+                ("savings", get_env("SAVINGS_ACCOUNT")?),
+                ("bills", get_env("BILLS_ACCOUNT")?),
+                ("insurance", get_env("INSURANCE_ACCOUNT")?),
+            ],
+        },
+    };
+    let mut accounts = Vec::new();
+    for (category, address) in watched.accounts {
+        let balance =
+            soroban_invoke_output("token", &watched.token, "balance", &["--id", &address])?;
+        accounts.push((category, address, balances::parse_amount(&balance)?));
+    }
+    let allowance = soroban_invoke_output(
+        "token",
+        &watched.token,
+        "allowance",
+        &["--from", &owner, "--spender", &split_id],
+    )?;
+
+    let bills_id = get_contract_id("BILL_PAYMENTS_CONTRACT_ID", "bills")?;
+    let unpaid =
+        soroban_invoke_output("bills", &bills_id, "get_total_unpaid", &["--owner", &owner])?;
+    let goals_id = get_contract_id("SAVINGS_GOALS_CONTRACT_ID", "goals")?;
+    let goals = soroban_invoke_output("goals", &goals_id, "get_all_goals", &["--owner", &owner])?;
+
+    let view = balances::FundsView {
+        token: watched.token,
+        accounts,
+        split_allowance: balances::parse_amount(&allowance)?,
+        unpaid_bills: balances::parse_amount(&unpaid)?,
+        goals: balances::parse_goals(&goals)?,
+    };
+    print!("{}", view.render());
+    Ok(())
+}
+
+async fn handle_report(
+    owner: Option<String>,
+    month: report::Month,
+    format: report::ReportFormat,
+) -> Result<()> {
+    let owner = match owner {
+        Some(owner) => owner,
+        None => get_env("OWNER_ADDRESS")?,
+    };
+    let (from, to) = (month.start.to_string(), month.end.to_string());
+    let window = [
+        "--owner",
+        owner.as_str(),
+        "--from_ts",
+        &from,
+        "--to_ts",
+        &to,
+    ];
+
+    let split_id = get_contract_id("REMITTANCE_SPLIT_CONTRACT_ID", "split")?;
+    let history = soroban_invoke_output(
+        "split",
+        &split_id,
+        "get_remittance_history",
+        &["--sender", &owner, "--from_index", "0", "--limit", "200"],
+    )?;
+    let remittances = report::parse_remittances(&history, &month)?;
+
+    let bills_id = get_contract_id("BILL_PAYMENTS_CONTRACT_ID", "bills")?;
+    let bills = soroban_invoke_output("bills", &bills_id, "get_statement", &window)?;
+    let insurance_id = get_contract_id("INSURANCE_CONTRACT_ID", "insurance")?;
+    let premiums_paid = soroban_invoke_output(
+        "insurance",
+        &insurance_id,
+        "get_premium_paid_total",
+        &window,
+    )?;
+
+    let goals_id = get_contract_id("SAVINGS_GOALS_CONTRACT_ID", "goals")?;
+    let goals = soroban_invoke_output("goals", &goals_id, "get_all_goals", &["--owner", &owner])?;
+    let mut savings = Vec::new();
+    for goal in balances::parse_goals(&goals)? {
+        let history = soroban_invoke_output(
+            "goals",
+            &goals_id,
+            "get_contribution_history",
+            &["--goal_id", &goal.id.to_string()],
+        )?;
+        let (count, amount) = report::parse_contributions(&history, &month)?;
+        if count > 0 {
+            savings.push(report::GoalContributions {
+                goal_id: goal.id,
+                name: goal.name,
+                count,
+                amount,
+            });
+        }
+    }
+
+    let report = report::Report {
+        owner,
+        month,
+        remittances,
+        bills: report::parse_bill_statement(&bills)?,
+        premiums_paid: balances::parse_amount(&premiums_paid)?,
+        savings,
+    };
+    print!("{}", report.render(format));
+    Ok(())
+}
+
+async fn handle_doctor() -> Result<()> {
+    let admin = env::var("ADMIN_ADDRESS").ok();
+    let mut reports = Vec::new();
+    for contract in ContractName::ALL {
+        let name = contract.registry_name();
+        let state = contract.contract_id().and_then(|contract_id| {
+            let query = |function| soroban_invoke_output(name, &contract_id, function, &[]);
+            Ok(doctor::ContractState {
+                version: doctor::parse_version(&query("get_version")?)?,
+                paused: doctor::parse_flag(&query("is_paused")?)?,
+                pause_admin: doctor::parse_admin(&query("get_pause_admin_public")?)?,
+                upgrade_admin: doctor::parse_admin(&query("get_upgrade_admin_public")?)?,
+            })
+        });
+        reports.push(match state {
+            Ok(state) => doctor::check(name, &state, admin.as_deref()),
+            Err(err) => doctor::ContractReport::unreachable(name, err.to_string()),
+        });
+    }
+    if admin.is_none() {
+        println!("warning: ADMIN_ADDRESS is not set; admin commands will not run\n");
+    }
+    print!("{}", doctor::render(&reports));
+    if reports
+        .iter()
+        .any(|report| report.severity() == doctor::Severity::Error)
+    {
+        return Err(anyhow!("doctor found errors"));
+    }
+    Ok(())
+}
+
+/// What an `admin` invocation asks for, once its flags are checked
+enum AdminRequest {
+    /// Send the signed transaction in the file
+    SubmitSigned(PathBuf),
+    /// Run the command, or with a file only assemble its transaction
+    Run {
+        command: AdminCommands,
+        sign_only: Option<PathBuf>,
+    },
+}
+
+fn admin_request(
+    sign_only: Option<PathBuf>,
+    submit_signed: Option<PathBuf>,
+    subcommand: Option<AdminCommands>,
+) -> Result<AdminRequest> {
+    match (submit_signed, subcommand) {
+        (Some(_), _) if sign_only.is_some() => Err(anyhow!(
+            "--submit-signed cannot be combined with --sign-only"
+        )),
+        (Some(_), Some(_)) => Err(anyhow!(
+            "--submit-signed cannot be combined with other admin commands"
+        )),
+        (Some(file), None) => Ok(AdminRequest::SubmitSigned(file)),
+        (None, Some(command)) => Ok(AdminRequest::Run { command, sign_only }),
+        (None, None) => Err(anyhow!(
+            "Specify an admin command or --submit-signed <FILE>"
+        )),
+    }
+}
+
+async fn handle_admin(
+    sign_only: Option<PathBuf>,
+    submit_signed: Option<PathBuf>,
+    subcommand: Option<AdminCommands>,
+) -> Result<()> {
+    let (subcommand, sign_only) = match admin_request(sign_only, submit_signed, subcommand)? {
+        AdminRequest::SubmitSigned(file) => {
+            let signed = fs::read_to_string(&file)?;
+            let output = run_soroban_tx("send", &[], signed.trim())?;
+            println!("{}", output);
+            return Ok(());
+        }
+        AdminRequest::Run { command, sign_only } => (command, sign_only),
+    };
+    let admin = get_env("ADMIN_ADDRESS")?;
+    let (contract, function, args): (ContractName, &str, Vec<String>) = match subcommand {
+        AdminCommands::Pause { contract } => (contract, "pause", vec![]),
+        AdminCommands::Unpause { contract } => (contract, "unpause", vec![]),
+        AdminCommands::SetAdmin {
+            contract,
+            new_admin,
+            upgrade,
+        } => {
+            let function = if upgrade {
+                "set_upgrade_admin"
+            } else {
+                "set_pause_admin"
+            };
+            (contract, function, vec!["--new_admin".into(), new_admin])
+        }
+        AdminCommands::Upgrade { contract, version } => (
+            contract,
+            "set_version",
+            vec!["--new_version".into(), version.to_string()],
+        ),
+        AdminCommands::Sign { file, signer } => return sign_transaction_file(&file, &signer),
+    };
+
+    let contract_id = contract.contract_id()?;
+    let mut cmd = Command::new("soroban");
+    cmd.arg("contract")
+        .arg("invoke")
+        .arg("--id")
+        .arg(&contract_id)
+        .arg("--source")
+        .arg(&admin);
+    if sign_only.is_some() {
+        cmd.arg("--sim-only");
+    }
+    cmd.arg("--").arg(function).arg("--caller").arg(&admin);
+    cmd.args(&args);
+    let output = cmd.output()?;
+    if !output.status.success() {
+        eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+        return Err(anyhow!("{} failed", function));
+    }
+    let stdout = String::from_utf8_lossy(&output.stdout);
+    match sign_only {
+        Some(file) => {
+            // --sim-only returns the transaction after simulation, with the
+            // footprint and resource fees the network requires.
+            fs::write(&file, stdout.trim())?;
+            println!(
+                "Unsigned {} transaction written to {}",
+                function,
+                file.display()
+            );
+        }
+        None => println!("{}", stdout),
+    }
+    Ok(())
+}
+
+/// Sign the transaction in `file` with `signer` and write it back, so several
+/// signers can add their signatures in turn.
+fn sign_transaction_file(file: &Path, signer: &str) -> Result<()> {
+    let xdr = fs::read_to_string(file)?;
+    let signed = run_soroban_tx("sign", &["--sign-with-key", signer], xdr.trim())?;
+    fs::write(file, signed)?;
+    println!("Signed {} with {}", file.display(), signer);
+    Ok(())
+}
+
+/// Run `soroban tx <action>` with the transaction XDR on stdin and return
+/// its trimmed stdout.
+fn run_soroban_tx(action: &str, args: &[&str], xdr: &str) -> Result<String> {
+    let mut child = Command::new("soroban")
+        .arg("tx")
+        .arg(action)
+        .args(args)
+        .stdin(Stdio::piped())
+        .stdout(Stdio::piped())
+        .stderr(Stdio::piped())
+        .spawn()?;
+    child
+        .stdin
+        .take()
+        .ok_or_else(|| anyhow!("Failed to open soroban stdin"))?
+        .write_all(xdr.as_bytes())?;
+    let output = child.wait_with_output()?;
+    if !output.status.success() {
+        eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+        return Err(anyhow!("soroban tx {} failed", action));
+    }
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+/// Resolve a contract ID from its env var, falling back to the on-chain
+/// registry (`REGISTRY_CONTRACT_ID`) under the given registry name.
+fn get_contract_id(env_var: &str, registry_name: &str) -> Result<String> {
+    if let Ok(contract_id) = env::var(env_var) {
+        return Ok(contract_id);
+    }
+    let registry_id = env::var("REGISTRY_CONTRACT_ID").map_err(|_| {
+        anyhow!(
+            "Environment variable {} not set and REGISTRY_CONTRACT_ID not set",
+            env_var
+        )
+    })?;
+    let network = get_env("SOROBAN_NETWORK")?;
+    resolve_from_registry(&registry_id, &network, registry_name)
+}
+
+fn resolve_from_registry(registry_id: &str, network: &str, name: &str) -> Result<String> {
+    let output = Command::new("soroban")
+        .arg("contract")
+        .arg("invoke")
+        .arg("--id")
+        .arg(registry_id)
+        .arg("--")
+        .arg("get_address")
+        .arg("--network")
+        .arg(network)
+        .arg("--name")
+        .arg(name)
+        .output()?;
+    if !output.status.success() {
+        eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+        return Err(anyhow!("Registry lookup for {} failed", name));
+    }
+    let address = String::from_utf8_lossy(&output.stdout)
+        .trim()
+        .trim_matches('"')
+        .to_string();
+    if address.is_empty() || address == "null" {
+        return Err(anyhow!(
+            "No {} address registered for network {}",
+            name,
+            network
+        ));
+    }
+    Ok(address)
 }
 
 fn get_env(env_var: &str) -> Result<String> {
     env::var(env_var).map_err(|_| anyhow!("Environment variable {} not set", env_var))
 }
 
-async fn run_soroban_invoke(contract_id: &str, function: &str, args: &[&str]) -> Result<()> {
+/// Invoke `function` on a contract and print its result.
+async fn run_soroban_invoke(
+    contract: &str,
+    contract_id: &str,
+    function: &str,
+    args: &[&str],
+) -> Result<()> {
+    let output = soroban_invoke_output(contract, contract_id, function, args)?;
+    println!("{}", output);
+    Ok(())
+}
+
+/// Invoke `function` on a contract and return its trimmed output. `contract`
+/// is its registry name, used to decode contract error codes when the call
+/// fails.
+fn soroban_invoke_output(
+    contract: &str,
+    contract_id: &str,
+    function: &str,
+    args: &[&str],
+) -> Result<String> {
+    soroban_invoke_signed(contract, contract_id, None, function, args)
+}
+
+/// Like `soroban_invoke_output`, with `source` (an identity, secret key or
+/// address) as the transaction source and signer when given.
+fn soroban_invoke_signed(
+    contract: &str,
+    contract_id: &str,
+    source: Option<&str>,
+    function: &str,
+    args: &[&str],
+) -> Result<String> {
     let mut cmd = Command::new("soroban");
     cmd.arg("contract")
         .arg("invoke")
         .arg("--id")
-        .arg(contract_id)
-        .arg("--")
-        .arg(function);
+        .arg(contract_id);
+    if let Some(source) = source {
+        cmd.arg("--source").arg(source);
+    }
+    cmd.arg("--").arg(function);
     for arg in args {
         cmd.arg(arg);
     }
     let output = cmd.output()?;
-    if output.status.success() {
-        println!("{}", String::from_utf8_lossy(&output.stdout));
-    } else {
-        eprintln!("{}", String::from_utf8_lossy(&output.stderr));
+    if !output.status.success() {
+        let stderr = String::from_utf8_lossy(&output.stderr);
+        eprintln!("{}", stderr);
+        if let Some(description) = errors::describe(contract, &stderr) {
+            return Err(anyhow!("{} failed: {}", function, description));
+        }
         return Err(anyhow!("Command failed"));
     }
-    Ok(())
+    Ok(String::from_utf8_lossy(&output.stdout).trim().to_string())
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn admin_args(args: &[&str]) -> Result<AdminRequest> {
+        let cli = Cli::try_parse_from(["remitwise-cli", "admin"].iter().chain(args))?;
+        match cli.command {
+            Commands::Admin {
+                sign_only,
+                submit_signed,
+                subcommand,
+            } => admin_request(sign_only, submit_signed, subcommand),
+            _ => unreachable!(),
+        }
+    }
+
+    #[test]
+    fn test_admin_flag_combinations() {
+        match admin_args(&["pause", "split", "--sign-only", "pause.xdr"]).unwrap() {
+            AdminRequest::Run {
+                command: AdminCommands::Pause { .. },
+                sign_only: Some(file),
+            } => assert_eq!(file, PathBuf::from("pause.xdr")),
+            _ => panic!("expected a sign-only pause"),
+        }
+        match admin_args(&["--submit-signed", "pause.xdr"]).unwrap() {
+            AdminRequest::SubmitSigned(file) => assert_eq!(file, PathBuf::from("pause.xdr")),
+            _ => panic!("expected a submission"),
+        }
+        assert!(matches!(
+            admin_args(&["unpause", "bills"]).unwrap(),
+            AdminRequest::Run {
+                sign_only: None,
+                ..
+            }
+        ));
+    }
+
+    #[test]
+    fn test_admin_rejects_conflicting_or_missing_commands() {
+        let err = admin_args(&["--submit-signed", "tx.xdr", "pause", "split"])
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("other admin commands"));
+        let err = admin_args(&["--submit-signed", "tx.xdr", "--sign-only", "out.xdr"])
+            .err()
+            .unwrap();
+        assert!(err.to_string().contains("--sign-only"));
+        let err = admin_args(&[]).err().unwrap();
+        assert!(err.to_string().contains("Specify an admin command"));
+        assert!(admin_args(&["pause", "ledger"]).is_err());
+    }
 }
@@ -0,0 +1,231 @@
+//! Guardian recovery status for `remitwise-cli recovery status`.
+//!
+//! Parses the `get_recovery_config` and `get_recovery_request` results of the
+//! goals, bills and insurance contracts and renders, per contract, the
+//! owner's guardians, the recovery in progress and how long is left of its
+//! veto window.
+
+use anyhow::{anyhow, Result};
+use serde_json::Value;
+
+/// An owner's guardians on one contract
+pub struct GuardianConfig {
+    pub guardians: Vec<String>,
+    pub threshold: u64,
+}
+
+/// A recovery in progress
+pub struct PendingRecovery {
+    pub new_owner: String,
+    pub approvals: Vec<String>,
+    pub initiated_at: u64,
+    /// Set once enough guardians approved
+    pub executable_at: Option<u64>,
+}
+
+/// Recovery state of an owner on one contract
+pub struct RecoveryStatus {
+    /// Registry name of the contract
+    pub contract: &'static str,
+    pub config: Option<GuardianConfig>,
+    pub request: Option<PendingRecovery>,
+}
+
+fn as_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        Value::Number(n) => n.as_u64(),
+        _ => None,
+    }
+}
+
+fn addresses(value: Option<&Value>) -> Result<Vec<String>> {
+    value
+        .and_then(Value::as_array)
+        .ok_or_else(|| anyhow!("Expected a list of addresses"))?
+        .iter()
+        .map(|address| {
+            address
+                .as_str()
+                .map(str::to_string)
+                .ok_or_else(|| anyhow!("Invalid address {}", address))
+        })
+        .collect()
+}
+
+/// Parse the result of `get_recovery_config`; `null` when recovery is off
+pub fn parse_config(output: &str) -> Result<Option<GuardianConfig>> {
+    let value: Value = serde_json::from_str(output.trim())?;
+    if value.is_null() {
+        return Ok(None);
+    }
+    Ok(Some(GuardianConfig {
+        guardians: addresses(value.get("guardians"))?,
+        threshold: value
+            .get("threshold")
+            .and_then(as_u64)
+            .ok_or_else(|| anyhow!("Invalid recovery threshold"))?,
+    }))
+}
+
+/// Parse the result of `get_recovery_request`; `null` when none is running
+pub fn parse_request(output: &str) -> Result<Option<PendingRecovery>> {
+    let value: Value = serde_json::from_str(output.trim())?;
+    if value.is_null() {
+        return Ok(None);
+    }
+    let executable_at = match value.get("executable_at") {
+        None | Some(Value::Null) => None,
+        Some(at) => Some(as_u64(at).ok_or_else(|| anyhow!("Invalid executable_at"))?),
+    };
+    Ok(Some(PendingRecovery {
+        new_owner: value
+            .get("new_owner")
+            .and_then(Value::as_str)
+            .ok_or_else(|| anyhow!("Recovery is missing `new_owner`"))?
+            .to_string(),
+        approvals: addresses(value.get("approvals"))?,
+        initiated_at: value
+            .get("initiated_at")
+            .and_then(as_u64)
+            .ok_or_else(|| anyhow!("Invalid initiated_at"))?,
+        executable_at,
+    }))
+}
+
+/// Format a number of seconds as e.g. `2d 03h 15m`, or `45s` under a minute
+pub fn format_countdown(secs: u64) -> String {
+    let (days, hours, minutes) = (secs / 86400, secs % 86400 / 3600, secs % 3600 / 60);
+    if days > 0 {
+        format!("{}d {:02}h {:02}m", days, hours, minutes)
+    } else if hours > 0 {
+        format!("{}h {:02}m", hours, minutes)
+    } else if minutes > 0 {
+        format!("{}m", minutes)
+    } else {
+        format!("{}s", secs)
+    }
+}
+
+impl RecoveryStatus {
+    /// Render the status as of `now` (unix seconds)
+    pub fn render(&self, now: u64) -> String {
+        let mut out = format!("{}\n", self.contract);
+        let config = match &self.config {
+            Some(config) => config,
+            None => {
+                out.push_str("  recovery off (no guardians registered)\n");
+                return out;
+            }
+        };
+        out.push_str(&format!(
+            "  guardians  {} of {} must approve\n",
+            config.threshold,
+            config.guardians.len()
+        ));
+        for guardian in &config.guardians {
+            out.push_str(&format!("    {}\n", guardian));
+        }
+
+        let request = match &self.request {
+            Some(request) => request,
+            None => {
+                out.push_str("  no recovery in progress\n");
+                return out;
+            }
+        };
+        out.push_str(&format!(
+            "  recovery   to {}, started {} ago\n",
+            request.new_owner,
+            format_countdown(now.saturating_sub(request.initiated_at))
+        ));
+        out.push_str(&format!(
+            "  approvals  {}/{}\n",
+            request.approvals.len(),
+            config.threshold
+        ));
+        let state = match request.executable_at {
+            None => format!(
+                "waiting for {} more approval(s)",
+                config
+                    .threshold
+                    .saturating_sub(request.approvals.len() as u64)
+            ),
+            Some(at) if at > now => format!(
+                "executable in {} (the owner can still veto)",
+                format_countdown(at - now)
+            ),
+            Some(_) => "ready to execute".to_string(),
+        };
+        out.push_str(&format!("  status     {}\n", state));
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_config_and_request() {
+        assert!(parse_config("null").unwrap().is_none());
+        let config = parse_config(r#"{"guardians":["GA","GB"],"threshold":2}"#)
+            .unwrap()
+            .unwrap();
+        assert_eq!(config.guardians, vec!["GA", "GB"]);
+        assert_eq!(config.threshold, 2);
+
+        let request = parse_request(
+            r#"{"approvals":["GA"],"executable_at":null,"initiated_at":1000,"new_owner":"GN"}"#,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(request.new_owner, "GN");
+        assert_eq!(request.executable_at, None);
+        let request = parse_request(
+            r#"{"approvals":["GA","GB"],"executable_at":"260200","initiated_at":1000,"new_owner":"GN"}"#,
+        )
+        .unwrap()
+        .unwrap();
+        assert_eq!(request.executable_at, Some(260_200));
+    }
+
+    #[test]
+    fn test_render_countdown() {
+        assert_eq!(
+            format_countdown(2 * 86400 + 3 * 3600 + 15 * 60),
+            "2d 03h 15m"
+        );
+        assert_eq!(format_countdown(3600 + 60), "1h 01m");
+        assert_eq!(format_countdown(45), "45s");
+
+        let mut status = RecoveryStatus {
+            contract: "goals",
+            config: Some(GuardianConfig {
+                guardians: vec!["GA".into(), "GB".into()],
+                threshold: 2,
+            }),
+            request: Some(PendingRecovery {
+                new_owner: "GN".into(),
+                approvals: vec!["GA".into()],
+                initiated_at: 1_000,
+                executable_at: None,
+            }),
+        };
+        assert!(status
+            .render(1_000)
+            .contains("waiting for 1 more approval(s)"));
+
+        if let Some(request) = status.request.as_mut() {
+            request.executable_at = Some(1_000 + 3 * 86400);
+        }
+        assert!(status
+            .render(1_000 + 3600)
+            .contains("executable in 2d 23h 00m"));
+        assert!(status
+            .render(1_000 + 3 * 86400)
+            .contains("ready to execute"));
+        status.config = None;
+        assert!(status.render(0).contains("recovery off"));
+    }
+}
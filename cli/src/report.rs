@@ -0,0 +1,458 @@
+//! Monthly household report for `remitwise-cli report`.
+//!
+//! Gathers what an owner sent and paid during one calendar month (UTC):
+//! remittances split by category, bills created and paid, insurance
+//! premiums paid and deposits into each savings goal. Renders it as a table
+//! to read, or as JSON or CSV to share. The contracts charge no fees of their
+//! own, so the fees line is always zero; network fees paid for the
+//! transactions are not tracked on chain and are left out.
+
+use anyhow::{anyhow, Result};
+use clap::ValueEnum;
+use serde_json::{json, Value};
+
+const MONTH_NAMES: [&str; 12] = [
+    "January",
+    "February",
+    "March",
+    "April",
+    "May",
+    "June",
+    "July",
+    "August",
+    "September",
+    "October",
+    "November",
+    "December",
+];
+
+/// Split categories, in the order of a remittance's `amounts`
+const CATEGORIES: [&str; 4] = ["spending", "savings", "bills", "insurance"];
+
+#[derive(Clone, Copy, ValueEnum)]
+pub enum ReportFormat {
+    Table,
+    Json,
+    Csv,
+}
+
+/// A calendar month (UTC) as a range of unix seconds
+#[derive(Clone, Debug, PartialEq, Eq)]
+pub struct Month {
+    pub year: i64,
+    /// 1 to 12
+    pub month: u32,
+    pub start: u64,
+    /// Last second of the month
+    pub end: u64,
+}
+
+impl Month {
+    pub fn contains(&self, ts: u64) -> bool {
+        self.start <= ts && ts <= self.end
+    }
+
+    fn label(&self) -> String {
+        format!("{:04}-{:02}", self.year, self.month)
+    }
+}
+
+/// Days since 1970-01-01 of a date (Howard Hinnant's `days_from_civil`)
+fn days_from_civil(year: i64, month: u32, day: u32) -> i64 {
+    let year = if month <= 2 { year - 1 } else { year };
+    let era = year.div_euclid(400);
+    let yoe = year - era * 400;
+    let mp = (month as i64 + 9) % 12;
+    let doy = (153 * mp + 2) / 5 + day as i64 - 1;
+    let doe = yoe * 365 + yoe / 4 - yoe / 100 + doy;
+    era * 146_097 + doe - 719_468
+}
+
+/// Parse a month given as `YYYY-MM`
+pub fn parse_month(month: &str) -> Result<Month> {
+    let invalid = || anyhow!("Invalid month `{}`; use YYYY-MM, e.g. 2024-05", month);
+    let (year, number) = month.trim().split_once('-').ok_or_else(invalid)?;
+    let year: i64 = year.parse().map_err(|_| invalid())?;
+    let number: u32 = number.parse().map_err(|_| invalid())?;
+    if !(1970..=9999).contains(&year) || !(1..=12).contains(&number) {
+        return Err(invalid());
+    }
+    let (next_year, next_month) = if number == 12 {
+        (year + 1, 1)
+    } else {
+        (year, number + 1)
+    };
+    Ok(Month {
+        year,
+        month: number,
+        start: days_from_civil(year, number, 1) as u64 * 86400,
+        end: days_from_civil(next_year, next_month, 1) as u64 * 86400 - 1,
+    })
+}
+
+fn as_u64(value: &Value) -> Option<u64> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        Value::Number(n) => n.as_u64(),
+        _ => None,
+    }
+}
+
+fn as_i128(value: &Value) -> Option<i128> {
+    match value {
+        Value::String(s) => s.parse().ok(),
+        Value::Number(n) => n.as_i64().map(i128::from),
+        _ => None,
+    }
+}
+
+fn records(output: &str) -> Result<Vec<Value>> {
+    match serde_json::from_str(output.trim())? {
+        Value::Array(records) => Ok(records),
+        _ => Err(anyhow!("Expected a list of records")),
+    }
+}
+
+/// Remittances sent during the month
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct RemittanceSummary {
+    pub count: u64,
+    pub total: i128,
+    /// Per split category, in `CATEGORIES` order
+    pub by_category: [i128; 4],
+}
+
+/// Sum the records of remittance split `get_remittance_history` sent in `month`
+pub fn parse_remittances(output: &str, month: &Month) -> Result<RemittanceSummary> {
+    let mut summary = RemittanceSummary::default();
+    for record in records(output)? {
+        let timestamp = record
+            .get("timestamp")
+            .and_then(as_u64)
+            .ok_or_else(|| anyhow!("Invalid remittance timestamp"))?;
+        if !month.contains(timestamp) {
+            continue;
+        }
+        summary.count += 1;
+        summary.total += record
+            .get("total_amount")
+            .and_then(as_i128)
+            .ok_or_else(|| anyhow!("Invalid remittance total_amount"))?;
+        let amounts = record
+            .get("amounts")
+            .and_then(Value::as_array)
+            .ok_or_else(|| anyhow!("Invalid remittance amounts"))?;
+        for (total, amount) in summary.by_category.iter_mut().zip(amounts) {
+            *total += as_i128(amount).ok_or_else(|| anyhow!("Invalid remittance amount"))?;
+        }
+    }
+    Ok(summary)
+}
+
+/// Deposits into one savings goal during the month
+#[derive(Debug, PartialEq, Eq)]
+pub struct GoalContributions {
+    pub goal_id: u64,
+    pub name: String,
+    pub count: u64,
+    pub amount: i128,
+}
+
+/// Sum the records of savings goals `get_contribution_history` made in
+/// `month`, as (number of deposits, total)
+pub fn parse_contributions(output: &str, month: &Month) -> Result<(u64, i128)> {
+    let (mut count, mut amount) = (0, 0i128);
+    for record in records(output)? {
+        let timestamp = record
+            .get("timestamp")
+            .and_then(as_u64)
+            .ok_or_else(|| anyhow!("Invalid contribution timestamp"))?;
+        if month.contains(timestamp) {
+            count += 1;
+            amount += record
+                .get("amount")
+                .and_then(as_i128)
+                .ok_or_else(|| anyhow!("Invalid contribution amount"))?;
+        }
+    }
+    Ok((count, amount))
+}
+
+/// Bill activity during the month
+#[derive(Debug, Default, PartialEq, Eq)]
+pub struct BillTotals {
+    pub created: i128,
+    pub paid: i128,
+    /// Bills paid after their due date
+    pub late_count: u64,
+}
+
+/// Sum the monthly totals of bill payments `get_statement`
+pub fn parse_bill_statement(output: &str) -> Result<BillTotals> {
+    let mut totals = BillTotals::default();
+    for month in records(output)? {
+        totals.created += month
+            .get("created")
+            .and_then(as_i128)
+            .ok_or_else(|| anyhow!("Invalid bill statement created"))?;
+        totals.paid += month
+            .get("paid")
+            .and_then(as_i128)
+            .ok_or_else(|| anyhow!("Invalid bill statement paid"))?;
+        totals.late_count += month
+            .get("late_count")
+            .and_then(as_u64)
+            .ok_or_else(|| anyhow!("Invalid bill statement late_count"))?;
+    }
+    Ok(totals)
+}
+
+/// Everything shown by `report`
+pub struct Report {
+    pub owner: String,
+    pub month: Month,
+    pub remittances: RemittanceSummary,
+    pub bills: BillTotals,
+    pub premiums_paid: i128,
+    /// Goals that received deposits during the month
+    pub savings: Vec<GoalContributions>,
+}
+
+/// Quote a CSV field when it holds a separator, quote or line break
+fn csv_field(field: &str) -> String {
+    if field.contains([',', '"', '\n', '\r']) {
+        format!("\"{}\"", field.replace('"', "\"\""))
+    } else {
+        field.to_string()
+    }
+}
+
+impl Report {
+    fn savings_total(&self) -> i128 {
+        self.savings.iter().map(|goal| goal.amount).sum()
+    }
+
+    pub fn render(&self, format: ReportFormat) -> String {
+        match format {
+            ReportFormat::Table => self.render_table(),
+            ReportFormat::Json => self.render_json(),
+            ReportFormat::Csv => self.render_csv(),
+        }
+    }
+
+    fn render_table(&self) -> String {
+        let mut out = format!(
+            "RemitWise report, {} {} (UTC)\nOwner {}\n\n",
+            MONTH_NAMES[self.month.month as usize - 1],
+            self.month.year,
+            self.owner
+        );
+        let line = |label: &str, count: Option<u64>, amount: i128| {
+            let count = count.map(|count| count.to_string()).unwrap_or_default();
+            format!("{:<28} {:>6} {:>20}\n", label, count, amount)
+        };
+        out.push_str(&line(
+            "Remittances sent",
+            Some(self.remittances.count),
+            self.remittances.total,
+        ));
+        for (category, amount) in CATEGORIES.iter().zip(self.remittances.by_category) {
+            out.push_str(&line(&format!("  to {}", category), None, amount));
+        }
+        out.push_str(&line("Bills created", None, self.bills.created));
+        out.push_str(&line(
+            &format!("Bills paid ({} late)", self.bills.late_count),
+            None,
+            self.bills.paid,
+        ));
+        out.push_str(&line("Insurance premiums paid", None, self.premiums_paid));
+        let deposits = self.savings.iter().map(|goal| goal.count).sum();
+        out.push_str(&line(
+            "Savings contributions",
+            Some(deposits),
+            self.savings_total(),
+        ));
+        for goal in &self.savings {
+            out.push_str(&line(
+                &format!("  #{} {}", goal.goal_id, goal.name),
+                Some(goal.count),
+                goal.amount,
+            ));
+        }
+        out.push_str(&line("Fees", None, 0));
+        out
+    }
+
+    fn render_json(&self) -> String {
+        let categories: serde_json::Map<String, Value> = CATEGORIES
+            .iter()
+            .zip(self.remittances.by_category)
+            .map(|(category, amount)| (category.to_string(), json!(amount.to_string())))
+            .collect();
+        let goals: Vec<Value> = self
+            .savings
+            .iter()
+            .map(|goal| {
+                json!({
+                    "goal_id": goal.goal_id,
+                    "name": goal.name,
+                    "deposits": goal.count,
+                    "amount": goal.amount.to_string(),
+                })
+            })
+            .collect();
+        let report = json!({
+            "owner": self.owner,
+            "month": self.month.label(),
+            "from": self.month.start,
+            "to": self.month.end,
+            "remittances": {
+                "count": self.remittances.count,
+                "total": self.remittances.total.to_string(),
+                "by_category": categories,
+            },
+            "bills_created": self.bills.created.to_string(),
+            "bills_paid": self.bills.paid.to_string(),
+            "bills_paid_late": self.bills.late_count,
+            "premiums_paid": self.premiums_paid.to_string(),
+            "savings": {
+                "total": self.savings_total().to_string(),
+                "goals": goals,
+            },
+            "fees": "0",
+        });
+        format!("{:#}\n", report)
+    }
+
+    fn render_csv(&self) -> String {
+        let mut out = String::from("month,section,item,count,amount\n");
+        let mut row = |section: &str, item: &str, count: Option<u64>, amount: i128| {
+            let count = count.map(|count| count.to_string()).unwrap_or_default();
+            out.push_str(&format!(
+                "{},{},{},{},{}\n",
+                self.month.label(),
+                section,
+                csv_field(item),
+                count,
+                amount
+            ));
+        };
+        row(
+            "remittances",
+            "total",
+            Some(self.remittances.count),
+            self.remittances.total,
+        );
+        for (category, amount) in CATEGORIES.iter().zip(self.remittances.by_category) {
+            row("remittances", category, None, amount);
+        }
+        row("bills", "created", None, self.bills.created);
+        row("bills", "paid", None, self.bills.paid);
+        row("bills", "paid late", Some(self.bills.late_count), 0);
+        row("insurance", "premiums", None, self.premiums_paid);
+        for goal in &self.savings {
+            row("savings", &goal.name, Some(goal.count), goal.amount);
+        }
+        row("fees", "total", None, 0);
+        out
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_parse_month_and_records() {
+        let may = parse_month("2024-05").unwrap();
+        assert_eq!((may.start, may.end), (1_714_521_600, 1_717_199_999));
+        let december = parse_month("2023-12").unwrap();
+        assert_eq!(december.end + 1, 1_704_067_200);
+        let february = parse_month("2024-02").unwrap();
+        assert_eq!(february.end + 1 - february.start, 29 * 86400);
+        assert!(parse_month("2024-13").is_err());
+        assert!(parse_month("May 2024").is_err());
+
+        let remittances = parse_remittances(
+            r#"[{"timestamp":1714521599,"total_amount":"999","amounts":["999","0","0","0"]},
+                {"timestamp":1714521600,"total_amount":"1000","amounts":["500","300","150","50"]},
+                {"timestamp":1717199999,"total_amount":"200","amounts":["100","60","30","10"]}]"#,
+            &may,
+        )
+        .unwrap();
+        assert_eq!(
+            remittances,
+            RemittanceSummary {
+                count: 2,
+                total: 1200,
+                by_category: [600, 360, 180, 60],
+            }
+        );
+
+        let contributions = parse_contributions(
+            r#"[{"amount":"300","source":["Remittance"],"timestamp":1715000000},
+                {"amount":"50","source":["Gift"],"timestamp":1717200000}]"#,
+            &may,
+        )
+        .unwrap();
+        assert_eq!(contributions, (1, 300));
+
+        let bills = parse_bill_statement(
+            r#"[{"created":"1200","late_count":0,"month":652,"paid":"200"},
+                {"created":"1000","late_count":1,"month":653,"paid":"1000"}]"#,
+        )
+        .unwrap();
+        assert_eq!(
+            bills,
+            BillTotals {
+                created: 2200,
+                paid: 1200,
+                late_count: 1,
+            }
+        );
+    }
+
+    #[test]
+    fn test_render_formats() {
+        let report = Report {
+            owner: "GOWNER".into(),
+            month: parse_month("2024-05").unwrap(),
+            remittances: RemittanceSummary {
+                count: 2,
+                total: 1200,
+                by_category: [600, 360, 180, 60],
+            },
+            bills: BillTotals {
+                created: 500,
+                paid: 400,
+                late_count: 1,
+            },
+            premiums_paid: 140,
+            savings: vec![GoalContributions {
+                goal_id: 1,
+                name: "School, fees".into(),
+                count: 1,
+                amount: 300,
+            }],
+        };
+
+        let table = report.render(ReportFormat::Table);
+        assert!(table.contains("May 2024"));
+        assert!(table.contains("  to savings"));
+        assert!(table.contains("#1 School, fees"));
+        assert!(table.contains("Bills paid (1 late)"));
+
+        let json: Value = serde_json::from_str(&report.render(ReportFormat::Json)).unwrap();
+        assert_eq!(json["month"], "2024-05");
+        assert_eq!(json["remittances"]["by_category"]["bills"], "180");
+        assert_eq!(json["savings"]["total"], "300");
+        assert_eq!(json["bills_paid_late"], 1);
+        assert_eq!(json["fees"], "0");
+
+        let csv = report.render(ReportFormat::Csv);
+        assert!(csv.starts_with("month,section,item,count,amount\n"));
+        assert!(csv.contains("2024-05,remittances,total,2,1200\n"));
+        assert!(csv.contains("2024-05,savings,\"School, fees\",1,300\n"));
+        assert!(csv.ends_with("2024-05,fees,total,,0\n"));
+    }
+}
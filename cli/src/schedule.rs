@@ -0,0 +1,135 @@
+//! Generation of cron entries and systemd units for recurring distributions.
+//!
+//! The generated jobs load the profile's environment file
+//! (`~/.config/remitwise/<profile>.env`, holding the variables listed in the
+//! README) and run `split distribute <amount>`. Every job fires at 09:00
+//! local time.
+
+/// How often a scheduled distribution runs
+#[derive(Clone, Copy, Debug, PartialEq, Eq)]
+pub enum Frequency {
+    Daily,
+    Weekly,
+    Monthly,
+}
+
+impl Frequency {
+    fn name(self) -> &'static str {
+        match self {
+            Frequency::Daily => "daily",
+            Frequency::Weekly => "weekly",
+            Frequency::Monthly => "monthly",
+        }
+    }
+
+    fn cron_expression(self) -> &'static str {
+        match self {
+            Frequency::Daily => "0 9 * * *",
+            Frequency::Weekly => "0 9 * * 1",
+            Frequency::Monthly => "0 9 1 * *",
+        }
+    }
+
+    fn on_calendar(self) -> &'static str {
+        match self {
+            Frequency::Daily => "*-*-* 09:00:00",
+            Frequency::Weekly => "Mon *-*-* 09:00:00",
+            Frequency::Monthly => "*-*-01 09:00:00",
+        }
+    }
+}
+
+/// A recurring `split distribute` invocation
+pub struct Schedule<'a> {
+    pub frequency: Frequency,
+    pub amount: u64,
+    pub profile: &'a str,
+    /// Absolute path of the remitwise-cli binary
+    pub cli_path: &'a str,
+}
+
+impl Schedule<'_> {
+    fn unit_name(&self) -> String {
+        format!("remitwise-distribute-{}", self.profile)
+    }
+
+    fn command(&self) -> String {
+        format!("{} split distribute {}", self.cli_path, self.amount)
+    }
+
+    /// A crontab line
+    pub fn to_cron(&self) -> String {
+        format!(
+            "# RemitWise {} distribution of {} (profile {})\n{} set -a; . \"$HOME/.config/remitwise/{}.env\"; set +a; {}\n",
+            self.frequency.name(),
+            self.amount,
+            self.profile,
+            self.frequency.cron_expression(),
+            self.profile,
+            self.command()
+        )
+    }
+
+    /// A user service and timer pair, to install under `~/.config/systemd/user/`
+    pub fn to_systemd(&self) -> String {
+        let name = self.unit_name();
+        format!(
+            "# {name}.service\n\
+             [Unit]\n\
+             Description=RemitWise {frequency} distribution of {amount} (profile {profile})\n\
+             \n\
+             [Service]\n\
+             Type=oneshot\n\
+             EnvironmentFile=%h/.config/remitwise/{profile}.env\n\
+             ExecStart={command}\n\
+             \n\
+             # {name}.timer\n\
+             [Unit]\n\
+             Description=Run {name}.service {frequency}\n\
+             \n\
+             [Timer]\n\
+             OnCalendar={calendar}\n\
+             Persistent=true\n\
+             \n\
+             [Install]\n\
+             WantedBy=timers.target\n",
+            frequency = self.frequency.name(),
+            amount = self.amount,
+            profile = self.profile,
+            command = self.command(),
+            calendar = self.frequency.on_calendar(),
+        )
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    fn schedule(frequency: Frequency) -> Schedule<'static> {
+        Schedule {
+            frequency,
+            amount: 500,
+            profile: "default",
+            cli_path: "/usr/local/bin/remitwise-cli",
+        }
+    }
+
+    #[test]
+    fn test_cron_line() {
+        let cron = schedule(Frequency::Monthly).to_cron();
+        let line = cron.lines().nth(1).unwrap();
+        assert!(line.starts_with("0 9 1 * * set -a; . \"$HOME/.config/remitwise/default.env\""));
+        assert!(line.ends_with("/usr/local/bin/remitwise-cli split distribute 500"));
+    }
+
+    #[test]
+    fn test_systemd_units() {
+        let units = schedule(Frequency::Weekly).to_systemd();
+        assert!(units.contains("# remitwise-distribute-default.service\n"));
+        assert!(units.contains("EnvironmentFile=%h/.config/remitwise/default.env\n"));
+        assert!(units.contains("ExecStart=/usr/local/bin/remitwise-cli split distribute 500\n"));
+        assert!(units.contains("# remitwise-distribute-default.timer\n"));
+        assert!(units.contains("OnCalendar=Mon *-*-* 09:00:00\n"));
+    }
+}
@@ -4,7 +4,7 @@ use soroban_sdk::{
     Env, Map, Symbol, Vec,
 };
 
-use remitwise_common::FamilyRole;
+use remitwise_common::{FamilyRole, ReentrancyGuard};
 
 // Storage TTL constants for active data
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280;
@@ -483,6 +483,7 @@ impl FamilyWallet {
         proposer.require_auth();
         Self::require_not_paused(&env);
         Self::require_role_at_least(&env, &proposer, FamilyRole::Member);
+        let _guard = ReentrancyGuard::enter(&env);
 
         if !Self::is_family_member(&env, &proposer) {
             panic!("Only family members can propose transactions");
@@ -561,6 +562,7 @@ impl FamilyWallet {
         signer.require_auth();
         Self::require_not_paused(&env);
         Self::require_role_at_least(&env, &signer, FamilyRole::Member);
+        let _guard = ReentrancyGuard::enter(&env);
 
         if !Self::is_family_member(&env, &signer) {
             panic!("Only family members can sign transactions");
@@ -733,6 +735,8 @@ impl FamilyWallet {
             .unwrap_or(false);
 
         if em_mode {
+            // The normal path below is guarded by propose_transaction
+            let _guard = ReentrancyGuard::enter(&env);
             return Self::execute_emergency_transfer_now(env, proposer, token, recipient, amount);
         }
 
@@ -1,11 +1,17 @@
 #![no_std]
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Map, String,
-    Symbol, Vec,
-    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Map, String, Symbol, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short,
+    token::TokenClient, Address, BytesN, Env, IntoVal, Map, String, Symbol, Val, Vec,
 };
 
-use remitwise_common::CoverageType;
+pub use remitwise_common::PauseState;
+use remitwise_common::{
+    month_index, AuditLog, CoverageType, EventCategory, EventPriority, GuardianRecovery, Pausable,
+    PriceOracleClient, RecoveryConfig, RecoveryError, RecoveryRequest, ReentrancyGuard, RefError,
+    RefRegistry, RemitwiseEvents, RATE_SCALE,
+};
 #[contracterror]
 #[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
 #[repr(u32)]
@@ -18,6 +24,59 @@ pub enum InsuranceError {
     FunctionPaused = 6,
     InvalidTimestamp = 7,
     BatchTooLarge = 8,
+    InvalidCoOwner = 9,
+    MemberNotFound = 10,
+    InvalidMember = 11,
+    SubLimitExceeded = 12,
+    /// Claims are not accepted yet; see `get_claims_unlock_at` for when they will be
+    WaitingPeriodActive = 13,
+    InsufficientStake = 14,
+    StakeLocked = 15,
+    OracleNotConfigured = 16,
+    RateUnavailable = 17,
+    ClaimNotFound = 18,
+    /// The claim has already been approved or rejected
+    ClaimNotPending = 19,
+    TooManyEvidence = 20,
+    /// The policy is already on a premium holiday
+    HolidayActive = 21,
+    /// The rate table has no rate for this coverage type and risk tier
+    TierRateNotSet = 22,
+    SavingsNotConfigured = 23,
+    NotLifePolicy = 24,
+    InvalidBeneficiaries = 25,
+    /// The policy already has a pending or approved survivor claim
+    SurvivorClaimExists = 26,
+    /// Guardians must be distinct, exclude the owner, and cover the threshold
+    InvalidRecoveryConfig = 27,
+    /// The holder already has a recovery in progress
+    RecoveryPending = 28,
+    RecoveryNotFound = 29,
+    /// The recovery lacks guardian approvals or its waiting period is running
+    RecoveryNotReady = 30,
+    /// The owner already has an approved claim for this incident
+    DuplicateIncident = 31,
+    /// Coverage exceeds the monthly premium times the coverage type's
+    /// maximum ratio; see `get_max_coverage_ratio` for the allowed maximum
+    CoverageRatioExceeded = 32,
+    /// The monthly premium is below the pricing contract's `Low`-tier quote
+    PremiumBelowQuote = 33,
+    /// Staking needs the stake token to be set with `set_stake_token`
+    StakeTokenNotSet = 34,
+    /// The stake token can't change while the pool has outstanding shares
+    StakeTokenInUse = 35,
+}
+
+impl From<RecoveryError> for InsuranceError {
+    fn from(err: RecoveryError) -> Self {
+        match err {
+            RecoveryError::InvalidConfig => InsuranceError::InvalidRecoveryConfig,
+            RecoveryError::NotGuardian => InsuranceError::Unauthorized,
+            RecoveryError::Pending => InsuranceError::RecoveryPending,
+            RecoveryError::NotFound => InsuranceError::RecoveryNotFound,
+            RecoveryError::NotReady => InsuranceError::RecoveryNotReady,
+        }
+    }
 }
 
 // Event topics
@@ -52,16 +111,114 @@ pub struct PremiumPaidEvent {
 pub struct PolicyDeactivatedEvent {
     pub policy_id: u32,
     pub name: String,
+    pub reason: DeactivationReason,
     pub timestamp: u64,
 }
 
 // Storage TTL constants
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
 const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
+const OWNER_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
+const OWNER_BUMP_AMOUNT: u32 = 518400; // ~30 days
+
+/// Keys of persistent entries
+#[contracttype]
+#[derive(Clone)]
+pub enum DataKey {
+    /// Sum of the monthly premiums of the holder's active policies
+    PremTotal(Address),
+    /// Evidence attached to a claim, oldest first
+    ClaimEvidence(u32),
+    /// Reviewer comments and the decision on a claim, oldest first
+    ClaimReviews(u32),
+    /// First approved claim of a policy owner for an incident
+    IncidentClaim(Address, BytesN<32>),
+    /// Total premiums paid on a policy
+    PremPaid(u32),
+    /// Premiums the holder paid by calendar month (months since 1970)
+    PremMonth(Address),
+}
 
 const CONTRACT_VERSION: u32 = 1;
 const MAX_BATCH_SIZE: u32 = 50;
+// Legacy instance map of premium totals, drained into `DataKey::PremTotal`
 const STORAGE_PREMIUM_TOTALS: Symbol = symbol_short!("PRM_TOT");
+const STORAGE_ACTIVE_COUNTS: Symbol = symbol_short!("ACT_CNT");
+const STORAGE_EXEC_HISTORY: Symbol = symbol_short!("EXEC_HIST");
+const MAX_EXECUTION_HISTORY: u32 = 100;
+const STORAGE_CLAIMS: Symbol = symbol_short!("CLAIMS");
+const STORAGE_INCIDENT_OVERRIDES: Symbol = symbol_short!("INC_OVR");
+/// How long an approved incident blocks another approval for the same owner
+pub const INCIDENT_REF_WINDOW: u64 = 365 * 24 * 60 * 60;
+// Temporary under `(INC_REF, digest)` for `INCIDENT_REF_WINDOW`
+const INCIDENT_REFS: RefRegistry = RefRegistry::new(symbol_short!("INC_REF"), INCIDENT_REF_WINDOW);
+const STORAGE_WAITING_PERIODS: Symbol = symbol_short!("WAIT_PER");
+const STORAGE_COVERAGE_RATIOS: Symbol = symbol_short!("COV_RATIO");
+const STORAGE_STAKING_CONFIG: Symbol = symbol_short!("STK_CFG");
+const STORAGE_STAKING_POOL: Symbol = symbol_short!("STK_POOL");
+const STORAGE_STAKE_POSITIONS: Symbol = symbol_short!("STK_POS");
+const STORAGE_STAKE_TOKEN: Symbol = symbol_short!("STK_TKN");
+/// Fixed-point scale of `StakingPool::earnings_per_share`
+const EARNINGS_SCALE: i128 = 1_000_000_000;
+const STORAGE_ORACLE: Symbol = symbol_short!("ORACLE");
+const STORAGE_DEACTIVATIONS: Symbol = symbol_short!("DEACT");
+const STORAGE_HOLIDAYS: Symbol = symbol_short!("HOLIDAY");
+const STORAGE_HOLIDAY_GUARDIANS: Symbol = symbol_short!("HOL_GRD");
+const STORAGE_TIER_RATES: Symbol = symbol_short!("TIER_RATE");
+const STORAGE_UNDERWRITING: Symbol = symbol_short!("UNDERWRT");
+const STORAGE_PRICING: Symbol = symbol_short!("PRICING");
+const STORAGE_SAVINGS: Symbol = symbol_short!("SAVINGS");
+const STORAGE_FUNDING_GOALS: Symbol = symbol_short!("FUND_GOAL");
+const STORAGE_VERIFIERS: Symbol = symbol_short!("VERIFIERS");
+const STORAGE_BENEFICIARIES: Symbol = symbol_short!("BENEFIC");
+const STORAGE_SURVIVOR_CLAIMS: Symbol = symbol_short!("SURV_CLM");
+const STORAGE_EVENT_SEQ: Symbol = symbol_short!("EVT_SEQ");
+
+/// Guardian recovery of a holder's policies
+const RECOVERY: GuardianRecovery = GuardianRecovery::new(symbol_short!("RECOVERY"));
+
+/// Period after creation in which deactivating a policy refunds every
+/// premium paid on it
+pub const COOLING_OFF_SECS: u64 = 14 * 86400;
+
+/// Maximum number of premium cycles a single premium holiday can skip
+pub const MAX_HOLIDAY_CYCLES: u32 = 3;
+
+/// Length of one premium cycle
+pub const PREMIUM_CYCLE_SECS: u64 = 30 * 86400;
+
+/// Premium pricing model, deployed as its own contract so it can be replaced
+/// without upgrading this one. Quotes the monthly premium of a coverage.
+#[contractclient(name = "PricingClient")]
+pub trait PricingTrait {
+    fn price(
+        env: Env,
+        coverage_type: CoverageType,
+        coverage_amount: i128,
+        risk_tier: RiskTier,
+    ) -> i128;
+}
+
+/// Savings goals contract that premium schedules can be funded from
+#[contractclient(name = "SavingsGoalsClient")]
+pub trait SavingsGoalsTrait {
+    fn withdraw_premium(
+        env: Env,
+        payer: Address,
+        owner: Address,
+        goal_id: u32,
+        amount: i128,
+    ) -> i128;
+}
+
+/// Maximum number of insured members on a single policy
+pub const MAX_INSURED_MEMBERS: u32 = 10;
+
+/// Maximum number of evidence items attached to a single claim
+pub const MAX_CLAIM_EVIDENCE: u32 = 20;
+
+/// Maximum number of beneficiaries named on a life policy
+pub const MAX_BENEFICIARIES: u32 = 10;
 
 /// Pagination constants
 pub const DEFAULT_PAGE_LIMIT: u32 = 20;
@@ -75,19 +232,141 @@ pub mod pause_functions {
     pub const CREATE_SCHED: Symbol = symbol_short!("crt_sch");
     pub const MODIFY_SCHED: Symbol = symbol_short!("mod_sch");
     pub const CANCEL_SCHED: Symbol = symbol_short!("can_sch");
+    pub const SUBMIT_CLAIM: Symbol = symbol_short!("sub_clm");
 }
 
-/// Insurance policy data structure with owner tracking for access control
-#[derive(Clone)]
+/// Relationship of an insured member to the policy owner
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Relationship {
+    Primary = 1,
+    Spouse = 2,
+    Child = 3,
+    Parent = 4,
+    Sibling = 5,
+    Other = 6,
+}
+
+/// A person covered by a family-plan policy
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InsuredMember {
+    pub id: u32,
+    /// Hash of the member's name; the name itself stays off-chain
+    pub name_hash: BytesN<32>,
+    pub relationship: Relationship,
+    /// Optional address the member can use to submit their own claims
+    pub address: Option<Address>,
+    /// Maximum total amount claimable for this member
+    pub sub_limit: i128,
+    /// Total amount claimed so far for this member
+    pub claimed: i128,
+}
+
+/// A claim filed against a policy on behalf of an insured member
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Claim {
+    pub id: u32,
+    pub policy_id: u32,
+    pub member_id: u32,
+    pub claimant: Address,
+    pub amount: i128,
+    pub submitted_at: u64,
+    pub status: ClaimStatus,
+    /// Amount of the claim charged to the reinsurance pool
+    pub pool_charge: i128,
+    /// Hash identifying the incident claimed for, shared by every claim
+    /// filed for the same incident under any of the owner's policies
+    pub incident_hash: BytesN<32>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ClaimStatus {
+    Pending = 0,
+    Approved = 1,
+    Rejected = 2,
+}
+
+/// A document supporting a claim, stored off-chain and referenced by hash
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimEvidence {
+    pub hash: BytesN<32>,
+    /// MIME type of the document, e.g. "application/pdf"
+    pub content_type: String,
+    pub added_by: Address,
+    pub added_at: u64,
+}
+
+/// A reviewer's comment on a claim. `decision` is the status the review
+/// moved the claim to, or `Pending` for a note made before deciding.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimReview {
+    pub reviewer: Address,
+    pub decision: ClaimStatus,
+    pub comment: String,
+    pub reviewed_at: u64,
+}
+
+/// A claim with its evidence and review trail
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ClaimDetail {
+    pub claim: Claim,
+    pub evidence: Vec<ClaimEvidence>,
+    pub reviews: Vec<ClaimReview>,
+}
+
+/// Person named to receive part of a life policy's payout
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Beneficiary {
+    pub address: Address,
+    /// Share of the payout; the shares of a policy add up to 100
+    pub percent: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SurvivorPayout {
+    pub beneficiary: Address,
+    pub amount: i128,
+}
+
+/// Death claim on a life policy, filed by a beneficiary and decided by a
+/// claim verifier
 #[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct SurvivorClaim {
+    pub id: u32,
+    pub policy_id: u32,
+    pub filed_by: Address,
+    /// Hash of the death certificate; the document stays off-chain
+    pub evidence_hash: BytesN<32>,
+    pub filed_at: u64,
+    pub status: ClaimStatus,
+    pub decided_by: Option<Address>,
+    /// Amount owed to each beneficiary, set on approval
+    pub payouts: Vec<SurvivorPayout>,
+    /// Amount of the payout charged to the reinsurance pool
+    pub pool_charge: i128,
+}
+
+/// Insurance policy data structure with owner tracking for access control
 #[derive(Clone)]
 #[contracttype]
 pub struct InsurancePolicy {
     pub id: u32,
     pub owner: Address,
+    /// Optional joint holder; can pay premiums and must co-sign deactivation or transfer
+    pub co_owner: Option<Address>,
     pub name: String,
     pub external_ref: Option<String>,
-    pub coverage_type: String,
     pub coverage_type: CoverageType,
     pub monthly_premium: i128,
     pub coverage_amount: i128,
@@ -95,8 +374,73 @@ pub struct InsurancePolicy {
     pub next_payment_date: u64,
     pub schedule_id: Option<u32>,
     pub tags: Vec<String>,
+    pub created_at: u64,
+    /// Insured members covered by the policy (family plans)
+    pub members: Vec<InsuredMember>,
+    /// Amount claimed across all members, including members since removed
+    pub total_claimed: i128,
+}
+
+/// Why a holder deactivated a policy
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum DeactivationReason {
+    Affordability = 0,
+    Switching = 1,
+    Dissatisfaction = 2,
+    ClaimDenied = 3,
+    /// Closed by an approved survivor claim
+    Deceased = 4,
+}
+
+/// Record kept when a policy is deactivated
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PolicyDeactivation {
+    pub reason: DeactivationReason,
+    pub deactivated_by: Address,
+    pub deactivated_at: u64,
+    /// Premiums owed back from the reserve for a cooling-off deactivation,
+    /// 0 otherwise
+    pub refund_due: i128,
+}
+
+/// Premium holiday declared on a policy. Coverage is reduced while it runs.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PremiumHoliday {
+    pub cycles: u32,
+    pub start: u64,
+    pub end: u64,
+    pub declared_by: Address,
+    /// Guardian who approved the holiday, if the policy has one
+    pub approved_by: Option<Address>,
 }
 
+/// Risk tier assigned by the underwriter from the questionnaire answers
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum RiskTier {
+    Low = 1,
+    Standard = 2,
+    Elevated = 3,
+    High = 4,
+}
+
+/// Underwriting record of a policy
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct Underwriting {
+    /// Hash of the questionnaire answers; the answers stay off-chain
+    pub questionnaire_hash: BytesN<32>,
+    pub risk_tier: RiskTier,
+    /// Tier in effect before `effective_at` (the initial tier at creation)
+    pub previous_tier: RiskTier,
+    pub effective_at: u64,
+    pub assessed_by: Address,
+}
 
 /// Paginated result for insurance policy queries
 #[contracttype]
@@ -126,19 +470,96 @@ pub struct PremiumSchedule {
     pub missed_count: u32,
 }
 
-#[contracterror]
-#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
-#[repr(u32)]
-pub enum InsuranceError {
-    InvalidPremium = 1,
-    InvalidCoverage = 2,
-    PolicyNotFound = 3,
-    PolicyInactive = 4,
-    Unauthorized = 5,
-    BatchTooLarge = 6,
+/// Summary of a single keeper run of `execute_due_premium_schedules`
+#[contracttype]
+#[derive(Clone)]
+pub struct ExecutionRecord {
+    pub timestamp: u64,
+    pub caller: Address,
+    pub executed_count: u32,
+    pub missed_total: u32,
+}
+
+/// Outcome of one `execute_due_premium_schedules` run
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct ExecutionReport {
+    /// IDs of the due schedules run for an active policy, paid or missed
+    pub executed: Vec<u32>,
+    /// IDs of due schedules whose policy is inactive or gone; they are
+    /// advanced without paying
+    pub skipped_inactive_policy: Vec<u32>,
+    /// Periods added to `missed_count` across all schedules, whether the
+    /// funding goal could not cover the premium or the run came late
+    pub missed_increments: u32,
+    /// Sum of the premiums paid by the run
+    pub total_premium_processed: i128,
+}
+
+/// Outcome for one policy of `batch_pay_premiums_partial`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PremiumPaymentResult {
+    pub policy_id: u32,
+    pub paid: bool,
+    /// `InsuranceError` code explaining why the premium was not paid; 0 when paid
+    pub error_code: u32,
+}
+
+/// Terms offered to backers of the insurance reserve
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakingConfig {
+    /// Seconds a deposit stays locked; every deposit restarts the lock
+    pub lockup_secs: u64,
+    /// Share of each premium paid credited to the pool, in basis points
+    pub premium_share_bps: u32,
 }
 
+/// Reinsurance pool totals.
+///
+/// `total_capital` is what the stakers can withdraw together: their stake
+/// plus premium income less absorbed losses. Income and losses are booked
+/// to the positions pro rata by shares through `earnings_per_share`.
+#[contracttype]
+#[derive(Clone, Debug, Default, Eq, PartialEq)]
+pub struct StakingPool {
+    pub total_capital: i128,
+    pub total_shares: i128,
+    pub premium_income: i128,
+    pub losses_absorbed: i128,
+    /// Premium income less losses booked per share, scaled by 10^9
+    pub earnings_per_share: i128,
+}
+
+/// A staker's position in the pool
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakePosition {
+    pub shares: i128,
+    pub deposited: i128,
+    pub withdrawn: i128,
+    pub unlock_at: u64,
+    /// Earnings booked to the position up to its last change
+    pub earnings: i128,
+    /// `shares * earnings_per_share` at the last change, already counted
+    pub earnings_debt: i128,
+}
 
+/// Per-staker accounting
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct StakerInfo {
+    pub staker: Address,
+    pub shares: i128,
+    /// Shares plus booked earnings; what the staker can withdraw
+    pub value: i128,
+    pub deposited: i128,
+    pub withdrawn: i128,
+    /// value + withdrawn - deposited; negative when losses exceed income
+    pub profit: i128,
+    pub unlock_at: u64,
+}
 
 #[contracttype]
 #[derive(Clone)]
@@ -152,6 +573,102 @@ pub enum InsuranceEvent {
     ScheduleMissed,
     ScheduleModified,
     ScheduleCancelled,
+    CoOwnerAdded,
+    CoOwnerRemoved,
+    PolicyTransferred,
+    MemberAdded,
+    MemberRemoved,
+    ClaimSubmitted,
+    WaitingPeriodSet,
+    StakingConfigured,
+    StakeDeposited,
+    StakeWithdrawn,
+    EarningsClaimed,
+    PremiumShared,
+    LossAbsorbed,
+    OracleConfigured,
+    ClaimEvidenceAdded,
+    ClaimReviewed,
+    CoolingOffRefund,
+    PremiumHolidayStarted,
+    PremiumHolidayEnded,
+    TierRateSet,
+    RiskTierChanged,
+    SavingsConfigured,
+    ScheduleFundingSet,
+    VerifierSet,
+    BeneficiariesSet,
+    SurvivorClaimFiled,
+    SurvivorClaimDecided,
+    SurvivorPaid,
+    OwnerMigrated,
+    RecoveryConfigured,
+    RecoveryInitiated,
+    RecoveryApproved,
+    RecoveryVetoed,
+    RecoveryExecuted,
+    PricingConfigured,
+    DuplicateIncidentAllowed,
+    CoverageRatioSet,
+    StakeTokenSet,
+}
+
+impl InsuranceEvent {
+    /// Category, priority and action symbol of the standard `RemitwiseEvents`
+    /// emission for this event
+    fn standard_topic(&self) -> (EventCategory, EventPriority, Symbol) {
+        use EventCategory::*;
+        use EventPriority::*;
+        match self {
+            InsuranceEvent::PolicyCreated => (State, Medium, symbol_short!("created")),
+            InsuranceEvent::PremiumPaid => (Transaction, High, symbol_short!("prem_paid")),
+            InsuranceEvent::PolicyDeactivated => (State, Medium, symbol_short!("deactive")),
+            InsuranceEvent::ExternalRefUpdated => (State, Low, symbol_short!("ext_ref")),
+            InsuranceEvent::ScheduleCreated => (State, Low, symbol_short!("sched_crt")),
+            InsuranceEvent::ScheduleExecuted => (Transaction, Medium, symbol_short!("sched_exe")),
+            InsuranceEvent::ScheduleMissed => (Alert, High, symbol_short!("sched_mis")),
+            InsuranceEvent::ScheduleModified => (State, Low, symbol_short!("sched_mod")),
+            InsuranceEvent::ScheduleCancelled => (State, Low, symbol_short!("sched_can")),
+            InsuranceEvent::CoOwnerAdded => (Access, Medium, symbol_short!("coown_add")),
+            InsuranceEvent::CoOwnerRemoved => (Access, Medium, symbol_short!("coown_rm")),
+            InsuranceEvent::PolicyTransferred => (Access, High, symbol_short!("transfer")),
+            InsuranceEvent::MemberAdded => (State, Low, symbol_short!("mbr_add")),
+            InsuranceEvent::MemberRemoved => (State, Low, symbol_short!("mbr_rm")),
+            InsuranceEvent::ClaimSubmitted => (Transaction, High, symbol_short!("claim")),
+            InsuranceEvent::WaitingPeriodSet => (System, Medium, symbol_short!("wait_per")),
+            InsuranceEvent::StakingConfigured => (System, Medium, symbol_short!("stk_cfg")),
+            InsuranceEvent::StakeDeposited => (Transaction, Medium, symbol_short!("stk_dep")),
+            InsuranceEvent::StakeWithdrawn => (Transaction, Medium, symbol_short!("stk_wdr")),
+            InsuranceEvent::EarningsClaimed => (Transaction, Medium, symbol_short!("stk_earn")),
+            InsuranceEvent::PremiumShared => (Transaction, Low, symbol_short!("prm_share")),
+            InsuranceEvent::LossAbsorbed => (Alert, Medium, symbol_short!("loss_abs")),
+            InsuranceEvent::OracleConfigured => (System, Medium, symbol_short!("oracle")),
+            InsuranceEvent::ClaimEvidenceAdded => (State, Low, symbol_short!("clm_evid")),
+            InsuranceEvent::ClaimReviewed => (Transaction, High, symbol_short!("clm_rev")),
+            InsuranceEvent::CoolingOffRefund => (Transaction, High, symbol_short!("refund")),
+            InsuranceEvent::PremiumHolidayStarted => (State, Medium, symbol_short!("hol_start")),
+            InsuranceEvent::PremiumHolidayEnded => (State, Medium, symbol_short!("hol_end")),
+            InsuranceEvent::TierRateSet => (System, Medium, symbol_short!("tier_rate")),
+            InsuranceEvent::RiskTierChanged => (State, Medium, symbol_short!("retier")),
+            InsuranceEvent::SavingsConfigured => (System, Medium, symbol_short!("savings")),
+            InsuranceEvent::ScheduleFundingSet => (State, Low, symbol_short!("sched_fnd")),
+            InsuranceEvent::VerifierSet => (Access, Medium, symbol_short!("verifier")),
+            InsuranceEvent::BeneficiariesSet => (State, Medium, symbol_short!("benefic")),
+            InsuranceEvent::SurvivorClaimFiled => (Transaction, High, symbol_short!("srv_claim")),
+            InsuranceEvent::SurvivorClaimDecided => (Transaction, High, symbol_short!("srv_dec")),
+            InsuranceEvent::SurvivorPaid => (Transaction, High, symbol_short!("srv_paid")),
+            InsuranceEvent::OwnerMigrated => (Access, High, symbol_short!("migrated")),
+            InsuranceEvent::RecoveryConfigured => (Access, Medium, symbol_short!("rcv_cfg")),
+            InsuranceEvent::RecoveryInitiated => (Access, High, symbol_short!("rcv_init")),
+            InsuranceEvent::RecoveryApproved => (Access, Medium, symbol_short!("rcv_appr")),
+            InsuranceEvent::RecoveryVetoed => (Access, High, symbol_short!("rcv_veto")),
+            InsuranceEvent::RecoveryExecuted => (Access, High, symbol_short!("rcv_exec")),
+            InsuranceEvent::PricingConfigured => (System, Medium, symbol_short!("pricing")),
+            InsuranceEvent::DuplicateIncidentAllowed => (Alert, High, symbol_short!("dup_incdt")),
+            InsuranceEvent::CoverageRatioSet => (System, Medium, symbol_short!("cov_ratio")),
+            InsuranceEvent::StakeTokenSet => (System, Medium, symbol_short!("stk_tkn")),
+        }
+    }
 }
 
 #[contract]
@@ -159,23 +676,6 @@ pub struct Insurance;
 
 #[contractimpl]
 impl Insurance {
-    /// Create a new insurance policy
-    ///
-    /// # Arguments
-    /// * `owner` - Address of the policy owner (must authorize)
-    /// * `name` - Name of the policy
-    /// * `coverage_type` - Type of coverage (e.g., "health", "emergency")
-    /// * `monthly_premium` - Monthly premium amount (must be positive)
-    /// * `coverage_amount` - Total coverage amount (must be positive)
-    /// * `external_ref` - Optional external system reference ID
-    ///
-    /// # Returns
-    /// The ID of the created policy
-    ///
-    /// # Panics
-    /// - If owner doesn't authorize the transaction
-    /// - If monthly_premium is not positive
-    /// - If coverage_amount is not positive
     // -----------------------------------------------------------------------
     // Internal helpers
     // -----------------------------------------------------------------------
@@ -190,22 +690,54 @@ impl Insurance {
         }
     }
 
+    /// Policies held by `owner` with an ID above `cursor`. `next_cursor` is
+    /// the last returned ID when more remain, 0 otherwise.
+    fn policy_page(
+        env: &Env,
+        owner: &Address,
+        cursor: u32,
+        limit: u32,
+        active_only: bool,
+    ) -> PolicyPage {
+        let limit = Self::clamp_limit(limit);
+        let policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(env));
+
+        let mut items: Vec<InsurancePolicy> = Vec::new(env);
+        let mut next_cursor = 0;
+        for (id, policy) in policies.iter() {
+            if id <= cursor || (active_only && !policy.active) {
+                continue;
+            }
+            if !Self::is_policy_holder(&policy, owner) {
+                continue;
+            }
+            if items.len() == limit {
+                next_cursor = items.last().map(|p| p.id).unwrap_or(0);
+                break;
+            }
+            items.push_back(policy);
+        }
+
+        let count = items.len();
+        PolicyPage {
+            items,
+            next_cursor,
+            count,
+        }
+    }
+
     fn get_pause_admin(env: &Env) -> Option<Address> {
-        env.storage().instance().get(&symbol_short!("PAUSE_ADM"))
+        Pausable::admin(env)
     }
     fn get_global_paused(env: &Env) -> bool {
-        env.storage()
-            .instance()
-            .get(&symbol_short!("PAUSED"))
-            .unwrap_or(false)
+        Pausable::is_paused(env)
     }
     fn is_function_paused(env: &Env, func: Symbol) -> bool {
-        env.storage()
-            .instance()
-            .get::<_, Map<Symbol, bool>>(&symbol_short!("PAUSED_FN"))
-            .unwrap_or_else(|| Map::new(env))
-            .get(func)
-            .unwrap_or(false)
+        Pausable::is_function_paused(env, func)
     }
     fn require_not_paused(env: &Env, func: Symbol) -> Result<(), InsuranceError> {
         if Self::get_global_paused(env) {
@@ -233,9 +765,7 @@ impl Insurance {
             Some(admin) if admin != caller => return Err(InsuranceError::Unauthorized),
             _ => {}
         }
-        env.storage()
-            .instance()
-            .set(&symbol_short!("PAUSE_ADM"), &new_admin);
+        Pausable::set_admin(&env, &new_admin);
         Ok(())
     }
     pub fn pause(env: Env, caller: Address) -> Result<(), InsuranceError> {
@@ -244,9 +774,7 @@ impl Insurance {
         if admin != caller {
             return Err(InsuranceError::Unauthorized);
         }
-        env.storage()
-            .instance()
-            .set(&symbol_short!("PAUSED"), &true);
+        Pausable::set_paused(&env, true);
         env.events()
             .publish((symbol_short!("insure"), symbol_short!("paused")), ());
         Ok(())
@@ -257,16 +785,13 @@ impl Insurance {
         if admin != caller {
             return Err(InsuranceError::Unauthorized);
         }
-        let unpause_at: Option<u64> = env.storage().instance().get(&symbol_short!("UNP_AT"));
-        if let Some(at) = unpause_at {
+        if let Some(at) = Pausable::unpause_at(&env) {
             if env.ledger().timestamp() < at {
                 panic!("Time-locked unpause not yet reached");
             }
-            env.storage().instance().remove(&symbol_short!("UNP_AT"));
+            Pausable::clear_unpause_at(&env);
         }
-        env.storage()
-            .instance()
-            .set(&symbol_short!("PAUSED"), &false);
+        Pausable::set_paused(&env, false);
         env.events()
             .publish((symbol_short!("insure"), symbol_short!("unpaused")), ());
         Ok(())
@@ -277,15 +802,7 @@ impl Insurance {
         if admin != caller {
             return Err(InsuranceError::Unauthorized);
         }
-        let mut m: Map<Symbol, bool> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("PAUSED_FN"))
-            .unwrap_or_else(|| Map::new(&env));
-        m.set(func, true);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("PAUSED_FN"), &m);
+        Pausable::set_function_paused(&env, func, true);
         Ok(())
     }
     pub fn unpause_function(env: Env, caller: Address, func: Symbol) -> Result<(), InsuranceError> {
@@ -294,19 +811,22 @@ impl Insurance {
         if admin != caller {
             return Err(InsuranceError::Unauthorized);
         }
-        let mut m: Map<Symbol, bool> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("PAUSED_FN"))
-            .unwrap_or_else(|| Map::new(&env));
-        m.set(func, false);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("PAUSED_FN"), &m);
+        Pausable::set_function_paused(&env, func, false);
         Ok(())
     }
-    pub fn emergency_pause_all(env: Env, caller: Address) {
-        let _ = Self::pause(env.clone(), caller.clone());
+    /// Pause the contract and every function in `pause_functions` (pause
+    /// admin only). Functions stay paused after `unpause` until they are
+    /// unpaused one by one.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    pub fn emergency_pause_all(env: Env, caller: Address) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(InsuranceError::Unauthorized)?;
+        if admin != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        Pausable::set_paused(&env, true);
         for func in [
             pause_functions::CREATE_POLICY,
             pause_functions::PAY_PREMIUM,
@@ -314,19 +834,42 @@ impl Insurance {
             pause_functions::CREATE_SCHED,
             pause_functions::MODIFY_SCHED,
             pause_functions::CANCEL_SCHED,
+            pause_functions::SUBMIT_CLAIM,
         ] {
-            let _ = Self::pause_function(env.clone(), caller.clone(), func);
+            Pausable::set_function_paused(&env, func, true);
         }
+        env.events()
+            .publish((symbol_short!("insure"), symbol_short!("paused")), ());
+        Ok(())
     }
     pub fn is_paused(env: Env) -> bool {
         Self::get_global_paused(&env)
     }
+    /// Global pause flag, paused functions, time lock and pause admin
+    pub fn get_pause_state(env: Env) -> PauseState {
+        Pausable::state(&env)
+    }
     pub fn get_version(env: Env) -> u32 {
         env.storage()
             .instance()
             .get(&symbol_short!("VERSION"))
             .unwrap_or(CONTRACT_VERSION)
     }
+    /// Sequence number of the last standard event emitted, 0 before the first.
+    /// Indexers compare it with the last `event_seq` they processed to find
+    /// events missing at the tail.
+    pub fn get_event_seq(env: Env) -> u64 {
+        env.storage()
+            .instance()
+            .get(&STORAGE_EVENT_SEQ)
+            .unwrap_or(0)
+    }
+    pub fn get_pause_admin_public(env: Env) -> Option<Address> {
+        Self::get_pause_admin(&env)
+    }
+    pub fn get_upgrade_admin_public(env: Env) -> Option<Address> {
+        Self::get_upgrade_admin(&env)
+    }
     fn get_upgrade_admin(env: &Env) -> Option<Address> {
         env.storage().instance().get(&symbol_short!("UPG_ADM"))
     }
@@ -365,6 +908,13 @@ impl Insurance {
             (symbol_short!("insure"), symbol_short!("upgraded")),
             (prev, new_version),
         );
+        Self::emit_standard(
+            &env,
+            EventCategory::System,
+            EventPriority::High,
+            symbol_short!("upgraded"),
+            (prev, new_version),
+        );
         Ok(())
     }
 
@@ -377,18 +927,13 @@ impl Insurance {
             panic!("Tags cannot be empty");
         }
         for tag in tags.iter() {
-            if tag.len() == 0 || tag.len() > 32 {
+            if tag.is_empty() || tag.len() > 32 {
                 panic!("Tag must be between 1 and 32 characters");
             }
         }
     }
 
-    pub fn add_tags_to_policy(
-        env: Env,
-        caller: Address,
-        policy_id: u32,
-        tags: Vec<String>,
-    ) {
+    pub fn add_tags_to_policy(env: Env, caller: Address, policy_id: u32, tags: Vec<String>) {
         caller.require_auth();
         Self::validate_tags(&tags);
         Self::extend_instance_ttl(&env);
@@ -416,16 +961,18 @@ impl Insurance {
 
         env.events().publish(
             (symbol_short!("insure"), symbol_short!("tags_add")),
+            (policy_id, caller.clone(), tags.clone()),
+        );
+        Self::emit_standard(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("tags_add"),
             (policy_id, caller, tags),
         );
     }
 
-    pub fn remove_tags_from_policy(
-        env: Env,
-        caller: Address,
-        policy_id: u32,
-        tags: Vec<String>,
-    ) {
+    pub fn remove_tags_from_policy(env: Env, caller: Address, policy_id: u32, tags: Vec<String>) {
         caller.require_auth();
         Self::validate_tags(&tags);
         Self::extend_instance_ttl(&env);
@@ -464,6 +1011,13 @@ impl Insurance {
 
         env.events().publish(
             (symbol_short!("insure"), symbol_short!("tags_rem")),
+            (policy_id, caller.clone(), tags.clone()),
+        );
+        Self::emit_standard(
+            &env,
+            EventCategory::State,
+            EventPriority::Low,
+            symbol_short!("tags_rem"),
             (policy_id, caller, tags),
         );
     }
@@ -486,6 +1040,8 @@ impl Insurance {
     ///
     /// # Errors
     /// * `InvalidAmount` - If monthly_premium ≤ 0 or coverage_amount ≤ 0
+    /// * `CoverageRatioExceeded` - If coverage_amount is more than
+    ///   monthly_premium times the coverage type's maximum ratio
     ///
     /// # Panics
     /// * If `owner` does not authorize the transaction (implicit via `require_auth()`)
@@ -498,7 +1054,6 @@ impl Insurance {
         monthly_premium: i128,
         coverage_amount: i128,
         external_ref: Option<String>,
-    ) -> u32 {
     ) -> Result<u32, InsuranceError> {
         owner.require_auth();
         Self::require_not_paused(&env, pause_functions::CREATE_POLICY)?;
@@ -506,6 +1061,18 @@ impl Insurance {
         if monthly_premium <= 0 || coverage_amount <= 0 {
             return Err(InsuranceError::InvalidAmount);
         }
+        if let Some(max_ratio) = Self::get_max_coverage_ratio(env.clone(), coverage_type) {
+            if coverage_amount > monthly_premium.saturating_mul(max_ratio as i128) {
+                return Err(InsuranceError::CoverageRatioExceeded);
+            }
+        }
+        if let Some(floor) =
+            Self::quote_premium(&env, coverage_type, coverage_amount, RiskTier::Low)?
+        {
+            if monthly_premium < floor {
+                return Err(InsuranceError::PremiumBelowQuote);
+            }
+        }
 
         Self::extend_instance_ttl(&env);
 
@@ -527,15 +1094,19 @@ impl Insurance {
         let policy = InsurancePolicy {
             id: next_id,
             owner: owner.clone(),
+            co_owner: None,
             name: name.clone(),
             external_ref,
-            coverage_type: coverage_type.clone(),
+            coverage_type,
             monthly_premium,
             coverage_amount,
             active: true,
             next_payment_date,
             schedule_id: None,
             tags: Vec::new(&env),
+            created_at: env.ledger().timestamp(),
+            members: Vec::new(&env),
+            total_claimed: 0,
         };
 
         let policy_owner = policy.owner.clone();
@@ -548,6 +1119,7 @@ impl Insurance {
             .instance()
             .set(&symbol_short!("NEXT_ID"), &next_id);
         Self::adjust_active_premium_total(&env, &owner, monthly_premium);
+        Self::adjust_active_count(&env, &owner, 1);
 
         env.events().publish(
             (POLICY_CREATED,),
@@ -561,10 +1133,10 @@ impl Insurance {
             },
         );
 
-        env.events().publish(
-            (symbol_short!("insure"), InsuranceEvent::PolicyCreated),
+        Self::publish_event(
+            &env,
+            InsuranceEvent::PolicyCreated,
             (next_id, policy_owner, policy_external_ref),
-            (next_id, owner),
         );
 
         Ok(next_id)
@@ -602,7 +1174,7 @@ impl Insurance {
             None => return Err(InsuranceError::PolicyNotFound),
         };
 
-        if policy.owner != caller {
+        if !Self::is_policy_holder(&policy, &caller) {
             return Err(InsuranceError::Unauthorized);
         }
         if !policy.active {
@@ -612,16 +1184,6 @@ impl Insurance {
         policy.next_payment_date = env.ledger().timestamp() + (30 * 86400);
 
         let policy_external_ref = policy.external_ref.clone();
-        let event = PremiumPaidEvent {
-            policy_id,
-            name: policy.name.clone(),
-            amount: policy.monthly_premium,
-            next_payment_date: policy.next_payment_date,
-            timestamp: env.ledger().timestamp(),
-        };
-        env.events().publish((PREMIUM_PAID,), event);
-
-        policies.set(policy_id, policy);
         policies.set(policy_id, policy.clone());
         env.storage()
             .instance()
@@ -638,10 +1200,12 @@ impl Insurance {
             },
         );
 
-        env.events().publish(
-            (symbol_short!("insure"), InsuranceEvent::PremiumPaid),
+        Self::publish_event(
+            &env,
+            InsuranceEvent::PremiumPaid,
             (policy_id, caller, policy_external_ref),
         );
+        Self::collect_premium(&env, policy.id, &policy.owner, policy.monthly_premium);
 
         Ok(())
     }
@@ -666,7 +1230,7 @@ impl Insurance {
                 Some(p) => p,
                 None => return Err(InsuranceError::PolicyNotFound),
             };
-            if policy.owner != caller {
+            if !Self::is_policy_holder(&policy, &caller) {
                 return Err(InsuranceError::Unauthorized);
             }
             if !policy.active {
@@ -687,10 +1251,8 @@ impl Insurance {
                 timestamp: current_time,
             };
             env.events().publish((PREMIUM_PAID,), event);
-            env.events().publish(
-                (symbol_short!("insure"), InsuranceEvent::PremiumPaid),
-                (id, caller.clone()),
-            );
+            Self::publish_event(&env, InsuranceEvent::PremiumPaid, (id, caller.clone()));
+            Self::collect_premium(&env, policy.id, &policy.owner, policy.monthly_premium);
             policies_map.set(id, policy);
             paid_count += 1;
         }
@@ -701,9 +1263,103 @@ impl Insurance {
             (symbol_short!("insure"), symbol_short!("batch_pay")),
             (paid_count, caller),
         );
+        Self::emit_standard_batch(
+            &env,
+            EventCategory::Transaction,
+            symbol_short!("prem_paid"),
+            paid_count,
+        );
         Ok(paid_count)
     }
 
+    /// Pay the premiums of several policies, skipping those that cannot be paid.
+    ///
+    /// Unlike `batch_pay_premiums`, an unknown, foreign or inactive policy does
+    /// not revert the batch: its entry in the returned list carries the error
+    /// code while the valid premiums are still applied. Results are in the
+    /// order of `policy_ids`.
+    ///
+    /// # Errors
+    /// * `BatchTooLarge` - If more than `MAX_BATCH_SIZE` policy IDs are given
+    /// * `ContractPaused` / `FunctionPaused` - If premium payments are paused
+    pub fn batch_pay_premiums_partial(
+        env: Env,
+        caller: Address,
+        policy_ids: Vec<u32>,
+    ) -> Result<Vec<PremiumPaymentResult>, InsuranceError> {
+        caller.require_auth();
+        Self::require_not_paused(&env, pause_functions::PAY_PREMIUM)?;
+        if policy_ids.len() > MAX_BATCH_SIZE {
+            return Err(InsuranceError::BatchTooLarge);
+        }
+        Self::extend_instance_ttl(&env);
+
+        let mut policies_map: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let current_time = env.ledger().timestamp();
+        let mut results = Vec::new(&env);
+        let mut paid_count = 0u32;
+        for id in policy_ids.iter() {
+            let checked = match policies_map.get(id) {
+                None => Err(InsuranceError::PolicyNotFound),
+                Some(p) if !Self::is_policy_holder(&p, &caller) => {
+                    Err(InsuranceError::Unauthorized)
+                }
+                Some(p) if !p.active => Err(InsuranceError::PolicyInactive),
+                Some(p) => Ok(p),
+            };
+            let mut policy = match checked {
+                Ok(p) => p,
+                Err(e) => {
+                    results.push_back(PremiumPaymentResult {
+                        policy_id: id,
+                        paid: false,
+                        error_code: e as u32,
+                    });
+                    continue;
+                }
+            };
+
+            policy.next_payment_date = current_time + (30 * 86400);
+            let event = PremiumPaidEvent {
+                policy_id: id,
+                name: policy.name.clone(),
+                amount: policy.monthly_premium,
+                next_payment_date: policy.next_payment_date,
+                timestamp: current_time,
+            };
+            env.events().publish((PREMIUM_PAID,), event);
+            Self::publish_event(&env, InsuranceEvent::PremiumPaid, (id, caller.clone()));
+            Self::collect_premium(&env, policy.id, &policy.owner, policy.monthly_premium);
+            policies_map.set(id, policy);
+            paid_count += 1;
+            results.push_back(PremiumPaymentResult {
+                policy_id: id,
+                paid: true,
+                error_code: 0,
+            });
+        }
+
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POLICIES"), &policies_map);
+        env.events().publish(
+            (symbol_short!("insure"), symbol_short!("batch_pay")),
+            (paid_count, caller),
+        );
+        Self::emit_standard_batch(
+            &env,
+            EventCategory::Transaction,
+            symbol_short!("prem_paid"),
+            paid_count,
+        );
+        Ok(results)
+    }
+
     /// Get a policy by ID
     ///
     /// # Arguments
@@ -721,27 +1377,54 @@ impl Insurance {
         policies.get(policy_id)
     }
 
-    /// Get all active policies for a specific owner
+    /// Get a page of active policies for a specific owner
     ///
     /// # Arguments
-    /// * `owner` - Address of the policy owner
+    /// * `owner` - Address of the policy owner or co-owner
+    /// * `cursor` - Start after this policy ID (0 for the first page)
+    /// * `limit` - Maximum items per page (0 = default, capped at MAX_PAGE_LIMIT)
     ///
     /// # Returns
-    /// Vec of active InsurancePolicy structs belonging to the owner
-    pub fn get_active_policies(env: Env, owner: Address) -> Vec<InsurancePolicy> {
-        let policies: Map<u32, InsurancePolicy> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("POLICIES"))
-            .unwrap_or_else(|| Map::new(&env));
+    /// PolicyPage of active policies held by the owner, including jointly held ones
+    pub fn get_active_policies(env: Env, owner: Address, cursor: u32, limit: u32) -> PolicyPage {
+        Self::policy_page(&env, &owner, cursor, limit, true)
+    }
 
-        let mut result = Vec::new(&env);
-        for (_, policy) in policies.iter() {
-            if policy.active && policy.owner == owner {
-                result.push_back(policy);
+    /// Get a page of all policies (active and inactive) for a specific owner
+    ///
+    /// Same cursor/limit semantics as `get_active_policies`.
+    pub fn get_all_policies_for_owner(
+        env: Env,
+        owner: Address,
+        cursor: u32,
+        limit: u32,
+    ) -> PolicyPage {
+        Self::policy_page(&env, &owner, cursor, limit, false)
+    }
+
+    /// Get the number of active policies held by `owner`, including jointly
+    /// held ones, without loading the policies themselves.
+    ///
+    /// # Arguments
+    /// * `owner` - Address of the policy owner or co-owner
+    pub fn get_active_policy_count(env: Env, owner: Address) -> u32 {
+        let counts: Option<Map<Address, u32>> =
+            env.storage().instance().get(&STORAGE_ACTIVE_COUNTS);
+        if let Some(count) = counts.and_then(|c| c.get(owner.clone())) {
+            return count;
+        }
+        let policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut count = 0;
+        for (_, policy) in policies.iter() {
+            if policy.active && Self::is_policy_holder(&policy, &owner) {
+                count += 1;
             }
         }
-        result
+        count
     }
 
     /// Get total monthly premium for all active policies of an owner
@@ -752,10 +1435,8 @@ impl Insurance {
     /// # Returns
     /// Total monthly premium amount for the owner's active policies
     pub fn get_total_monthly_premium(env: Env, owner: Address) -> i128 {
-        if let Some(totals) = Self::get_active_premium_totals_map(&env) {
-            if let Some(total) = totals.get(owner.clone()) {
-                return total;
-            }
+        if let Some(total) = Self::get_active_premium_total(&env, &owner) {
+            return total;
         }
 
         let mut total = 0i128;
@@ -766,32 +1447,321 @@ impl Insurance {
             .unwrap_or_else(|| Map::new(&env));
 
         for (_, policy) in policies.iter() {
-            if policy.active && policy.owner == owner {
+            if policy.active && Self::is_policy_holder(&policy, &owner) {
                 total += policy.monthly_premium;
             }
         }
         total
     }
 
+    /// Get the total monthly premium of an owner's active policies converted
+    /// to `display_asset` (e.g. "NGN") through the FX oracle, for display only.
+    ///
+    /// The result is rounded down to whole units of the display asset.
+    ///
+    /// # Errors
+    /// * `OracleNotConfigured` - If no FX oracle has been set
+    /// * `RateUnavailable` - If the oracle has no positive rate for `display_asset`
+    pub fn get_total_monthly_premium_in(
+        env: Env,
+        owner: Address,
+        display_asset: String,
+    ) -> Result<i128, InsuranceError> {
+        let oracle: Address = env
+            .storage()
+            .instance()
+            .get(&STORAGE_ORACLE)
+            .ok_or(InsuranceError::OracleNotConfigured)?;
+        let rate = PriceOracleClient::new(&env, &oracle)
+            .get_rate(&display_asset)
+            .filter(|r| *r > 0)
+            .ok_or(InsuranceError::RateUnavailable)?;
+        Self::get_total_monthly_premium(env, owner)
+            .checked_mul(RATE_SCALE)
+            .map(|v| v / rate)
+            .ok_or(InsuranceError::InvalidAmount)
+    }
+
+    /// Configure the FX oracle used by `get_total_monthly_premium_in` (admin only).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the contract admin
+    pub fn set_price_oracle(
+        env: Env,
+        caller: Address,
+        oracle: Address,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(InsuranceError::Unauthorized)?;
+        if admin != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(&STORAGE_ORACLE, &oracle);
+        Self::publish_event(&env, InsuranceEvent::OracleConfigured, oracle);
+        Ok(())
+    }
+
+    pub fn get_price_oracle(env: Env) -> Option<Address> {
+        env.storage().instance().get(&STORAGE_ORACLE)
+    }
+
+    /// Reason, time and any cooling-off refund of a policy's deactivation
+    pub fn get_deactivation(env: Env, policy_id: u32) -> Option<PolicyDeactivation> {
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, PolicyDeactivation>>(&STORAGE_DEACTIVATIONS)
+            .and_then(|records| records.get(policy_id))
+    }
+
+    /// Total premiums paid on a policy since it was created
+    pub fn get_premiums_paid(env: Env, policy_id: u32) -> i128 {
+        env.storage()
+            .persistent()
+            .get(&DataKey::PremPaid(policy_id))
+            .unwrap_or(0)
+    }
+
+    /// Total premiums `owner` paid between `from_ts` and `to_ts`, across all
+    /// of their policies.
+    ///
+    /// Payments are booked per calendar month (UTC) against whoever owned
+    /// the policy at the time, so the window is widened to whole months from
+    /// the one containing `from_ts` to the one containing `to_ts`.
+    pub fn get_premium_paid_total(env: Env, owner: Address, from_ts: u64, to_ts: u64) -> i128 {
+        if from_ts > to_ts {
+            return 0;
+        }
+        let (first, last) = (month_index(from_ts), month_index(to_ts));
+        let months: Option<Map<u32, i128>> =
+            env.storage().persistent().get(&DataKey::PremMonth(owner));
+        let mut total = 0i128;
+        if let Some(months) = months {
+            for (month, amount) in months.iter() {
+                if month > last {
+                    break;
+                }
+                if month >= first {
+                    total = total.saturating_add(amount);
+                }
+            }
+        }
+        total
+    }
+
+    /// Require `guardian` to approve every premium holiday on a policy, or
+    /// stop requiring it with `None`. Replacing or removing a guardian needs
+    /// the current guardian's authorization.
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not a policy holder
+    pub fn set_holiday_guardian(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        guardian: Option<Address>,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let policy =
+            Self::get_policy(env.clone(), policy_id).ok_or(InsuranceError::PolicyNotFound)?;
+        if !Self::is_policy_holder(&policy, &caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+
+        let mut guardians: Map<u32, Address> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_HOLIDAY_GUARDIANS)
+            .unwrap_or_else(|| Map::new(&env));
+        if let Some(current) = guardians.get(policy_id) {
+            current.require_auth();
+        }
+        match guardian {
+            Some(guardian) => guardians.set(policy_id, guardian),
+            None => {
+                guardians.remove(policy_id);
+            }
+        }
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&STORAGE_HOLIDAY_GUARDIANS, &guardians);
+        Ok(())
+    }
+
+    pub fn get_holiday_guardian(env: Env, policy_id: u32) -> Option<Address> {
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, Address>>(&STORAGE_HOLIDAY_GUARDIANS)
+            .and_then(|guardians| guardians.get(policy_id))
+    }
+
+    /// Pause premiums on a policy for `cycles` premium cycles, starting now.
+    ///
+    /// Premium schedules skip their runs during the holiday without counting
+    /// them as missed, and the next payment date moves past its end. Coverage
+    /// is reduced until the holiday ends; see `is_coverage_reduced`. If the
+    /// policy has a holiday guardian, the guardian must also authorize.
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not a policy holder
+    /// * `PolicyInactive` - If the policy is not active
+    /// * `InvalidAmount` - If cycles is zero or above `MAX_HOLIDAY_CYCLES`
+    /// * `HolidayActive` - If the policy is already on a holiday
+    pub fn declare_premium_holiday(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        cycles: u32,
+    ) -> Result<PremiumHoliday, InsuranceError> {
+        caller.require_auth();
+
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut policy = policies
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+        if !Self::is_policy_holder(&policy, &caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if !policy.active {
+            return Err(InsuranceError::PolicyInactive);
+        }
+        if cycles == 0 || cycles > MAX_HOLIDAY_CYCLES {
+            return Err(InsuranceError::InvalidAmount);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut holidays = Self::get_holidays(&env);
+        if holidays
+            .get(policy_id)
+            .is_some_and(|holiday| holiday.end > now)
+        {
+            return Err(InsuranceError::HolidayActive);
+        }
+        let approved_by = Self::get_holiday_guardian(env.clone(), policy_id);
+        if let Some(guardian) = &approved_by {
+            guardian.require_auth();
+        }
+
+        let holiday = PremiumHoliday {
+            cycles,
+            start: now,
+            end: now + PREMIUM_CYCLE_SECS * cycles as u64,
+            declared_by: caller,
+            approved_by,
+        };
+        holidays.set(policy_id, holiday.clone());
+        policy.next_payment_date = policy.next_payment_date.max(holiday.end);
+        policies.set(policy_id, policy);
+
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(&STORAGE_HOLIDAYS, &holidays);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POLICIES"), &policies);
+
+        Self::publish_event(
+            &env,
+            InsuranceEvent::PremiumHolidayStarted,
+            (policy_id, holiday.start, holiday.end),
+        );
+        Ok(holiday)
+    }
+
+    /// End a policy's premium holiday early. Premiums fall due again from now.
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist or has no holiday
+    /// * `Unauthorized` - If caller is not a policy holder
+    pub fn end_premium_holiday(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut policy = policies
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+        if !Self::is_policy_holder(&policy, &caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+
+        let now = env.ledger().timestamp();
+        let mut holidays = Self::get_holidays(&env);
+        let holiday = holidays
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+        holidays.remove(policy_id);
+        if holiday.end > now && policy.next_payment_date == holiday.end {
+            policy.next_payment_date = now;
+            policies.set(policy_id, policy);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("POLICIES"), &policies);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(&STORAGE_HOLIDAYS, &holidays);
+        Self::publish_event(
+            &env,
+            InsuranceEvent::PremiumHolidayEnded,
+            (policy_id, now.min(holiday.end)),
+        );
+        Ok(())
+    }
+
+    /// The policy's premium holiday, while it is running
+    pub fn get_premium_holiday(env: Env, policy_id: u32) -> Option<PremiumHoliday> {
+        let now = env.ledger().timestamp();
+        Self::get_holidays(&env)
+            .get(policy_id)
+            .filter(|holiday| holiday.end > now)
+    }
+
+    /// Whether the policy's coverage is reduced by a running premium holiday
+    pub fn is_coverage_reduced(env: Env, policy_id: u32) -> bool {
+        Self::get_premium_holiday(env, policy_id).is_some()
+    }
+
     /// Deactivate a policy
     ///
+    /// Jointly held policies need both the owner and the co-owner to authorize.
+    /// Within `COOLING_OFF_SECS` of creation, every premium paid on the policy
+    /// is recorded as a refund due from the reserve.
+    ///
     /// # Arguments
-    /// * `caller` - Address of the caller (must be the policy owner)
+    /// * `caller` - Address of the caller (must be the policy owner or co-owner)
     /// * `policy_id` - ID of the policy
+    /// * `reason` - Why the policy is being deactivated
     ///
     /// # Returns
     /// True if deactivation was successful
     ///
     /// # Panics
-    /// - If caller is not the policy owner
+    /// - If caller is not a policy holder
     /// - If policy is not found
     pub fn deactivate_policy(
         env: Env,
         caller: Address,
         policy_id: u32,
+        reason: DeactivationReason,
     ) -> Result<bool, InsuranceError> {
         caller.require_auth();
         Self::require_not_paused(&env, pause_functions::DEACTIVATE)?;
+        Self::extend_instance_ttl(&env);
 
         let mut policies: Map<u32, InsurancePolicy> = env
             .storage()
@@ -803,14 +1773,14 @@ impl Insurance {
             .get(policy_id)
             .ok_or(InsuranceError::PolicyNotFound)?;
 
-        if policy.owner != caller {
+        if !Self::is_policy_holder(&policy, &caller) {
             return Err(InsuranceError::Unauthorized);
         }
+        Self::require_all_holders_auth(&policy, &caller);
 
         let was_active = policy.active;
         policy.active = false;
         let policy_external_ref = policy.external_ref.clone();
-        policies.set(policy_id, policy);
         let premium_amount = policy.monthly_premium;
         policies.set(policy_id, policy.clone());
         env.storage()
@@ -818,20 +1788,24 @@ impl Insurance {
             .set(&symbol_short!("POLICIES"), &policies);
 
         if was_active {
-            Self::adjust_active_premium_total(&env, &caller, -premium_amount);
+            Self::adjust_holders_premium_total(&env, &policy, -premium_amount);
+            Self::adjust_holders_active_count(&env, &policy, -1);
+            Self::record_deactivation(&env, &policy, &caller, reason);
         }
         let event = PolicyDeactivatedEvent {
             policy_id,
             name: policy.name.clone(),
+            reason,
             timestamp: env.ledger().timestamp(),
         };
         env.events().publish((POLICY_DEACTIVATED,), event);
-        env.events().publish(
-            (symbol_short!("insure"), InsuranceEvent::PolicyDeactivated),
+        Self::publish_event(
+            &env,
+            InsuranceEvent::PolicyDeactivated,
             (policy_id, caller, policy_external_ref),
         );
 
-        true
+        Ok(true)
     }
 
     /// Set or clear an external reference ID for a policy
@@ -873,46 +1847,2415 @@ impl Insurance {
             .instance()
             .set(&symbol_short!("POLICIES"), &policies);
 
-        env.events().publish(
-            (symbol_short!("insure"), InsuranceEvent::ExternalRefUpdated),
+        Self::publish_event(
+            &env,
+            InsuranceEvent::ExternalRefUpdated,
             (policy_id, caller, external_ref),
-            (symbol_short!("insuranc"), InsuranceEvent::PolicyDeactivated),
-            (policy_id, caller),
         );
 
-        Ok(true)
+        true
     }
 
-    /// Extend the TTL of instance storage
-    fn extend_instance_ttl(env: &Env) {
+    // -----------------------------------------------------------------------
+    // Joint ownership
+    // -----------------------------------------------------------------------
+
+    /// Add a co-owner to a policy.
+    ///
+    /// Both the owner and the new co-owner must authorize. The co-owner can pay
+    /// premiums and sees the policy in owner-scoped queries.
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not the policy owner
+    /// * `PolicyInactive` - If the policy is not active
+    /// * `InvalidCoOwner` - If the policy already has a co-owner or co_owner is the owner
+    pub fn add_co_owner(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        co_owner: Address,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut policy = policies
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+
+        if policy.owner != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if !policy.active {
+            return Err(InsuranceError::PolicyInactive);
+        }
+        if policy.co_owner.is_some() || co_owner == policy.owner {
+            return Err(InsuranceError::InvalidCoOwner);
+        }
+        co_owner.require_auth();
+
+        Self::extend_instance_ttl(&env);
+
+        policy.co_owner = Some(co_owner.clone());
+        Self::adjust_active_premium_total(&env, &co_owner, policy.monthly_premium);
+        Self::adjust_active_count(&env, &co_owner, 1);
+        policies.set(policy_id, policy);
         env.storage()
             .instance()
-            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+            .set(&symbol_short!("POLICIES"), &policies);
+
+        Self::publish_event(
+            &env,
+            InsuranceEvent::CoOwnerAdded,
+            (policy_id, caller, co_owner),
+        );
+
+        Ok(())
+    }
+
+    /// Remove the co-owner from a policy. Both holders must authorize.
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not a policy holder
+    /// * `InvalidCoOwner` - If the policy has no co-owner
+    pub fn remove_co_owner(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut policy = policies
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+
+        if !Self::is_policy_holder(&policy, &caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        let co_owner = policy
+            .co_owner
+            .clone()
+            .ok_or(InsuranceError::InvalidCoOwner)?;
+        Self::require_all_holders_auth(&policy, &caller);
+
+        Self::extend_instance_ttl(&env);
+
+        if policy.active {
+            Self::adjust_active_premium_total(&env, &co_owner, -policy.monthly_premium);
+            Self::adjust_active_count(&env, &co_owner, -1);
+        }
+        policy.co_owner = None;
+        policies.set(policy_id, policy);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POLICIES"), &policies);
+
+        Self::publish_event(
+            &env,
+            InsuranceEvent::CoOwnerRemoved,
+            (policy_id, caller, co_owner),
+        );
+
+        Ok(())
+    }
+
+    /// Transfer a policy to a new primary owner.
+    ///
+    /// Both holders of a jointly held policy must authorize. The co-owner, if
+    /// any, is kept unless it is the new owner, in which case it is cleared.
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not a policy holder
+    /// * `PolicyInactive` - If the policy is not active
+    /// * `InvalidCoOwner` - If new_owner is already the owner
+    pub fn transfer_policy(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        new_owner: Address,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut policy = policies
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+
+        if !Self::is_policy_holder(&policy, &caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if !policy.active {
+            return Err(InsuranceError::PolicyInactive);
+        }
+        if new_owner == policy.owner {
+            return Err(InsuranceError::InvalidCoOwner);
+        }
+        Self::require_all_holders_auth(&policy, &caller);
+
+        Self::extend_instance_ttl(&env);
+
+        let previous_owner = policy.owner.clone();
+        Self::adjust_holders_premium_total(&env, &policy, -policy.monthly_premium);
+        Self::adjust_holders_active_count(&env, &policy, -1);
+        if policy.co_owner == Some(new_owner.clone()) {
+            policy.co_owner = None;
+        }
+        policy.owner = new_owner.clone();
+        Self::adjust_holders_premium_total(&env, &policy, policy.monthly_premium);
+        Self::adjust_holders_active_count(&env, &policy, 1);
+
+        policies.set(policy_id, policy);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POLICIES"), &policies);
+
+        Self::publish_event(
+            &env,
+            InsuranceEvent::PolicyTransferred,
+            (policy_id, previous_owner, new_owner),
+        );
+
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Owner migration
+    // -----------------------------------------------------------------------
+
+    /// Move `old_owner`'s place on each of `policy_ids` to `new_owner`, e.g.
+    /// after a key rotation or a lost wallet (pause admin only).
+    ///
+    /// `new_owner` must authorize, and so must `old_owner`, or `guardian`
+    /// when it is given and is one of `old_owner`'s recovery guardians (see
+    /// `set_recovery_guardians`). Without the admin, a holder who lost their
+    /// key is recovered by their guardians' M-of-N vote instead, see
+    /// `initiate_recovery`.
+    ///
+    /// `old_owner` may be the owner or the co-owner of each policy. Premium
+    /// schedules of the policies, premium totals and active counts follow the
+    /// policies.
+    ///
+    /// # Returns
+    /// Number of policies migrated
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin, guardian is not
+    ///   one of old_owner's recovery guardians, or old_owner does not hold a
+    ///   policy
+    /// * `BatchTooLarge` - If more than `MAX_BATCH_SIZE` policies are given
+    /// * `PolicyNotFound` - If a policy_id does not exist
+    /// * `InvalidCoOwner` - If new_owner is old_owner or already holds a policy
+    pub fn migrate_owner(
+        env: Env,
+        caller: Address,
+        old_owner: Address,
+        new_owner: Address,
+        policy_ids: Vec<u32>,
+        guardian: Option<Address>,
+    ) -> Result<u32, InsuranceError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(InsuranceError::Unauthorized)?;
+        if admin != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        new_owner.require_auth();
+        match guardian {
+            Some(guardian) => {
+                if !RECOVERY
+                    .config(&env, &old_owner)
+                    .is_some_and(|config| config.guardians.contains(&guardian))
+                {
+                    return Err(InsuranceError::Unauthorized);
+                }
+                guardian.require_auth();
+            }
+            None => old_owner.require_auth(),
+        }
+        if new_owner == old_owner {
+            return Err(InsuranceError::InvalidCoOwner);
+        }
+        if policy_ids.len() > MAX_BATCH_SIZE {
+            return Err(InsuranceError::BatchTooLarge);
+        }
+
+        let count = Self::move_policies(&env, &old_owner, &new_owner, &policy_ids)?;
+        Self::publish_event(
+            &env,
+            InsuranceEvent::OwnerMigrated,
+            (old_owner, new_owner, count),
+        );
+        Ok(count)
+    }
+
+    // -----------------------------------------------------------------------
+    // Guardian recovery
+    // -----------------------------------------------------------------------
+
+    /// Register the guardians who can move the owner's policies to a new
+    /// address if the owner loses their key, and how many of them must
+    /// agree. An empty list turns recovery off and drops any recovery in
+    /// progress.
+    ///
+    /// # Errors
+    /// * `InvalidRecoveryConfig` - If guardians repeat, include the owner,
+    ///   exceed `MAX_RECOVERY_GUARDIANS`, or threshold is 0 or above their
+    ///   number
+    pub fn set_recovery_guardians(
+        env: Env,
+        owner: Address,
+        guardians: Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), InsuranceError> {
+        RECOVERY.set_guardians(&env, &owner, &guardians, threshold)?;
+        Self::extend_instance_ttl(&env);
+        Self::publish_event(
+            &env,
+            InsuranceEvent::RecoveryConfigured,
+            (owner, guardians.len(), threshold),
+        );
+        Ok(())
+    }
+
+    pub fn get_recovery_config(env: Env, owner: Address) -> Option<RecoveryConfig> {
+        RECOVERY.config(&env, &owner)
+    }
+
+    /// Start recovering `owner`'s policies to `new_owner`. The initiating
+    /// guardian's approval is counted.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If guardian is not one of owner's guardians
+    /// * `RecoveryPending` - If a recovery of owner is already in progress
+    /// * `InvalidRecoveryConfig` - If new_owner is the owner
+    pub fn initiate_recovery(
+        env: Env,
+        guardian: Address,
+        owner: Address,
+        new_owner: Address,
+    ) -> Result<(), InsuranceError> {
+        RECOVERY.initiate(&env, &guardian, &owner, &new_owner)?;
+        Self::extend_instance_ttl(&env);
+        Self::publish_event(
+            &env,
+            InsuranceEvent::RecoveryInitiated,
+            (owner, new_owner, guardian),
+        );
+        Ok(())
+    }
+
+    /// Approve the recovery of `owner` as one of their guardians. Reaching
+    /// the threshold starts the `RECOVERY_DELAY_SECS` veto window.
+    ///
+    /// # Returns
+    /// Number of approvals so far
+    ///
+    /// # Errors
+    /// * `RecoveryNotFound` - If no recovery of owner is in progress
+    /// * `Unauthorized` - If guardian is not one of owner's guardians
+    pub fn approve_recovery(
+        env: Env,
+        guardian: Address,
+        owner: Address,
+    ) -> Result<u32, InsuranceError> {
+        let approvals = RECOVERY.approve(&env, &guardian, &owner)?;
+        Self::extend_instance_ttl(&env);
+        Self::publish_event(
+            &env,
+            InsuranceEvent::RecoveryApproved,
+            (owner, guardian, approvals),
+        );
+        Ok(approvals)
+    }
+
+    /// Stop a recovery of the caller's policies before it is executed.
+    ///
+    /// # Errors
+    /// * `RecoveryNotFound` - If no recovery of owner is in progress
+    pub fn veto_recovery(env: Env, owner: Address) -> Result<(), InsuranceError> {
+        let request = RECOVERY.veto(&env, &owner)?;
+        Self::extend_instance_ttl(&env);
+        Self::publish_event(
+            &env,
+            InsuranceEvent::RecoveryVetoed,
+            (owner, request.new_owner),
+        );
+        Ok(())
+    }
+
+    /// Move `owner`'s place on every policy they hold, as owner or co-owner,
+    /// to the recovery's new owner once the waiting period has passed
+    /// without a veto. The new owner must authorize. Premium schedules and
+    /// totals follow the policies as in `migrate_owner`.
+    ///
+    /// # Returns
+    /// Number of policies moved
+    ///
+    /// # Errors
+    /// * `RecoveryNotFound` - If no recovery of owner is in progress
+    /// * `RecoveryNotReady` - If the guardians' threshold has not been met or
+    ///   the waiting period has not passed
+    /// * `InvalidCoOwner` - If the new owner already holds one of the policies
+    pub fn execute_recovery(env: Env, owner: Address) -> Result<u32, InsuranceError> {
+        let new_owner = RECOVERY.complete(&env, &owner)?;
+        let policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut policy_ids = Vec::new(&env);
+        for (policy_id, policy) in policies.iter() {
+            if Self::is_policy_holder(&policy, &owner) {
+                policy_ids.push_back(policy_id);
+            }
+        }
+
+        let count = Self::move_policies(&env, &owner, &new_owner, &policy_ids)?;
+        Self::publish_event(
+            &env,
+            InsuranceEvent::RecoveryExecuted,
+            (owner, new_owner, count),
+        );
+        Ok(count)
+    }
+
+    pub fn get_recovery_request(env: Env, owner: Address) -> Option<RecoveryRequest> {
+        RECOVERY.request(&env, &owner)
+    }
+
+    /// Move `old_owner`'s place on each of `policy_ids` to `new_owner`,
+    /// shifting premium totals, active counts and premium schedules
+    fn move_policies(
+        env: &Env,
+        old_owner: &Address,
+        new_owner: &Address,
+        policy_ids: &Vec<u32>,
+    ) -> Result<u32, InsuranceError> {
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(env));
+
+        Self::extend_instance_ttl(env);
+        // A repeated ID finds the policy already moved and fails as
+        // Unauthorized, which undoes the whole migration.
+        for policy_id in policy_ids.iter() {
+            let mut policy = policies
+                .get(policy_id)
+                .ok_or(InsuranceError::PolicyNotFound)?;
+            if !Self::is_policy_holder(&policy, old_owner) {
+                return Err(InsuranceError::Unauthorized);
+            }
+            if Self::is_policy_holder(&policy, new_owner) {
+                return Err(InsuranceError::InvalidCoOwner);
+            }
+            if policy.active {
+                Self::adjust_holders_premium_total(env, &policy, -policy.monthly_premium);
+                Self::adjust_holders_active_count(env, &policy, -1);
+            }
+            if policy.owner == *old_owner {
+                policy.owner = new_owner.clone();
+            } else {
+                policy.co_owner = Some(new_owner.clone());
+            }
+            if policy.active {
+                Self::adjust_holders_premium_total(env, &policy, policy.monthly_premium);
+                Self::adjust_holders_active_count(env, &policy, 1);
+            }
+            policies.set(policy_id, policy);
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POLICIES"), &policies);
+
+        let mut schedules: Map<u32, PremiumSchedule> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("PREM_SCH"))
+            .unwrap_or_else(|| Map::new(env));
+        for (schedule_id, mut schedule) in schedules.iter() {
+            if schedule.owner == *old_owner && policy_ids.contains(schedule.policy_id) {
+                schedule.owner = new_owner.clone();
+                schedules.set(schedule_id, schedule);
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&symbol_short!("PREM_SCH"), &schedules);
+
+        Ok(policy_ids.len())
+    }
+
+    // -----------------------------------------------------------------------
+    // Insured members and claims
+    // -----------------------------------------------------------------------
+
+    /// Add an insured member to a policy (family plans).
+    ///
+    /// # Arguments
+    /// * `caller` - A policy holder (must authorize)
+    /// * `name_hash` - Hash of the member's name
+    /// * `relationship` - Relationship of the member to the owner
+    /// * `address` - Optional address the member can submit claims with
+    /// * `sub_limit` - Per-member coverage limit (0 < sub_limit ≤ coverage_amount)
+    ///
+    /// # Returns
+    /// The ID of the new member
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not a policy holder
+    /// * `PolicyInactive` - If the policy is not active
+    /// * `InvalidMember` - If the sub-limit is out of range or the policy is full
+    pub fn add_insured_member(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        name_hash: BytesN<32>,
+        relationship: Relationship,
+        address: Option<Address>,
+        sub_limit: i128,
+    ) -> Result<u32, InsuranceError> {
+        caller.require_auth();
+
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut policy = policies
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+
+        if !Self::is_policy_holder(&policy, &caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if !policy.active {
+            return Err(InsuranceError::PolicyInactive);
+        }
+        if sub_limit <= 0
+            || sub_limit > policy.coverage_amount
+            || policy.members.len() >= MAX_INSURED_MEMBERS
+        {
+            return Err(InsuranceError::InvalidMember);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let member_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_MBR"))
+            .unwrap_or(0u32)
+            + 1;
+
+        policy.members.push_back(InsuredMember {
+            id: member_id,
+            name_hash,
+            relationship,
+            address,
+            sub_limit,
+            claimed: 0,
+        });
+        policies.set(policy_id, policy);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POLICIES"), &policies);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_MBR"), &member_id);
+
+        Self::publish_event(
+            &env,
+            InsuranceEvent::MemberAdded,
+            (policy_id, member_id, relationship),
+        );
+
+        Ok(member_id)
+    }
+
+    /// Remove an insured member from a policy. Claims already filed for the
+    /// member are kept and still count against the policy's coverage.
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not a policy holder
+    /// * `MemberNotFound` - If the member is not on the policy
+    pub fn remove_insured_member(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        member_id: u32,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut policy = policies
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+
+        if !Self::is_policy_holder(&policy, &caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        let index = Self::find_member(&policy, member_id)?;
+
+        Self::extend_instance_ttl(&env);
+
+        policy.members.remove(index);
+        policies.set(policy_id, policy);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POLICIES"), &policies);
+
+        Self::publish_event(&env, InsuranceEvent::MemberRemoved, (policy_id, member_id));
+
+        Ok(())
+    }
+
+    /// Submit a claim for an insured member.
+    ///
+    /// The claimant must be a policy holder or the member's own address. The
+    /// amount counts against both the member's sub-limit and the policy's
+    /// overall coverage. `incident_hash` identifies the incident; only one
+    /// claim per incident can be approved across the owner's policies.
+    ///
+    /// # Returns
+    /// The ID of the new claim
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount ≤ 0
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `PolicyInactive` - If the policy is not active
+    /// * `MemberNotFound` - If the member is not on the policy
+    /// * `Unauthorized` - If claimant is neither a holder nor the member
+    /// * `SubLimitExceeded` - If the claim exceeds the member's sub-limit or the remaining coverage
+    /// * `WaitingPeriodActive` - If the coverage type's waiting period has not elapsed
+    pub fn submit_claim(
+        env: Env,
+        claimant: Address,
+        policy_id: u32,
+        member_id: u32,
+        amount: i128,
+        incident_hash: BytesN<32>,
+    ) -> Result<u32, InsuranceError> {
+        claimant.require_auth();
+        Self::require_not_paused(&env, pause_functions::SUBMIT_CLAIM)?;
+
+        if amount <= 0 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut policy = policies
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+
+        if !policy.active {
+            return Err(InsuranceError::PolicyInactive);
+        }
+        if env.ledger().timestamp() < Self::claims_unlock_at(&env, &policy) {
+            return Err(InsuranceError::WaitingPeriodActive);
+        }
+        let index = Self::find_member(&policy, member_id)?;
+        let mut member = policy.members.get(index).unwrap();
+
+        if !Self::is_policy_holder(&policy, &claimant) && member.address.as_ref() != Some(&claimant)
+        {
+            return Err(InsuranceError::Unauthorized);
+        }
+
+        let member_claimed = member.claimed.saturating_add(amount);
+        let policy_claimed = policy.total_claimed.saturating_add(amount);
+        if member_claimed > member.sub_limit || policy_claimed > policy.coverage_amount {
+            return Err(InsuranceError::SubLimitExceeded);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        member.claimed = member_claimed;
+        policy.members.set(index, member);
+        policy.total_claimed = policy_claimed;
+        policies.set(policy_id, policy);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POLICIES"), &policies);
+
+        let mut claims: Map<u32, Claim> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_CLAIMS)
+            .unwrap_or_else(|| Map::new(&env));
+        let claim_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_CLM"))
+            .unwrap_or(0u32)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_CLM"), &claim_id);
+
+        Self::publish_event(
+            &env,
+            InsuranceEvent::ClaimSubmitted,
+            (claim_id, policy_id, member_id, amount),
+        );
+        claims.set(
+            claim_id,
+            Claim {
+                id: claim_id,
+                policy_id,
+                member_id,
+                claimant: claimant.clone(),
+                amount,
+                submitted_at: env.ledger().timestamp(),
+                status: ClaimStatus::Pending,
+                pool_charge: 0,
+                incident_hash,
+            },
+        );
+        env.storage().instance().set(&STORAGE_CLAIMS, &claims);
+
+        Ok(claim_id)
+    }
+
+    pub fn get_claim(env: Env, claim_id: u32) -> Option<Claim> {
+        let claims: Map<u32, Claim> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_CLAIMS)
+            .unwrap_or_else(|| Map::new(&env));
+        claims.get(claim_id)
+    }
+
+    /// Get all claims filed against a policy, oldest first.
+    pub fn get_policy_claims(env: Env, policy_id: u32) -> Vec<Claim> {
+        let claims: Map<u32, Claim> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_CLAIMS)
+            .unwrap_or_else(|| Map::new(&env));
+        let mut result = Vec::new(&env);
+        for (_, claim) in claims.iter() {
+            if claim.policy_id == policy_id {
+                result.push_back(claim);
+            }
+        }
+        result
+    }
+
+    /// Attach a supporting document to a pending claim.
+    ///
+    /// Either a policy holder or the claimant may add evidence, up to
+    /// `MAX_CLAIM_EVIDENCE` items. Returns the number of items attached.
+    ///
+    /// # Errors
+    /// * `ClaimNotFound` - If claim_id does not exist
+    /// * `ClaimNotPending` - If the claim has already been decided
+    /// * `Unauthorized` - If caller is neither a policy holder nor the claimant
+    /// * `TooManyEvidence` - If the claim already has `MAX_CLAIM_EVIDENCE` items
+    pub fn add_claim_evidence(
+        env: Env,
+        caller: Address,
+        claim_id: u32,
+        hash: BytesN<32>,
+        content_type: String,
+    ) -> Result<u32, InsuranceError> {
+        caller.require_auth();
+        let claim = Self::get_claim(env.clone(), claim_id).ok_or(InsuranceError::ClaimNotFound)?;
+        if claim.status != ClaimStatus::Pending {
+            return Err(InsuranceError::ClaimNotPending);
+        }
+        if claim.claimant != caller {
+            let policy = Self::get_policy(env.clone(), claim.policy_id)
+                .ok_or(InsuranceError::PolicyNotFound)?;
+            if !Self::is_policy_holder(&policy, &caller) {
+                return Err(InsuranceError::Unauthorized);
+            }
+        }
+
+        let key = DataKey::ClaimEvidence(claim_id);
+        let mut items: Vec<ClaimEvidence> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(&env));
+        if items.len() >= MAX_CLAIM_EVIDENCE {
+            return Err(InsuranceError::TooManyEvidence);
+        }
+
+        items.push_back(ClaimEvidence {
+            hash: hash.clone(),
+            content_type,
+            added_by: caller,
+            added_at: env.ledger().timestamp(),
+        });
+        let count = items.len();
+        Self::set_entry(&env, &key, &items);
+
+        Self::publish_event(&env, InsuranceEvent::ClaimEvidenceAdded, (claim_id, hash));
+        Ok(count)
+    }
+
+    /// Record a reviewer note on a pending claim without deciding it
+    /// (pause admin only).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    /// * `ClaimNotFound` - If claim_id does not exist
+    /// * `ClaimNotPending` - If the claim has already been decided
+    pub fn comment_on_claim(
+        env: Env,
+        reviewer: Address,
+        claim_id: u32,
+        comment: String,
+    ) -> Result<(), InsuranceError> {
+        Self::review_claim(&env, reviewer, claim_id, ClaimStatus::Pending, comment)
+    }
+
+    /// Approve a pending claim with a reviewer comment (pause admin only).
+    ///
+    /// The claim amount is charged to the reinsurance pool on approval, up to
+    /// the capital available, and recorded as the claim's `pool_charge`.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    /// * `ClaimNotFound` - If claim_id does not exist
+    /// * `ClaimNotPending` - If the claim has already been decided
+    /// * `DuplicateIncident` - If the policy owner already has an approved
+    ///   claim for the same incident and no override was granted
+    pub fn approve_claim(
+        env: Env,
+        reviewer: Address,
+        claim_id: u32,
+        comment: String,
+    ) -> Result<(), InsuranceError> {
+        Self::review_claim(&env, reviewer, claim_id, ClaimStatus::Approved, comment)
+    }
+
+    /// Reject a pending claim with a reviewer comment (pause admin only).
+    ///
+    /// The claimed amount is released back to the member's sub-limit and the
+    /// policy's coverage. Pending claims are not charged to the pool, so
+    /// nothing is taken from it.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    /// * `ClaimNotFound` - If claim_id does not exist
+    /// * `ClaimNotPending` - If the claim has already been decided
+    pub fn reject_claim(
+        env: Env,
+        reviewer: Address,
+        claim_id: u32,
+        comment: String,
+    ) -> Result<(), InsuranceError> {
+        Self::review_claim(&env, reviewer, claim_id, ClaimStatus::Rejected, comment)
+    }
+
+    /// Let a pending claim be approved even though its owner already has an
+    /// approved claim for the same incident (pause admin only).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    /// * `ClaimNotFound` - If claim_id does not exist
+    /// * `ClaimNotPending` - If the claim has already been decided
+    pub fn allow_duplicate_incident(
+        env: Env,
+        caller: Address,
+        claim_id: u32,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(InsuranceError::Unauthorized)?;
+        if admin != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        let claim = Self::get_claim(env.clone(), claim_id).ok_or(InsuranceError::ClaimNotFound)?;
+        if claim.status != ClaimStatus::Pending {
+            return Err(InsuranceError::ClaimNotPending);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut overrides: Map<u32, bool> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_INCIDENT_OVERRIDES)
+            .unwrap_or_else(|| Map::new(&env));
+        overrides.set(claim_id, true);
+        env.storage()
+            .instance()
+            .set(&STORAGE_INCIDENT_OVERRIDES, &overrides);
+
+        Self::publish_event(
+            &env,
+            InsuranceEvent::DuplicateIncidentAllowed,
+            (claim_id, claim.incident_hash),
+        );
+        Ok(())
+    }
+
+    /// Get the first approved claim for an incident of an owner, if any.
+    pub fn get_incident_claim(env: Env, owner: Address, incident_hash: BytesN<32>) -> Option<u32> {
+        env.storage()
+            .persistent()
+            .get(&DataKey::IncidentClaim(owner, incident_hash))
+    }
+
+    /// Get a claim together with its evidence and reviewer comments.
+    pub fn get_claim_detail(env: Env, claim_id: u32) -> Option<ClaimDetail> {
+        let claim = Self::get_claim(env.clone(), claim_id)?;
+        let evidence = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ClaimEvidence(claim_id))
+            .unwrap_or_else(|| Vec::new(&env));
+        let reviews = env
+            .storage()
+            .persistent()
+            .get(&DataKey::ClaimReviews(claim_id))
+            .unwrap_or_else(|| Vec::new(&env));
+        Some(ClaimDetail {
+            claim,
+            evidence,
+            reviews,
+        })
+    }
+
+    fn review_claim(
+        env: &Env,
+        reviewer: Address,
+        claim_id: u32,
+        decision: ClaimStatus,
+        comment: String,
+    ) -> Result<(), InsuranceError> {
+        reviewer.require_auth();
+        let admin = Self::get_pause_admin(env).ok_or(InsuranceError::Unauthorized)?;
+        if admin != reviewer {
+            return Err(InsuranceError::Unauthorized);
+        }
+        let mut claims: Map<u32, Claim> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_CLAIMS)
+            .unwrap_or_else(|| Map::new(env));
+        let mut claim = claims.get(claim_id).ok_or(InsuranceError::ClaimNotFound)?;
+        if claim.status != ClaimStatus::Pending {
+            return Err(InsuranceError::ClaimNotPending);
+        }
+        if decision == ClaimStatus::Approved {
+            Self::record_incident(env, &claim)?;
+            claim.pool_charge = Self::absorb_loss(env, claim_id, claim.amount);
+        }
+
+        Self::extend_instance_ttl(env);
+        if decision == ClaimStatus::Rejected {
+            Self::release_claim(env, &claim);
+        }
+        if decision != ClaimStatus::Pending {
+            claim.status = decision;
+            claims.set(claim_id, claim);
+            env.storage().instance().set(&STORAGE_CLAIMS, &claims);
+        }
+
+        let key = DataKey::ClaimReviews(claim_id);
+        let mut trail: Vec<ClaimReview> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Vec::new(env));
+        trail.push_back(ClaimReview {
+            reviewer,
+            decision,
+            comment,
+            reviewed_at: env.ledger().timestamp(),
+        });
+        Self::set_entry(env, &key, &trail);
+
+        if decision != ClaimStatus::Pending {
+            Self::publish_event(env, InsuranceEvent::ClaimReviewed, (claim_id, decision));
+        }
+        Ok(())
+    }
+
+    /// Idempotency reference of an owner's incident in `INCIDENT_REFS`
+    fn incident_ref(env: &Env, owner: Address, incident_hash: BytesN<32>) -> BytesN<32> {
+        AuditLog::digest(env, (owner, incident_hash))
+    }
+
+    /// Record the incident of a claim being approved against its policy
+    /// owner, failing if the owner already has an approved claim for it and
+    /// the admin has not allowed this one through.
+    fn record_incident(env: &Env, claim: &Claim) -> Result<(), InsuranceError> {
+        let owner = Self::get_policy(env.clone(), claim.policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?
+            .owner;
+        let reference = Self::incident_ref(env, owner.clone(), claim.incident_hash.clone());
+        match INCIDENT_REFS.record_ref(env, &reference) {
+            Ok(()) => {
+                let key = DataKey::IncidentClaim(owner, claim.incident_hash.clone());
+                if !env.storage().persistent().has(&key) {
+                    Self::set_entry(env, &key, &claim.id);
+                }
+                Ok(())
+            }
+            Err(RefError::Duplicate) => {
+                let mut overrides: Map<u32, bool> = env
+                    .storage()
+                    .instance()
+                    .get(&STORAGE_INCIDENT_OVERRIDES)
+                    .unwrap_or_else(|| Map::new(env));
+                if !overrides.contains_key(claim.id) {
+                    return Err(InsuranceError::DuplicateIncident);
+                }
+                overrides.remove(claim.id);
+                env.storage()
+                    .instance()
+                    .set(&STORAGE_INCIDENT_OVERRIDES, &overrides);
+                Ok(())
+            }
+        }
+    }
+
+    /// Undo the accounting of a rejected claim: give the amount back to the
+    /// member's sub-limit and any pool charge back to the pool.
+    fn release_claim(env: &Env, claim: &Claim) {
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(env));
+        if let Some(mut policy) = policies.get(claim.policy_id) {
+            policy.total_claimed = policy.total_claimed.saturating_sub(claim.amount).max(0);
+            // The member may have been removed from the policy since
+            if let Ok(index) = Self::find_member(&policy, claim.member_id) {
+                let mut member = policy.members.get(index).unwrap();
+                member.claimed = member.claimed.saturating_sub(claim.amount).max(0);
+                policy.members.set(index, member);
+            }
+            policies.set(claim.policy_id, policy);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("POLICIES"), &policies);
+        }
+
+        if claim.pool_charge > 0 {
+            let mut pool = Self::get_staking_pool(env.clone());
+            Self::book_earnings(&mut pool, claim.pool_charge);
+            pool.losses_absorbed = pool.losses_absorbed.saturating_sub(claim.pool_charge);
+            pool.total_capital = pool.total_capital.saturating_add(claim.pool_charge);
+            env.storage().instance().set(&STORAGE_STAKING_POOL, &pool);
+        }
+    }
+
+    /// Set the waiting period for a coverage type (pause admin only).
+    ///
+    /// Claims on policies of this type are rejected until `period_secs` after
+    /// the policy was created. Applies to existing policies as well.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    pub fn set_waiting_period(
+        env: Env,
+        caller: Address,
+        coverage_type: CoverageType,
+        period_secs: u64,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(InsuranceError::Unauthorized)?;
+        if admin != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+
+        let mut periods: Map<CoverageType, u64> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_WAITING_PERIODS)
+            .unwrap_or_else(|| Map::new(&env));
+        periods.set(coverage_type, period_secs);
+        env.storage()
+            .instance()
+            .set(&STORAGE_WAITING_PERIODS, &periods);
+
+        Self::publish_event(
+            &env,
+            InsuranceEvent::WaitingPeriodSet,
+            (coverage_type, period_secs),
+        );
+
+        Ok(())
+    }
+
+    /// Get the waiting period in seconds for a coverage type (0 if none).
+    pub fn get_waiting_period(env: Env, coverage_type: CoverageType) -> u64 {
+        let periods: Map<CoverageType, u64> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_WAITING_PERIODS)
+            .unwrap_or_else(|| Map::new(&env));
+        periods.get(coverage_type).unwrap_or(0)
+    }
+
+    /// Get the timestamp from which claims on a policy are accepted.
+    pub fn get_claims_unlock_at(env: Env, policy_id: u32) -> Option<u64> {
+        let policy = Self::get_policy(env.clone(), policy_id)?;
+        Some(Self::claims_unlock_at(&env, &policy))
+    }
+
+    fn claims_unlock_at(env: &Env, policy: &InsurancePolicy) -> u64 {
+        let period = Self::get_waiting_period(env.clone(), policy.coverage_type);
+        policy.created_at.saturating_add(period)
+    }
+
+    // -----------------------------------------------------------------------
+    // Survivor claims (life policies)
+    // -----------------------------------------------------------------------
+
+    /// Allow or disallow a verifier to decide survivor claims (pause admin
+    /// only).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    pub fn set_claim_verifier(
+        env: Env,
+        caller: Address,
+        verifier: Address,
+        allowed: bool,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(InsuranceError::Unauthorized)?;
+        if admin != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut verifiers: Map<Address, bool> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_VERIFIERS)
+            .unwrap_or_else(|| Map::new(&env));
+        if allowed {
+            verifiers.set(verifier.clone(), true);
+        } else {
+            verifiers.remove(verifier.clone());
+        }
+        env.storage().instance().set(&STORAGE_VERIFIERS, &verifiers);
+        Self::publish_event(&env, InsuranceEvent::VerifierSet, (verifier, allowed));
+        Ok(())
+    }
+
+    pub fn is_claim_verifier(env: Env, verifier: Address) -> bool {
+        env.storage()
+            .instance()
+            .get::<_, Map<Address, bool>>(&STORAGE_VERIFIERS)
+            .is_some_and(|verifiers| verifiers.contains_key(verifier))
+    }
+
+    /// Name the beneficiaries of a life policy and their percentage of the
+    /// payout, replacing any previous list (policy holder only).
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `Unauthorized` - If caller is not a policy holder
+    /// * `NotLifePolicy` - If the policy is not Life coverage
+    /// * `InvalidBeneficiaries` - If the list is empty, longer than
+    ///   `MAX_BENEFICIARIES`, names an address twice or does not add up to 100%
+    /// * `SurvivorClaimExists` - If a survivor claim is pending
+    pub fn set_beneficiaries(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        beneficiaries: Vec<Beneficiary>,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let policy =
+            Self::get_policy(env.clone(), policy_id).ok_or(InsuranceError::PolicyNotFound)?;
+        if !Self::is_policy_holder(&policy, &caller) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if policy.coverage_type != CoverageType::Life {
+            return Err(InsuranceError::NotLifePolicy);
+        }
+        if beneficiaries.is_empty() || beneficiaries.len() > MAX_BENEFICIARIES {
+            return Err(InsuranceError::InvalidBeneficiaries);
+        }
+        let mut total = 0u32;
+        for (i, beneficiary) in beneficiaries.iter().enumerate() {
+            if beneficiary.percent == 0
+                || beneficiaries
+                    .iter()
+                    .skip(i + 1)
+                    .any(|other| other.address == beneficiary.address)
+            {
+                return Err(InsuranceError::InvalidBeneficiaries);
+            }
+            total = total.saturating_add(beneficiary.percent);
+        }
+        if total != 100 {
+            return Err(InsuranceError::InvalidBeneficiaries);
+        }
+        if Self::get_survivor_claim(env.clone(), policy_id)
+            .is_some_and(|claim| claim.status == ClaimStatus::Pending)
+        {
+            return Err(InsuranceError::SurvivorClaimExists);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut all: Map<u32, Vec<Beneficiary>> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_BENEFICIARIES)
+            .unwrap_or_else(|| Map::new(&env));
+        let count = beneficiaries.len();
+        all.set(policy_id, beneficiaries);
+        env.storage().instance().set(&STORAGE_BENEFICIARIES, &all);
+        Self::publish_event(&env, InsuranceEvent::BeneficiariesSet, (policy_id, count));
+        Ok(())
+    }
+
+    pub fn get_beneficiaries(env: Env, policy_id: u32) -> Vec<Beneficiary> {
+        env.storage()
+            .instance()
+            .get::<_, Map<u32, Vec<Beneficiary>>>(&STORAGE_BENEFICIARIES)
+            .and_then(|all| all.get(policy_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// File a death claim on a life policy as one of its beneficiaries.
+    /// `evidence_hash` references the death certificate, kept off-chain.
+    ///
+    /// # Returns
+    /// The ID of the new claim, from the same sequence as `submit_claim`
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If policy_id does not exist
+    /// * `NotLifePolicy` - If the policy is not Life coverage
+    /// * `PolicyInactive` - If the policy is not active
+    /// * `WaitingPeriodActive` - If the policy's waiting period has not passed
+    /// * `Unauthorized` - If beneficiary is not named on the policy
+    /// * `SurvivorClaimExists` - If a survivor claim is pending or was approved
+    pub fn file_survivor_claim(
+        env: Env,
+        beneficiary: Address,
+        policy_id: u32,
+        evidence_hash: BytesN<32>,
+    ) -> Result<u32, InsuranceError> {
+        beneficiary.require_auth();
+        Self::require_not_paused(&env, pause_functions::SUBMIT_CLAIM)?;
+
+        let policy =
+            Self::get_policy(env.clone(), policy_id).ok_or(InsuranceError::PolicyNotFound)?;
+        if policy.coverage_type != CoverageType::Life {
+            return Err(InsuranceError::NotLifePolicy);
+        }
+        if !policy.active {
+            return Err(InsuranceError::PolicyInactive);
+        }
+        if env.ledger().timestamp() < Self::claims_unlock_at(&env, &policy) {
+            return Err(InsuranceError::WaitingPeriodActive);
+        }
+        if !Self::get_beneficiaries(env.clone(), policy_id)
+            .iter()
+            .any(|b| b.address == beneficiary)
+        {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if Self::get_survivor_claim(env.clone(), policy_id)
+            .is_some_and(|claim| claim.status != ClaimStatus::Rejected)
+        {
+            return Err(InsuranceError::SurvivorClaimExists);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let claim_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_CLM"))
+            .unwrap_or(0u32)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_CLM"), &claim_id);
+
+        let mut claims = Self::get_survivor_claims(&env);
+        claims.set(
+            policy_id,
+            SurvivorClaim {
+                id: claim_id,
+                policy_id,
+                filed_by: beneficiary.clone(),
+                evidence_hash: evidence_hash.clone(),
+                filed_at: env.ledger().timestamp(),
+                status: ClaimStatus::Pending,
+                decided_by: None,
+                payouts: Vec::new(&env),
+                pool_charge: 0,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&STORAGE_SURVIVOR_CLAIMS, &claims);
+
+        Self::publish_event(
+            &env,
+            InsuranceEvent::SurvivorClaimFiled,
+            (claim_id, policy_id, beneficiary, evidence_hash),
+        );
+        Ok(claim_id)
+    }
+
+    /// Approve the pending survivor claim of a policy (verifier only).
+    ///
+    /// The coverage amount is paid out to the beneficiaries by their
+    /// percentages, with any rounding remainder going to the first one, and
+    /// charged to the reinsurance pool. The policy is then closed with
+    /// `DeactivationReason::Deceased`.
+    ///
+    /// # Returns
+    /// The payout of each beneficiary
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If verifier is not an allowed claim verifier
+    /// * `ClaimNotFound` - If the policy has no survivor claim
+    /// * `ClaimNotPending` - If the claim has already been decided
+    /// * `PolicyInactive` - If the policy was closed in the meantime
+    pub fn approve_survivor_claim(
+        env: Env,
+        verifier: Address,
+        policy_id: u32,
+    ) -> Result<Vec<SurvivorPayout>, InsuranceError> {
+        let mut claim = Self::pending_survivor_claim(&env, &verifier, policy_id)?;
+
+        let mut policies: Map<u32, InsurancePolicy> = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("POLICIES"))
+            .unwrap_or_else(|| Map::new(&env));
+        let mut policy = policies
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+        if !policy.active {
+            return Err(InsuranceError::PolicyInactive);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let beneficiaries = Self::get_beneficiaries(env.clone(), policy_id);
+        let mut payouts = Vec::new(&env);
+        let mut paid = 0i128;
+        for beneficiary in beneficiaries.iter() {
+            let amount = policy
+                .coverage_amount
+                .saturating_mul(beneficiary.percent as i128)
+                / 100;
+            paid = paid.saturating_add(amount);
+            payouts.push_back(SurvivorPayout {
+                beneficiary: beneficiary.address,
+                amount,
+            });
+        }
+        if let Some(mut first) = payouts.get(0) {
+            first.amount = first.amount.saturating_add(policy.coverage_amount - paid);
+            payouts.set(0, first);
+        }
+
+        claim.status = ClaimStatus::Approved;
+        claim.decided_by = Some(verifier.clone());
+        claim.payouts = payouts.clone();
+        claim.pool_charge = Self::absorb_loss(&env, claim.id, policy.coverage_amount);
+        let claim_id = claim.id;
+        let mut claims = Self::get_survivor_claims(&env);
+        claims.set(policy_id, claim);
+        env.storage()
+            .instance()
+            .set(&STORAGE_SURVIVOR_CLAIMS, &claims);
+        Self::publish_event(
+            &env,
+            InsuranceEvent::SurvivorClaimDecided,
+            (claim_id, policy_id, ClaimStatus::Approved, verifier.clone()),
+        );
+        for payout in payouts.iter() {
+            Self::publish_event(
+                &env,
+                InsuranceEvent::SurvivorPaid,
+                (claim_id, payout.beneficiary, payout.amount),
+            );
+        }
+
+        policy.active = false;
+        policies.set(policy_id, policy.clone());
+        env.storage()
+            .instance()
+            .set(&symbol_short!("POLICIES"), &policies);
+        Self::adjust_holders_premium_total(&env, &policy, -policy.monthly_premium);
+        Self::adjust_holders_active_count(&env, &policy, -1);
+        Self::record_deactivation(&env, &policy, &verifier, DeactivationReason::Deceased);
+        env.events().publish(
+            (POLICY_DEACTIVATED,),
+            PolicyDeactivatedEvent {
+                policy_id,
+                name: policy.name.clone(),
+                reason: DeactivationReason::Deceased,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        Self::publish_event(
+            &env,
+            InsuranceEvent::PolicyDeactivated,
+            (policy_id, verifier, policy.external_ref),
+        );
+
+        Ok(payouts)
+    }
+
+    /// Reject the pending survivor claim of a policy (verifier only). A
+    /// beneficiary can file again with new evidence.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If verifier is not an allowed claim verifier
+    /// * `ClaimNotFound` - If the policy has no survivor claim
+    /// * `ClaimNotPending` - If the claim has already been decided
+    pub fn reject_survivor_claim(
+        env: Env,
+        verifier: Address,
+        policy_id: u32,
+    ) -> Result<(), InsuranceError> {
+        let mut claim = Self::pending_survivor_claim(&env, &verifier, policy_id)?;
+
+        Self::extend_instance_ttl(&env);
+        claim.status = ClaimStatus::Rejected;
+        claim.decided_by = Some(verifier.clone());
+        let claim_id = claim.id;
+        let mut claims = Self::get_survivor_claims(&env);
+        claims.set(policy_id, claim);
+        env.storage()
+            .instance()
+            .set(&STORAGE_SURVIVOR_CLAIMS, &claims);
+        Self::publish_event(
+            &env,
+            InsuranceEvent::SurvivorClaimDecided,
+            (claim_id, policy_id, ClaimStatus::Rejected, verifier),
+        );
+        Ok(())
+    }
+
+    /// Latest survivor claim filed on a policy, if any.
+    pub fn get_survivor_claim(env: Env, policy_id: u32) -> Option<SurvivorClaim> {
+        Self::get_survivor_claims(&env).get(policy_id)
+    }
+
+    fn pending_survivor_claim(
+        env: &Env,
+        verifier: &Address,
+        policy_id: u32,
+    ) -> Result<SurvivorClaim, InsuranceError> {
+        verifier.require_auth();
+        if !Self::is_claim_verifier(env.clone(), verifier.clone()) {
+            return Err(InsuranceError::Unauthorized);
+        }
+        let claim = Self::get_survivor_claim(env.clone(), policy_id)
+            .ok_or(InsuranceError::ClaimNotFound)?;
+        if claim.status != ClaimStatus::Pending {
+            return Err(InsuranceError::ClaimNotPending);
+        }
+        Ok(claim)
+    }
+
+    fn get_survivor_claims(env: &Env) -> Map<u32, SurvivorClaim> {
+        env.storage()
+            .instance()
+            .get(&STORAGE_SURVIVOR_CLAIMS)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    // -----------------------------------------------------------------------
+    // Underwriting
+    // -----------------------------------------------------------------------
+
+    /// Set the monthly premium rate for a coverage type and risk tier, in
+    /// basis points of the coverage amount (pause admin only).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    /// * `InvalidAmount` - If rate_bps is zero or exceeds 10_000
+    pub fn set_tier_rate(
+        env: Env,
+        caller: Address,
+        coverage_type: CoverageType,
+        risk_tier: RiskTier,
+        rate_bps: u32,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(InsuranceError::Unauthorized)?;
+        if admin != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if rate_bps == 0 || rate_bps > 10_000 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut rates = Self::get_tier_rates(&env);
+        rates.set((coverage_type, risk_tier), rate_bps);
+        env.storage().instance().set(&STORAGE_TIER_RATES, &rates);
+
+        Self::publish_event(
+            &env,
+            InsuranceEvent::TierRateSet,
+            (coverage_type, risk_tier, rate_bps),
+        );
+        Ok(())
+    }
+
+    pub fn get_tier_rate(
+        env: Env,
+        coverage_type: CoverageType,
+        risk_tier: RiskTier,
+    ) -> Option<u32> {
+        Self::get_tier_rates(&env).get((coverage_type, risk_tier))
+    }
+
+    /// Set (or clear with `None`) the pricing contract that quotes premiums
+    /// (pause admin only).
+    ///
+    /// While it is set, `estimate_premium` returns its `price` instead of the
+    /// rate table's, and `create_policy` rejects premiums below its Low-tier
+    /// quote. A new pricing model is rolled out by pointing this at another
+    /// contract.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    pub fn set_pricing_contract(
+        env: Env,
+        caller: Address,
+        pricing: Option<Address>,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(InsuranceError::Unauthorized)?;
+        if admin != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        match &pricing {
+            Some(pricing) => env.storage().instance().set(&STORAGE_PRICING, pricing),
+            None => env.storage().instance().remove(&STORAGE_PRICING),
+        }
+        Self::publish_event(&env, InsuranceEvent::PricingConfigured, pricing);
+        Ok(())
+    }
+
+    pub fn get_pricing_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&STORAGE_PRICING)
+    }
+
+    /// Cap the coverage of new policies of a coverage type at `max_ratio`
+    /// times their monthly premium (pause admin only). `None` lifts the cap.
+    ///
+    /// Existing policies are not affected.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    /// * `InvalidAmount` - If max_ratio is zero
+    pub fn set_max_coverage_ratio(
+        env: Env,
+        caller: Address,
+        coverage_type: CoverageType,
+        max_ratio: Option<u32>,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(InsuranceError::Unauthorized)?;
+        if admin != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if max_ratio == Some(0) {
+            return Err(InsuranceError::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut ratios: Map<CoverageType, u32> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_COVERAGE_RATIOS)
+            .unwrap_or_else(|| Map::new(&env));
+        match max_ratio {
+            Some(max_ratio) => ratios.set(coverage_type, max_ratio),
+            None => {
+                ratios.remove(coverage_type);
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&STORAGE_COVERAGE_RATIOS, &ratios);
+
+        Self::publish_event(
+            &env,
+            InsuranceEvent::CoverageRatioSet,
+            (coverage_type, max_ratio),
+        );
+        Ok(())
+    }
+
+    /// Largest coverage-to-monthly-premium ratio allowed for new policies of
+    /// a coverage type, or `None` if uncapped
+    pub fn get_max_coverage_ratio(env: Env, coverage_type: CoverageType) -> Option<u32> {
+        let ratios: Map<CoverageType, u32> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_COVERAGE_RATIOS)
+            .unwrap_or_else(|| Map::new(&env));
+        ratios.get(coverage_type)
+    }
+
+    /// Move up to `limit` owners' premium totals out of the legacy `PRM_TOT`
+    /// instance map into per-owner persistent entries (pause admin only).
+    /// An owner's total is also moved the next time it changes; this drains
+    /// owners whose policies no longer change.
+    ///
+    /// # Returns
+    /// Number of owners left in the map; it is deleted when this reaches 0
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    pub fn migrate_premium_totals(
+        env: Env,
+        caller: Address,
+        limit: u32,
+    ) -> Result<u32, InsuranceError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(InsuranceError::Unauthorized)?;
+        if admin != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+
+        let mut totals: Map<Address, i128> =
+            match env.storage().instance().get(&STORAGE_PREMIUM_TOTALS) {
+                Some(totals) => totals,
+                None => return Ok(0),
+            };
+        Self::extend_instance_ttl(&env);
+        for (owner, total) in totals.clone().iter().take(limit as usize) {
+            totals.remove(owner.clone());
+            Self::set_premium_total_entry(&env, &owner, total);
+        }
+        Self::store_legacy_premium_totals(&env, &totals);
+        Ok(totals.len())
+    }
+
+    /// Monthly premium for `coverage_amount` quoted by the pricing contract,
+    /// or at the rate table's rate for the coverage type and risk tier when
+    /// no pricing contract is set.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If coverage_amount is not positive
+    /// * `RateUnavailable` - If the pricing contract fails or quotes a
+    ///   premium that is not positive
+    /// * `TierRateNotSet` - If the rate table has no rate for the pair
+    pub fn estimate_premium(
+        env: Env,
+        coverage_type: CoverageType,
+        risk_tier: RiskTier,
+        coverage_amount: i128,
+    ) -> Result<i128, InsuranceError> {
+        if coverage_amount <= 0 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+        if let Some(premium) = Self::quote_premium(&env, coverage_type, coverage_amount, risk_tier)?
+        {
+            return Ok(premium);
+        }
+        let rate_bps = Self::get_tier_rate(env, coverage_type, risk_tier)
+            .ok_or(InsuranceError::TierRateNotSet)?;
+        let premium = coverage_amount.saturating_mul(rate_bps as i128) / 10_000;
+        if premium <= 0 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+        Ok(premium)
+    }
+
+    /// Create a policy priced from the rate table. The underwriter (the pause
+    /// admin) co-signs, assigning the risk tier from the owner's questionnaire
+    /// answers, whose hash is kept with the policy.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If underwriter is not the pause admin
+    /// * `TierRateNotSet` - If the rate table has no rate for the pair
+    /// * Any error of `create_policy`
+    #[allow(clippy::too_many_arguments)]
+    pub fn create_underwritten_policy(
+        env: Env,
+        owner: Address,
+        underwriter: Address,
+        name: String,
+        coverage_type: CoverageType,
+        coverage_amount: i128,
+        external_ref: Option<String>,
+        questionnaire_hash: BytesN<32>,
+        risk_tier: RiskTier,
+    ) -> Result<u32, InsuranceError> {
+        underwriter.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(InsuranceError::Unauthorized)?;
+        if admin != underwriter {
+            return Err(InsuranceError::Unauthorized);
+        }
+        let monthly_premium =
+            Self::estimate_premium(env.clone(), coverage_type, risk_tier, coverage_amount)?;
+        let policy_id = Self::create_policy(
+            env.clone(),
+            owner,
+            name,
+            coverage_type,
+            monthly_premium,
+            coverage_amount,
+            external_ref,
+        )?;
+
+        let mut records = Self::get_underwriting_records(&env);
+        records.set(
+            policy_id,
+            Underwriting {
+                questionnaire_hash,
+                risk_tier,
+                previous_tier: risk_tier,
+                effective_at: env.ledger().timestamp(),
+                assessed_by: underwriter,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&STORAGE_UNDERWRITING, &records);
+        Ok(policy_id)
+    }
+
+    /// Move a policy to a new risk tier from `effective_at` (pause admin
+    /// only). The premium is not changed; `estimate_premium` with the new
+    /// tier gives the renewal price.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    /// * `PolicyNotFound` - If the policy has no underwriting record
+    /// * `InvalidTimestamp` - If effective_at is in the past
+    pub fn retier_policy(
+        env: Env,
+        caller: Address,
+        policy_id: u32,
+        risk_tier: RiskTier,
+        effective_at: u64,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(InsuranceError::Unauthorized)?;
+        if admin != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        let now = env.ledger().timestamp();
+        if effective_at < now {
+            return Err(InsuranceError::InvalidTimestamp);
+        }
+
+        let mut records = Self::get_underwriting_records(&env);
+        let mut record = records
+            .get(policy_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+        let old_tier = Self::tier_in_effect(&record, now);
+        record.previous_tier = old_tier;
+        record.risk_tier = risk_tier;
+        record.effective_at = effective_at;
+        record.assessed_by = caller;
+        records.set(policy_id, record);
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&STORAGE_UNDERWRITING, &records);
+
+        Self::publish_event(
+            &env,
+            InsuranceEvent::RiskTierChanged,
+            (policy_id, old_tier, risk_tier, effective_at),
+        );
+        Ok(())
+    }
+
+    pub fn get_underwriting(env: Env, policy_id: u32) -> Option<Underwriting> {
+        Self::get_underwriting_records(&env).get(policy_id)
+    }
+
+    /// Risk tier of a policy in effect now
+    pub fn get_risk_tier(env: Env, policy_id: u32) -> Option<RiskTier> {
+        let now = env.ledger().timestamp();
+        Self::get_underwriting(env, policy_id).map(|record| Self::tier_in_effect(&record, now))
+    }
+
+    fn tier_in_effect(record: &Underwriting, now: u64) -> RiskTier {
+        if now >= record.effective_at {
+            record.risk_tier
+        } else {
+            record.previous_tier
+        }
+    }
+
+    /// Premium quoted by the pricing contract, or `None` when none is set
+    fn quote_premium(
+        env: &Env,
+        coverage_type: CoverageType,
+        coverage_amount: i128,
+        risk_tier: RiskTier,
+    ) -> Result<Option<i128>, InsuranceError> {
+        let pricing: Address = match env.storage().instance().get(&STORAGE_PRICING) {
+            Some(pricing) => pricing,
+            None => return Ok(None),
+        };
+        match PricingClient::new(env, &pricing).try_price(
+            &coverage_type,
+            &coverage_amount,
+            &risk_tier,
+        ) {
+            Ok(Ok(premium)) if premium > 0 => Ok(Some(premium)),
+            _ => Err(InsuranceError::RateUnavailable),
+        }
+    }
+
+    fn get_tier_rates(env: &Env) -> Map<(CoverageType, RiskTier), u32> {
+        env.storage()
+            .instance()
+            .get(&STORAGE_TIER_RATES)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn get_underwriting_records(env: &Env) -> Map<u32, Underwriting> {
+        env.storage()
+            .instance()
+            .get(&STORAGE_UNDERWRITING)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    // -----------------------------------------------------------------------
+    // Reinsurance staking pool
+    // -----------------------------------------------------------------------
+
+    /// Set the lockup and premium share offered to stakers (pause admin only).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    /// * `InvalidAmount` - If premium_share_bps exceeds 10_000
+    pub fn configure_staking(
+        env: Env,
+        caller: Address,
+        config: StakingConfig,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(InsuranceError::Unauthorized)?;
+        if admin != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if config.premium_share_bps > 10_000 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&STORAGE_STAKING_CONFIG, &config);
+
+        Self::publish_event(
+            &env,
+            InsuranceEvent::StakingConfigured,
+            (config.lockup_secs, config.premium_share_bps),
+        );
+
+        Ok(())
+    }
+
+    /// Set the token stakers deposit and withdraw (pause admin only).
+    ///
+    /// The token can only be changed while the pool has no outstanding
+    /// shares, since the capital already staked is held in the current one.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    /// * `StakeTokenInUse` - If a different token is set and the pool has shares
+    pub fn set_stake_token(
+        env: Env,
+        caller: Address,
+        token: Address,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(InsuranceError::Unauthorized)?;
+        if admin != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if Self::get_stake_token(env.clone()).is_some_and(|current| current != token)
+            && Self::get_staking_pool(env.clone()).total_shares > 0
+        {
+            return Err(InsuranceError::StakeTokenInUse);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(&STORAGE_STAKE_TOKEN, &token);
+        Self::publish_event(&env, InsuranceEvent::StakeTokenSet, token);
+        Ok(())
+    }
+
+    pub fn get_stake_token(env: Env) -> Option<Address> {
+        env.storage().instance().get(&STORAGE_STAKE_TOKEN)
+    }
+
+    pub fn get_staking_config(env: Env) -> StakingConfig {
+        env.storage()
+            .instance()
+            .get(&STORAGE_STAKING_CONFIG)
+            .unwrap_or(StakingConfig {
+                lockup_secs: 0,
+                premium_share_bps: 0,
+            })
+    }
+
+    /// Deposit capital backing the insurance reserve.
+    ///
+    /// `amount` of the stake token is transferred from the staker to this
+    /// contract and one share is minted per unit. Earnings are booked from
+    /// the deposit on, so earlier stakers keep the premium income and losses
+    /// booked before it. The staker's whole position is locked for
+    /// `lockup_secs` from now.
+    ///
+    /// # Returns
+    /// The number of shares minted
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount ≤ 0
+    /// * `StakeTokenNotSet` - If no stake token has been set
+    pub fn deposit_stake(env: Env, staker: Address, amount: i128) -> Result<i128, InsuranceError> {
+        staker.require_auth();
+        let _guard = ReentrancyGuard::enter(&env);
+        if amount <= 0 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+        let token = Self::get_stake_token(env.clone()).ok_or(InsuranceError::StakeTokenNotSet)?;
+
+        let mut pool = Self::get_staking_pool(env.clone());
+        let shares = amount;
+
+        let config = Self::get_staking_config(env.clone());
+        let mut positions = Self::get_stake_positions(&env);
+        let mut position = positions.get(staker.clone()).unwrap_or(StakePosition {
+            shares: 0,
+            deposited: 0,
+            withdrawn: 0,
+            unlock_at: 0,
+            earnings: 0,
+            earnings_debt: 0,
+        });
+        Self::settle_earnings(&pool, &mut position);
+        position.shares += shares;
+        position.earnings_debt = Self::accrued_earnings(&pool, position.shares);
+        position.deposited += amount;
+        position.unlock_at = env.ledger().timestamp().saturating_add(config.lockup_secs);
+        positions.set(staker.clone(), position);
+
+        pool.total_capital += amount;
+        pool.total_shares += shares;
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&STORAGE_STAKE_POSITIONS, &positions);
+        env.storage().instance().set(&STORAGE_STAKING_POOL, &pool);
+
+        TokenClient::new(&env, &token).transfer(&staker, &env.current_contract_address(), &amount);
+
+        Self::publish_event(
+            &env,
+            InsuranceEvent::StakeDeposited,
+            (staker, amount, shares),
+        );
+
+        Ok(shares)
+    }
+
+    /// Withdraw `amount` from the staker's position once the lockup has
+    /// ended. The position's booked earnings are first folded into its
+    /// shares, so it can withdraw its stake plus premium income less
+    /// absorbed losses; `amount` shares are then burned and `amount` of the
+    /// stake token is transferred from this contract to the staker.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If amount ≤ 0
+    /// * `StakeTokenNotSet` - If no stake token has been set
+    /// * `InsufficientStake` - If amount exceeds the position's current value
+    /// * `StakeLocked` - If the lockup has not ended
+    pub fn withdraw_stake(env: Env, staker: Address, amount: i128) -> Result<(), InsuranceError> {
+        staker.require_auth();
+        let _guard = ReentrancyGuard::enter(&env);
+        if amount <= 0 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+        let token = Self::get_stake_token(env.clone()).ok_or(InsuranceError::StakeTokenNotSet)?;
+
+        let mut positions = Self::get_stake_positions(&env);
+        let mut position = positions
+            .get(staker.clone())
+            .ok_or(InsuranceError::InsufficientStake)?;
+        if env.ledger().timestamp() < position.unlock_at {
+            return Err(InsuranceError::StakeLocked);
+        }
+
+        let mut pool = Self::get_staking_pool(env.clone());
+        Self::settle_earnings(&pool, &mut position);
+        Self::fold_earnings(&mut pool, &mut position);
+        if amount > position.shares || amount > pool.total_capital {
+            return Err(InsuranceError::InsufficientStake);
+        }
+        let burned = amount;
+
+        position.shares -= burned;
+        position.earnings_debt = Self::accrued_earnings(&pool, position.shares);
+        position.withdrawn += amount;
+        pool.total_shares -= burned;
+        pool.total_capital -= amount;
+        positions.set(staker.clone(), position);
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&STORAGE_STAKE_POSITIONS, &positions);
+        env.storage().instance().set(&STORAGE_STAKING_POOL, &pool);
+
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &staker, &amount);
+
+        Self::publish_event(
+            &env,
+            InsuranceEvent::StakeWithdrawn,
+            (staker, amount, burned),
+        );
+
+        Ok(())
+    }
+
+    /// Pay out the premium income booked to the staker's position, net of
+    /// the losses it has absorbed. The lockup only holds the stake, so
+    /// earnings can be claimed at any time; the shares stay in the pool.
+    ///
+    /// # Returns
+    /// The amount of the stake token transferred to the staker
+    ///
+    /// # Errors
+    /// * `StakeTokenNotSet` - If no stake token has been set
+    /// * `InsufficientStake` - If the staker has no position
+    /// * `InvalidAmount` - If the position has no earnings to claim
+    pub fn claim_stake_earnings(env: Env, staker: Address) -> Result<i128, InsuranceError> {
+        staker.require_auth();
+        let _guard = ReentrancyGuard::enter(&env);
+        let token = Self::get_stake_token(env.clone()).ok_or(InsuranceError::StakeTokenNotSet)?;
+
+        let mut positions = Self::get_stake_positions(&env);
+        let mut position = positions
+            .get(staker.clone())
+            .ok_or(InsuranceError::InsufficientStake)?;
+        let mut pool = Self::get_staking_pool(env.clone());
+        Self::settle_earnings(&pool, &mut position);
+        let amount = position.earnings.min(pool.total_capital);
+        if amount <= 0 {
+            return Err(InsuranceError::InvalidAmount);
+        }
+
+        position.earnings -= amount;
+        position.withdrawn += amount;
+        pool.total_capital -= amount;
+        positions.set(staker.clone(), position);
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&STORAGE_STAKE_POSITIONS, &positions);
+        env.storage().instance().set(&STORAGE_STAKING_POOL, &pool);
+
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &staker, &amount);
+
+        Self::publish_event(&env, InsuranceEvent::EarningsClaimed, (staker, amount));
+
+        Ok(amount)
+    }
+
+    pub fn get_staking_pool(env: Env) -> StakingPool {
+        env.storage()
+            .instance()
+            .get(&STORAGE_STAKING_POOL)
+            .unwrap_or_default()
+    }
+
+    /// Get a staker's shares and what the position can withdraw, its
+    /// booked earnings included.
+    pub fn get_staker_info(env: Env, staker: Address) -> Option<StakerInfo> {
+        let mut position = Self::get_stake_positions(&env).get(staker.clone())?;
+        Self::settle_earnings(&Self::get_staking_pool(env), &mut position);
+        let value = position.shares.saturating_add(position.earnings).max(0);
+        Some(StakerInfo {
+            staker,
+            shares: position.shares,
+            value,
+            deposited: position.deposited,
+            withdrawn: position.withdrawn,
+            profit: value + position.withdrawn - position.deposited,
+            unlock_at: position.unlock_at,
+        })
+    }
+
+    fn get_stake_positions(env: &Env) -> Map<Address, StakePosition> {
+        env.storage()
+            .instance()
+            .get(&STORAGE_STAKE_POSITIONS)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn accrued_earnings(pool: &StakingPool, shares: i128) -> i128 {
+        shares.saturating_mul(pool.earnings_per_share) / EARNINGS_SCALE
+    }
+
+    /// Move the earnings booked since the position last changed into
+    /// `position.earnings`.
+    fn settle_earnings(pool: &StakingPool, position: &mut StakePosition) {
+        let accrued = Self::accrued_earnings(pool, position.shares);
+        position.earnings = position
+            .earnings
+            .saturating_add(accrued - position.earnings_debt);
+        position.earnings_debt = accrued;
+    }
+
+    /// Turn the position's settled earnings into shares, one per unit, so
+    /// its shares equal what it can withdraw. A position whose losses exceed
+    /// its stake is left with none.
+    fn fold_earnings(pool: &mut StakingPool, position: &mut StakePosition) {
+        let shares = position.shares.saturating_add(position.earnings).max(0);
+        pool.total_shares = pool.total_shares - position.shares + shares;
+        position.shares = shares;
+        position.earnings = 0;
+        position.earnings_debt = Self::accrued_earnings(pool, shares);
+    }
+
+    /// Book `amount` of income (negative for a loss) pro rata over all shares.
+    fn book_earnings(pool: &mut StakingPool, amount: i128) {
+        if pool.total_shares == 0 {
+            return;
+        }
+        let per_share = amount.saturating_mul(EARNINGS_SCALE) / pool.total_shares;
+        pool.earnings_per_share = pool.earnings_per_share.saturating_add(per_share);
+    }
+
+    /// Book a paid premium against its policy and share it with the pool.
+    fn collect_premium(env: &Env, policy_id: u32, owner: &Address, premium: i128) {
+        let total = Self::get_premiums_paid(env.clone(), policy_id).saturating_add(premium);
+        Self::set_entry(env, &DataKey::PremPaid(policy_id), &total);
+
+        let key = DataKey::PremMonth(owner.clone());
+        let mut months: Map<u32, i128> = env
+            .storage()
+            .persistent()
+            .get(&key)
+            .unwrap_or_else(|| Map::new(env));
+        let month = month_index(env.ledger().timestamp());
+        months.set(
+            month,
+            months.get(month).unwrap_or(0).saturating_add(premium),
+        );
+        Self::set_entry(env, &key, &months);
+
+        Self::share_premium(env, premium);
+    }
+
+    fn get_holidays(env: &Env) -> Map<u32, PremiumHoliday> {
+        env.storage()
+            .instance()
+            .get(&STORAGE_HOLIDAYS)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Keep the deactivation record and, inside the cooling-off window, the
+    /// full premium refund owed to the holders. A policy closed by a survivor
+    /// claim gets no refund.
+    fn record_deactivation(
+        env: &Env,
+        policy: &InsurancePolicy,
+        caller: &Address,
+        reason: DeactivationReason,
+    ) {
+        let now = env.ledger().timestamp();
+        let refund_due = if reason != DeactivationReason::Deceased
+            && now < policy.created_at.saturating_add(COOLING_OFF_SECS)
+        {
+            Self::get_premiums_paid(env.clone(), policy.id)
+        } else {
+            0
+        };
+
+        let mut records: Map<u32, PolicyDeactivation> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_DEACTIVATIONS)
+            .unwrap_or_else(|| Map::new(env));
+        records.set(
+            policy.id,
+            PolicyDeactivation {
+                reason,
+                deactivated_by: caller.clone(),
+                deactivated_at: now,
+                refund_due,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&STORAGE_DEACTIVATIONS, &records);
+
+        if refund_due > 0 {
+            Self::publish_event(
+                env,
+                InsuranceEvent::CoolingOffRefund,
+                (policy.id, policy.owner.clone(), refund_due),
+            );
+        }
+    }
+
+    fn share_premium(env: &Env, premium: i128) {
+        let mut pool = Self::get_staking_pool(env.clone());
+        if pool.total_shares == 0 {
+            return;
+        }
+        let config = Self::get_staking_config(env.clone());
+        let share = premium.saturating_mul(config.premium_share_bps as i128) / 10_000;
+        if share <= 0 {
+            return;
+        }
+        Self::book_earnings(&mut pool, share);
+        pool.premium_income = pool.premium_income.saturating_add(share);
+        pool.total_capital = pool.total_capital.saturating_add(share);
+        env.storage().instance().set(&STORAGE_STAKING_POOL, &pool);
+
+        Self::publish_event(env, InsuranceEvent::PremiumShared, share);
+    }
+
+    /// Charge a claim payout to the pool, booked pro rata over all shares
+    /// and capped at the pool's remaining capital, which it reduces. Returns
+    /// the amount charged.
+    fn absorb_loss(env: &Env, claim_id: u32, amount: i128) -> i128 {
+        let mut pool = Self::get_staking_pool(env.clone());
+        let loss = amount.min(pool.total_capital);
+        if loss <= 0 || pool.total_shares == 0 {
+            return 0;
+        }
+        Self::book_earnings(&mut pool, -loss);
+        pool.losses_absorbed = pool.losses_absorbed.saturating_add(loss);
+        pool.total_capital -= loss;
+        env.storage().instance().set(&STORAGE_STAKING_POOL, &pool);
+
+        Self::publish_event(env, InsuranceEvent::LossAbsorbed, (claim_id, loss));
+        loss
+    }
+
+    /// Publish `event` under both the legacy `("insure", event)` topic and the
+    /// standard `RemitwiseEvents` schema.
+    ///
+    /// The legacy topic is kept for one more contract version so existing
+    /// indexers can migrate.
+    fn publish_event<T>(env: &Env, event: InsuranceEvent, data: T)
+    where
+        T: IntoVal<Env, Val> + Clone,
+        (u64, T): IntoVal<Env, Val>,
+    {
+        let (category, priority, action) = event.standard_topic();
+        env.events()
+            .publish((symbol_short!("insure"), event), data.clone());
+        Self::emit_standard(env, category, priority, action, data);
+    }
+
+    /// Emit a standard `RemitwiseEvents` event with `(event_seq, data)` as its
+    /// payload. `event_seq` starts at 1 and goes up by one with every standard
+    /// event this contract emits, so a consumer that sees a jump has missed
+    /// events and can replay from the last number it processed.
+    fn emit_standard<T>(
+        env: &Env,
+        category: EventCategory,
+        priority: EventPriority,
+        action: Symbol,
+        data: T,
+    ) where
+        (u64, T): IntoVal<Env, Val>,
+    {
+        let seq = Self::get_event_seq(env.clone()) + 1;
+        env.storage().instance().set(&STORAGE_EVENT_SEQ, &seq);
+        RemitwiseEvents::emit(env, category, priority, action, (seq, data));
+    }
+
+    /// Standard batch summary, numbered like every other standard event
+    fn emit_standard_batch(env: &Env, category: EventCategory, action: Symbol, count: u32) {
+        Self::emit_standard(
+            env,
+            category,
+            EventPriority::Low,
+            symbol_short!("batch"),
+            (action, count),
+        );
+    }
+
+    fn find_member(policy: &InsurancePolicy, member_id: u32) -> Result<u32, InsuranceError> {
+        policy
+            .members
+            .iter()
+            .position(|m| m.id == member_id)
+            .map(|i| i as u32)
+            .ok_or(InsuranceError::MemberNotFound)
+    }
+
+    /// Extend the TTL of instance storage
+    fn extend_instance_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+
+    /// Cached premium total of `owner`, from its own entry or, until it is
+    /// migrated, the legacy map
+    fn get_active_premium_total(env: &Env, owner: &Address) -> Option<i128> {
+        let key = DataKey::PremTotal(owner.clone());
+        if let Some(total) = env.storage().persistent().get(&key) {
+            return Some(total);
+        }
+        env.storage()
+            .instance()
+            .get::<_, Map<Address, i128>>(&STORAGE_PREMIUM_TOTALS)
+            .and_then(|totals| totals.get(owner.clone()))
+    }
+
+    fn adjust_active_premium_total(env: &Env, owner: &Address, delta: i128) {
+        if delta == 0 {
+            return;
+        }
+        Self::migrate_premium_total(env, owner);
+        let key = DataKey::PremTotal(owner.clone());
+        let current: i128 = env.storage().persistent().get(&key).unwrap_or(0);
+        let next = if delta >= 0 {
+            current.saturating_add(delta)
+        } else {
+            current.saturating_sub(delta.saturating_abs())
+        };
+        env.storage().persistent().set(&key, &next);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, OWNER_LIFETIME_THRESHOLD, OWNER_BUMP_AMOUNT);
+    }
+
+    /// Move `owner`'s entry of the legacy `PRM_TOT` map to its own
+    /// persistent entry, deleting the map once it is empty. Returns whether
+    /// there was an entry to move.
+    fn migrate_premium_total(env: &Env, owner: &Address) -> bool {
+        let mut totals: Map<Address, i128> =
+            match env.storage().instance().get(&STORAGE_PREMIUM_TOTALS) {
+                Some(totals) => totals,
+                None => return false,
+            };
+        let total = match totals.get(owner.clone()) {
+            Some(total) => total,
+            None => return false,
+        };
+        totals.remove(owner.clone());
+        Self::store_legacy_premium_totals(env, &totals);
+        Self::set_premium_total_entry(env, owner, total);
+        true
+    }
+
+    /// Write what is left of the legacy `PRM_TOT` map, deleting it once empty
+    fn store_legacy_premium_totals(env: &Env, totals: &Map<Address, i128>) {
+        if totals.is_empty() {
+            env.storage().instance().remove(&STORAGE_PREMIUM_TOTALS);
+        } else {
+            env.storage()
+                .instance()
+                .set(&STORAGE_PREMIUM_TOTALS, totals);
+        }
+    }
+
+    fn set_premium_total_entry(env: &Env, owner: &Address, total: i128) {
+        let key = DataKey::PremTotal(owner.clone());
+        env.storage().persistent().set(&key, &total);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, OWNER_LIFETIME_THRESHOLD, OWNER_BUMP_AMOUNT);
+    }
+
+    /// Write a persistent entry and bump its TTL
+    fn set_entry<V: IntoVal<Env, Val>>(env: &Env, key: &DataKey, value: &V) {
+        env.storage().persistent().set(key, value);
+        env.storage()
+            .persistent()
+            .extend_ttl(key, OWNER_LIFETIME_THRESHOLD, OWNER_BUMP_AMOUNT);
+    }
+
+    fn is_policy_holder(policy: &InsurancePolicy, address: &Address) -> bool {
+        policy.owner == *address || policy.co_owner.as_ref() == Some(address)
+    }
+
+    /// Require authorization from the holder that is not `caller`, if any.
+    fn require_all_holders_auth(policy: &InsurancePolicy, caller: &Address) {
+        if policy.owner != *caller {
+            policy.owner.require_auth();
+        }
+        if let Some(co_owner) = &policy.co_owner {
+            if co_owner != caller {
+                co_owner.require_auth();
+            }
+        }
     }
 
-    fn get_active_premium_totals_map(env: &Env) -> Option<Map<Address, i128>> {
-        env.storage().instance().get(&STORAGE_PREMIUM_TOTALS)
+    fn adjust_holders_premium_total(env: &Env, policy: &InsurancePolicy, delta: i128) {
+        Self::adjust_active_premium_total(env, &policy.owner, delta);
+        if let Some(co_owner) = &policy.co_owner {
+            Self::adjust_active_premium_total(env, co_owner, delta);
+        }
     }
 
-    fn adjust_active_premium_total(env: &Env, owner: &Address, delta: i128) {
-        if delta == 0 {
-            return;
-        }
-        let mut totals: Map<Address, i128> = env
+    fn adjust_active_count(env: &Env, holder: &Address, delta: i32) {
+        let mut counts: Map<Address, u32> = env
             .storage()
             .instance()
-            .get(&STORAGE_PREMIUM_TOTALS)
+            .get(&STORAGE_ACTIVE_COUNTS)
             .unwrap_or_else(|| Map::new(env));
-        let current = totals.get(owner.clone()).unwrap_or(0);
-        let next = if delta >= 0 {
-            current.saturating_add(delta)
-        } else {
-            current.saturating_sub(delta.saturating_abs())
-        };
-        totals.set(owner.clone(), next);
+        let current = counts.get(holder.clone()).unwrap_or(0);
+        counts.set(holder.clone(), current.saturating_add_signed(delta));
         env.storage()
             .instance()
-            .set(&STORAGE_PREMIUM_TOTALS, &totals);
+            .set(&STORAGE_ACTIVE_COUNTS, &counts);
+    }
+
+    fn adjust_holders_active_count(env: &Env, policy: &InsurancePolicy, delta: i32) {
+        Self::adjust_active_count(env, &policy.owner, delta);
+        if let Some(co_owner) = &policy.co_owner {
+            Self::adjust_active_count(env, co_owner, delta);
+        }
     }
 
     // -----------------------------------------------------------------------
@@ -929,20 +4272,6 @@ impl Insurance {
         owner.require_auth();
         Self::require_not_paused(&env, pause_functions::CREATE_SCHED)?;
 
-        let name = String::from_str(&env, "Health Insurance");
-        let coverage_type = String::from_str(&env, "health");
-        let monthly_premium = 100;
-        let coverage_amount = 10000;
-        let external_ref = Some(String::from_str(&env, "POLICY-EXT-1"));
-
-        let policy_id = client.create_policy(
-            &owner,
-            &name,
-            &coverage_type,
-            &monthly_premium,
-            &coverage_amount,
-            &external_ref,
-        );
         let mut policies: Map<u32, InsurancePolicy> = env
             .storage()
             .instance()
@@ -957,17 +4286,6 @@ impl Insurance {
             return Err(InsuranceError::Unauthorized);
         }
 
-        let policy = client.get_policy(&policy_id).unwrap();
-        assert_eq!(policy.id, 1);
-        assert_eq!(policy.owner, owner);
-        assert_eq!(policy.name, name);
-        assert_eq!(policy.external_ref, external_ref);
-        assert_eq!(policy.coverage_type, coverage_type);
-        assert_eq!(policy.monthly_premium, monthly_premium);
-        assert_eq!(policy.coverage_amount, coverage_amount);
-        assert!(policy.active);
-        assert_eq!(policy.next_payment_date, 1000000000 + (30 * 86400));
-    }
         let current_time = env.ledger().timestamp();
         if next_due <= current_time {
             return Err(InsuranceError::InvalidTimestamp);
@@ -981,8 +4299,6 @@ impl Insurance {
             .get(&symbol_short!("PREM_SCH"))
             .unwrap_or_else(|| Map::new(&env));
 
-        client.create_policy(&owner, &name, &coverage_type, &0, &10000, &None);
-    }
         let next_schedule_id = env
             .storage()
             .instance()
@@ -1005,8 +4321,6 @@ impl Insurance {
 
         policy.schedule_id = Some(next_schedule_id);
 
-        client.create_policy(&owner, &name, &coverage_type, &-100, &10000, &None);
-    }
         schedules.set(next_schedule_id, schedule);
         env.storage()
             .instance()
@@ -1020,8 +4334,9 @@ impl Insurance {
             .instance()
             .set(&symbol_short!("POLICIES"), &policies);
 
-        env.events().publish(
-            (symbol_short!("insure"), InsuranceEvent::ScheduleCreated),
+        Self::publish_event(
+            &env,
+            InsuranceEvent::ScheduleCreated,
             (next_schedule_id, owner),
         );
 
@@ -1070,8 +4385,9 @@ impl Insurance {
             .instance()
             .set(&symbol_short!("PREM_SCH"), &schedules);
 
-        env.events().publish(
-            (symbol_short!("insure"), InsuranceEvent::ScheduleModified),
+        Self::publish_event(
+            &env,
+            InsuranceEvent::ScheduleModified,
             (schedule_id, caller),
         );
 
@@ -1110,8 +4426,9 @@ impl Insurance {
             .instance()
             .set(&symbol_short!("PREM_SCH"), &schedules);
 
-        env.events().publish(
-            (symbol_short!("insure"), InsuranceEvent::ScheduleCancelled),
+        Self::publish_event(
+            &env,
+            InsuranceEvent::ScheduleCancelled,
             (schedule_id, caller),
         );
 
@@ -1119,11 +4436,32 @@ impl Insurance {
     }
 
     /// Execute due premium schedules (public, callable by anyone - keeper pattern)
-    pub fn execute_due_premium_schedules(env: Env) -> Vec<u32> {
+    ///
+    /// Each run is recorded in the execution history, see `get_execution_history`.
+    /// While the contract or `pay_premium` is paused the run does nothing and
+    /// schedules stay due; the first run after unpausing pays each once and
+    /// counts the periods that passed as missed.
+    ///
+    /// # Arguments
+    /// * `caller` - Address of the keeper running the executor (must authorize)
+    ///
+    /// # Returns
+    /// An `ExecutionReport` of what the run did; schedules skipped for a
+    /// premium holiday do not appear in it
+    pub fn execute_due_premium_schedules(env: Env, caller: Address) -> ExecutionReport {
+        caller.require_auth();
         Self::extend_instance_ttl(&env);
+        let mut report = ExecutionReport {
+            executed: Vec::new(&env),
+            skipped_inactive_policy: Vec::new(&env),
+            missed_increments: 0,
+            total_premium_processed: 0,
+        };
+        if Self::require_not_paused(&env, pause_functions::PAY_PREMIUM).is_err() {
+            return report;
+        }
 
         let current_time = env.ledger().timestamp();
-        let mut executed = Vec::new(&env);
 
         let mut schedules: Map<u32, PremiumSchedule> = env
             .storage()
@@ -1137,20 +4475,75 @@ impl Insurance {
             .get(&symbol_short!("POLICIES"))
             .unwrap_or_else(|| Map::new(&env));
 
+        let mut holidays = Self::get_holidays(&env);
+        let holiday_count = holidays.len();
+        for (policy_id, holiday) in holidays.iter() {
+            if holiday.end <= current_time {
+                holidays.remove(policy_id);
+                Self::publish_event(
+                    &env,
+                    InsuranceEvent::PremiumHolidayEnded,
+                    (policy_id, holiday.end),
+                );
+            }
+        }
+        if holidays.len() != holiday_count {
+            env.storage().instance().set(&STORAGE_HOLIDAYS, &holidays);
+        }
+
         for (schedule_id, mut schedule) in schedules.iter() {
             if !schedule.active || schedule.next_due > current_time {
                 continue;
             }
 
+            // Runs falling in a premium holiday are skipped, not missed
+            if holidays.contains_key(schedule.policy_id) {
+                if schedule.recurring && schedule.interval > 0 {
+                    while schedule.next_due <= current_time {
+                        schedule.next_due += schedule.interval;
+                    }
+                    schedules.set(schedule_id, schedule);
+                }
+                continue;
+            }
+
+            let mut policy_active = false;
             if let Some(mut policy) = policies.get(schedule.policy_id) {
                 if policy.active {
-                    policy.next_payment_date = current_time + (30 * 86400);
-                    policies.set(schedule.policy_id, policy.clone());
-
-                    env.events().publish(
-                        (symbol_short!("insure"), InsuranceEvent::PremiumPaid),
-                        (schedule.policy_id, policy.owner),
-                    );
+                    policy_active = true;
+                    if Self::draw_from_funding_goal(
+                        &env,
+                        schedule_id,
+                        &schedule.owner,
+                        policy.monthly_premium,
+                    ) {
+                        policy.next_payment_date = current_time + (30 * 86400);
+                        policies.set(schedule.policy_id, policy.clone());
+
+                        Self::collect_premium(
+                            &env,
+                            policy.id,
+                            &policy.owner,
+                            policy.monthly_premium,
+                        );
+                        report.total_premium_processed = report
+                            .total_premium_processed
+                            .saturating_add(policy.monthly_premium);
+                        Self::publish_event(
+                            &env,
+                            InsuranceEvent::PremiumPaid,
+                            (schedule.policy_id, policy.owner),
+                        );
+                    } else {
+                        // The funding goal could not cover the premium
+                        schedule.missed_count += 1;
+                        report.missed_increments += 1;
+                        Self::publish_event(
+                            &env,
+                            InsuranceEvent::ScheduleMissed,
+                            (schedule_id, 1u32),
+                        );
+                    }
                 }
             }
 
@@ -1165,10 +4558,12 @@ impl Insurance {
                 }
                 schedule.missed_count += missed;
                 schedule.next_due = next;
+                report.missed_increments += missed;
 
                 if missed > 0 {
-                    env.events().publish(
-                        (symbol_short!("insure"), InsuranceEvent::ScheduleMissed),
+                    Self::publish_event(
+                        &env,
+                        InsuranceEvent::ScheduleMissed,
                         (schedule_id, missed),
                     );
                 }
@@ -1177,12 +4572,13 @@ impl Insurance {
             }
 
             schedules.set(schedule_id, schedule);
-            executed.push_back(schedule_id);
+            if policy_active {
+                report.executed.push_back(schedule_id);
+            } else {
+                report.skipped_inactive_policy.push_back(schedule_id);
+            }
 
-            env.events().publish(
-                (symbol_short!("insure"), InsuranceEvent::ScheduleExecuted),
-                schedule_id,
-            );
+            Self::publish_event(&env, InsuranceEvent::ScheduleExecuted, schedule_id);
         }
 
         env.storage()
@@ -1192,7 +4588,166 @@ impl Insurance {
             .instance()
             .set(&symbol_short!("POLICIES"), &policies);
 
-        executed
+        Self::append_execution_record(
+            &env,
+            ExecutionRecord {
+                timestamp: current_time,
+                caller,
+                executed_count: report.executed.len(),
+                missed_total: report.missed_increments,
+            },
+        );
+
+        report
+    }
+
+    /// Set the savings goals contract that premium schedules can be funded
+    /// from (pause admin only).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    pub fn set_savings_contract(
+        env: Env,
+        caller: Address,
+        savings: Address,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let admin = Self::get_pause_admin(&env).ok_or(InsuranceError::Unauthorized)?;
+        if admin != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(&STORAGE_SAVINGS, &savings);
+        Self::publish_event(&env, InsuranceEvent::SavingsConfigured, savings);
+        Ok(())
+    }
+
+    pub fn get_savings_contract(env: Env) -> Option<Address> {
+        env.storage().instance().get(&STORAGE_SAVINGS)
+    }
+
+    /// Fund a premium schedule from a savings goal, or stop with `None`.
+    ///
+    /// Each run then withdraws the premium from the goal through the savings
+    /// contract's `withdraw_premium`, so the goal owner must have named this
+    /// contract as the goal's premium payer. Only the schedule owner's own
+    /// goals pay: a run drawing on another account's goal, or one the goal
+    /// cannot cover, is counted as missed and the premium is not paid.
+    ///
+    /// # Errors
+    /// * `PolicyNotFound` - If the schedule does not exist
+    /// * `Unauthorized` - If caller is not the schedule owner
+    /// * `SavingsNotConfigured` - If no savings contract is set
+    pub fn set_schedule_funding_goal(
+        env: Env,
+        caller: Address,
+        schedule_id: u32,
+        goal_id: Option<u32>,
+    ) -> Result<(), InsuranceError> {
+        caller.require_auth();
+        let schedule = Self::get_premium_schedule(env.clone(), schedule_id)
+            .ok_or(InsuranceError::PolicyNotFound)?;
+        if schedule.owner != caller {
+            return Err(InsuranceError::Unauthorized);
+        }
+        if goal_id.is_some() && Self::get_savings_contract(env.clone()).is_none() {
+            return Err(InsuranceError::SavingsNotConfigured);
+        }
+
+        Self::extend_instance_ttl(&env);
+        let mut funding = Self::get_funding_goals(&env);
+        match goal_id {
+            Some(goal_id) => funding.set(schedule_id, goal_id),
+            None => {
+                funding.remove(schedule_id);
+            }
+        }
+        env.storage()
+            .instance()
+            .set(&STORAGE_FUNDING_GOALS, &funding);
+
+        Self::publish_event(
+            &env,
+            InsuranceEvent::ScheduleFundingSet,
+            (schedule_id, goal_id),
+        );
+        Ok(())
+    }
+
+    pub fn get_schedule_funding_goal(env: Env, schedule_id: u32) -> Option<u32> {
+        Self::get_funding_goals(&env).get(schedule_id)
+    }
+
+    /// Withdraw a premium from the schedule's funding goal, if it has one.
+    /// Returns false when the goal cannot pay it or is not `owner`'s.
+    fn draw_from_funding_goal(env: &Env, schedule_id: u32, owner: &Address, premium: i128) -> bool {
+        let goal_id = match Self::get_funding_goals(env).get(schedule_id) {
+            Some(goal_id) => goal_id,
+            None => return true,
+        };
+        let savings: Address = match env.storage().instance().get(&STORAGE_SAVINGS) {
+            Some(savings) => savings,
+            None => return false,
+        };
+        matches!(
+            SavingsGoalsClient::new(env, &savings).try_withdraw_premium(
+                &env.current_contract_address(),
+                owner,
+                &goal_id,
+                &premium,
+            ),
+            Ok(Ok(_))
+        )
+    }
+
+    fn get_funding_goals(env: &Env) -> Map<u32, u32> {
+        env.storage()
+            .instance()
+            .get(&STORAGE_FUNDING_GOALS)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Get recorded keeper runs, oldest first.
+    ///
+    /// At most the last `MAX_EXECUTION_HISTORY` runs are kept.
+    ///
+    /// # Arguments
+    /// * `offset` - Index of the first record to return
+    /// * `limit` - Maximum number of records (0 = default, capped at MAX_PAGE_LIMIT)
+    pub fn get_execution_history(env: Env, offset: u32, limit: u32) -> Vec<ExecutionRecord> {
+        let history: Vec<ExecutionRecord> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_EXEC_HISTORY)
+            .unwrap_or_else(|| Vec::new(&env));
+        let len = history.len();
+        let mut out = Vec::new(&env);
+        if offset >= len {
+            return out;
+        }
+        let end = offset.saturating_add(Self::clamp_limit(limit)).min(len);
+        for i in offset..end {
+            if let Some(record) = history.get(i) {
+                out.push_back(record);
+            }
+        }
+        out
+    }
+
+    fn append_execution_record(env: &Env, record: ExecutionRecord) {
+        let mut history: Vec<ExecutionRecord> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_EXEC_HISTORY)
+            .unwrap_or_else(|| Vec::new(env));
+        if history.len() >= MAX_EXECUTION_HISTORY {
+            history.pop_front();
+        }
+        history.push_back(record);
+        env.storage()
+            .instance()
+            .set(&STORAGE_EXEC_HISTORY, &history);
     }
 
     /// Get all premium schedules for an owner
@@ -1226,887 +4781,3 @@ impl Insurance {
 
 #[cfg(test)]
 mod test;
-
-#[cfg(test)]
-mod test_events {
-    use super::*;
-    use proptest::prelude::*;
-    use soroban_sdk::testutils::storage::Instance as _;
-    use soroban_sdk::testutils::{Address as _, Events, Ledger, LedgerInfo};
-    use soroban_sdk::{Env, String};
-
-    fn make_env() -> Env {
-        Env::default()
-    }
-
-    fn setup_policies(
-        env: &Env,
-        client: &InsuranceClient,
-        owner: &Address,
-        count: u32,
-    ) -> Vec<u32> {
-        let mut ids = Vec::new(env);
-        for i in 0..count {
-            let id = client.create_policy(
-                owner,
-                &String::from_str(env, "Policy"),
-                &CoverageType::Health,
-                &(50i128 * (i as i128 + 1)),
-                &(10000i128 * (i as i128 + 1)),
-            );
-            ids.push_back(id);
-        }
-        ids
-    }
-
-    // --- get_active_policies ---
-
-    #[test]
-    fn test_create_policy_invalid_premium() {
-        let env = Env::default();
-        let contract_id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &contract_id);
-        let owner = Address::generate(&env);
-
-        let page = client.get_active_policies(&owner, &0, &0);
-        assert_eq!(page.count, 0);
-        assert_eq!(page.next_cursor, 0);
-    }
-
-        client.create_policy(&owner, &name, &coverage_type, &100, &0, &None);
-    #[test]
-    fn test_get_active_policies_single_page() {
-        let env = make_env();
-        env.mock_all_auths();
-
-        // Use the .try_ version of the function to capture the error result
-        let result = client.try_create_policy(
-            &owner,
-            &String::from_str(&env, "Life"),
-            &String::from_str(&env, "Health"),
-            &0, // This is invalid
-            &10000,
-        );
-
-        // Assert that the result matches our custom error code
-        assert_eq!(result, Err(Ok(InsuranceError::InvalidAmount)));
-    }
-
-    #[test]
-    fn test_create_policy_emits_event() {
-        let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &contract_id);
-        let owner = Address::generate(&env);
-
-        // No policies created — policy ID 999 does not exist; contract panics
-        let result = client.try_pay_premium(&owner, &999u32);
-        // Create a policy
-        let policy_id = client.create_policy(
-            &owner,
-            &String::from_str(&env, "Health Insurance"),
-            &String::from_str(&env, "health"),
-            &100,
-            &50000,
-        );
-        assert_eq!(policy_id, 1);
-
-        // Contract panics when policy not found
-        assert!(result.is_err());
-    }
-
-    #[test]
-    fn test_get_active_policies_pagination() {
-        let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &contract_id);
-        let owner = Address::generate(&env);
-
-        let name = String::from_str(&env, "Health Insurance");
-        let coverage_type = String::from_str(&env, "health");
-        let policy_id = client.create_policy(&owner, &name, &coverage_type, &100, &10000, &None);
-        setup_policies(&env, &client, &owner, 7);
-
-        let page1 = client.get_active_policies(&owner, &0, &3);
-        assert_eq!(page1.count, 3);
-        assert!(page1.next_cursor > 0);
-
-        let page2 = client.get_active_policies(&owner, &page1.next_cursor, &3);
-        assert_eq!(page2.count, 3);
-        assert!(page2.next_cursor > 0);
-
-        let page3 = client.get_active_policies(&owner, &page2.next_cursor, &3);
-        assert_eq!(page3.count, 1);
-        assert_eq!(page3.next_cursor, 0);
-    }
-        // Create a policy
-        let policy_id = client.create_policy(
-            &owner,
-            &String::from_str(&env, "Emergency Coverage"),
-            &String::from_str(&env, "emergency"),
-            &75,
-            &25000,
-        );
-
-        env.mock_all_auths();
-
-        let name = String::from_str(&env, "Health Insurance");
-        let coverage_type = String::from_str(&env, "health");
-        let policy_id = client.create_policy(&owner, &name, &coverage_type, &100, &10000, &None);
-        let ids = setup_policies(&env, &client, &owner, 4);
-        // Deactivate policy #2
-        client.deactivate_policy(&owner, &ids.get(1).unwrap());
-
-        let page = client.get_active_policies(&owner, &0, &10);
-        assert_eq!(page.count, 3); // only 3 active
-        for p in page.items.iter() {
-            assert!(p.active, "only active policies should be returned");
-        }
-    }
-
-    #[test]
-    fn test_get_active_policies_multi_owner_isolation() {
-        let env = make_env();
-        env.mock_all_auths();
-        let id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &id);
-        let owner_a = Address::generate(&env);
-        let owner_b = Address::generate(&env);
-        // Get events before paying premium
-        let events_before = env.events().all().len();
-
-        // Pay premium
-        let result = client.pay_premium(&owner, &policy_id);
-        assert!(result);
-
-        // Verify PremiumPaid event was emitted (2 new events: topic + enum)
-        let events_after = env.events().all().len();
-        assert_eq!(events_after - events_before, 2);
-    }
-
-    #[test]
-    fn test_deactivate_policy_emits_event() {
-        let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &contract_id);
-        let owner = Address::generate(&env);
-
-        // Create a policy
-        let policy_id = client.create_policy(
-            &owner,
-            &String::from_str(&env, "Life Insurance"),
-            &String::from_str(&env, "life"),
-            &200,
-            &100000,
-        );
-
-        env.mock_all_auths();
-
-        // Get events before deactivating
-        let events_before = env.events().all().len();
-
-        // Deactivate policy
-        let result = client.deactivate_policy(&owner, &policy_id);
-        assert!(result);
-
-        // Verify PolicyDeactivated event was emitted (2 new events: topic + enum)
-        let events_after = env.events().all().len();
-        assert_eq!(events_after - events_before, 2);
-    }
-
-    #[test]
-    fn test_create_policy_emits_event_exists() {
-        let env = make_env();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &contract_id);
-        let owner = Address::generate(&env);
-
-        // Create multiple policies
-        let name1 = String::from_str(&env, "Health Insurance");
-        let coverage_type1 = String::from_str(&env, "health");
-        let policy_id1 = client.create_policy(&owner, &name1, &coverage_type1, &100, &10000, &None);
-
-        let name2 = String::from_str(&env, "Emergency Insurance");
-        let coverage_type2 = String::from_str(&env, "emergency");
-        let policy_id2 = client.create_policy(&owner, &name2, &coverage_type2, &200, &20000, &None);
-
-        let name3 = String::from_str(&env, "Life Insurance");
-        let coverage_type3 = String::from_str(&env, "life");
-        let policy_id3 = client.create_policy(&owner, &name3, &coverage_type3, &300, &30000, &None);
-        let policy_id = client.create_policy(
-        client.create_policy(
-            &owner,
-            &String::from_str(&env, "Health Insurance"),
-            &CoverageType::Health,
-            &String::from_str(&env, "Policy 1"),
-            &String::from_str(&env, "health"),
-            &100,
-            &50000,
-        );
-        client.create_policy(
-            &owner,
-            &String::from_str(&env, "Policy 2"),
-            &String::from_str(&env, "life"),
-            &200,
-            &100000,
-        );
-        client.create_policy(
-            &owner,
-            &String::from_str(&env, "Policy 3"),
-            &String::from_str(&env, "emergency"),
-            &75,
-            &25000,
-        );
-
-        client.pay_premium(&owner, &policy_id);
-
-        let events_after = env.events().all().len();
-        assert_eq!(events_after - events_before, 2);
-    }
-
-    #[test]
-    fn test_policy_lifecycle_emits_all_events() {
-        let env = Env::default();
-        env.mock_all_auths();
-        let contract_id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &contract_id);
-        let owner = Address::generate(&env);
-
-        // Create multiple policies
-        let name1 = String::from_str(&env, "Health Insurance");
-        let coverage_type1 = String::from_str(&env, "health");
-        client.create_policy(&owner, &name1, &coverage_type1, &100, &10000, &None);
-
-        let name2 = String::from_str(&env, "Emergency Insurance");
-        let coverage_type2 = String::from_str(&env, "emergency");
-        client.create_policy(&owner, &name2, &coverage_type2, &200, &20000, &None);
-
-        let name3 = String::from_str(&env, "Life Insurance");
-        let coverage_type3 = String::from_str(&env, "life");
-        let policy_id3 = client.create_policy(&owner, &name3, &coverage_type3, &300, &30000, &None);
-        // Create a policy
-        let policy_id = client.create_policy(
-            &owner,
-            &String::from_str(&env, "Complete Lifecycle"),
-            &CoverageType::Health,
-            &150,
-            &75000,
-        );
-
-        env.mock_all_auths();
-
-        // Pay premium
-        client.pay_premium(&owner, &policy_id);
-
-        // Deactivate
-        client.deactivate_policy(&owner, &policy_id);
-
-        // Should have 6 events: 2 Created + 2 PremiumPaid + 2 Deactivated
-        let events = env.events().all();
-        assert_eq!(events.len(), 6);
-    }
-
-    // ====================================================================
-    // Storage TTL Extension Tests
-    //
-    // Verify that instance storage TTL is properly extended on
-    // state-changing operations, preventing unexpected data expiration.
-    //
-    // Contract TTL configuration:
-    //   INSTANCE_LIFETIME_THRESHOLD = 17,280 ledgers (~1 day)
-    //   INSTANCE_BUMP_AMOUNT        = 518,400 ledgers (~30 days)
-    //
-    // Operations extending instance TTL:
-    //   create_policy, pay_premium, batch_pay_premiums,
-    //   deactivate_policy, create_premium_schedule,
-    //   modify_premium_schedule, cancel_premium_schedule,
-    //   execute_due_premium_schedules
-    // ====================================================================
-
-    /// Verify that create_policy extends instance storage TTL.
-    #[test]
-    fn test_instance_ttl_extended_on_create_policy() {
-        let env = Env::default();
-        env.mock_all_auths();
-
-        env.ledger().set(LedgerInfo {
-            protocol_version: 20,
-            sequence_number: 100,
-            timestamp: 1000,
-            network_id: [0; 32],
-            base_reserve: 10,
-            min_temp_entry_ttl: 100,
-            min_persistent_entry_ttl: 100,
-            max_entry_ttl: 700_000,
-        });
-
-        let contract_id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &contract_id);
-        let owner = Address::generate(&env);
-
-        let name = String::from_str(&env, "Health Insurance");
-        let coverage_type = String::from_str(&env, "health");
-        let policy_id = client.create_policy(&owner, &name, &coverage_type, &100, &10000, &None);
-
-        let result = client.deactivate_policy(&owner, &policy_id);
-        assert!(result);
-        // create_policy calls extend_instance_ttl
-        let policy_id = client.create_policy(
-            &owner,
-            &String::from_str(&env, "Health Insurance"),
-            &CoverageType::Health,
-            &100,
-            &50000,
-        );
-        assert_eq!(policy_id, 1);
-
-        // Inspect instance TTL — must be at least INSTANCE_BUMP_AMOUNT
-        let ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
-        assert!(
-            ttl >= 518_400,
-            "Instance TTL ({}) must be >= INSTANCE_BUMP_AMOUNT (518,400) after create_policy",
-            ttl
-        );
-    }
-
-    /// Verify that pay_premium refreshes instance TTL after ledger advancement.
-    ///
-    /// extend_ttl(threshold, extend_to) only extends when TTL <= threshold.
-    /// We advance the ledger far enough for TTL to drop below 17,280.
-    #[test]
-    fn test_instance_ttl_refreshed_on_pay_premium() {
-        let env = Env::default();
-        env.mock_all_auths();
-
-        env.ledger().set(LedgerInfo {
-            protocol_version: 20,
-            sequence_number: 100,
-            timestamp: 1000,
-            network_id: [0; 32],
-            base_reserve: 10,
-            min_temp_entry_ttl: 100,
-            min_persistent_entry_ttl: 100,
-            max_entry_ttl: 700_000,
-        });
-
-        let contract_id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &contract_id);
-        let owner = Address::generate(&env);
-
-        client.create_policy(
-            &owner,
-            &String::from_str(&env, "Life Insurance"),
-            &String::from_str(&env, "life"),
-            &200,
-            &100000,
-        );
-
-        // Advance ledger so TTL drops below threshold (17,280)
-        // After create_policy: live_until = 518,500. At seq 510,000: TTL = 8,500
-        env.ledger().set(LedgerInfo {
-            protocol_version: 20,
-            sequence_number: 510_000,
-            timestamp: 500_000,
-            network_id: [0; 32],
-            base_reserve: 10,
-            min_temp_entry_ttl: 100,
-            min_persistent_entry_ttl: 100,
-            max_entry_ttl: 700_000,
-        });
-
-        // pay_premium calls extend_instance_ttl → re-extends TTL to 518,400
-        client.pay_premium(&owner, &1);
-
-        let ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
-        assert!(
-            ttl >= 518_400,
-            "Instance TTL ({}) must be >= 518,400 after pay_premium",
-            ttl
-        );
-    }
-
-    /// Verify data persists across repeated operations spanning multiple
-    /// ledger advancements, proving TTL is continuously renewed.
-    #[test]
-    fn test_set_external_ref_success() {
-        let env = create_test_env();
-        let contract_id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &contract_id);
-        let owner = Address::generate(&env);
-
-        let name = String::from_str(&env, "Health Insurance");
-        let coverage_type = String::from_str(&env, "health");
-        let policy_id = client.create_policy(&owner, &name, &coverage_type, &100, &10000, &None);
-
-        let external_ref = Some(String::from_str(&env, "POLICY-EXT-99"));
-        assert!(client.set_external_ref(&owner, &policy_id, &external_ref));
-
-        let policy = client.get_policy(&policy_id).unwrap();
-        assert_eq!(policy.external_ref, external_ref);
-    }
-
-    #[test]
-    #[should_panic(expected = "Only the policy owner can update this policy reference")]
-    fn test_set_external_ref_unauthorized() {
-        let env = create_test_env();
-        let contract_id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &contract_id);
-        let owner = Address::generate(&env);
-        let other = Address::generate(&env);
-
-        let name = String::from_str(&env, "Health Insurance");
-        let coverage_type = String::from_str(&env, "health");
-        let policy_id = client.create_policy(&owner, &name, &coverage_type, &100, &10000, &None);
-
-        client.set_external_ref(
-            &other,
-            &policy_id,
-            &Some(String::from_str(&env, "POLICY-EXT-99")),
-        );
-    }
-
-    #[test]
-    fn test_multiple_policies_management() {
-        let env = create_test_env();
-    fn test_policy_data_persists_across_ledger_advancements() {
-        let env = Env::default();
-        env.mock_all_auths();
-
-        env.ledger().set(LedgerInfo {
-            protocol_version: 20,
-            sequence_number: 100,
-            timestamp: 1000,
-            network_id: [0; 32],
-            base_reserve: 10,
-            min_temp_entry_ttl: 100,
-            min_persistent_entry_ttl: 100,
-            max_entry_ttl: 700_000,
-        });
-
-        let contract_id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &contract_id);
-        let owner = Address::generate(&env);
-
-        // Phase 1: Create policy at seq 100. live_until = 518,500
-        let policy_id = client.create_policy(
-            &owner,
-            &String::from_str(&env, "Auto Insurance"),
-            &String::from_str(&env, "auto"),
-            &150,
-            &75000,
-        );
-
-        for (i, policy_name) in policy_names.iter().enumerate() {
-            let premium = ((i + 1) as i128) * 100;
-            let coverage = ((i + 1) as i128) * 10000;
-            let policy_id = client.create_policy(
-                &owner,
-                policy_name,
-                &coverage_type,
-                &premium,
-                &coverage,
-                &None,
-            );
-            policy_ids.push_back(policy_id);
-        }
-        // Phase 2: Advance to seq 510,000 (TTL = 8,500 < 17,280)
-        env.ledger().set(LedgerInfo {
-            protocol_version: 20,
-            sequence_number: 510_000,
-            timestamp: 510_000,
-            network_id: [0; 32],
-            base_reserve: 10,
-            min_temp_entry_ttl: 100,
-            min_persistent_entry_ttl: 100,
-            max_entry_ttl: 700_000,
-        });
-
-        client.pay_premium(&owner, &policy_id);
-
-        // Phase 3: Advance to seq 1,020,000 (TTL = 8,400 < 17,280)
-        env.ledger().set(LedgerInfo {
-            protocol_version: 20,
-            sequence_number: 1_020_000,
-            timestamp: 1_020_000,
-            network_id: [0; 32],
-            base_reserve: 10,
-            min_temp_entry_ttl: 100,
-            min_persistent_entry_ttl: 100,
-            max_entry_ttl: 700_000,
-        });
-
-        let policy_id2 = client.create_policy(
-            &owner,
-            &String::from_str(&env, "Travel Insurance"),
-            &String::from_str(&env, "travel"),
-            &50,
-            &20000,
-        );
-
-        // All policies should be accessible
-        let p1 = client.get_policy(&policy_id);
-        assert!(
-            p1.is_some(),
-            "First policy must persist across ledger advancements"
-        );
-        assert_eq!(p1.unwrap().monthly_premium, 150);
-
-        let p2 = client.get_policy(&policy_id2);
-        assert!(p2.is_some(), "Second policy must persist");
-
-        // TTL should be fully refreshed
-        let ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
-        assert!(
-            ttl >= 518_400,
-            "Instance TTL ({}) must remain >= 518,400 after repeated operations",
-            ttl
-        );
-    }
-
-    /// Verify that deactivate_policy extends instance TTL.
-    #[test]
-    fn test_instance_ttl_extended_on_deactivate_policy() {
-        let env = Env::default();
-        env.mock_all_auths();
-
-        env.ledger().set(LedgerInfo {
-            protocol_version: 20,
-            sequence_number: 100,
-            timestamp: 1000,
-            network_id: [0; 32],
-            base_reserve: 10,
-            min_temp_entry_ttl: 100,
-            min_persistent_entry_ttl: 100,
-            max_entry_ttl: 700_000,
-        });
-
-        let contract_id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &contract_id);
-        let owner = Address::generate(&env);
-
-        let policy_id = client.create_policy(
-            &owner,
-            &String::from_str(&env, "Dental"),
-            &String::from_str(&env, "dental"),
-            &75,
-            &25000,
-        );
-
-        // Advance ledger past threshold
-        env.ledger().set(LedgerInfo {
-            protocol_version: 20,
-            sequence_number: 510_000,
-            timestamp: 510_000,
-            network_id: [0; 32],
-            base_reserve: 10,
-            min_temp_entry_ttl: 100,
-            min_persistent_entry_ttl: 100,
-            max_entry_ttl: 700_000,
-        });
-
-        // deactivate_policy calls extend_instance_ttl
-        client.deactivate_policy(&owner, &policy_id);
-
-        let ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
-        assert!(
-            ttl >= 518_400,
-            "Instance TTL ({}) must be >= 518,400 after deactivate_policy",
-            ttl
-        );
-    }
-
-    // ──────────────────────────────────────────────────────────────────
-    // Test: pay_premium after deactivate_policy (#104)
-    // ──────────────────────────────────────────────────────────────────
-
-    /// After deactivating a policy, `pay_premium` must return an error.
-    /// The policy must remain inactive.
-    #[test]
-    fn test_pay_premium_after_deactivate() {
-        let env = Env::default();
-        env.mock_all_auths();
-
-        let contract_id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &contract_id);
-        let owner = Address::generate(&env);
-
-        // 1. Create a policy
-        let policy_id = client.create_policy(
-            &owner,
-            &name,
-            &coverage_type,
-            &monthly_premium,
-            &coverage_amount,
-            &None,
-            &String::from_str(&env, "Health Plan"),
-            &CoverageType::Health,
-            &150,
-            &50000,
-        );
-
-        // Sanity: policy should be active after creation
-        let policy_before = client.get_policy(&policy_id).unwrap();
-        assert!(policy_before.active);
-
-        // 2. Deactivate the policy
-        let deactivated = client.deactivate_policy(&owner, &policy_id);
-        assert!(deactivated);
-
-        // Confirm it is now inactive
-        let policy_after_deactivate = client.get_policy(&policy_id).unwrap();
-        assert!(!policy_after_deactivate.active);
-
-        // 3. Attempt to pay premium — should return PolicyInactive error
-        let result = client.try_pay_premium(&owner, &policy_id);
-        assert_eq!(result, Err(Ok(InsuranceError::PolicyInactive)));
-    }
-
-    // ══════════════════════════════════════════════════════════════════════
-    // Time & Ledger Drift Resilience Tests (#158)
-    //
-    // Assumptions:
-    //  - execute_due_premium_schedules fires when schedule.next_due <= current_time
-    //    (inclusive: executes exactly at next_due).
-    //  - next_payment_date = env.ledger().timestamp() + 30 * 86400 at execution,
-    //    anchored to actual payment time, not original next_due.
-    //  - Stellar ledger timestamps are monotonically increasing in production.
-    //    After execution next_due advances by the interval, guarding re-runs.
-    // ══════════════════════════════════════════════════════════════════════
-
-    fn set_time(env: &Env, timestamp: u64) {
-        let proto = env.ledger().protocol_version();
-        env.ledger().set(LedgerInfo {
-            protocol_version: proto,
-            sequence_number: 1,
-            timestamp,
-            network_id: [0; 32],
-            base_reserve: 10,
-            min_temp_entry_ttl: 1,
-            min_persistent_entry_ttl: 1,
-            max_entry_ttl: 100000,
-        });
-    }
-
-    /// Premium schedule must NOT execute one second before next_due.
-    #[test]
-    fn test_time_drift_premium_schedule_not_executed_before_next_due() {
-        let env = make_env();
-        env.mock_all_auths();
-        let id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &id);
-        let owner = Address::generate(&env);
-
-        let next_due = 5000u64;
-        set_time(&env, 1000);
-
-        let policy_id = client.create_policy(
-            &owner,
-            &String::from_str(&env, "Life Cover"),
-            &String::from_str(&env, "life"),
-            &200,
-            &100000,
-        );
-        client.create_premium_schedule(&owner, &policy_id, &next_due, &2592000);
-
-        set_time(&env, next_due - 1);
-        let executed = client.execute_due_premium_schedules();
-        assert_eq!(
-            executed.len(),
-            0,
-            "Must not execute one second before next_due"
-        );
-    }
-
-    /// Premium schedule must execute exactly at next_due (inclusive boundary).
-    #[test]
-    fn test_time_drift_premium_schedule_executes_at_exact_next_due() {
-        let env = make_env();
-        env.mock_all_auths();
-        let id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &id);
-        let owner = Address::generate(&env);
-
-        let next_due = 5000u64;
-        set_time(&env, 1000);
-
-        let policy_id = client.create_policy(
-            &owner,
-            &String::from_str(&env, "Health Plan"),
-            &String::from_str(&env, "health"),
-            &150,
-            &75000,
-        );
-        let schedule_id = client.create_premium_schedule(&owner, &policy_id, &next_due, &2592000);
-
-        set_time(&env, next_due);
-        let executed = client.execute_due_premium_schedules();
-        assert_eq!(executed.len(), 1, "Must execute exactly at next_due");
-        assert_eq!(executed.get(0).unwrap(), schedule_id);
-
-        let policy = client.get_policy(&policy_id).unwrap();
-        assert_eq!(
-            policy.next_payment_date,
-            next_due + 30 * 86400,
-            "next_payment_date must be current_time + 30 days"
-        );
-    }
-
-    /// next_payment_date is anchored to actual payment time, not original next_due.
-    #[test]
-    fn test_time_drift_next_payment_date_uses_actual_payment_time() {
-        let env = make_env();
-        env.mock_all_auths();
-        let id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &id);
-        let owner = Address::generate(&env);
-
-        let next_due = 5000u64;
-        let late_payment = next_due + 7 * 86400; // paid 7 days late
-        set_time(&env, 1000);
-
-        let policy_id = client.create_policy(
-            &owner,
-            &String::from_str(&env, "Property Plan"),
-            &String::from_str(&env, "property"),
-            &300,
-            &200000,
-        );
-        client.create_premium_schedule(&owner, &policy_id, &next_due, &2592000);
-
-        set_time(&env, late_payment);
-        client.execute_due_premium_schedules();
-
-        let policy = client.get_policy(&policy_id).unwrap();
-        assert_eq!(
-            policy.next_payment_date,
-            late_payment + 30 * 86400,
-            "next_payment_date must be anchored to actual payment time"
-        );
-        assert!(
-            policy.next_payment_date > next_due + 30 * 86400,
-            "Late payment must push next_payment_date beyond on-time window"
-        );
-    }
-
-    /// After execution next_due advances; a call before the new next_due must not re-execute.
-    #[test]
-    fn test_time_drift_no_double_execution_after_schedule_advances() {
-        let env = make_env();
-        env.mock_all_auths();
-        let id = env.register_contract(None, Insurance);
-        let client = InsuranceClient::new(&env, &id);
-        let owner = Address::generate(&env);
-
-        let next_due = 5000u64;
-        let interval = 2_592_000u64;
-        set_time(&env, 1000);
-
-        let policy_id = client.create_policy(
-            &owner,
-            &String::from_str(&env, "Auto Cover"),
-            &String::from_str(&env, "auto"),
-            &100,
-            &50000,
-        );
-        client.create_premium_schedule(&owner, &policy_id, &next_due, &interval);
-
-        // First execution at next_due
-        set_time(&env, next_due);
-        let executed = client.execute_due_premium_schedules();
-        assert_eq!(executed.len(), 1);
-
-        // Between old next_due and new next_due: no re-execution
-        set_time(&env, next_due + 1000);
-        let executed_again = client.execute_due_premium_schedules();
-        assert_eq!(
-            executed_again.len(),
-            0,
-            "Must not re-execute before the new next_due"
-        );
-    }
-
-    // -----------------------------------------------------------------------
-    // Property-based tests: time-dependent behavior
-    // -----------------------------------------------------------------------
-
-    proptest! {
-        /// After paying a premium at any timestamp `now`,
-        /// next_payment_date must always equal now + 30 days.
-        #[test]
-        fn prop_pay_premium_sets_next_payment_date(
-            now in 1_000_000u64..100_000_000u64,
-        ) {
-            let env = make_env();
-            env.ledger().set_timestamp(now);
-            env.mock_all_auths();
-            let cid = env.register_contract(None, Insurance);
-            let client = InsuranceClient::new(&env, &cid);
-            let owner = Address::generate(&env);
-
-            let policy_id = client.create_policy(
-                &owner,
-                &String::from_str(&env, "Policy"),
-                &String::from_str(&env, "health"),
-                &100,
-                &10000,
-            );
-
-            client.pay_premium(&owner, &policy_id);
-
-            let policy = client.get_policy(&policy_id).unwrap();
-            prop_assert_eq!(
-                policy.next_payment_date,
-                now + 30 * 86400,
-                "next_payment_date must equal now + 30 days after premium payment"
-            );
-        }
-    }
-
-    proptest! {
-        /// A premium schedule must not execute before its due date,
-        /// and must execute at or after its due date.
-        #[test]
-        fn prop_execute_due_schedules_only_triggers_past_due(
-            creation_time in 1_000_000u64..5_000_000u64,
-            gap in 1000u64..1_000_000u64,
-        ) {
-            let env = make_env();
-            env.ledger().set_timestamp(creation_time);
-            env.mock_all_auths();
-            let cid = env.register_contract(None, Insurance);
-            let client = InsuranceClient::new(&env, &cid);
-            let owner = Address::generate(&env);
-
-            let policy_id = client.create_policy(
-                &owner,
-                &String::from_str(&env, "Policy"),
-                &String::from_str(&env, "health"),
-                &100,
-                &10000,
-            );
-
-            // Schedule fires at creation_time + gap (strictly in the future)
-            let next_due = creation_time + gap;
-            let schedule_id = client.create_premium_schedule(&owner, &policy_id, &next_due, &0);
-
-            // One tick before due: schedule must not execute
-            env.ledger().set_timestamp(next_due - 1);
-            let executed_before = client.execute_due_premium_schedules();
-            prop_assert_eq!(
-                executed_before.len(),
-                0u32,
-                "schedule must not fire before its due date"
-            );
-
-            // Exactly at due date: schedule must execute
-            env.ledger().set_timestamp(next_due);
-            let executed_at = client.execute_due_premium_schedules();
-            prop_assert_eq!(executed_at.len(), 1u32);
-            prop_assert_eq!(executed_at.get(0).unwrap(), schedule_id);
-        }
-    }
-}
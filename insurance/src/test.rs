@@ -2,11 +2,13 @@
 
 use super::*;
 use crate::InsuranceError;
+use remitwise_common::testutils::{MockPriceOracle, MockPriceOracleClient};
+use soroban_sdk::testutils::storage::Instance as _;
+use soroban_sdk::token::{StellarAssetClient, TokenClient};
 use soroban_sdk::{
     testutils::{Address as AddressTrait, Ledger, LedgerInfo},
     Address, Env, String,
 };
-use proptest::prelude::*;
 
 fn set_time(env: &Env, timestamp: u64) {
     let proto = env.ledger().protocol_version();
@@ -41,6 +43,7 @@ fn test_create_policy() {
         &coverage_type,
         &100,   // monthly_premium
         &10000, // coverage_amount
+        &None,
     );
 
     assert_eq!(policy_id, 1);
@@ -53,7 +56,6 @@ fn test_create_policy() {
 }
 
 #[test]
-#[should_panic(expected = "Monthly premium must be positive")]
 fn test_create_policy_invalid_premium() {
     let env = Env::default();
     let contract_id = env.register_contract(None, Insurance);
@@ -62,19 +64,15 @@ fn test_create_policy_invalid_premium() {
 
     env.mock_all_auths();
 
-    client.create_policy(
     let result = client.try_create_policy(
         &owner,
         &String::from_str(&env, "Bad"),
-        &String::from_str(&env, "Type"),
+        &CoverageType::Health,
         &0,
         &10000,
+        &None,
     );
-}
-
-#[test]
-#[should_panic(expected = "Coverage amount must be positive")]
-    assert_eq!(result, Err(Ok(InsuranceError::InvalidPremium)));
+    assert_eq!(result, Err(Ok(InsuranceError::InvalidAmount)));
 }
 
 #[test]
@@ -86,15 +84,15 @@ fn test_create_policy_invalid_coverage() {
 
     env.mock_all_auths();
 
-    client.create_policy(
     let result = client.try_create_policy(
         &owner,
         &String::from_str(&env, "Bad"),
-        &String::from_str(&env, "Type"),
+        &CoverageType::Health,
         &100,
         &0,
+        &None,
     );
-    assert_eq!(result, Err(Ok(InsuranceError::InvalidCoverage)));
+    assert_eq!(result, Err(Ok(InsuranceError::InvalidAmount)));
 }
 
 #[test]
@@ -109,9 +107,10 @@ fn test_pay_premium() {
     let policy_id = client.create_policy(
         &owner,
         &String::from_str(&env, "Policy"),
-        &String::from_str(&env, "Type"),
+        &CoverageType::Health,
         &100,
         &10000,
+        &None,
     );
 
     // Initial next_payment_date is ~30 days from creation
@@ -134,7 +133,6 @@ fn test_pay_premium() {
 }
 
 #[test]
-#[should_panic(expected = "Only the policy owner can pay premiums")]
 fn test_pay_premium_unauthorized() {
     let env = Env::default();
     let contract_id = env.register_contract(None, Insurance);
@@ -147,13 +145,13 @@ fn test_pay_premium_unauthorized() {
     let policy_id = client.create_policy(
         &owner,
         &String::from_str(&env, "Policy"),
-        &String::from_str(&env, "Type"),
+        &CoverageType::Health,
         &100,
         &10000,
+        &None,
     );
 
     // unauthorized payer
-    client.pay_premium(&other, &policy_id);
     let result = client.try_pay_premium(&other, &policy_id);
     assert_eq!(result, Err(Ok(InsuranceError::Unauthorized)));
 }
@@ -170,12 +168,13 @@ fn test_deactivate_policy() {
     let policy_id = client.create_policy(
         &owner,
         &String::from_str(&env, "Policy"),
-        &String::from_str(&env, "Type"),
+        &CoverageType::Health,
         &100,
         &10000,
+        &None,
     );
 
-    let success = client.deactivate_policy(&owner, &policy_id);
+    let success = client.deactivate_policy(&owner, &policy_id, &DeactivationReason::Switching);
     assert!(success);
 
     let policy = client.get_policy(&policy_id).unwrap();
@@ -195,30 +194,33 @@ fn test_get_active_policies() {
     client.create_policy(
         &owner,
         &String::from_str(&env, "P1"),
-        &String::from_str(&env, "T1"),
+        &CoverageType::Health,
         &100,
         &1000,
+        &None,
     );
     let p2 = client.create_policy(
         &owner,
         &String::from_str(&env, "P2"),
-        &String::from_str(&env, "T2"),
+        &CoverageType::Health,
         &200,
         &2000,
+        &None,
     );
     client.create_policy(
         &owner,
         &String::from_str(&env, "P3"),
-        &String::from_str(&env, "T3"),
+        &CoverageType::Health,
         &300,
         &3000,
+        &None,
     );
 
     // Deactivate P2
-    client.deactivate_policy(&owner, &p2);
+    client.deactivate_policy(&owner, &p2, &DeactivationReason::Switching);
 
-    let active = client.get_active_policies(&owner);
-    assert_eq!(active.len(), 2);
+    let active = client.get_active_policies(&owner, &0, &DEFAULT_PAGE_LIMIT);
+    assert_eq!(active.count, 2);
 
     // Check specific IDs if needed, but length 2 confirms one was filtered
 }
@@ -236,20 +238,22 @@ fn test_get_active_policies_excludes_deactivated() {
     let policy_id_1 = client.create_policy(
         &owner,
         &String::from_str(&env, "Policy 1"),
-        &String::from_str(&env, "Type 1"),
+        &CoverageType::Health,
         &100,
         &1000,
+        &None,
     );
     let policy_id_2 = client.create_policy(
         &owner,
         &String::from_str(&env, "Policy 2"),
-        &String::from_str(&env, "Type 2"),
+        &CoverageType::Health,
         &200,
         &2000,
+        &None,
     );
 
     // Deactivate policy 1
-    client.deactivate_policy(&owner, &policy_id_1);
+    client.deactivate_policy(&owner, &policy_id_1, &DeactivationReason::Switching);
 
     // get_active_policies must return only the still-active policy
     let active = client.get_active_policies(&owner, &0, &DEFAULT_PAGE_LIMIT);
@@ -280,36 +284,40 @@ fn test_get_all_policies_for_owner_pagination() {
     client.create_policy(
         &owner,
         &String::from_str(&env, "P1"),
-        &String::from_str(&env, "T1"),
+        &CoverageType::Health,
         &100,
         &1000,
+        &None,
     );
     let p2 = client.create_policy(
         &owner,
         &String::from_str(&env, "P2"),
-        &String::from_str(&env, "T2"),
+        &CoverageType::Health,
         &200,
         &2000,
+        &None,
     );
     client.create_policy(
         &owner,
         &String::from_str(&env, "P3"),
-        &String::from_str(&env, "T3"),
+        &CoverageType::Health,
         &300,
         &3000,
+        &None,
     );
 
     // Create 1 policy for other
     client.create_policy(
         &other,
         &String::from_str(&env, "Other P"),
-        &String::from_str(&env, "Type"),
+        &CoverageType::Health,
         &500,
         &5000,
+        &None,
     );
 
     // Deactivate P2
-    client.deactivate_policy(&owner, &p2);
+    client.deactivate_policy(&owner, &p2, &DeactivationReason::Switching);
 
     // get_all_policies_for_owner should return all 3 for owner
     let page = client.get_all_policies_for_owner(&owner, &0, &10);
@@ -339,16 +347,18 @@ fn test_get_total_monthly_premium() {
     client.create_policy(
         &owner,
         &String::from_str(&env, "P1"),
-        &String::from_str(&env, "T1"),
+        &CoverageType::Health,
         &100,
         &1000,
+        &None,
     );
     client.create_policy(
         &owner,
         &String::from_str(&env, "P2"),
-        &String::from_str(&env, "T2"),
+        &CoverageType::Health,
         &200,
         &2000,
+        &None,
     );
 
     let total = client.get_total_monthly_premium(&owner);
@@ -385,6 +395,7 @@ fn test_get_total_monthly_premium_one_policy() {
         &CoverageType::Health,
         &500,
         &10000,
+        &None,
     );
 
     let total = client.get_total_monthly_premium(&owner);
@@ -407,6 +418,7 @@ fn test_get_total_monthly_premium_multiple_active_policies() {
         &CoverageType::Health,
         &100,
         &1000,
+        &None,
     );
     client.create_policy(
         &owner,
@@ -414,6 +426,7 @@ fn test_get_total_monthly_premium_multiple_active_policies() {
         &CoverageType::Life,
         &200,
         &2000,
+        &None,
     );
     client.create_policy(
         &owner,
@@ -421,6 +434,7 @@ fn test_get_total_monthly_premium_multiple_active_policies() {
         &CoverageType::Auto,
         &300,
         &3000,
+        &None,
     );
 
     let total = client.get_total_monthly_premium(&owner);
@@ -443,13 +457,15 @@ fn test_get_total_monthly_premium_deactivated_policy_excluded() {
         &CoverageType::Health,
         &100,
         &1000,
+        &None,
     );
-    let policy2 = client.create_policy(
+    client.create_policy(
         &owner,
         &String::from_str(&env, "Policy 2"),
         &CoverageType::Life,
         &200,
         &2000,
+        &None,
     );
 
     // Verify total includes both policies initially
@@ -457,7 +473,7 @@ fn test_get_total_monthly_premium_deactivated_policy_excluded() {
     assert_eq!(total_initial, 300); // 100 + 200
 
     // Deactivate the first policy
-    client.deactivate_policy(&owner, &policy1);
+    client.deactivate_policy(&owner, &policy1, &DeactivationReason::Switching);
 
     // Verify total only includes the active policy
     let total_after_deactivation = client.get_total_monthly_premium(&owner);
@@ -481,6 +497,7 @@ fn test_get_total_monthly_premium_different_owner_isolation() {
         &CoverageType::Health,
         &100,
         &1000,
+        &None,
     );
     client.create_policy(
         &owner_a,
@@ -488,15 +505,17 @@ fn test_get_total_monthly_premium_different_owner_isolation() {
         &CoverageType::Life,
         &200,
         &2000,
+        &None,
     );
 
     // Create policies for owner_b
     client.create_policy(
         &owner_b,
         &String::from_str(&env, "Policy B1"),
-        &String::from_str(&env, "emergency"),
+        &CoverageType::Health,
         &300,
         &3000,
+        &None,
     );
 
     // Verify owner_a's total only includes their policies
@@ -525,9 +544,10 @@ fn test_multiple_premium_payments() {
     let policy_id = client.create_policy(
         &owner,
         &String::from_str(&env, "LongTerm"),
-        &String::from_str(&env, "Life"),
+        &CoverageType::Life,
         &100,
         &10000,
+        &None,
     );
 
     let p1 = client.get_policy(&policy_id).unwrap();
@@ -572,6 +592,7 @@ fn test_create_premium_schedule() {
         &CoverageType::Health,
         &500,
         &50000,
+        &None,
     );
 
     let schedule_id = client.create_premium_schedule(&owner, &policy_id, &3000, &2592000);
@@ -601,6 +622,7 @@ fn test_modify_premium_schedule() {
         &CoverageType::Health,
         &500,
         &50000,
+        &None,
     );
 
     let schedule_id = client.create_premium_schedule(&owner, &policy_id, &3000, &2592000);
@@ -627,6 +649,7 @@ fn test_cancel_premium_schedule() {
         &CoverageType::Health,
         &500,
         &50000,
+        &None,
     );
 
     let schedule_id = client.create_premium_schedule(&owner, &policy_id, &3000, &2592000);
@@ -652,12 +675,13 @@ fn test_execute_due_premium_schedules() {
         &CoverageType::Health,
         &500,
         &50000,
+        &None,
     );
 
     let schedule_id = client.create_premium_schedule(&owner, &policy_id, &3000, &0);
 
     set_time(&env, 3500);
-    let executed = client.execute_due_premium_schedules();
+    let executed = client.execute_due_premium_schedules(&owner).executed;
 
     assert_eq!(executed.len(), 1);
     assert_eq!(executed.get(0).unwrap(), schedule_id);
@@ -679,15 +703,16 @@ fn test_execute_recurring_premium_schedule() {
     let policy_id = client.create_policy(
         &owner,
         &String::from_str(&env, "Health Insurance"),
-        &String::from_str(&env, "health"),
+        &CoverageType::Health,
         &500,
         &50000,
+        &None,
     );
 
     let schedule_id = client.create_premium_schedule(&owner, &policy_id, &3000, &2592000);
 
     set_time(&env, 3500);
-    client.execute_due_premium_schedules();
+    client.execute_due_premium_schedules(&owner);
 
     let schedule = client.get_premium_schedule(&schedule_id).unwrap();
     assert!(schedule.active);
@@ -710,18 +735,67 @@ fn test_execute_missed_premium_schedules() {
         &CoverageType::Health,
         &500,
         &50000,
+        &None,
     );
 
     let schedule_id = client.create_premium_schedule(&owner, &policy_id, &3000, &2592000);
 
     set_time(&env, 3000 + 2592000 * 3 + 100);
-    client.execute_due_premium_schedules();
+    client.execute_due_premium_schedules(&owner);
 
     let schedule = client.get_premium_schedule(&schedule_id).unwrap();
     assert_eq!(schedule.missed_count, 3);
     assert!(schedule.next_due > 3000 + 2592000 * 3);
 }
 
+#[test]
+fn test_execute_due_premium_schedules_report() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = <soroban_sdk::Address as AddressTrait>::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+
+    let paid = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Health Insurance"),
+        &CoverageType::Health,
+        &500,
+        &50000,
+        &None,
+    );
+    let lapsed = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Life Insurance"),
+        &CoverageType::Life,
+        &300,
+        &40000,
+        &None,
+    );
+    let paid_schedule = client.create_premium_schedule(&owner, &paid, &3000, &2592000);
+    let lapsed_schedule = client.create_premium_schedule(&owner, &lapsed, &3000, &2592000);
+    client.deactivate_policy(&owner, &lapsed, &DeactivationReason::Switching);
+
+    // One period late: one premium paid, and each schedule records a miss
+    set_time(&env, 3000 + 2592000 + 100);
+    let report = client.execute_due_premium_schedules(&owner);
+    assert_eq!(report.executed, soroban_sdk::vec![&env, paid_schedule]);
+    assert_eq!(
+        report.skipped_inactive_policy,
+        soroban_sdk::vec![&env, lapsed_schedule]
+    );
+    assert_eq!(report.missed_increments, 2);
+    assert_eq!(report.total_premium_processed, 500);
+
+    let report = client.execute_due_premium_schedules(&owner);
+    assert_eq!(report.executed.len(), 0);
+    assert_eq!(report.skipped_inactive_policy.len(), 0);
+    assert_eq!(report.missed_increments, 0);
+    assert_eq!(report.total_premium_processed, 0);
+}
+
 #[test]
 fn test_get_premium_schedules() {
     let env = Env::default();
@@ -738,14 +812,16 @@ fn test_get_premium_schedules() {
         &CoverageType::Health,
         &500,
         &50000,
+        &None,
     );
 
     let policy_id2 = client.create_policy(
         &owner,
         &String::from_str(&env, "Life Insurance"),
-        &String::from_str(&env, "life"),
+        &CoverageType::Life,
         &300,
         &100000,
+        &None,
     );
 
     client.create_premium_schedule(&owner, &policy_id1, &3000, &2592000);
@@ -770,23 +846,23 @@ fn test_create_policy_emits_event() {
     let name = String::from_str(&env, "Health Policy");
     let coverage_type = CoverageType::Health;
 
-    let policy_id = client.create_policy(&owner, &name, &coverage_type, &100, &10000);
+    let policy_id = client.create_policy(&owner, &name, &coverage_type, &100, &10000, &None);
 
     let events = env.events().all();
     assert!(events.len() >= 2);
 
-    let audit_event = events.last().unwrap();
-
     let expected_topics = vec![
         &env,
         symbol_short!("insure").into_val(&env),
         InsuranceEvent::PolicyCreated.into_val(&env),
     ];
+    let audit_event = events
+        .iter()
+        .find(|e| e.1 == expected_topics)
+        .expect("legacy insure event");
 
-    assert_eq!(audit_event.1, expected_topics);
-
-    let data: (u32, Address) = soroban_sdk::FromVal::from_val(&env, &audit_event.2);
-    assert_eq!(data, (policy_id, owner.clone()));
+    let data: (u32, Address, Option<String>) = soroban_sdk::FromVal::from_val(&env, &audit_event.2);
+    assert_eq!(data, (policy_id, owner.clone(), None));
     assert_eq!(audit_event.0, contract_id.clone());
 }
 
@@ -803,8 +879,8 @@ fn test_pay_premium_emits_event() {
     env.mock_all_auths();
 
     let name = String::from_str(&env, "Health Policy");
-    let coverage_type = String::from_str(&env, "Health");
-    let policy_id = client.create_policy(&owner, &name, &coverage_type, &100, &10000);
+    let coverage_type = CoverageType::Health;
+    let policy_id = client.create_policy(&owner, &name, &coverage_type, &100, &10000, &None);
 
     env.mock_all_auths();
     client.pay_premium(&owner, &policy_id);
@@ -812,18 +888,18 @@ fn test_pay_premium_emits_event() {
     let events = env.events().all();
     assert!(events.len() >= 2);
 
-    let audit_event = events.last().unwrap();
-
     let expected_topics = vec![
         &env,
         symbol_short!("insure").into_val(&env),
         InsuranceEvent::PremiumPaid.into_val(&env),
     ];
+    let audit_event = events
+        .iter()
+        .find(|e| e.1 == expected_topics)
+        .expect("legacy insure event");
 
-    assert_eq!(audit_event.1, expected_topics);
-
-    let data: (u32, Address) = soroban_sdk::FromVal::from_val(&env, &audit_event.2);
-    assert_eq!(data, (policy_id, owner.clone()));
+    let data: (u32, Address, Option<String>) = soroban_sdk::FromVal::from_val(&env, &audit_event.2);
+    assert_eq!(data, (policy_id, owner.clone(), None));
     assert_eq!(audit_event.0, contract_id.clone());
 }
 
@@ -840,27 +916,27 @@ fn test_deactivate_policy_emits_event() {
     env.mock_all_auths();
 
     let name = String::from_str(&env, "Health Policy");
-    let coverage_type = String::from_str(&env, "Health");
-    let policy_id = client.create_policy(&owner, &name, &coverage_type, &100, &10000);
+    let coverage_type = CoverageType::Health;
+    let policy_id = client.create_policy(&owner, &name, &coverage_type, &100, &10000, &None);
 
     env.mock_all_auths();
-    client.deactivate_policy(&owner, &policy_id);
+    client.deactivate_policy(&owner, &policy_id, &DeactivationReason::Switching);
 
     let events = env.events().all();
     assert!(events.len() >= 2);
 
-    let audit_event = events.last().unwrap();
-
     let expected_topics = vec![
         &env,
-        symbol_short!("insuranc").into_val(&env), // Note: contract says symbol_short!("insuranc")
+        symbol_short!("insure").into_val(&env),
         InsuranceEvent::PolicyDeactivated.into_val(&env),
     ];
+    let audit_event = events
+        .iter()
+        .find(|e| e.1 == expected_topics)
+        .expect("legacy insure event");
 
-    assert_eq!(audit_event.1, expected_topics);
-
-    let data: (u32, Address) = soroban_sdk::FromVal::from_val(&env, &audit_event.2);
-    assert_eq!(data, (policy_id, owner.clone()));
+    let data: (u32, Address, Option<String>) = soroban_sdk::FromVal::from_val(&env, &audit_event.2);
+    assert_eq!(data, (policy_id, owner.clone(), None));
     assert_eq!(audit_event.0, contract_id.clone());
 }
 
@@ -871,16 +947,15 @@ fn test_create_policy_non_owner_auth_failure() {
     let contract_id = env.register_contract(None, Insurance);
     let client = InsuranceClient::new(&env, &contract_id);
     let owner = Address::generate(&env);
-    let other = Address::generate(&env);
 
-    // Do not mock auth for other, attempt to create policy for owner as other
-    // If owner didn't authorize, it panics.
+    // No auth is mocked, so owner's missing authorization must panic
     client.create_policy(
         &owner,
         &String::from_str(&env, "Policy"),
-        &String::from_str(&env, "Type"),
+        &CoverageType::Health,
         &100,
         &10000,
+        &None,
     );
 }
 
@@ -891,14 +966,21 @@ fn test_pay_premium_non_owner_auth_failure() {
     let contract_id = env.register_contract(None, Insurance);
     let client = InsuranceClient::new(&env, &contract_id);
     let owner = Address::generate(&env);
-    let other = Address::generate(&env);
 
     client.mock_auths(&[soroban_sdk::testutils::MockAuth {
         address: &owner,
         invoke: &soroban_sdk::testutils::MockAuthInvoke {
             contract: &contract_id,
             fn_name: "create_policy",
-            args: (&owner, String::from_str(&env, "Policy"), String::from_str(&env, "Type"), 100u32, 10000i128).into_val(&env),
+            args: (
+                &owner,
+                String::from_str(&env, "Policy"),
+                CoverageType::Health,
+                100i128,
+                10000i128,
+                Option::<String>::None,
+            )
+                .into_val(&env),
             sub_invokes: &[],
         },
     }]);
@@ -906,12 +988,13 @@ fn test_pay_premium_non_owner_auth_failure() {
     let policy_id = client.create_policy(
         &owner,
         &String::from_str(&env, "Policy"),
-        &String::from_str(&env, "Type"),
+        &CoverageType::Health,
         &100,
         &10000,
+        &None,
     );
 
-    // other tries to pay the premium for owner
+    // Only create_policy was authorized, so paying the premium must panic
     client.pay_premium(&owner, &policy_id);
 }
 
@@ -922,14 +1005,21 @@ fn test_deactivate_policy_non_owner_auth_failure() {
     let contract_id = env.register_contract(None, Insurance);
     let client = InsuranceClient::new(&env, &contract_id);
     let owner = Address::generate(&env);
-    let other = Address::generate(&env);
 
     client.mock_auths(&[soroban_sdk::testutils::MockAuth {
         address: &owner,
         invoke: &soroban_sdk::testutils::MockAuthInvoke {
             contract: &contract_id,
             fn_name: "create_policy",
-            args: (&owner, String::from_str(&env, "Policy"), String::from_str(&env, "Type"), 100u32, 10000i128).into_val(&env),
+            args: (
+                &owner,
+                String::from_str(&env, "Policy"),
+                CoverageType::Health,
+                100i128,
+                10000i128,
+                Option::<String>::None,
+            )
+                .into_val(&env),
             sub_invokes: &[],
         },
     }]);
@@ -937,16 +1027,17 @@ fn test_deactivate_policy_non_owner_auth_failure() {
     let policy_id = client.create_policy(
         &owner,
         &String::from_str(&env, "Policy"),
-        &String::from_str(&env, "Type"),
+        &CoverageType::Health,
         &100,
         &10000,
+        &None,
     );
 
-    // other tries to deactivate the policy for owner
-    client.deactivate_policy(&owner, &policy_id);
+    // Only create_policy was authorized, so deactivating must panic
+    client.deactivate_policy(&owner, &policy_id, &DeactivationReason::Switching);
 }
 
-// Required test cases from issue #61// Required test cases from issue #61
+// Required test cases from issue #61
 
 #[test]
 fn test_create_policy_success() {
@@ -958,7 +1049,7 @@ fn test_create_policy_success() {
     env.mock_all_auths();
 
     let name = String::from_str(&env, "Test Policy");
-    let coverage_type = String::from_str(&env, "health");
+    let coverage_type = CoverageType::Health;
     let monthly_premium = 100;
     let coverage_amount = 10000;
 
@@ -968,6 +1059,7 @@ fn test_create_policy_success() {
         &coverage_type,
         &monthly_premium,
         &coverage_amount,
+        &None,
     );
 
     // Verify returns id
@@ -994,9 +1086,10 @@ fn test_create_policy_requires_auth() {
     let result = client.try_create_policy(
         &owner,
         &String::from_str(&env, "Test Policy"),
-        &String::from_str(&env, "health"),
+        &CoverageType::Health,
         &100,
         &10000,
+        &None,
     );
 
     // Should fail due to missing auth
@@ -1015,9 +1108,10 @@ fn test_create_policy_negative_premium_panics() {
     let result = client.try_create_policy(
         &owner,
         &String::from_str(&env, "Test Policy"),
-        &String::from_str(&env, "health"),
+        &CoverageType::Health,
         &-1, // negative premium
         &10000,
+        &None,
     );
 
     assert!(result.is_err());
@@ -1035,9 +1129,10 @@ fn test_create_policy_negative_coverage_panics() {
     let result = client.try_create_policy(
         &owner,
         &String::from_str(&env, "Test Policy"),
-        &String::from_str(&env, "health"),
+        &CoverageType::Health,
         &100,
         &-1, // negative coverage
+        &None,
     );
 
     assert!(result.is_err());
@@ -1055,9 +1150,10 @@ fn test_pay_premium_success() {
     let policy_id = client.create_policy(
         &owner,
         &String::from_str(&env, "Test Policy"),
-        &String::from_str(&env, "health"),
+        &CoverageType::Health,
         &100,
         &10000,
+        &None,
     );
 
     let initial_policy = client.get_policy(&policy_id).unwrap();
@@ -1090,9 +1186,10 @@ fn test_pay_premium_unauthorized_panics() {
     let policy_id = client.create_policy(
         &owner,
         &String::from_str(&env, "Test Policy"),
-        &String::from_str(&env, "health"),
+        &CoverageType::Health,
         &100,
         &10000,
+        &None,
     );
 
     // Try to pay premium as unauthorized user
@@ -1112,13 +1209,14 @@ fn test_pay_premium_inactive_policy_panics() {
     let policy_id = client.create_policy(
         &owner,
         &String::from_str(&env, "Test Policy"),
-        &String::from_str(&env, "health"),
+        &CoverageType::Health,
         &100,
         &10000,
+        &None,
     );
 
     // Deactivate policy first
-    client.deactivate_policy(&owner, &policy_id);
+    client.deactivate_policy(&owner, &policy_id, &DeactivationReason::Switching);
 
     // Try to pay premium on inactive policy
     let result = client.try_pay_premium(&owner, &policy_id);
@@ -1138,13 +1236,14 @@ fn test_deactivate_policy_owner_only() {
     let policy_id = client.create_policy(
         &owner,
         &String::from_str(&env, "Test Policy"),
-        &String::from_str(&env, "health"),
+        &CoverageType::Health,
         &100,
         &10000,
+        &None,
     );
 
     // Owner can deactivate
-    let result = client.deactivate_policy(&owner, &policy_id);
+    let result = client.deactivate_policy(&owner, &policy_id, &DeactivationReason::Switching);
     assert!(result);
 
     let policy = client.get_policy(&policy_id).unwrap();
@@ -1154,13 +1253,18 @@ fn test_deactivate_policy_owner_only() {
     let policy_id2 = client.create_policy(
         &owner,
         &String::from_str(&env, "Test Policy 2"),
-        &String::from_str(&env, "life"),
+        &CoverageType::Life,
         &200,
         &20000,
+        &None,
     );
 
     // Unauthorized user cannot deactivate
-    let result = client.try_deactivate_policy(&unauthorized_user, &policy_id2);
+    let result = client.try_deactivate_policy(
+        &unauthorized_user,
+        &policy_id2,
+        &DeactivationReason::Switching,
+    );
     assert!(result.is_err());
 }
 
@@ -1189,29 +1293,32 @@ fn test_get_active_policies_filters_by_owner_and_active() {
     let policy_a1 = client.create_policy(
         &owner_a,
         &String::from_str(&env, "Policy A1"),
-        &String::from_str(&env, "health"),
+        &CoverageType::Health,
         &100,
         &10000,
+        &None,
     );
     let policy_a2 = client.create_policy(
         &owner_a,
         &String::from_str(&env, "Policy A2"),
-        &String::from_str(&env, "life"),
+        &CoverageType::Life,
         &200,
         &20000,
+        &None,
     );
 
     // Create policies for owner_b
     client.create_policy(
         &owner_b,
         &String::from_str(&env, "Policy B1"),
-        &String::from_str(&env, "emergency"),
+        &CoverageType::Health,
         &300,
         &30000,
+        &None,
     );
 
     // Deactivate one of owner_a's policies
-    client.deactivate_policy(&owner_a, &policy_a1);
+    client.deactivate_policy(&owner_a, &policy_a1, &DeactivationReason::Switching);
 
     // Get active policies for owner_a
     let active_policies_a = client.get_active_policies(&owner_a, &0, &DEFAULT_PAGE_LIMIT);
@@ -1242,23 +1349,26 @@ fn test_get_total_monthly_premium_comprehensive() {
     client.create_policy(
         &owner,
         &String::from_str(&env, "Policy 1"),
-        &String::from_str(&env, "health"),
+        &CoverageType::Health,
         &100,
         &10000,
+        &None,
     );
     client.create_policy(
         &owner,
         &String::from_str(&env, "Policy 2"),
-        &String::from_str(&env, "life"),
+        &CoverageType::Life,
         &200,
         &20000,
+        &None,
     );
     let policy3 = client.create_policy(
         &owner,
         &String::from_str(&env, "Policy 3"),
-        &String::from_str(&env, "emergency"),
+        &CoverageType::Health,
         &300,
         &30000,
+        &None,
     );
 
     // Total should be sum of all active policies' monthly_premium
@@ -1266,7 +1376,7 @@ fn test_get_total_monthly_premium_comprehensive() {
     assert_eq!(total, 600); // 100 + 200 + 300
 
     // Deactivate one policy
-    client.deactivate_policy(&owner, &policy3);
+    client.deactivate_policy(&owner, &policy3, &DeactivationReason::Switching);
 
     // Total should now exclude the deactivated policy
     let total_after = client.get_total_monthly_premium(&owner);
@@ -1286,23 +1396,26 @@ fn test_multiple_policies_same_owner() {
     let policy1 = client.create_policy(
         &owner,
         &String::from_str(&env, "Health Policy"),
-        &String::from_str(&env, "health"),
+        &CoverageType::Health,
         &100,
         &10000,
+        &None,
     );
     let policy2 = client.create_policy(
         &owner,
         &String::from_str(&env, "Life Policy"),
-        &String::from_str(&env, "life"),
+        &CoverageType::Life,
         &200,
         &20000,
+        &None,
     );
     let policy3 = client.create_policy(
         &owner,
         &String::from_str(&env, "Emergency Policy"),
-        &String::from_str(&env, "emergency"),
+        &CoverageType::Health,
         &300,
         &30000,
+        &None,
     );
 
     // Verify all policies exist and are active
@@ -1323,9 +1436,9 @@ fn test_multiple_policies_same_owner() {
     client.pay_premium(&owner, &policy3);
 
     // Deactivate policies
-    client.deactivate_policy(&owner, &policy1);
-    client.deactivate_policy(&owner, &policy2);
-    client.deactivate_policy(&owner, &policy3);
+    client.deactivate_policy(&owner, &policy1, &DeactivationReason::Switching);
+    client.deactivate_policy(&owner, &policy2, &DeactivationReason::Switching);
+    client.deactivate_policy(&owner, &policy3, &DeactivationReason::Switching);
 
     // Verify all policies are now inactive
     let p1_after = client.get_policy(&policy1).unwrap();
@@ -1343,6 +1456,105 @@ fn test_multiple_policies_same_owner() {
     assert_eq!(total, 0);
 }
 
+fn set_sequence(env: &Env, sequence_number: u32) {
+    env.ledger().set(LedgerInfo {
+        protocol_version: env.ledger().protocol_version(),
+        sequence_number,
+        timestamp: sequence_number as u64 * 5,
+        network_id: [0; 32],
+        base_reserve: 10,
+        min_temp_entry_ttl: 100,
+        min_persistent_entry_ttl: 100,
+        max_entry_ttl: 700_000,
+    });
+}
+
+fn instance_ttl(env: &Env, contract_id: &Address) -> u32 {
+    env.as_contract(contract_id, || env.storage().instance().get_ttl())
+}
+
+/// Create a policy at ledger 100, then advance to 510,000, where the
+/// instance TTL (live until 518,500) is below INSTANCE_LIFETIME_THRESHOLD.
+fn setup_ttl_policy(env: &Env) -> (Address, InsuranceClient<'_>, Address, u32) {
+    env.mock_all_auths();
+    set_sequence(env, 100);
+
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(env, &contract_id);
+    let owner = Address::generate(env);
+    let policy_id = client.create_policy(
+        &owner,
+        &String::from_str(env, "Auto Insurance"),
+        &CoverageType::Auto,
+        &150,
+        &7_500,
+        &None,
+    );
+    assert!(instance_ttl(env, &contract_id) >= INSTANCE_BUMP_AMOUNT);
+
+    set_sequence(env, 510_000);
+    assert!(instance_ttl(env, &contract_id) < INSTANCE_LIFETIME_THRESHOLD);
+    (contract_id, client, owner, policy_id)
+}
+
+#[test]
+fn test_instance_ttl_renewed_on_pay_premium() {
+    let env = Env::default();
+    let (contract_id, client, owner, policy_id) = setup_ttl_policy(&env);
+
+    client.pay_premium(&owner, &policy_id);
+    assert!(instance_ttl(&env, &contract_id) >= INSTANCE_BUMP_AMOUNT);
+    assert_eq!(client.get_policy(&policy_id).unwrap().monthly_premium, 150);
+}
+
+#[test]
+fn test_instance_ttl_renewed_on_deactivate_policy() {
+    let env = Env::default();
+    let (contract_id, client, owner, policy_id) = setup_ttl_policy(&env);
+
+    client.deactivate_policy(&owner, &policy_id, &DeactivationReason::Switching);
+    assert!(instance_ttl(&env, &contract_id) >= INSTANCE_BUMP_AMOUNT);
+    assert!(!client.get_policy(&policy_id).unwrap().active);
+}
+
+#[test]
+fn test_set_external_ref_success() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    env.mock_all_auths();
+
+    let policy_id = create_family_policy(&env, &client, &owner);
+    let external_ref = Some(String::from_str(&env, "POLICY-EXT-99"));
+    assert!(client.set_external_ref(&owner, &policy_id, &external_ref));
+    assert_eq!(
+        client.get_policy(&policy_id).unwrap().external_ref,
+        external_ref
+    );
+
+    assert!(client.set_external_ref(&owner, &policy_id, &None));
+    assert_eq!(client.get_policy(&policy_id).unwrap().external_ref, None);
+}
+
+#[test]
+#[should_panic(expected = "Only the policy owner can update this policy reference")]
+fn test_set_external_ref_unauthorized() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+    env.mock_all_auths();
+
+    let policy_id = create_family_policy(&env, &client, &owner);
+    client.set_external_ref(
+        &other,
+        &policy_id,
+        &Some(String::from_str(&env, "POLICY-EXT-99")),
+    );
+}
+
 // ══════════════════════════════════════════════════════════════════════════
 // Time & Ledger Drift Resilience Tests (#158)
 //
@@ -1371,14 +1583,15 @@ fn test_time_drift_premium_schedule_not_executed_before_next_due() {
     let policy_id = client.create_policy(
         &owner,
         &String::from_str(&env, "Life Cover"),
-        &String::from_str(&env, "life"),
+        &CoverageType::Life,
         &200,
         &100000,
+        &None,
     );
     client.create_premium_schedule(&owner, &policy_id, &next_due, &2592000);
 
     set_time(&env, next_due - 1);
-    let executed = client.execute_due_premium_schedules();
+    let executed = client.execute_due_premium_schedules(&owner).executed;
     assert_eq!(
         executed.len(),
         0,
@@ -1401,14 +1614,15 @@ fn test_time_drift_premium_schedule_executes_at_exact_next_due() {
     let policy_id = client.create_policy(
         &owner,
         &String::from_str(&env, "Health Plan"),
-        &String::from_str(&env, "health"),
+        &CoverageType::Health,
         &150,
         &75000,
+        &None,
     );
     let schedule_id = client.create_premium_schedule(&owner, &policy_id, &next_due, &2592000);
 
     set_time(&env, next_due);
-    let executed = client.execute_due_premium_schedules();
+    let executed = client.execute_due_premium_schedules(&owner).executed;
     assert_eq!(
         executed.len(),
         1,
@@ -1441,14 +1655,15 @@ fn test_time_drift_next_payment_date_uses_actual_payment_time() {
     let policy_id = client.create_policy(
         &owner,
         &String::from_str(&env, "Property Plan"),
-        &String::from_str(&env, "property"),
+        &CoverageType::Property,
         &300,
         &200000,
+        &None,
     );
     client.create_premium_schedule(&owner, &policy_id, &next_due, &2592000);
 
     set_time(&env, late_payment_time);
-    client.execute_due_premium_schedules();
+    client.execute_due_premium_schedules(&owner);
 
     let policy = client.get_policy(&policy_id).unwrap();
     assert_eq!(
@@ -1479,25 +1694,1807 @@ fn test_time_drift_no_double_execution_after_schedule_advances() {
     let policy_id = client.create_policy(
         &owner,
         &String::from_str(&env, "Auto Cover"),
-        &String::from_str(&env, "auto"),
+        &CoverageType::Auto,
         &100,
         &50000,
+        &None,
     );
     client.create_premium_schedule(&owner, &policy_id, &next_due, &interval);
 
     // First execution at next_due
     set_time(&env, next_due);
-    let executed = client.execute_due_premium_schedules();
+    let executed = client.execute_due_premium_schedules(&owner).executed;
     assert_eq!(executed.len(), 1);
 
     // Between old next_due and new next_due: no re-execution
     // NOTE: In production, ledger time is monotonic. This also covers repeated
     //       calls within the same ledger window before the next cycle.
     set_time(&env, next_due + 1000);
-    let executed_again = client.execute_due_premium_schedules();
+    let executed_again = client.execute_due_premium_schedules(&owner).executed;
     assert_eq!(
         executed_again.len(),
         0,
         "Schedule must not re-execute before the new next_due"
     );
 }
+
+// -----------------------------------------------------------------------
+// Joint (co-owned) policies
+// -----------------------------------------------------------------------
+
+fn setup_joint_policy(env: &Env, client: &InsuranceClient) -> (Address, Address, u32) {
+    let owner = Address::generate(env);
+    let co_owner = Address::generate(env);
+    let policy_id = client.create_policy(
+        &owner,
+        &String::from_str(env, "Family Health"),
+        &CoverageType::Health,
+        &100,
+        &10000,
+        &None,
+    );
+    client.add_co_owner(&owner, &policy_id, &co_owner);
+    (owner, co_owner, policy_id)
+}
+
+#[test]
+fn test_co_owner_can_pay_premium() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let (_owner, co_owner, policy_id) = setup_joint_policy(&env, &client);
+
+    client.pay_premium(&co_owner, &policy_id);
+    let policy = client.get_policy(&policy_id).unwrap();
+    assert_eq!(policy.co_owner, Some(co_owner));
+}
+
+#[test]
+fn test_co_owned_policy_in_both_owner_queries() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let (owner, co_owner, _policy_id) = setup_joint_policy(&env, &client);
+
+    assert_eq!(
+        client
+            .get_active_policies(&owner, &0, &DEFAULT_PAGE_LIMIT)
+            .count,
+        1
+    );
+    assert_eq!(
+        client
+            .get_active_policies(&co_owner, &0, &DEFAULT_PAGE_LIMIT)
+            .count,
+        1
+    );
+    assert_eq!(client.get_total_monthly_premium(&owner), 100);
+    assert_eq!(client.get_total_monthly_premium(&co_owner), 100);
+}
+
+#[test]
+fn test_deactivate_joint_policy_requires_both_signatures() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let (owner, co_owner, policy_id) = setup_joint_policy(&env, &client);
+
+    client.deactivate_policy(&owner, &policy_id, &DeactivationReason::Switching);
+
+    let auths = env.auths();
+    assert!(auths.iter().any(|(addr, _)| *addr == owner));
+    assert!(auths.iter().any(|(addr, _)| *addr == co_owner));
+    assert_eq!(client.get_total_monthly_premium(&owner), 0);
+    assert_eq!(client.get_total_monthly_premium(&co_owner), 0);
+}
+
+#[test]
+fn test_transfer_joint_policy_moves_premium_totals() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let (owner, co_owner, policy_id) = setup_joint_policy(&env, &client);
+    let new_owner = Address::generate(&env);
+
+    client.transfer_policy(&co_owner, &policy_id, &new_owner);
+
+    let policy = client.get_policy(&policy_id).unwrap();
+    assert_eq!(policy.owner, new_owner);
+    assert_eq!(policy.co_owner, Some(co_owner.clone()));
+    assert_eq!(client.get_total_monthly_premium(&owner), 0);
+    assert_eq!(client.get_total_monthly_premium(&new_owner), 100);
+    assert_eq!(client.get_total_monthly_premium(&co_owner), 100);
+}
+
+#[test]
+fn test_migrate_owner_moves_policies_and_schedules() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    client.set_pause_admin(&admin, &admin);
+
+    let (owner, co_owner, joint_id) = setup_joint_policy(&env, &client);
+    let life_id = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Life"),
+        &CoverageType::Life,
+        &50,
+        &10000,
+        &None,
+    );
+    let schedule_id = client.create_premium_schedule(&owner, &life_id, &3000, &2592000);
+    let new_owner = Address::generate(&env);
+    let policy_ids = Vec::from_array(&env, [joint_id, life_id]);
+
+    assert_eq!(
+        client.try_migrate_owner(&owner, &owner, &new_owner, &policy_ids, &None),
+        Err(Ok(InsuranceError::Unauthorized))
+    );
+    assert_eq!(
+        client.migrate_owner(&admin, &owner, &new_owner, &policy_ids, &None),
+        2
+    );
+    let auths = env.auths();
+    assert!(auths.iter().any(|(addr, _)| *addr == owner));
+    assert!(auths.iter().any(|(addr, _)| *addr == new_owner));
+
+    let joint = client.get_policy(&joint_id).unwrap();
+    assert_eq!(joint.owner, new_owner);
+    assert_eq!(joint.co_owner, Some(co_owner.clone()));
+    assert_eq!(client.get_total_monthly_premium(&owner), 0);
+    assert_eq!(client.get_active_policy_count(&owner), 0);
+    assert_eq!(client.get_total_monthly_premium(&new_owner), 150);
+    assert_eq!(client.get_active_policy_count(&new_owner), 2);
+    assert_eq!(client.get_total_monthly_premium(&co_owner), 100);
+    assert_eq!(
+        client.get_premium_schedule(&schedule_id).unwrap().owner,
+        new_owner
+    );
+    assert_eq!(client.get_premium_schedules(&owner).len(), 0);
+
+    // The co-owner's place moves the same way, with their own signature
+    let co_new = Address::generate(&env);
+    let joint_only = Vec::from_array(&env, [joint_id]);
+    client.migrate_owner(&admin, &co_owner, &co_new, &joint_only, &None);
+    assert_eq!(
+        client.get_policy(&joint_id).unwrap().co_owner,
+        Some(co_new.clone())
+    );
+    assert_eq!(client.get_total_monthly_premium(&co_new), 100);
+    assert_eq!(
+        client.try_migrate_owner(&admin, &co_new, &new_owner, &joint_only, &None),
+        Err(Ok(InsuranceError::InvalidCoOwner))
+    );
+}
+
+#[test]
+fn test_migrate_owner_with_recovery_guardian() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    let admin = Address::generate(&env);
+    client.set_pause_admin(&admin, &admin);
+
+    let owner = Address::generate(&env);
+    let policy_id = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Life"),
+        &CoverageType::Life,
+        &50,
+        &10000,
+        &None,
+    );
+    let guardian = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    let policy_ids = Vec::from_array(&env, [policy_id]);
+
+    assert_eq!(
+        client.try_migrate_owner(
+            &admin,
+            &owner,
+            &new_owner,
+            &policy_ids,
+            &Some(guardian.clone())
+        ),
+        Err(Ok(InsuranceError::Unauthorized))
+    );
+    client.set_recovery_guardians(&owner, &Vec::from_array(&env, [guardian.clone()]), &1);
+    assert_eq!(
+        client.try_migrate_owner(&admin, &owner, &new_owner, &policy_ids, &Some(stranger)),
+        Err(Ok(InsuranceError::Unauthorized))
+    );
+
+    assert_eq!(
+        client.migrate_owner(
+            &admin,
+            &owner,
+            &new_owner,
+            &policy_ids,
+            &Some(guardian.clone())
+        ),
+        1
+    );
+    // The guardian signed in place of the holder, who lost their key
+    let auths = env.auths();
+    assert!(auths.iter().any(|(addr, _)| *addr == guardian));
+    assert!(!auths.iter().any(|(addr, _)| *addr == owner));
+    assert_eq!(client.get_policy(&policy_id).unwrap().owner, new_owner);
+}
+
+#[test]
+fn test_guardian_recovery_moves_all_held_policies() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+
+    let (owner, co_owner, joint_id) = setup_joint_policy(&env, &client);
+    let life_id = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Life"),
+        &CoverageType::Life,
+        &50,
+        &10000,
+        &None,
+    );
+    let g1 = Address::generate(&env);
+    let g2 = Address::generate(&env);
+    let new_owner = Address::generate(&env);
+    client.set_recovery_guardians(&owner, &Vec::from_array(&env, [g1.clone(), g2.clone()]), &2);
+
+    assert_eq!(
+        client.try_initiate_recovery(&co_owner, &owner, &new_owner),
+        Err(Ok(InsuranceError::Unauthorized))
+    );
+    client.initiate_recovery(&g1, &owner, &new_owner);
+    assert_eq!(
+        client.try_execute_recovery(&owner),
+        Err(Ok(InsuranceError::RecoveryNotReady))
+    );
+    assert_eq!(client.approve_recovery(&g2, &owner), 2);
+
+    set_time(&env, 1_000 + remitwise_common::RECOVERY_DELAY_SECS);
+    assert_eq!(client.execute_recovery(&owner), 2);
+    assert_eq!(client.get_recovery_request(&owner), None);
+    assert_eq!(client.get_policy(&joint_id).unwrap().owner, new_owner);
+    assert_eq!(client.get_policy(&life_id).unwrap().owner, new_owner);
+    assert_eq!(client.get_total_monthly_premium(&owner), 0);
+    assert_eq!(client.get_total_monthly_premium(&new_owner), 150);
+    assert_eq!(client.get_total_monthly_premium(&co_owner), 100);
+
+    // A vetoed recovery cannot be executed
+    client.set_recovery_guardians(&co_owner, &Vec::from_array(&env, [g1.clone()]), &1);
+    client.initiate_recovery(&g1, &co_owner, &owner);
+    client.veto_recovery(&co_owner);
+    assert_eq!(
+        client.try_execute_recovery(&co_owner),
+        Err(Ok(InsuranceError::RecoveryNotFound))
+    );
+}
+
+#[test]
+fn test_add_co_owner_rejects_owner_and_non_owner() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+    let policy_id = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Life"),
+        &CoverageType::Life,
+        &50,
+        &5000,
+        &None,
+    );
+
+    let result = client.try_add_co_owner(&owner, &policy_id, &owner);
+    assert_eq!(result, Err(Ok(InsuranceError::InvalidCoOwner)));
+
+    let result = client.try_add_co_owner(&stranger, &policy_id, &stranger);
+    assert_eq!(result, Err(Ok(InsuranceError::Unauthorized)));
+}
+
+#[test]
+fn test_execution_history_records_runs() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let keeper = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+
+    let policy_id = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Health"),
+        &CoverageType::Health,
+        &100,
+        &10000,
+        &None,
+    );
+    client.create_premium_schedule(&owner, &policy_id, &3000, &2592000);
+
+    set_time(&env, 3000 + 2592000 * 2 + 1);
+    client.execute_due_premium_schedules(&keeper);
+    client.execute_due_premium_schedules(&keeper);
+
+    let history = client.get_execution_history(&0, &10);
+    assert_eq!(history.len(), 2);
+
+    let first = history.get(0).unwrap();
+    assert_eq!(first.caller, keeper);
+    assert_eq!(first.executed_count, 1);
+    assert_eq!(first.missed_total, 2);
+    assert_eq!(first.timestamp, 3000 + 2592000 * 2 + 1);
+
+    let second = history.get(1).unwrap();
+    assert_eq!(second.executed_count, 0);
+    assert_eq!(second.missed_total, 0);
+
+    assert_eq!(client.get_execution_history(&1, &10).len(), 1);
+    assert_eq!(client.get_execution_history(&5, &10).len(), 0);
+}
+
+#[test]
+fn test_execution_history_is_bounded() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let keeper = Address::generate(&env);
+
+    env.mock_all_auths();
+    env.budget().reset_unlimited();
+
+    for _ in 0..(MAX_EXECUTION_HISTORY + 5) {
+        client.execute_due_premium_schedules(&keeper);
+    }
+
+    let mut total = 0;
+    let mut offset = 0;
+    loop {
+        let page = client.get_execution_history(&offset, &MAX_PAGE_LIMIT);
+        if page.is_empty() {
+            break;
+        }
+        total += page.len();
+        offset += page.len();
+    }
+    assert_eq!(total, MAX_EXECUTION_HISTORY);
+}
+
+fn create_family_policy(env: &Env, client: &InsuranceClient, owner: &Address) -> u32 {
+    client.create_policy(
+        owner,
+        &String::from_str(env, "Family Health"),
+        &CoverageType::Health,
+        &100,
+        &10000,
+        &None,
+    )
+}
+
+fn incident(env: &Env, n: u8) -> BytesN<32> {
+    BytesN::from_array(env, &[n; 32])
+}
+
+#[test]
+fn test_add_and_remove_insured_members() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let child = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let policy_id = create_family_policy(&env, &client, &owner);
+    let spouse_id = client.add_insured_member(
+        &owner,
+        &policy_id,
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &Relationship::Spouse,
+        &None,
+        &5000,
+    );
+    let child_id = client.add_insured_member(
+        &owner,
+        &policy_id,
+        &BytesN::from_array(&env, &[2u8; 32]),
+        &Relationship::Child,
+        &Some(child.clone()),
+        &3000,
+    );
+    assert_ne!(spouse_id, child_id);
+
+    let policy = client.get_policy(&policy_id).unwrap();
+    assert_eq!(policy.members.len(), 2);
+    assert_eq!(policy.members.get(1).unwrap().address, Some(child));
+
+    client.remove_insured_member(&owner, &policy_id, &spouse_id);
+    let policy = client.get_policy(&policy_id).unwrap();
+    assert_eq!(policy.members.len(), 1);
+    assert_eq!(policy.members.get(0).unwrap().id, child_id);
+
+    let result = client.try_remove_insured_member(&owner, &policy_id, &spouse_id);
+    assert_eq!(result, Err(Ok(InsuranceError::MemberNotFound)));
+}
+
+#[test]
+fn test_add_insured_member_validation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let policy_id = create_family_policy(&env, &client, &owner);
+    let name_hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    let result = client.try_add_insured_member(
+        &owner,
+        &policy_id,
+        &name_hash,
+        &Relationship::Parent,
+        &None,
+        &20000,
+    );
+    assert_eq!(result, Err(Ok(InsuranceError::InvalidMember)));
+
+    let result = client.try_add_insured_member(
+        &stranger,
+        &policy_id,
+        &name_hash,
+        &Relationship::Parent,
+        &None,
+        &1000,
+    );
+    assert_eq!(result, Err(Ok(InsuranceError::Unauthorized)));
+}
+
+#[test]
+fn test_submit_claim_enforces_member_sub_limit() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let child = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+
+    let policy_id = create_family_policy(&env, &client, &owner);
+    let child_id = client.add_insured_member(
+        &owner,
+        &policy_id,
+        &BytesN::from_array(&env, &[2u8; 32]),
+        &Relationship::Child,
+        &Some(child.clone()),
+        &3000,
+    );
+
+    let claim_id = client.submit_claim(&child, &policy_id, &child_id, &2000, &incident(&env, 1));
+    let claim = client.get_claim(&claim_id).unwrap();
+    assert_eq!(claim.member_id, child_id);
+    assert_eq!(claim.claimant, child);
+    assert_eq!(claim.submitted_at, 1000);
+
+    let result = client.try_submit_claim(&owner, &policy_id, &child_id, &1500, &incident(&env, 2));
+    assert_eq!(result, Err(Ok(InsuranceError::SubLimitExceeded)));
+
+    let result =
+        client.try_submit_claim(&stranger, &policy_id, &child_id, &100, &incident(&env, 3));
+    assert_eq!(result, Err(Ok(InsuranceError::Unauthorized)));
+
+    client.submit_claim(&owner, &policy_id, &child_id, &1000, &incident(&env, 4));
+    let policy = client.get_policy(&policy_id).unwrap();
+    assert_eq!(policy.members.get(0).unwrap().claimed, 3000);
+    assert_eq!(client.get_policy_claims(&policy_id).len(), 2);
+}
+
+#[test]
+fn test_claims_cannot_exceed_policy_coverage() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let policy_id = create_family_policy(&env, &client, &owner);
+    let first = client.add_insured_member(
+        &owner,
+        &policy_id,
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &Relationship::Primary,
+        &None,
+        &8000,
+    );
+    let second = client.add_insured_member(
+        &owner,
+        &policy_id,
+        &BytesN::from_array(&env, &[2u8; 32]),
+        &Relationship::Spouse,
+        &None,
+        &8000,
+    );
+
+    client.submit_claim(&owner, &policy_id, &first, &7000, &incident(&env, 1));
+    let result = client.try_submit_claim(&owner, &policy_id, &second, &4000, &incident(&env, 2));
+    assert_eq!(result, Err(Ok(InsuranceError::SubLimitExceeded)));
+    client.submit_claim(&owner, &policy_id, &second, &3000, &incident(&env, 3));
+}
+
+#[test]
+fn test_removed_member_claims_still_count_against_coverage() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let name_hash = BytesN::from_array(&env, &[1u8; 32]);
+
+    env.mock_all_auths();
+
+    let policy_id = create_family_policy(&env, &client, &owner);
+    let member = client.add_insured_member(
+        &owner,
+        &policy_id,
+        &name_hash,
+        &Relationship::Spouse,
+        &None,
+        &8000,
+    );
+    client.submit_claim(&owner, &policy_id, &member, &8000, &incident(&env, 1));
+
+    // Re-adding the member gives a fresh sub-limit, but not fresh coverage
+    client.remove_insured_member(&owner, &policy_id, &member);
+    let readded = client.add_insured_member(
+        &owner,
+        &policy_id,
+        &name_hash,
+        &Relationship::Spouse,
+        &None,
+        &8000,
+    );
+    let result = client.try_submit_claim(&owner, &policy_id, &readded, &8000, &incident(&env, 2));
+    assert_eq!(result, Err(Ok(InsuranceError::SubLimitExceeded)));
+
+    client.submit_claim(&owner, &policy_id, &readded, &2000, &incident(&env, 3));
+    assert_eq!(client.get_policy(&policy_id).unwrap().total_claimed, 10000);
+}
+
+#[test]
+fn test_claims_rejected_during_waiting_period() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+
+    client.set_pause_admin(&admin, &admin);
+    client.set_waiting_period(&admin, &CoverageType::Health, &(30 * 86400));
+    assert_eq!(client.get_waiting_period(&CoverageType::Health), 30 * 86400);
+    assert_eq!(client.get_waiting_period(&CoverageType::Life), 0);
+
+    let policy_id = create_family_policy(&env, &client, &owner);
+    let member_id = client.add_insured_member(
+        &owner,
+        &policy_id,
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &Relationship::Primary,
+        &None,
+        &5000,
+    );
+
+    let unlock_at = client.get_claims_unlock_at(&policy_id).unwrap();
+    assert_eq!(unlock_at, 1000 + 30 * 86400);
+
+    set_time(&env, unlock_at - 1);
+    let result = client.try_submit_claim(&owner, &policy_id, &member_id, &100, &incident(&env, 1));
+    assert_eq!(result, Err(Ok(InsuranceError::WaitingPeriodActive)));
+
+    set_time(&env, unlock_at);
+    client.submit_claim(&owner, &policy_id, &member_id, &100, &incident(&env, 2));
+}
+
+#[test]
+fn test_set_waiting_period_requires_admin() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    env.mock_all_auths();
+
+    let result = client.try_set_waiting_period(&stranger, &CoverageType::Health, &86400);
+    assert_eq!(result, Err(Ok(InsuranceError::Unauthorized)));
+
+    client.set_pause_admin(&admin, &admin);
+    let result = client.try_set_waiting_period(&stranger, &CoverageType::Health, &86400);
+    assert_eq!(result, Err(Ok(InsuranceError::Unauthorized)));
+}
+
+#[test]
+fn test_max_coverage_ratio_rejects_underpriced_policies() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let name = String::from_str(&env, "Cheap Cover");
+
+    env.mock_all_auths();
+    client.set_pause_admin(&admin, &admin);
+    assert_eq!(
+        client.try_set_max_coverage_ratio(&owner, &CoverageType::Health, &Some(500)),
+        Err(Ok(InsuranceError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_set_max_coverage_ratio(&admin, &CoverageType::Health, &Some(0)),
+        Err(Ok(InsuranceError::InvalidAmount))
+    );
+    client.set_max_coverage_ratio(&admin, &CoverageType::Health, &Some(500));
+    assert_eq!(
+        client.get_max_coverage_ratio(&CoverageType::Health),
+        Some(500)
+    );
+    assert_eq!(client.get_max_coverage_ratio(&CoverageType::Life), None);
+
+    // 1 stroop a month cannot buy 1M of cover
+    let result =
+        client.try_create_policy(&owner, &name, &CoverageType::Health, &1, &1_000_000, &None);
+    assert_eq!(result, Err(Ok(InsuranceError::CoverageRatioExceeded)));
+
+    // Exactly at the cap is allowed; other coverage types are uncapped
+    client.create_policy(
+        &owner,
+        &name,
+        &CoverageType::Health,
+        &2_000,
+        &1_000_000,
+        &None,
+    );
+    client.create_policy(&owner, &name, &CoverageType::Life, &1, &1_000_000, &None);
+
+    client.set_max_coverage_ratio(&admin, &CoverageType::Health, &None);
+    client.create_policy(&owner, &name, &CoverageType::Health, &1, &1_000_000, &None);
+}
+
+/// Register a stake token, set it on the contract and mint `amount` to each
+/// backer.
+fn setup_stake_token(
+    env: &Env,
+    client: &InsuranceClient,
+    admin: &Address,
+    backers: &[&Address],
+    amount: i128,
+) -> TokenClient<'static> {
+    let token = env.register_stellar_asset_contract_v2(Address::generate(env));
+    for backer in backers {
+        StellarAssetClient::new(env, &token.address()).mint(backer, &amount);
+    }
+    client.set_stake_token(admin, &token.address());
+    TokenClient::new(env, &token.address())
+}
+
+#[test]
+fn test_staking_pool_shares_premiums_and_losses() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let backer_a = Address::generate(&env);
+    let backer_b = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+
+    client.set_pause_admin(&admin, &admin);
+    let token = setup_stake_token(&env, &client, &admin, &[&backer_a, &backer_b], 5000);
+    client.configure_staking(
+        &admin,
+        &StakingConfig {
+            lockup_secs: 86400,
+            premium_share_bps: 2000,
+        },
+    );
+    assert_eq!(client.deposit_stake(&backer_a, &1000), 1000);
+    assert_eq!(client.deposit_stake(&backer_b, &3000), 3000);
+    assert_eq!(token.balance(&contract_id), 4000);
+    assert_eq!(token.balance(&backer_a), 4000);
+
+    let policy_id = create_family_policy(&env, &client, &owner);
+    client.pay_premium(&owner, &policy_id);
+    let pool = client.get_staking_pool();
+    assert_eq!(pool.premium_income, 20);
+    assert_eq!(pool.total_capital, 4020);
+    let info_a = client.get_staker_info(&backer_a).unwrap();
+    assert_eq!(info_a.value, 1005);
+    assert_eq!(info_a.profit, 5);
+
+    let member_id = client.add_insured_member(
+        &owner,
+        &policy_id,
+        &BytesN::from_array(&env, &[1u8; 32]),
+        &Relationship::Primary,
+        &None,
+        &5000,
+    );
+    let claim_id = client.submit_claim(&owner, &policy_id, &member_id, &400, &incident(&env, 1));
+    // The pool is only charged once the claim is approved
+    assert_eq!(client.get_staking_pool().losses_absorbed, 0);
+    client.approve_claim(&admin, &claim_id, &String::from_str(&env, "ok"));
+    assert_eq!(client.get_claim(&claim_id).unwrap().pool_charge, 400);
+
+    // The loss comes out of the capital, pro rata by shares
+    let pool = client.get_staking_pool();
+    assert_eq!(pool.total_capital, 3620);
+    assert_eq!(pool.losses_absorbed, 400);
+    let info_a = client.get_staker_info(&backer_a).unwrap();
+    assert_eq!(info_a.value, 905);
+    assert_eq!(info_a.profit, -95);
+    let info_b = client.get_staker_info(&backer_b).unwrap();
+    assert_eq!(info_b.value, 2715);
+    assert_eq!(info_b.profit, -285);
+
+    let result = client.try_withdraw_stake(&backer_a, &905);
+    assert_eq!(result, Err(Ok(InsuranceError::StakeLocked)));
+
+    set_time(&env, 1000 + 86400);
+    let result = client.try_withdraw_stake(&backer_a, &1000);
+    assert_eq!(result, Err(Ok(InsuranceError::InsufficientStake)));
+    client.withdraw_stake(&backer_a, &905);
+    let info_a = client.get_staker_info(&backer_a).unwrap();
+    assert_eq!(info_a.shares, 0);
+    assert_eq!(info_a.withdrawn, 905);
+    assert_eq!(info_a.profit, -95);
+    assert_eq!(token.balance(&backer_a), 4905);
+    assert_eq!(token.balance(&contract_id), 3095);
+    assert_eq!(client.get_staking_pool().total_capital, 2715);
+
+    // A later deposit does not share in earnings booked before it
+    client.deposit_stake(&backer_a, &1000);
+    client.pay_premium(&owner, &policy_id);
+    assert_eq!(client.get_staker_info(&backer_a).unwrap().profit, -90);
+    assert_eq!(client.get_staker_info(&backer_b).unwrap().profit, -270);
+
+    // Premium income can be claimed without touching the stake
+    assert_eq!(client.claim_stake_earnings(&backer_a), 5);
+    let info_a = client.get_staker_info(&backer_a).unwrap();
+    assert_eq!(info_a.value, 1000);
+    assert_eq!(info_a.profit, -90);
+    assert_eq!(token.balance(&backer_a), 3910);
+    let result = client.try_claim_stake_earnings(&backer_a);
+    assert_eq!(result, Err(Ok(InsuranceError::InvalidAmount)));
+
+    // The last staker takes out their stake less their share of the loss
+    let result = client.try_withdraw_stake(&backer_b, &2731);
+    assert_eq!(result, Err(Ok(InsuranceError::InsufficientStake)));
+    client.withdraw_stake(&backer_b, &2730);
+    assert_eq!(token.balance(&backer_b), 4730);
+    assert_eq!(client.get_staking_pool().total_capital, 1000);
+    // What the pool charged for the claim stays in the contract
+    assert_eq!(token.balance(&contract_id), 1360);
+}
+
+#[test]
+fn test_configure_staking_validation() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.set_pause_admin(&admin, &admin);
+
+    let config = StakingConfig {
+        lockup_secs: 0,
+        premium_share_bps: 10_001,
+    };
+    let result = client.try_configure_staking(&admin, &config);
+    assert_eq!(result, Err(Ok(InsuranceError::InvalidAmount)));
+
+    let config = StakingConfig {
+        lockup_secs: 0,
+        premium_share_bps: 500,
+    };
+    let result = client.try_configure_staking(&stranger, &config);
+    assert_eq!(result, Err(Ok(InsuranceError::Unauthorized)));
+
+    let result = client.try_deposit_stake(&stranger, &0);
+    assert_eq!(result, Err(Ok(InsuranceError::InvalidAmount)));
+    let result = client.try_deposit_stake(&stranger, &100);
+    assert_eq!(result, Err(Ok(InsuranceError::StakeTokenNotSet)));
+    assert_eq!(client.get_staker_info(&stranger), None);
+}
+
+#[test]
+fn test_stake_token_locked_while_shares_outstanding() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let backer = Address::generate(&env);
+
+    env.mock_all_auths();
+    client.set_pause_admin(&admin, &admin);
+    setup_stake_token(&env, &client, &admin, &[&backer], 1000);
+    client.deposit_stake(&backer, &1000);
+
+    let other = env.register_stellar_asset_contract_v2(Address::generate(&env));
+    let result = client.try_set_stake_token(&admin, &other.address());
+    assert_eq!(result, Err(Ok(InsuranceError::StakeTokenInUse)));
+
+    client.withdraw_stake(&backer, &1000);
+    client.set_stake_token(&admin, &other.address());
+    assert_eq!(client.get_stake_token(), Some(other.address()));
+}
+
+#[test]
+fn test_batch_pay_premiums_partial_reports_per_policy_results() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let stranger = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+
+    let good = create_family_policy(&env, &client, &owner);
+    let inactive = create_family_policy(&env, &client, &owner);
+    let foreign = create_family_policy(&env, &client, &stranger);
+    client.deactivate_policy(&owner, &inactive, &DeactivationReason::Switching);
+
+    set_time(&env, 5000);
+    let ids = Vec::from_array(&env, [good, inactive, foreign, 99]);
+    let results = client.batch_pay_premiums_partial(&owner, &ids);
+
+    assert_eq!(results.len(), 4);
+    assert_eq!(
+        results.get(0).unwrap(),
+        PremiumPaymentResult {
+            policy_id: good,
+            paid: true,
+            error_code: 0,
+        }
+    );
+    assert_eq!(
+        results.get(1).unwrap().error_code,
+        InsuranceError::PolicyInactive as u32
+    );
+    assert_eq!(
+        results.get(2).unwrap().error_code,
+        InsuranceError::Unauthorized as u32
+    );
+    assert_eq!(
+        results.get(3).unwrap().error_code,
+        InsuranceError::PolicyNotFound as u32
+    );
+    assert!(!results.get(3).unwrap().paid);
+
+    // Only the valid premium was applied
+    assert_eq!(
+        client.get_policy(&good).unwrap().next_payment_date,
+        5000 + 30 * 86400
+    );
+    assert_eq!(
+        client.get_policy(&foreign).unwrap().next_payment_date,
+        1000 + 30 * 86400
+    );
+}
+
+#[test]
+fn test_active_policy_count_tracks_lifecycle() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let (owner, co_owner, joint) = setup_joint_policy(&env, &client);
+    let solo = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Life"),
+        &CoverageType::Life,
+        &50,
+        &5000,
+        &None,
+    );
+    assert_eq!(client.get_active_policy_count(&owner), 2);
+    assert_eq!(client.get_active_policy_count(&co_owner), 1);
+
+    let new_owner = Address::generate(&env);
+    client.transfer_policy(&owner, &solo, &new_owner);
+    assert_eq!(client.get_active_policy_count(&owner), 1);
+    assert_eq!(client.get_active_policy_count(&new_owner), 1);
+
+    client.deactivate_policy(&owner, &joint, &DeactivationReason::Switching);
+    assert_eq!(client.get_active_policy_count(&owner), 0);
+    assert_eq!(client.get_active_policy_count(&co_owner), 0);
+    assert_eq!(
+        client.get_active_policy_count(&new_owner),
+        client
+            .get_active_policies(&new_owner, &0, &DEFAULT_PAGE_LIMIT)
+            .count
+    );
+}
+
+#[test]
+fn test_claim_evidence_and_review_trail() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let child = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.set_pause_admin(&admin, &admin);
+
+    let policy_id = create_family_policy(&env, &client, &owner);
+    let child_id = client.add_insured_member(
+        &owner,
+        &policy_id,
+        &BytesN::from_array(&env, &[2u8; 32]),
+        &Relationship::Child,
+        &Some(child.clone()),
+        &3000,
+    );
+    let claim_id = client.submit_claim(&child, &policy_id, &child_id, &2000, &incident(&env, 1));
+    assert_eq!(
+        client.get_claim(&claim_id).unwrap().status,
+        ClaimStatus::Pending
+    );
+
+    let receipt = BytesN::from_array(&env, &[7u8; 32]);
+    let pdf = String::from_str(&env, "application/pdf");
+    assert_eq!(
+        client.add_claim_evidence(&child, &claim_id, &receipt, &pdf),
+        1
+    );
+    let photo = BytesN::from_array(&env, &[8u8; 32]);
+    let jpeg = String::from_str(&env, "image/jpeg");
+    assert_eq!(
+        client.add_claim_evidence(&owner, &claim_id, &photo, &jpeg),
+        2
+    );
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_add_claim_evidence(&stranger, &claim_id, &photo, &jpeg),
+        Err(Ok(InsuranceError::Unauthorized))
+    );
+
+    let note = String::from_str(&env, "Requested hospital invoice");
+    client.comment_on_claim(&admin, &claim_id, &note);
+    let verdict = String::from_str(&env, "Invoice matches receipt");
+    assert_eq!(
+        client.try_approve_claim(&owner, &claim_id, &verdict),
+        Err(Ok(InsuranceError::Unauthorized))
+    );
+    set_time(&env, 2000);
+    client.approve_claim(&admin, &claim_id, &verdict);
+
+    let detail = client.get_claim_detail(&claim_id).unwrap();
+    assert_eq!(detail.claim.status, ClaimStatus::Approved);
+    assert_eq!(detail.evidence.len(), 2);
+    assert_eq!(detail.evidence.get(0).unwrap().hash, receipt);
+    assert_eq!(detail.evidence.get(1).unwrap().added_by, owner);
+    assert_eq!(detail.reviews.len(), 2);
+    assert_eq!(
+        detail.reviews.get(0).unwrap().decision,
+        ClaimStatus::Pending
+    );
+    let decision = detail.reviews.get(1).unwrap();
+    assert_eq!(decision.decision, ClaimStatus::Approved);
+    assert_eq!(decision.comment, verdict);
+    assert_eq!(decision.reviewed_at, 2000);
+    // Each claim keeps its evidence and reviews in its own entries
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        assert!(storage.has(&DataKey::ClaimEvidence(claim_id)));
+        assert!(storage.has(&DataKey::ClaimReviews(claim_id)));
+    });
+
+    // Decided claims are closed to new evidence and reviews
+    assert_eq!(
+        client.try_add_claim_evidence(&child, &claim_id, &photo, &jpeg),
+        Err(Ok(InsuranceError::ClaimNotPending))
+    );
+    assert_eq!(
+        client.try_reject_claim(&admin, &claim_id, &verdict),
+        Err(Ok(InsuranceError::ClaimNotPending))
+    );
+    assert_eq!(client.get_claim_detail(&999), None);
+}
+
+#[test]
+fn test_rejected_claim_releases_member_sub_limit() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.set_pause_admin(&admin, &admin);
+
+    let policy_id = create_family_policy(&env, &client, &owner);
+    let member_id = client.add_insured_member(
+        &owner,
+        &policy_id,
+        &BytesN::from_array(&env, &[3u8; 32]),
+        &Relationship::Spouse,
+        &None,
+        &3000,
+    );
+    let claim_id = client.submit_claim(&owner, &policy_id, &member_id, &2500, &incident(&env, 1));
+    assert_eq!(
+        client.try_submit_claim(&owner, &policy_id, &member_id, &1000, &incident(&env, 2)),
+        Err(Ok(InsuranceError::SubLimitExceeded))
+    );
+
+    client.reject_claim(&admin, &claim_id, &String::from_str(&env, "Not covered"));
+    assert_eq!(
+        client.get_claim(&claim_id).unwrap().status,
+        ClaimStatus::Rejected
+    );
+    client.submit_claim(&owner, &policy_id, &member_id, &1000, &incident(&env, 3));
+}
+
+#[test]
+fn test_duplicate_incident_needs_admin_override() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let admin = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.set_pause_admin(&admin, &admin);
+
+    // The same accident claimed under a health and an auto policy
+    let health_id = create_family_policy(&env, &client, &owner);
+    let auto_id = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Car"),
+        &CoverageType::Auto,
+        &100,
+        &10000,
+        &None,
+    );
+    let mut claims = Vec::new(&env);
+    for policy_id in [health_id, auto_id] {
+        let member_id = client.add_insured_member(
+            &owner,
+            &policy_id,
+            &BytesN::from_array(&env, &[4u8; 32]),
+            &Relationship::Spouse,
+            &None,
+            &3000,
+        );
+        claims.push_back(client.submit_claim(
+            &owner,
+            &policy_id,
+            &member_id,
+            &1000,
+            &incident(&env, 7),
+        ));
+    }
+    let (first, second) = (claims.get(0).unwrap(), claims.get(1).unwrap());
+    let comment = String::from_str(&env, "Covered");
+
+    client.approve_claim(&admin, &first, &comment);
+    assert_eq!(
+        client.get_incident_claim(&owner, &incident(&env, 7)),
+        Some(first)
+    );
+    assert_eq!(
+        client.try_approve_claim(&admin, &second, &comment),
+        Err(Ok(InsuranceError::DuplicateIncident))
+    );
+    assert_eq!(
+        client.get_claim(&second).unwrap().status,
+        ClaimStatus::Pending
+    );
+
+    assert_eq!(
+        client.try_allow_duplicate_incident(&owner, &second),
+        Err(Ok(InsuranceError::Unauthorized))
+    );
+    client.allow_duplicate_incident(&admin, &second);
+    client.approve_claim(&admin, &second, &comment);
+    assert_eq!(
+        client.get_claim(&second).unwrap().status,
+        ClaimStatus::Approved
+    );
+    assert_eq!(
+        client.get_incident_claim(&owner, &incident(&env, 7)),
+        Some(first)
+    );
+    assert_eq!(
+        client.get_incident_claim(&Address::generate(&env), &incident(&env, 7)),
+        None
+    );
+}
+
+#[test]
+fn test_survivor_claim_pays_beneficiaries_and_closes_policy() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let admin = Address::generate(&env);
+    let verifier = Address::generate(&env);
+    let spouse = Address::generate(&env);
+    let child = Address::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+    client.set_pause_admin(&admin, &admin);
+    client.set_claim_verifier(&admin, &verifier, &true);
+
+    let health_id = create_family_policy(&env, &client, &owner);
+    let policy_id = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Term Life"),
+        &CoverageType::Life,
+        &100,
+        &10001,
+        &None,
+    );
+    let mut beneficiaries = Vec::new(&env);
+    beneficiaries.push_back(Beneficiary {
+        address: spouse.clone(),
+        percent: 60,
+    });
+    beneficiaries.push_back(Beneficiary {
+        address: child.clone(),
+        percent: 30,
+    });
+    assert_eq!(
+        client.try_set_beneficiaries(&owner, &policy_id, &beneficiaries),
+        Err(Ok(InsuranceError::InvalidBeneficiaries))
+    );
+    assert_eq!(
+        client.try_set_beneficiaries(&owner, &health_id, &beneficiaries),
+        Err(Ok(InsuranceError::NotLifePolicy))
+    );
+    beneficiaries.set(
+        1,
+        Beneficiary {
+            address: child.clone(),
+            percent: 40,
+        },
+    );
+    client.set_beneficiaries(&owner, &policy_id, &beneficiaries);
+
+    let certificate = BytesN::from_array(&env, &[9u8; 32]);
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_file_survivor_claim(&stranger, &policy_id, &certificate),
+        Err(Ok(InsuranceError::Unauthorized))
+    );
+    client.file_survivor_claim(&child, &policy_id, &certificate);
+    assert_eq!(
+        client.try_file_survivor_claim(&spouse, &policy_id, &certificate),
+        Err(Ok(InsuranceError::SurvivorClaimExists))
+    );
+
+    // A rejected claim can be filed again
+    client.reject_survivor_claim(&verifier, &policy_id);
+    let claim_id = client.file_survivor_claim(&spouse, &policy_id, &certificate);
+    assert_eq!(
+        client.try_approve_survivor_claim(&admin, &policy_id),
+        Err(Ok(InsuranceError::Unauthorized))
+    );
+
+    let payouts = client.approve_survivor_claim(&verifier, &policy_id);
+    assert_eq!(payouts.len(), 2);
+    assert_eq!(payouts.get(0).unwrap().beneficiary, spouse);
+    assert_eq!(payouts.get(0).unwrap().amount, 6001);
+    assert_eq!(payouts.get(1).unwrap().amount, 4000);
+
+    let claim = client.get_survivor_claim(&policy_id).unwrap();
+    assert_eq!(claim.id, claim_id);
+    assert_eq!(claim.status, ClaimStatus::Approved);
+    assert_eq!(claim.decided_by, Some(verifier.clone()));
+    assert!(!client.get_policy(&policy_id).unwrap().active);
+    let closure = client.get_deactivation(&policy_id).unwrap();
+    assert_eq!(closure.reason, DeactivationReason::Deceased);
+    assert_eq!(closure.refund_due, 0);
+    assert_eq!(
+        client.try_approve_survivor_claim(&verifier, &policy_id),
+        Err(Ok(InsuranceError::ClaimNotPending))
+    );
+}
+
+#[test]
+fn test_total_monthly_premium_in_display_asset() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let owner = Address::generate(&env);
+    client.create_policy(
+        &owner,
+        &String::from_str(&env, "Health"),
+        &CoverageType::Health,
+        &6,
+        &5000,
+        &None,
+    );
+    client.create_policy(
+        &owner,
+        &String::from_str(&env, "Life"),
+        &CoverageType::Life,
+        &3,
+        &5000,
+        &None,
+    );
+    let ngn = String::from_str(&env, "NGN");
+    assert_eq!(
+        client.try_get_total_monthly_premium_in(&owner, &ngn),
+        Err(Ok(InsuranceError::OracleNotConfigured))
+    );
+
+    let admin = Address::generate(&env);
+    client.set_pause_admin(&admin, &admin);
+    let oracle = env.register_contract(None, MockPriceOracle);
+    // 1 NGN = 0.0006 token units; anything else is unquoted
+    MockPriceOracleClient::new(&env, &oracle).set_rate(&ngn, &6_000);
+    assert_eq!(
+        client.try_set_price_oracle(&owner, &oracle),
+        Err(Ok(InsuranceError::Unauthorized))
+    );
+    client.set_price_oracle(&admin, &oracle);
+    assert_eq!(client.get_price_oracle(), Some(oracle));
+
+    // 9 token units at 0.0006 per NGN
+    assert_eq!(client.get_total_monthly_premium_in(&owner, &ngn), 15_000);
+    assert_eq!(
+        client.try_get_total_monthly_premium_in(&owner, &String::from_str(&env, "KES")),
+        Err(Ok(InsuranceError::RateUnavailable))
+    );
+}
+
+#[test]
+fn test_deactivation_in_cooling_off_refunds_premiums() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+
+    let owner = Address::generate(&env);
+    let early = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Health"),
+        &CoverageType::Health,
+        &100,
+        &5000,
+        &None,
+    );
+    let late = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Life"),
+        &CoverageType::Life,
+        &40,
+        &5000,
+        &None,
+    );
+    client.pay_premium(&owner, &early);
+    client.pay_premium(&owner, &early);
+    client.pay_premium(&owner, &late);
+    assert_eq!(client.get_premiums_paid(&early), 200);
+
+    set_time(&env, 1_000 + COOLING_OFF_SECS - 1);
+    client.deactivate_policy(&owner, &early, &DeactivationReason::Dissatisfaction);
+    assert_eq!(
+        client.get_deactivation(&early),
+        Some(PolicyDeactivation {
+            reason: DeactivationReason::Dissatisfaction,
+            deactivated_by: owner.clone(),
+            deactivated_at: 1_000 + COOLING_OFF_SECS - 1,
+            refund_due: 200,
+        })
+    );
+
+    // Once the window has closed, premiums are no longer refunded
+    set_time(&env, 1_000 + COOLING_OFF_SECS);
+    client.deactivate_policy(&owner, &late, &DeactivationReason::Affordability);
+    let record = client.get_deactivation(&late).unwrap();
+    assert_eq!(record.reason, DeactivationReason::Affordability);
+    assert_eq!(record.refund_due, 0);
+}
+
+#[test]
+fn test_premium_paid_total_by_month() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let owner = Address::generate(&env);
+    let (may_1, may_10, jun_3, jul_1) =
+        (1_714_521_600, 1_715_299_200, 1_717_372_800, 1_719_792_000);
+    set_time(&env, may_10);
+    let health = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Health"),
+        &CoverageType::Health,
+        &100,
+        &5000,
+        &None,
+    );
+    let life = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Life"),
+        &CoverageType::Life,
+        &40,
+        &5000,
+        &None,
+    );
+    client.pay_premium(&owner, &health);
+    client.pay_premium(&owner, &life);
+    set_time(&env, jun_3);
+    client.pay_premium(&owner, &health);
+
+    // May only, given as any range inside the month
+    assert_eq!(
+        client.get_premium_paid_total(&owner, &may_10, &(may_10 + 60)),
+        140
+    );
+    assert_eq!(
+        client.get_premium_paid_total(&owner, &may_1, &(jul_1 - 1)),
+        240
+    );
+    assert_eq!(
+        client.get_premium_paid_total(&owner, &jul_1, &(jul_1 + 86400)),
+        0
+    );
+    assert_eq!(client.get_premium_paid_total(&owner, &jun_3, &may_1), 0);
+    assert_eq!(
+        client.get_premium_paid_total(&Address::generate(&env), &may_1, &jul_1),
+        0
+    );
+    // Both tallies are persistent entries of their own
+    env.as_contract(&contract_id, || {
+        let storage = env.storage().persistent();
+        assert_eq!(storage.get(&DataKey::PremPaid(health)), Some(200i128));
+        assert!(storage.has(&DataKey::PremMonth(owner.clone())));
+        assert!(!env.storage().instance().has(&symbol_short!("PRM_MON")));
+    });
+}
+
+#[test]
+fn test_premium_holiday_skips_schedule_runs_without_missing() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+
+    let owner = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    let policy_id = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Health"),
+        &CoverageType::Health,
+        &100,
+        &5000,
+        &None,
+    );
+    let schedule_id =
+        client.create_premium_schedule(&owner, &policy_id, &2_000, &PREMIUM_CYCLE_SECS);
+    client.set_holiday_guardian(&owner, &policy_id, &Some(guardian.clone()));
+
+    let result = client.try_declare_premium_holiday(&owner, &policy_id, &(MAX_HOLIDAY_CYCLES + 1));
+    assert_eq!(result, Err(Ok(InsuranceError::InvalidAmount)));
+    let holiday = client.declare_premium_holiday(&owner, &policy_id, &2);
+    assert_eq!(holiday.end, 1_000 + 2 * PREMIUM_CYCLE_SECS);
+    assert_eq!(holiday.approved_by, Some(guardian));
+    assert!(client.is_coverage_reduced(&policy_id));
+    assert_eq!(
+        client.get_policy(&policy_id).unwrap().next_payment_date,
+        holiday.end
+    );
+    let result = client.try_declare_premium_holiday(&owner, &policy_id, &1);
+    assert_eq!(result, Err(Ok(InsuranceError::HolidayActive)));
+
+    // Both runs inside the holiday are skipped, not missed
+    set_time(&env, 2_000 + PREMIUM_CYCLE_SECS + 10);
+    assert_eq!(
+        client.execute_due_premium_schedules(&owner).executed.len(),
+        0
+    );
+    let schedule = client.get_premium_schedule(&schedule_id).unwrap();
+    assert_eq!(schedule.missed_count, 0);
+    assert_eq!(schedule.next_due, 2_000 + 2 * PREMIUM_CYCLE_SECS);
+    assert_eq!(client.get_premiums_paid(&policy_id), 0);
+
+    // The first run after the holiday is collected again
+    set_time(&env, 2_000 + 2 * PREMIUM_CYCLE_SECS);
+    assert_eq!(
+        client.execute_due_premium_schedules(&owner).executed.len(),
+        1
+    );
+    assert!(!client.is_coverage_reduced(&policy_id));
+    assert_eq!(client.get_premium_holiday(&policy_id), None);
+    assert_eq!(client.get_premiums_paid(&policy_id), 100);
+    assert_eq!(
+        client
+            .get_premium_schedule(&schedule_id)
+            .unwrap()
+            .missed_count,
+        0
+    );
+}
+
+#[test]
+fn test_underwritten_policy_priced_by_risk_tier_and_retiered() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    client.set_pause_admin(&admin, &admin);
+    let result = client.try_set_tier_rate(&owner, &CoverageType::Health, &RiskTier::Low, &100);
+    assert_eq!(result, Err(Ok(InsuranceError::Unauthorized)));
+    client.set_tier_rate(&admin, &CoverageType::Health, &RiskTier::Standard, &200);
+    client.set_tier_rate(&admin, &CoverageType::Health, &RiskTier::High, &500);
+
+    assert_eq!(
+        client.estimate_premium(&CoverageType::Health, &RiskTier::High, &10_000),
+        500
+    );
+    let result = client.try_estimate_premium(&CoverageType::Health, &RiskTier::Low, &10_000);
+    assert_eq!(result, Err(Ok(InsuranceError::TierRateNotSet)));
+
+    let hash = BytesN::from_array(&env, &[7u8; 32]);
+    let policy_id = client.create_underwritten_policy(
+        &owner,
+        &admin,
+        &String::from_str(&env, "Health"),
+        &CoverageType::Health,
+        &10_000,
+        &None,
+        &hash,
+        &RiskTier::Standard,
+    );
+    assert_eq!(client.get_policy(&policy_id).unwrap().monthly_premium, 200);
+    let record = client.get_underwriting(&policy_id).unwrap();
+    assert_eq!(record.questionnaire_hash, hash);
+    assert_eq!(record.assessed_by, admin);
+    assert_eq!(client.get_risk_tier(&policy_id), Some(RiskTier::Standard));
+
+    let result = client.try_retier_policy(&admin, &policy_id, &RiskTier::High, &999);
+    assert_eq!(result, Err(Ok(InsuranceError::InvalidTimestamp)));
+    client.retier_policy(&admin, &policy_id, &RiskTier::High, &5_000);
+    assert_eq!(client.get_risk_tier(&policy_id), Some(RiskTier::Standard));
+    set_time(&env, 5_000);
+    assert_eq!(client.get_risk_tier(&policy_id), Some(RiskTier::High));
+    let record = client.get_underwriting(&policy_id).unwrap();
+    assert_eq!(record.previous_tier, RiskTier::Standard);
+    assert_eq!(record.effective_at, 5_000);
+}
+
+mod pricing {
+    use remitwise_common::CoverageType;
+    use soroban_sdk::{contract, contractimpl, Env};
+
+    use crate::RiskTier;
+
+    /// Charges 1% of the coverage a month per risk tier step, and cannot
+    /// price coverage above 1,000,000
+    #[contract]
+    pub struct MockPricing;
+
+    #[contractimpl]
+    impl MockPricing {
+        pub fn price(
+            _env: Env,
+            _coverage_type: CoverageType,
+            coverage_amount: i128,
+            risk_tier: RiskTier,
+        ) -> i128 {
+            if coverage_amount > 1_000_000 {
+                return 0;
+            }
+            coverage_amount * risk_tier as i128 / 100
+        }
+    }
+}
+
+#[test]
+fn test_pricing_contract_quotes_and_floors_premiums() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    client.set_pause_admin(&admin, &admin);
+    client.set_tier_rate(&admin, &CoverageType::Health, &RiskTier::High, &500);
+    let pricing_id = env.register_contract(None, pricing::MockPricing);
+    let result = client.try_set_pricing_contract(&owner, &Some(pricing_id.clone()));
+    assert_eq!(result, Err(Ok(InsuranceError::Unauthorized)));
+    client.set_pricing_contract(&admin, &Some(pricing_id.clone()));
+    assert_eq!(client.get_pricing_contract(), Some(pricing_id));
+
+    // The pricing contract replaces the rate table
+    assert_eq!(
+        client.estimate_premium(&CoverageType::Health, &RiskTier::High, &10_000),
+        400
+    );
+    let result = client.try_estimate_premium(&CoverageType::Health, &RiskTier::Low, &2_000_000);
+    assert_eq!(result, Err(Ok(InsuranceError::RateUnavailable)));
+
+    // Owner-chosen premiums may not undercut the Low-tier quote
+    let name = String::from_str(&env, "Health");
+    let result =
+        client.try_create_policy(&owner, &name, &CoverageType::Health, &99, &10_000, &None);
+    assert_eq!(result, Err(Ok(InsuranceError::PremiumBelowQuote)));
+    client.create_policy(&owner, &name, &CoverageType::Health, &100, &10_000, &None);
+
+    client.set_pricing_contract(&admin, &None);
+    assert_eq!(
+        client.estimate_premium(&CoverageType::Health, &RiskTier::High, &10_000),
+        500
+    );
+}
+
+mod savings_goals {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Map};
+
+    /// Goal owners and balances without locks
+    #[contract]
+    pub struct MockSavingsGoals;
+
+    #[contractimpl]
+    impl MockSavingsGoals {
+        pub fn set_goal(env: Env, goal_id: u32, owner: Address, amount: i128) {
+            let mut goals = Self::goals(&env);
+            goals.set(goal_id, (owner, amount));
+            env.storage()
+                .instance()
+                .set(&symbol_short!("GOALS"), &goals);
+        }
+
+        pub fn withdraw_premium(
+            env: Env,
+            payer: Address,
+            owner: Address,
+            goal_id: u32,
+            amount: i128,
+        ) -> i128 {
+            payer.require_auth();
+            let (goal_owner, balance) = Self::goals(&env).get(goal_id).unwrap();
+            if goal_owner != owner {
+                panic!("Unauthorized");
+            }
+            if amount > balance {
+                panic!("Insufficient balance");
+            }
+            Self::set_goal(env, goal_id, owner, balance - amount);
+            balance - amount
+        }
+
+        pub fn get_balance(env: Env, goal_id: u32) -> i128 {
+            Self::goals(&env)
+                .get(goal_id)
+                .map_or(0, |(_, amount)| amount)
+        }
+
+        fn goals(env: &Env) -> Map<u32, (Address, i128)> {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("GOALS"))
+                .unwrap_or_else(|| Map::new(env))
+        }
+    }
+}
+
+#[test]
+fn test_premium_schedule_funded_from_savings_goal() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let policy_id = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Health"),
+        &CoverageType::Health,
+        &100,
+        &5000,
+        &None,
+    );
+    let schedule_id =
+        client.create_premium_schedule(&owner, &policy_id, &2_000, &PREMIUM_CYCLE_SECS);
+    let result = client.try_set_schedule_funding_goal(&owner, &schedule_id, &Some(7));
+    assert_eq!(result, Err(Ok(InsuranceError::SavingsNotConfigured)));
+
+    let savings_id = env.register_contract(None, savings_goals::MockSavingsGoals);
+    let savings = savings_goals::MockSavingsGoalsClient::new(&env, &savings_id);
+    client.set_pause_admin(&admin, &admin);
+    client.set_savings_contract(&admin, &savings_id);
+    client.set_schedule_funding_goal(&owner, &schedule_id, &Some(7));
+    assert_eq!(client.get_schedule_funding_goal(&schedule_id), Some(7));
+    savings.set_goal(&7, &owner, &150);
+
+    set_time(&env, 2_000);
+    assert_eq!(
+        client.execute_due_premium_schedules(&owner).executed.len(),
+        1
+    );
+    assert_eq!(savings.get_balance(&7), 50);
+    assert_eq!(client.get_premiums_paid(&policy_id), 100);
+
+    // The goal cannot cover the next premium
+    set_time(&env, 2_000 + PREMIUM_CYCLE_SECS);
+    client.execute_due_premium_schedules(&owner);
+    assert_eq!(savings.get_balance(&7), 50);
+    assert_eq!(client.get_premiums_paid(&policy_id), 100);
+    let schedule = client.get_premium_schedule(&schedule_id).unwrap();
+    assert_eq!(schedule.missed_count, 1);
+    assert_eq!(schedule.next_due, 2_000 + 2 * PREMIUM_CYCLE_SECS);
+
+    // Another account's goal does not pay the owner's premium
+    let other = Address::generate(&env);
+    savings.set_goal(&8, &other, &1_000);
+    client.set_schedule_funding_goal(&owner, &schedule_id, &Some(8));
+    set_time(&env, 2_000 + 2 * PREMIUM_CYCLE_SECS);
+    client.execute_due_premium_schedules(&owner);
+    assert_eq!(savings.get_balance(&8), 1_000);
+    assert_eq!(client.get_premiums_paid(&policy_id), 100);
+    assert_eq!(
+        client
+            .get_premium_schedule(&schedule_id)
+            .unwrap()
+            .missed_count,
+        2
+    );
+}
+
+#[test]
+fn test_premium_totals_migrate_out_of_legacy_map() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let idle = Address::generate(&env);
+    let others = [Address::generate(&env), Address::generate(&env)];
+
+    env.mock_all_auths();
+    client.set_pause_admin(&admin, &admin);
+    // Totals as written before they were sharded
+    env.as_contract(&contract_id, || {
+        let mut totals: Map<Address, i128> = Map::new(&env);
+        totals.set(owner.clone(), 300);
+        totals.set(idle.clone(), 50);
+        for other in others.iter() {
+            totals.set(other.clone(), 10);
+        }
+        env.storage()
+            .instance()
+            .set(&STORAGE_PREMIUM_TOTALS, &totals);
+    });
+    assert_eq!(client.get_total_monthly_premium(&owner), 300);
+
+    // A change to the total moves it to the owner's own entry
+    client.create_policy(
+        &owner,
+        &String::from_str(&env, "Life"),
+        &CoverageType::Life,
+        &100,
+        &10_000,
+        &None,
+    );
+    assert_eq!(client.get_total_monthly_premium(&owner), 400);
+    env.as_contract(&contract_id, || {
+        let key = DataKey::PremTotal(owner.clone());
+        assert_eq!(env.storage().persistent().get::<_, i128>(&key), Some(400));
+        let totals: Map<Address, i128> = env
+            .storage()
+            .instance()
+            .get(&STORAGE_PREMIUM_TOTALS)
+            .unwrap();
+        assert_eq!(totals.len(), 3);
+        assert!(!totals.contains_key(owner.clone()));
+    });
+
+    // The admin drains the rest in batches, then the map is gone
+    assert_eq!(
+        client.try_migrate_premium_totals(&owner, &10),
+        Err(Ok(InsuranceError::Unauthorized))
+    );
+    assert_eq!(client.migrate_premium_totals(&admin, &2), 1);
+    assert_eq!(client.migrate_premium_totals(&admin, &2), 0);
+    assert_eq!(client.migrate_premium_totals(&admin, &2), 0);
+    env.as_contract(&contract_id, || {
+        assert!(!env.storage().instance().has(&STORAGE_PREMIUM_TOTALS));
+    });
+    assert_eq!(client.get_total_monthly_premium(&idle), 50);
+    assert_eq!(client.get_total_monthly_premium(&others[1]), 10);
+    assert_eq!(client.get_total_monthly_premium(&owner), 400);
+}
+
+#[test]
+fn test_emergency_pause_all_reported_by_pause_state() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    env.mock_all_auths();
+
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    client.set_pause_admin(&admin, &admin);
+    let policy_id = client.create_policy(
+        &owner,
+        &String::from_str(&env, "Health"),
+        &CoverageType::Health,
+        &100,
+        &5000,
+        &None,
+    );
+    let state = client.get_pause_state();
+    assert!(!state.paused);
+    assert_eq!(state.paused_functions.len(), 0);
+
+    assert_eq!(
+        client.try_emergency_pause_all(&owner),
+        Err(Ok(InsuranceError::Unauthorized))
+    );
+    client.emergency_pause_all(&admin);
+    let state = client.get_pause_state();
+    assert!(state.paused);
+    assert_eq!(state.paused_functions.len(), 7);
+    assert_eq!(state.pause_admin, Some(admin.clone()));
+
+    // Unpausing the contract leaves each function paused
+    client.unpause(&admin);
+    assert_eq!(
+        client.try_pay_premium(&owner, &policy_id),
+        Err(Ok(InsuranceError::FunctionPaused))
+    );
+    client.unpause_function(&admin, &pause_functions::PAY_PREMIUM);
+    client.pay_premium(&owner, &policy_id);
+    assert_eq!(client.get_pause_state().paused_functions.len(), 6);
+}
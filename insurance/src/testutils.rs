@@ -0,0 +1,30 @@
+//! Setup helpers for tests of this contract and of crates built on it,
+//! enabled by the `testutils` feature.
+
+use crate::{Insurance, InsuranceClient};
+use remitwise_common::CoverageType;
+use soroban_sdk::{Address, Env, String};
+
+/// Register the contract
+pub fn register(env: &Env) -> InsuranceClient<'_> {
+    let contract_id = env.register_contract(None, Insurance);
+    InsuranceClient::new(env, &contract_id)
+}
+
+/// Create an active health policy for `owner`. Auths must be mocked.
+pub fn health_policy(
+    env: &Env,
+    client: &InsuranceClient,
+    owner: &Address,
+    monthly_premium: i128,
+    coverage_amount: i128,
+) -> u32 {
+    client.create_policy(
+        owner,
+        &String::from_str(env, "Health"),
+        &CoverageType::Health,
+        &monthly_premium,
+        &coverage_amount,
+        &None,
+    )
+}
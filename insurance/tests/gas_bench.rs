@@ -47,7 +47,7 @@ fn bench_get_total_monthly_premium_worst_case() {
     let name = String::from_str(&env, "BenchPolicy");
     let coverage_type = CoverageType::Health;
     for _ in 0..100 {
-        client.create_policy(&owner, &name, &coverage_type, &100i128, &10_000i128);
+        client.create_policy(&owner, &name, &coverage_type, &100i128, &10_000i128, &None);
     }
 
     let expected_total = 100i128 * 100i128;
@@ -17,7 +17,8 @@
 //!   DEFAULT_PAGE_LIMIT          = 20
 //!   MAX_BATCH_SIZE              = 50
 
-use insurance::{Insurance, InsuranceClient};
+use insurance::{DeactivationReason, Insurance, InsuranceClient};
+use remitwise_common::CoverageType;
 use soroban_sdk::testutils::storage::Instance as _;
 use soroban_sdk::testutils::{Address as AddressTrait, EnvTestConfig, Ledger, LedgerInfo};
 use soroban_sdk::{Address, Env, String};
@@ -73,10 +74,10 @@ fn stress_200_policies_single_user() {
     let owner = Address::generate(&env);
 
     let name = String::from_str(&env, "StressPolicy");
-    let coverage_type = String::from_str(&env, "health");
+    let coverage_type = CoverageType::Health;
 
     for _ in 0..200 {
-        client.create_policy(&owner, &name, &coverage_type, &100i128, &10_000i128);
+        client.create_policy(&owner, &name, &coverage_type, &100i128, &10_000i128, &None);
     }
 
     // Verify aggregate monthly premium
@@ -106,11 +107,13 @@ fn stress_200_policies_single_user() {
         cursor = page.next_cursor;
     }
 
-    assert_eq!(collected, 200, "Pagination must return all 200 active policies");
-    // get_active_policies sets next_cursor = last_returned_id; when a page is exactly
-    // full the caller receives a non-zero cursor that produces a trailing empty page,
-    // so the round-trip count is pages = ceil(200/50) + 1 trailing = 5.
-    assert!(pages >= 4 && pages <= 5, "Expected 4-5 pages for 200 policies at limit 50, got {}", pages);
+    assert_eq!(
+        collected, 200,
+        "Pagination must return all 200 active policies"
+    );
+    // get_active_policies only returns a non-zero next_cursor when more policies
+    // remain, so there is no trailing empty page: pages = ceil(200/50) = 4.
+    assert_eq!(pages, 4, "Expected 4 pages for 200 policies at limit 50");
 }
 
 /// Create 200 policies and verify instance TTL remains valid after the instance
@@ -123,10 +126,10 @@ fn stress_instance_ttl_valid_after_200_policies() {
     let owner = Address::generate(&env);
 
     let name = String::from_str(&env, "TTLPolicy");
-    let coverage_type = String::from_str(&env, "life");
+    let coverage_type = CoverageType::Life;
 
     for _ in 0..200 {
-        client.create_policy(&owner, &name, &coverage_type, &50i128, &5_000i128);
+        client.create_policy(&owner, &name, &coverage_type, &50i128, &5_000i128, &None);
     }
 
     let ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
@@ -153,7 +156,7 @@ fn stress_policies_across_10_users() {
     const POLICIES_PER_USER: u32 = 20;
     const PREMIUM_PER_POLICY: i128 = 150;
     let name = String::from_str(&env, "UserPolicy");
-    let coverage_type = String::from_str(&env, "health");
+    let coverage_type = CoverageType::Health;
 
     let users: std::vec::Vec<Address> = (0..N_USERS).map(|_| Address::generate(&env)).collect();
 
@@ -165,6 +168,7 @@ fn stress_policies_across_10_users() {
                 &coverage_type,
                 &PREMIUM_PER_POLICY,
                 &50_000i128,
+                &None,
             );
         }
     }
@@ -214,11 +218,11 @@ fn stress_ttl_re_bumped_after_ledger_advancement() {
     let owner = Address::generate(&env);
 
     let name = String::from_str(&env, "TTLStress");
-    let coverage_type = String::from_str(&env, "health");
+    let coverage_type = CoverageType::Health;
 
     // Phase 1: 50 creates
     for _ in 0..50 {
-        client.create_policy(&owner, &name, &coverage_type, &100i128, &10_000i128);
+        client.create_policy(&owner, &name, &coverage_type, &100i128, &10_000i128, &None);
     }
 
     let ttl_batch1 = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
@@ -249,7 +253,7 @@ fn stress_ttl_re_bumped_after_ledger_advancement() {
     );
 
     // Phase 3: create_policy fires extend_ttl → re-bumped
-    client.create_policy(&owner, &name, &coverage_type, &100i128, &10_000i128);
+    client.create_policy(&owner, &name, &coverage_type, &100i128, &10_000i128, &None);
 
     let ttl_rebumped = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
     assert!(
@@ -270,9 +274,10 @@ fn stress_ttl_re_bumped_by_pay_premium_after_ledger_advancement() {
     let policy_id = client.create_policy(
         &owner,
         &String::from_str(&env, "PayTTL"),
-        &String::from_str(&env, "health"),
+        &CoverageType::Health,
         &200i128,
         &20_000i128,
+        &None,
     );
 
     // Advance ledger so TTL drops below threshold
@@ -288,8 +293,7 @@ fn stress_ttl_re_bumped_by_pay_premium_after_ledger_advancement() {
     });
 
     // pay_premium must re-bump TTL
-    let paid = client.pay_premium(&owner, &policy_id);
-    assert!(paid, "pay_premium must succeed");
+    client.pay_premium(&owner, &policy_id);
 
     let ttl = env.as_contract(&contract_id, || env.storage().instance().get_ttl());
     assert!(
@@ -314,11 +318,11 @@ fn stress_batch_pay_premiums_at_max_batch_size() {
 
     const BATCH_SIZE: u32 = 50; // MAX_BATCH_SIZE
     let name = String::from_str(&env, "BatchPolicy");
-    let coverage_type = String::from_str(&env, "health");
+    let coverage_type = CoverageType::Health;
 
     let mut policy_ids = std::vec![];
     for _ in 0..BATCH_SIZE {
-        let id = client.create_policy(&owner, &name, &coverage_type, &100i128, &10_000i128);
+        let id = client.create_policy(&owner, &name, &coverage_type, &100i128, &10_000i128, &None);
         policy_ids.push(id);
     }
 
@@ -365,15 +369,15 @@ fn stress_deactivate_half_of_200_policies() {
     let owner = Address::generate(&env);
 
     let name = String::from_str(&env, "DeactPolicy");
-    let coverage_type = String::from_str(&env, "life");
+    let coverage_type = CoverageType::Life;
 
     for _ in 0..200 {
-        client.create_policy(&owner, &name, &coverage_type, &80i128, &8_000i128);
+        client.create_policy(&owner, &name, &coverage_type, &80i128, &8_000i128, &None);
     }
 
     // Deactivate even-numbered policies (IDs 2, 4, 6, …, 200)
     for id in (2u32..=200).step_by(2) {
-        client.deactivate_policy(&owner, &id);
+        client.deactivate_policy(&owner, &id, &DeactivationReason::Switching);
     }
 
     // get_active_policies must return only the 100 remaining active ones
@@ -415,10 +419,10 @@ fn bench_get_active_policies_first_page_of_200() {
     let owner = Address::generate(&env);
 
     let name = String::from_str(&env, "BenchPolicy");
-    let coverage_type = String::from_str(&env, "health");
+    let coverage_type = CoverageType::Health;
 
     for _ in 0..200 {
-        client.create_policy(&owner, &name, &coverage_type, &100i128, &10_000i128);
+        client.create_policy(&owner, &name, &coverage_type, &100i128, &10_000i128, &None);
     }
 
     let (cpu, mem, page) = measure(&env, || client.get_active_policies(&owner, &0u32, &50u32));
@@ -439,10 +443,10 @@ fn bench_get_total_monthly_premium_200_policies() {
     let owner = Address::generate(&env);
 
     let name = String::from_str(&env, "PremBench");
-    let coverage_type = String::from_str(&env, "health");
+    let coverage_type = CoverageType::Health;
 
     for _ in 0..200 {
-        client.create_policy(&owner, &name, &coverage_type, &100i128, &10_000i128);
+        client.create_policy(&owner, &name, &coverage_type, &100i128, &10_000i128, &None);
     }
 
     let expected = 200i128 * 100;
@@ -464,11 +468,11 @@ fn bench_batch_pay_premiums_50_policies() {
     let owner = Address::generate(&env);
 
     let name = String::from_str(&env, "BatchBench");
-    let coverage_type = String::from_str(&env, "health");
+    let coverage_type = CoverageType::Health;
 
     let mut policy_ids = std::vec![];
     for _ in 0..50 {
-        let id = client.create_policy(&owner, &name, &coverage_type, &100i128, &10_000i128);
+        let id = client.create_policy(&owner, &name, &coverage_type, &100i128, &10_000i128, &None);
         policy_ids.push(id);
     }
 
@@ -0,0 +1,124 @@
+#![cfg(test)]
+
+use insurance::{DeactivationReason, Insurance, InsuranceClient, InsuranceEvent};
+use remitwise_common::CoverageType;
+use soroban_sdk::testutils::Address as _;
+use soroban_sdk::{
+    symbol_short, testutils::Events, Address, Env, String, Symbol, TryFromVal, Val, Vec,
+};
+
+/// Find the standard `Remitwise` event with the given action among the events
+/// of the last invocation and return its (category, priority).
+fn find_standard_event(e: &Env, action: Symbol) -> Option<(u32, u32)> {
+    for (_, topics, _) in e.events().all().iter() {
+        if topics.len() != 4 {
+            continue;
+        }
+        let namespace = Symbol::try_from_val(e, &topics.get(0).unwrap());
+        let event_action = Symbol::try_from_val(e, &topics.get(3).unwrap());
+        if namespace == Ok(symbol_short!("Remitwise")) && event_action == Ok(action.clone()) {
+            let category = u32::try_from_val(e, &topics.get(1).unwrap()).unwrap();
+            let priority = u32::try_from_val(e, &topics.get(2).unwrap()).unwrap();
+            return Some((category, priority));
+        }
+    }
+    None
+}
+
+/// `event_seq` of every standard event of the last invocation, in order
+fn standard_event_seqs(e: &Env) -> std::vec::Vec<u64> {
+    let mut seqs = std::vec::Vec::new();
+    for (_, topics, data) in e.events().all().iter() {
+        let namespace = Symbol::try_from_val(e, &topics.get(0).unwrap());
+        if topics.len() == 4 && namespace == Ok(symbol_short!("Remitwise")) {
+            let payload = Vec::<Val>::try_from_val(e, &data).unwrap();
+            seqs.push(u64::try_from_val(e, &payload.get(0).unwrap()).unwrap());
+        }
+    }
+    seqs
+}
+
+fn has_legacy_event(e: &Env, event: InsuranceEvent) -> bool {
+    let expected: Vec<Val> = soroban_sdk::vec![
+        e,
+        symbol_short!("insure").to_val(),
+        soroban_sdk::IntoVal::into_val(&event, e),
+    ];
+    e.events()
+        .all()
+        .iter()
+        .any(|(_, topics, _)| topics.len() == 2 && topics == expected)
+}
+
+#[test]
+fn test_lifecycle_events_use_standard_schema() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    e.mock_all_auths();
+
+    let policy_id = client.create_policy(
+        &owner,
+        &String::from_str(&e, "Health"),
+        &CoverageType::Health,
+        &100,
+        &10_000,
+        &None,
+    );
+    // Category: State (1), Priority: Medium (1)
+    assert_eq!(
+        find_standard_event(&e, symbol_short!("created")),
+        Some((1, 1))
+    );
+    assert!(has_legacy_event(&e, InsuranceEvent::PolicyCreated));
+
+    client.pay_premium(&owner, &policy_id);
+    // Category: Transaction (0), Priority: High (2)
+    assert_eq!(
+        find_standard_event(&e, symbol_short!("prem_paid")),
+        Some((0, 2))
+    );
+    assert!(has_legacy_event(&e, InsuranceEvent::PremiumPaid));
+
+    client.deactivate_policy(&owner, &policy_id, &DeactivationReason::Switching);
+    assert_eq!(
+        find_standard_event(&e, symbol_short!("deactive")),
+        Some((1, 1))
+    );
+    assert!(has_legacy_event(&e, InsuranceEvent::PolicyDeactivated));
+}
+
+#[test]
+fn test_standard_events_carry_sequence_numbers() {
+    let e = Env::default();
+    let contract_id = e.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&e, &contract_id);
+    let owner = Address::generate(&e);
+    e.mock_all_auths();
+    assert_eq!(client.get_event_seq(), 0);
+
+    let policy_id = client.create_policy(
+        &owner,
+        &String::from_str(&e, "Health"),
+        &CoverageType::Health,
+        &100,
+        &10_000,
+        &None,
+    );
+    assert_eq!(standard_event_seqs(&e), [1]);
+
+    // The second policy, then one premium event per policy plus the batch
+    // summary, numbered in order after the first
+    let second_id = client.create_policy(
+        &owner,
+        &String::from_str(&e, "Life"),
+        &CoverageType::Life,
+        &50,
+        &5_000,
+        &None,
+    );
+    client.batch_pay_premiums(&owner, &soroban_sdk::vec![&e, policy_id, second_id]);
+    assert_eq!(standard_event_seqs(&e), [1, 2, 3, 4, 5]);
+    assert_eq!(client.get_event_seq(), 5);
+}
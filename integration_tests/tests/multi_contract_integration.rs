@@ -88,7 +88,7 @@ fn test_multi_contract_user_flow() {
 
     // Step 5: Calculate split for a remittance amount
     let total_remittance = 10_000i128;
-    let amounts = remittance_client.calculate_split(&total_remittance);
+    let amounts = remittance_client.calculate_split(&user, &total_remittance);
     assert_eq!(amounts.len(), 4, "Should have 4 allocation amounts");
 
     // Extract amounts
@@ -149,7 +149,7 @@ fn test_split_with_rounding() {
 
     // Calculate split for an amount that will have rounding
     let total = 1_000i128;
-    let amounts = remittance_client.calculate_split(&total);
+    let amounts = remittance_client.calculate_split(&user, &total);
 
     let spending = amounts.get(0).unwrap();
     let savings = amounts.get(1).unwrap();
@@ -95,9 +95,10 @@ pub trait FamilyWalletTrait {
 /// Gas estimation: ~3000 gas per split calculation
 #[contractclient(name = "RemittanceSplitClient")]
 pub trait RemittanceSplitTrait {
-    /// Calculate split amounts from a total remittance amount
+    /// Calculate split amounts from a total remittance amount using `owner`'s split
     ///
     /// # Arguments
+    /// * `owner` - The user whose split configuration applies
     /// * `total_amount` - The total amount to split (must be positive)
     ///
     /// # Returns
@@ -105,7 +106,7 @@ pub trait RemittanceSplitTrait {
     ///
     /// # Gas Estimation
     /// ~3000 gas
-    fn calculate_split(env: Env, total_amount: i128) -> Vec<i128>;
+    fn calculate_split(env: Env, owner: Address, total_amount: i128) -> Vec<i128>;
 }
 
 /// Savings Goals contract client interface
@@ -391,6 +392,7 @@ impl Orchestrator {
     /// # Arguments
     /// * `env` - The contract environment
     /// * `remittance_split_addr` - Address of the Remittance Split contract
+    /// * `owner` - User whose split configuration is applied
     /// * `total_amount` - Total remittance amount to split (must be positive)
     ///
     /// # Returns
@@ -408,6 +410,7 @@ impl Orchestrator {
     fn extract_allocations(
         env: &Env,
         remittance_split_addr: &Address,
+        owner: &Address,
         total_amount: i128,
     ) -> Result<Vec<i128>, OrchestratorError> {
         // Validate amount is positive
@@ -421,7 +424,7 @@ impl Orchestrator {
         // Gas estimation: ~3000 gas
         // Call the remittance split contract to calculate allocations
         // This returns Vec<i128> with [spending, savings, bills, insurance]
-        let allocations = split_client.calculate_split(&total_amount);
+        let allocations = split_client.calculate_split(owner, &total_amount);
 
         Ok(allocations)
     }
@@ -978,11 +981,18 @@ impl Orchestrator {
         )?;
 
         // Step 4: Extract allocations from remittance split
-        let allocations = Self::extract_allocations(&env, &remittance_split_addr, total_amount)
-            .map_err(|e| {
-                Self::emit_error_event(&env, &caller, symbol_short!("split"), e as u32, timestamp);
-                e
-            })?;
+        let allocations =
+            Self::extract_allocations(&env, &remittance_split_addr, &caller, total_amount)
+                .map_err(|e| {
+                    Self::emit_error_event(
+                        &env,
+                        &caller,
+                        symbol_short!("split"),
+                        e as u32,
+                        timestamp,
+                    );
+                    e
+                })?;
 
         // Extract individual amounts
         let spending_amount = allocations.get(0).unwrap_or(0);
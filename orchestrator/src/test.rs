@@ -28,7 +28,7 @@ pub struct MockRemittanceSplit;
 impl MockRemittanceSplit {
     /// Mock implementation of calculate_split
     /// Returns [40%, 30%, 20%, 10%] split
-    pub fn calculate_split(env: Env, total_amount: i128) -> Vec<i128> {
+    pub fn calculate_split(env: Env, _owner: Address, total_amount: i128) -> Vec<i128> {
         let spending = (total_amount * 40) / 100;
         let savings = (total_amount * 30) / 100;
         let bills = (total_amount * 20) / 100;
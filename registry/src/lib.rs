@@ -0,0 +1,318 @@
+#![no_std]
+//! # Registry Contract
+//!
+//! Canonical on-chain directory of RemitWise deployment addresses.
+//!
+//! Each entry is keyed by a network symbol (e.g. `testnet`, `mainnet`) and a
+//! contract name (e.g. `split`, `bills`, `goals`, `insurance`). Only the admin
+//! can publish or remove entries; anyone can resolve them. Every update bumps
+//! the entry's version and the previous entry is kept in a bounded history so
+//! callers can audit redeployments.
+
+use soroban_sdk::{
+    contract, contracterror, contractimpl, contracttype, symbol_short, Address, Env, Map, Symbol,
+    Vec,
+};
+
+// Storage TTL constants
+const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
+const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
+
+const KEY_ADMIN: Symbol = symbol_short!("ADMIN");
+const KEY_ENTRIES: Symbol = symbol_short!("ENTRIES");
+const KEY_HISTORY: Symbol = symbol_short!("HISTORY");
+
+/// Maximum number of superseded entries kept per (network, name)
+pub const MAX_HISTORY_ENTRIES: u32 = 10;
+
+/// Well-known contract names used by the RemitWise tooling
+pub mod names {
+    use soroban_sdk::{symbol_short, Symbol};
+
+    pub const SPLIT: Symbol = symbol_short!("split");
+    pub const BILLS: Symbol = symbol_short!("bills");
+    pub const GOALS: Symbol = symbol_short!("goals");
+    pub const INSURANCE: Symbol = symbol_short!("insurance");
+    pub const FAMILY: Symbol = symbol_short!("family");
+    pub const REPORTING: Symbol = symbol_short!("reporting");
+    pub const ORCHESTRATOR: Symbol = symbol_short!("orch");
+}
+
+#[contracterror]
+#[derive(Copy, Clone, Debug, Eq, PartialEq, PartialOrd, Ord)]
+#[repr(u32)]
+pub enum RegistryError {
+    AlreadyInitialized = 1,
+    NotInitialized = 2,
+    Unauthorized = 3,
+    EntryNotFound = 4,
+}
+
+/// Storage key for a single registry entry
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistryKey {
+    pub network: Symbol,
+    pub name: Symbol,
+}
+
+/// A versioned deployment address
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RegistryEntry {
+    pub network: Symbol,
+    pub name: Symbol,
+    pub address: Address,
+    pub version: u32,
+    pub updated_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RegistryEvent {
+    Initialized,
+    AdminChanged,
+    AddressSet,
+    AddressRemoved,
+}
+
+#[contract]
+pub struct RegistryContract;
+
+#[contractimpl]
+impl RegistryContract {
+    /// Initialize the registry with an admin address.
+    ///
+    /// # Errors
+    /// * `AlreadyInitialized` - If the registry has already been initialized
+    pub fn init(env: Env, admin: Address) -> Result<(), RegistryError> {
+        admin.require_auth();
+
+        if env.storage().instance().has(&KEY_ADMIN) {
+            return Err(RegistryError::AlreadyInitialized);
+        }
+
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(&KEY_ADMIN, &admin);
+
+        env.events().publish(
+            (symbol_short!("registry"), RegistryEvent::Initialized),
+            admin,
+        );
+
+        Ok(())
+    }
+
+    /// Hand the admin role over to a new address (current admin only).
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the registry has not been initialized
+    /// * `Unauthorized` - If caller is not the admin
+    pub fn set_admin(env: Env, caller: Address, new_admin: Address) -> Result<(), RegistryError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(&KEY_ADMIN, &new_admin);
+
+        env.events().publish(
+            (symbol_short!("registry"), RegistryEvent::AdminChanged),
+            (caller, new_admin),
+        );
+
+        Ok(())
+    }
+
+    /// Publish the address of `name` on `network` (admin only).
+    ///
+    /// Overwriting an existing entry bumps its version and moves the previous
+    /// entry into the history. A removed entry that is published again
+    /// continues from the version it was removed at.
+    ///
+    /// # Returns
+    /// The version number of the new entry (starting at 1)
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the registry has not been initialized
+    /// * `Unauthorized` - If caller is not the admin
+    pub fn set_address(
+        env: Env,
+        caller: Address,
+        network: Symbol,
+        name: Symbol,
+        address: Address,
+    ) -> Result<u32, RegistryError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        let key = RegistryKey {
+            network: network.clone(),
+            name: name.clone(),
+        };
+        let mut entries: Map<RegistryKey, RegistryEntry> = env
+            .storage()
+            .instance()
+            .get(&KEY_ENTRIES)
+            .unwrap_or_else(|| Map::new(&env));
+
+        let version = match entries.get(key.clone()) {
+            Some(previous) => {
+                let version = previous.version + 1;
+                Self::push_history(&env, &key, previous);
+                version
+            }
+            None => Self::get_history(env.clone(), key.network.clone(), key.name.clone())
+                .last()
+                .map_or(1, |removed| removed.version + 1),
+        };
+
+        let entry = RegistryEntry {
+            network,
+            name,
+            address,
+            version,
+            updated_at: env.ledger().timestamp(),
+        };
+        entries.set(key, entry.clone());
+
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(&KEY_ENTRIES, &entries);
+
+        env.events().publish(
+            (symbol_short!("registry"), RegistryEvent::AddressSet),
+            entry,
+        );
+
+        Ok(version)
+    }
+
+    /// Remove the entry for `name` on `network` (admin only).
+    ///
+    /// The removed entry is kept in the history.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the registry has not been initialized
+    /// * `Unauthorized` - If caller is not the admin
+    /// * `EntryNotFound` - If no entry exists for the key
+    pub fn remove_address(
+        env: Env,
+        caller: Address,
+        network: Symbol,
+        name: Symbol,
+    ) -> Result<(), RegistryError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        let key = RegistryKey { network, name };
+        let mut entries: Map<RegistryKey, RegistryEntry> = env
+            .storage()
+            .instance()
+            .get(&KEY_ENTRIES)
+            .unwrap_or_else(|| Map::new(&env));
+
+        let previous = entries
+            .get(key.clone())
+            .ok_or(RegistryError::EntryNotFound)?;
+        entries.remove(key.clone());
+        Self::push_history(&env, &key, previous);
+
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(&KEY_ENTRIES, &entries);
+
+        env.events().publish(
+            (symbol_short!("registry"), RegistryEvent::AddressRemoved),
+            key,
+        );
+
+        Ok(())
+    }
+
+    /// Resolve the current address of `name` on `network`.
+    pub fn get_address(env: Env, network: Symbol, name: Symbol) -> Option<Address> {
+        Self::get_entry(env, network, name).map(|entry| entry.address)
+    }
+
+    /// Get the current versioned entry of `name` on `network`.
+    pub fn get_entry(env: Env, network: Symbol, name: Symbol) -> Option<RegistryEntry> {
+        let entries: Map<RegistryKey, RegistryEntry> = env
+            .storage()
+            .instance()
+            .get(&KEY_ENTRIES)
+            .unwrap_or_else(|| Map::new(&env));
+        entries.get(RegistryKey { network, name })
+    }
+
+    /// Get all current entries published for `network`.
+    pub fn get_entries(env: Env, network: Symbol) -> Vec<RegistryEntry> {
+        let entries: Map<RegistryKey, RegistryEntry> = env
+            .storage()
+            .instance()
+            .get(&KEY_ENTRIES)
+            .unwrap_or_else(|| Map::new(&env));
+
+        let mut result = Vec::new(&env);
+        for (key, entry) in entries.iter() {
+            if key.network == network {
+                result.push_back(entry);
+            }
+        }
+        result
+    }
+
+    /// Get superseded entries of `name` on `network`, oldest first.
+    pub fn get_history(env: Env, network: Symbol, name: Symbol) -> Vec<RegistryEntry> {
+        let history: Map<RegistryKey, Vec<RegistryEntry>> = env
+            .storage()
+            .instance()
+            .get(&KEY_HISTORY)
+            .unwrap_or_else(|| Map::new(&env));
+        history
+            .get(RegistryKey { network, name })
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Get the admin address
+    pub fn get_admin(env: Env) -> Option<Address> {
+        env.storage().instance().get(&KEY_ADMIN)
+    }
+
+    // -----------------------------------------------------------------------
+    // Internal helpers
+    // -----------------------------------------------------------------------
+
+    fn require_admin(env: &Env, caller: &Address) -> Result<(), RegistryError> {
+        let admin: Address = env
+            .storage()
+            .instance()
+            .get(&KEY_ADMIN)
+            .ok_or(RegistryError::NotInitialized)?;
+        if *caller != admin {
+            return Err(RegistryError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn push_history(env: &Env, key: &RegistryKey, entry: RegistryEntry) {
+        let mut history: Map<RegistryKey, Vec<RegistryEntry>> = env
+            .storage()
+            .instance()
+            .get(&KEY_HISTORY)
+            .unwrap_or_else(|| Map::new(env));
+        let mut entries = history.get(key.clone()).unwrap_or_else(|| Vec::new(env));
+        if entries.len() >= MAX_HISTORY_ENTRIES {
+            entries.pop_front();
+        }
+        entries.push_back(entry);
+        history.set(key.clone(), entries);
+        env.storage().instance().set(&KEY_HISTORY, &history);
+    }
+
+    fn extend_instance_ttl(env: &Env) {
+        env.storage()
+            .instance()
+            .extend_ttl(INSTANCE_LIFETIME_THRESHOLD, INSTANCE_BUMP_AMOUNT);
+    }
+}
+
+#[cfg(test)]
+mod test;
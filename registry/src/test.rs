@@ -0,0 +1,156 @@
+use super::*;
+use soroban_sdk::testutils::{Address as _, Ledger};
+use soroban_sdk::{symbol_short, Address, Env};
+
+fn setup() -> (Env, RegistryContractClient<'static>, Address) {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RegistryContract);
+    let client = RegistryContractClient::new(&env, &contract_id);
+    let admin = Address::generate(&env);
+    client.init(&admin);
+    (env, client, admin)
+}
+
+#[test]
+fn test_init_twice_fails() {
+    let (_env, client, admin) = setup();
+    let result = client.try_init(&admin);
+    assert_eq!(result, Err(Ok(RegistryError::AlreadyInitialized)));
+}
+
+#[test]
+fn test_set_and_resolve_address() {
+    let (env, client, admin) = setup();
+    let network = symbol_short!("testnet");
+    let split = Address::generate(&env);
+
+    let version = client.set_address(&admin, &network, &names::SPLIT, &split);
+    assert_eq!(version, 1);
+    assert_eq!(client.get_address(&network, &names::SPLIT), Some(split));
+    assert_eq!(client.get_address(&network, &names::BILLS), None);
+    assert_eq!(
+        client.get_address(&symbol_short!("mainnet"), &names::SPLIT),
+        None
+    );
+}
+
+#[test]
+fn test_update_bumps_version_and_keeps_history() {
+    let (env, client, admin) = setup();
+    let network = symbol_short!("testnet");
+    let first = Address::generate(&env);
+    let second = Address::generate(&env);
+
+    client.set_address(&admin, &network, &names::INSURANCE, &first);
+    env.ledger().with_mut(|li| li.timestamp = 5_000);
+    let version = client.set_address(&admin, &network, &names::INSURANCE, &second);
+
+    assert_eq!(version, 2);
+    let entry = client.get_entry(&network, &names::INSURANCE).unwrap();
+    assert_eq!(entry.address, second);
+    assert_eq!(entry.version, 2);
+    assert_eq!(entry.updated_at, 5_000);
+
+    let history = client.get_history(&network, &names::INSURANCE);
+    assert_eq!(history.len(), 1);
+    assert_eq!(history.get(0).unwrap().address, first);
+    assert_eq!(history.get(0).unwrap().version, 1);
+}
+
+#[test]
+fn test_history_is_bounded() {
+    let (env, client, admin) = setup();
+    let network = symbol_short!("testnet");
+
+    for _ in 0..(MAX_HISTORY_ENTRIES + 3) {
+        client.set_address(&admin, &network, &names::GOALS, &Address::generate(&env));
+    }
+
+    let history = client.get_history(&network, &names::GOALS);
+    assert_eq!(history.len(), MAX_HISTORY_ENTRIES);
+    assert_eq!(history.get(0).unwrap().version, 3);
+}
+
+#[test]
+fn test_non_admin_cannot_set_address() {
+    let (env, client, _admin) = setup();
+    let stranger = Address::generate(&env);
+    let result = client.try_set_address(
+        &stranger,
+        &symbol_short!("testnet"),
+        &names::BILLS,
+        &Address::generate(&env),
+    );
+    assert_eq!(result, Err(Ok(RegistryError::Unauthorized)));
+}
+
+#[test]
+fn test_get_entries_filters_by_network() {
+    let (env, client, admin) = setup();
+    let testnet = symbol_short!("testnet");
+    let mainnet = symbol_short!("mainnet");
+
+    client.set_address(&admin, &testnet, &names::SPLIT, &Address::generate(&env));
+    client.set_address(&admin, &testnet, &names::BILLS, &Address::generate(&env));
+    client.set_address(&admin, &mainnet, &names::SPLIT, &Address::generate(&env));
+
+    assert_eq!(client.get_entries(&testnet).len(), 2);
+    assert_eq!(client.get_entries(&mainnet).len(), 1);
+}
+
+#[test]
+fn test_remove_address() {
+    let (env, client, admin) = setup();
+    let network = symbol_short!("testnet");
+    let bills = Address::generate(&env);
+    client.set_address(&admin, &network, &names::BILLS, &bills);
+
+    client.remove_address(&admin, &network, &names::BILLS);
+    assert_eq!(client.get_address(&network, &names::BILLS), None);
+    assert_eq!(client.get_history(&network, &names::BILLS).len(), 1);
+
+    let result = client.try_remove_address(&admin, &network, &names::BILLS);
+    assert_eq!(result, Err(Ok(RegistryError::EntryNotFound)));
+}
+
+#[test]
+fn test_readd_after_remove_continues_version() {
+    let (env, client, admin) = setup();
+    let network = symbol_short!("testnet");
+    let bills = Address::generate(&env);
+    client.set_address(&admin, &network, &names::BILLS, &bills);
+    client.set_address(&admin, &network, &names::BILLS, &bills);
+    client.remove_address(&admin, &network, &names::BILLS);
+
+    let redeployed = Address::generate(&env);
+    assert_eq!(
+        client.set_address(&admin, &network, &names::BILLS, &redeployed),
+        3
+    );
+    assert_eq!(
+        client.set_address(&admin, &network, &names::BILLS, &bills),
+        4
+    );
+    let mut versions = Vec::new(&env);
+    for entry in client.get_history(&network, &names::BILLS).iter() {
+        versions.push_back(entry.version);
+    }
+    assert_eq!(versions, Vec::from_array(&env, [1u32, 2, 3]));
+}
+
+#[test]
+fn test_set_admin_transfers_control() {
+    let (env, client, admin) = setup();
+    let new_admin = Address::generate(&env);
+    client.set_admin(&admin, &new_admin);
+    assert_eq!(client.get_admin(), Some(new_admin.clone()));
+
+    let result = client.try_set_address(
+        &admin,
+        &symbol_short!("testnet"),
+        &names::SPLIT,
+        &Address::generate(&env),
+    );
+    assert_eq!(result, Err(Ok(RegistryError::Unauthorized)));
+}
@@ -1,11 +1,16 @@
 #![no_std]
 mod test;
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
 
 use soroban_sdk::{
-    contract, contracterror, contractimpl, contracttype, symbol_short, token::TokenClient, vec,
-    Address, Env, Map, Symbol, Vec,
+    contract, contractclient, contracterror, contractimpl, contracttype, symbol_short,
+    token::TokenClient, vec, Address, BytesN, Env, IntoVal, Map, Symbol, TryFromVal, Val, Vec,
 };
 
+pub use remitwise_common::AuditRecord;
+use remitwise_common::{month_index, AuditLog, ReentrancyGuard, RefRegistry};
+
 // Event topics
 const SPLIT_INITIALIZED: Symbol = symbol_short!("init");
 const SPLIT_CALCULATED: Symbol = symbol_short!("calc");
@@ -36,6 +41,50 @@ pub enum RemittanceSplitError {
     ChecksumMismatch = 9,
     InvalidDueDate = 10,
     ScheduleNotFound = 11,
+    FallbackNotConfigured = 12,
+    InvalidFallback = 13,
+    CategoryNotFrozen = 14,
+    UnsupportedToken = 15,
+    ProposalRequired = 16,
+    ProposalNotFound = 17,
+    ProposalNotReady = 18,
+    ProposalNotPending = 19,
+    NoPendingChange = 20,
+    BatchTooLarge = 21,
+    InsufficientBalance = 22,
+    ComplianceRejected = 23,
+    InboundWatchNotFound = 24,
+    InboundNotEligible = 25,
+    InboundAlreadySettled = 26,
+    /// Top-up targets must not be negative and categories must not repeat
+    /// in the priority order
+    InvalidTopUpTargets = 27,
+    /// The owner has no emergency redirect in force
+    RedirectNotActive = 28,
+    /// The reported inbound amount is more than the owner's balance has grown
+    /// since the watch was registered or last settled
+    InboundExceedsReceived = 29,
+}
+
+/// Allocation categories, in the same order as the split percentages
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum SplitCategory {
+    Spending = 0,
+    Savings = 1,
+    Bills = 2,
+    Insurance = 3,
+}
+
+/// Where the rounding remainder of a split goes
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RemainderSink {
+    /// Added to one category's share (insurance by default)
+    Category(SplitCategory),
+    /// Held by the contract for the owner and claimable with `claim_dust`
+    Dust,
 }
 
 #[derive(Clone)]
@@ -45,7 +94,7 @@ pub struct Allocation {
     pub amount: i128,
 }
 
-#[derive(Clone)]
+#[derive(Clone, Debug, Eq, PartialEq)]
 #[contracttype]
 pub struct AccountGroup {
     pub spending: Address,
@@ -54,9 +103,89 @@ pub struct AccountGroup {
     pub insurance: Address,
 }
 
+/// Target balances of the category accounts for the top-up distribution
+/// mode
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct TopUpTargets {
+    pub spending: i128,
+    pub savings: i128,
+    pub bills: i128,
+    pub insurance: i128,
+    /// Categories in the order they are topped up; those left out get
+    /// nothing beyond what overflows to savings
+    pub priority: Vec<SplitCategory>,
+}
+
+impl TopUpTargets {
+    fn target(&self, category: SplitCategory) -> i128 {
+        match category {
+            SplitCategory::Spending => self.spending,
+            SplitCategory::Savings => self.savings,
+            SplitCategory::Bills => self.bills,
+            SplitCategory::Insurance => self.insurance,
+        }
+    }
+}
+
+/// Routing of all of an owner's distributions to a safe address while a
+/// category account is compromised
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct EmergencyRedirect {
+    pub safe_address: Address,
+    /// The owner or their guardian
+    pub activated_by: Address,
+    pub activated_at: u64,
+    /// When the redirect lapses, once deactivation has been requested
+    pub ends_at: Option<u64>,
+    /// Safe address requested by activating again with a different address;
+    /// it replaces `safe_address` at `change_at`
+    pub pending_address: Option<Address>,
+    pub change_at: Option<u64>,
+}
+
 // Storage TTL constants
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
 const INSTANCE_BUMP_AMOUNT: u32 = 518400; // ~30 days
+const OWNER_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
+const OWNER_BUMP_AMOUNT: u32 = 518400; // ~30 days
+
+// Per-owner storage tags. Each owner's state lives in persistent storage under
+// `(tag, owner)` so one deployment serves every user and each user's entries
+// expire independently.
+const KEY_CONFIG: Symbol = symbol_short!("CONFIG");
+const KEY_NONCE: Symbol = symbol_short!("NONCE");
+const KEY_FALLBACK: Symbol = symbol_short!("FALLBACK");
+const KEY_FROZEN: Symbol = symbol_short!("FROZEN");
+const KEY_LARGE_POLICY: Symbol = symbol_short!("LRG_POL");
+const KEY_HISTORY: Symbol = symbol_short!("REM_HIST");
+const KEY_REMAINDER_SINK: Symbol = symbol_short!("REM_SINK");
+const KEY_DUST: Symbol = symbol_short!("DUST");
+const KEY_GUARDIAN: Symbol = symbol_short!("GUARDIAN");
+const KEY_PENDING_SPLIT: Symbol = symbol_short!("PEND_SPL");
+const KEY_AUTO_INVEST: Symbol = symbol_short!("AUTO_INV");
+const KEY_AUTO_SCHEDULE: Symbol = symbol_short!("AUTO_SCH");
+const KEY_CLAIM_WINDOW: Symbol = symbol_short!("CLM_WIN");
+const KEY_ESCROW: Symbol = symbol_short!("ESCROW");
+const KEY_COMPLIANCE: Symbol = symbol_short!("COMPLY");
+const KEY_REBALANCE: Symbol = symbol_short!("REBAL_SRC");
+const KEY_OVERRIDES: Symbol = symbol_short!("CMP_OVRD");
+const KEY_INBOUND: Symbol = symbol_short!("INBOUND");
+const KEY_RELAYERS: Symbol = symbol_short!("RELAYERS");
+const KEY_TOP_UP: Symbol = symbol_short!("TOP_UP");
+const KEY_EMERGENCY: Symbol = symbol_short!("EMERGENCY");
+const KEY_SCHEDULES: Symbol = symbol_short!("REM_SCH");
+/// How long a settled inbound payment is remembered, so a relayer cannot
+/// replay it soon after it was split
+pub const INBOUND_REF_WINDOW: u64 = 90 * 24 * 60 * 60;
+// Temporary under `(INB_REF, tx_hash)` for `INBOUND_REF_WINDOW`
+const INBOUND_REFS: RefRegistry = RefRegistry::new(symbol_short!("INB_REF"), INBOUND_REF_WINDOW);
+// Persistent under `(KEY_RECEIPTS, distribution_id)`
+const KEY_RECEIPTS: Symbol = symbol_short!("RECEIPTS");
+// Persistent under `(KEY_PROPOSAL, proposal_id)`. Committed and cancelled
+// proposals are no longer bumped, so their entries expire.
+const KEY_PROPOSAL: Symbol = symbol_short!("PROPOSAL");
 
 /// Split configuration with owner tracking for access control
 #[derive(Clone)]
@@ -79,9 +208,25 @@ pub struct SplitCalculatedEvent {
     pub savings_amount: i128,
     pub bills_amount: i128,
     pub insurance_amount: i128,
+    /// Rounding remainder, already included in one of the amounts unless
+    /// the owner's remainder sink is `Dust`
+    pub remainder: i128,
     pub timestamp: u64,
 }
 
+/// Published when a distribution's transfers are made
+#[derive(Clone, Debug, Eq, PartialEq)]
+#[contracttype]
+pub struct DistributionEvent {
+    pub distribution_id: u64,
+    pub sender: Address,
+    pub total_amount: i128,
+    /// Amounts sent to spending, savings, bills and insurance
+    pub amounts: Vec<i128>,
+    pub remainder: i128,
+    pub remainder_sink: RemainderSink,
+}
+
 /// Events emitted by the contract for audit trail
 #[contracttype]
 #[derive(Clone, Debug, Eq, PartialEq)]
@@ -89,6 +234,15 @@ pub enum SplitEvent {
     Initialized,
     Updated,
     Calculated,
+    Distributed,
+    ChangePending,
+    ChangeAccepted,
+    ChangeCancelled,
+    AutoInvested,
+    AllocationEscrowed,
+    AllocationClaimed,
+    AllocationReturned,
+    BatchDistributed,
 }
 
 /// Snapshot for data export/import (migration). Checksum is a simple numeric digest for on-chain verification.
@@ -110,6 +264,338 @@ pub struct AuditEntry {
     pub success: bool,
 }
 
+/// Declared purpose of a remittance, for compliance-style reporting
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum PurposeCode {
+    FamilySupport = 1,
+    SchoolFees = 2,
+    Medical = 3,
+    Other = 4,
+}
+
+/// A completed distribution, as kept in the remittance history.
+/// `memo` is all zeroes when no memo hash was supplied.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RemittanceRecord {
+    pub sender: Address,
+    pub total_amount: i128,
+    pub memo: BytesN<32>,
+    pub purpose: PurposeCode,
+    pub timestamp: u64,
+    /// Amounts actually sent to spending, savings, bills and insurance
+    pub amounts: Vec<i128>,
+    /// Distribution whose receipts are returned by `get_distribution_receipts`
+    pub distribution_id: u64,
+}
+
+/// One leg of a distribution: what went to which account.
+///
+/// `receipt_id` is `distribution_id * 4 + category`, so a recipient can quote
+/// it and it leads back to the distribution.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistributionReceipt {
+    pub receipt_id: u64,
+    pub distribution_id: u64,
+    pub sender: Address,
+    pub category: SplitCategory,
+    pub recipient: Address,
+    pub token: Address,
+    pub amount: i128,
+    /// Held by the contract until the recipient claims it
+    pub escrowed: bool,
+    pub timestamp: u64,
+}
+
+/// Realized allocation of a sender's distributions over a period
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EffectiveSplit {
+    pub remittance_count: u32,
+    pub total_amount: i128,
+    /// Amounts sent to spending, savings, bills and insurance
+    pub amounts: Vec<i128>,
+    /// Share of `total_amount` per category, in basis points
+    pub percentages_bps: Vec<u32>,
+    /// Currently configured percentages, for comparison
+    pub configured_percentages: Vec<u32>,
+}
+
+/// Aggregated remittances for one purpose code
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PurposeTotal {
+    pub purpose: PurposeCode,
+    pub count: u32,
+    pub total_amount: i128,
+}
+
+/// Contracts `suggest_rebalance` reads an owner's commitments from. A
+/// category whose contract is not set keeps its configured percentage.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RebalanceSources {
+    pub bills: Option<Address>,
+    pub insurance: Option<Address>,
+    pub savings: Option<Address>,
+}
+
+/// Why `suggest_rebalance` moved a percentage
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RebalanceReason {
+    /// No distributions in the window to compare commitments with
+    NoRecentRemittances,
+    BillsAboveSplit,
+    BillsBelowSplit,
+    PremiumsAboveSplit,
+    PremiumsBelowSplit,
+    /// Goals are short of their targets and the savings share is too small
+    /// to close the gap in `GOAL_CATCH_UP_MONTHS`
+    GoalShortfall,
+    /// Commitments add up to more than the remittances; shares were scaled
+    /// down in proportion and nothing is left for spending
+    NeedsExceedRemittances,
+}
+
+/// Percentages `suggest_rebalance` proposes, with the reasons for the changes
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RebalanceSuggestion {
+    pub spending_percent: u32,
+    pub savings_percent: u32,
+    pub bills_percent: u32,
+    pub insurance_percent: u32,
+    /// Empty when the configured split already matches
+    pub reasons: Vec<RebalanceReason>,
+}
+
+/// Two-phase flow required for distributions at or above `threshold`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct LargeTransferPolicy {
+    pub threshold: i128,
+    /// Seconds a proposal must wait before it can be committed
+    pub delay_secs: u64,
+    /// Optional co-signer whose approval lifts the delay
+    pub co_signer: Option<Address>,
+}
+
+/// Guardian whose approval is needed for large changes to an owner's split
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct GuardianPolicy {
+    pub guardian: Address,
+    /// Percentage points a change may move between categories without
+    /// the guardian's approval
+    pub max_shift: u32,
+}
+
+/// Split change waiting for the guardian's approval
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PendingSplitChange {
+    pub spending_percent: u32,
+    pub savings_percent: u32,
+    pub bills_percent: u32,
+    pub insurance_percent: u32,
+    /// Percentage points the change moves between categories
+    pub shift: u32,
+    pub proposed_at: u64,
+}
+
+/// Savings schedule fed by each distribution's savings allocation
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AutoInvestConfig {
+    /// Savings goals contract holding the goal
+    pub savings_contract: Address,
+    pub goal_id: u32,
+    /// Date by which each allocation should be fully saved
+    pub target_date: u64,
+    /// Seconds between runs of the savings schedule
+    pub interval: u64,
+}
+
+/// Savings schedule as returned by the savings goals contract
+#[contracttype]
+#[derive(Clone)]
+pub struct SavingsSchedule {
+    pub id: u32,
+    pub owner: Address,
+    pub goal_id: u32,
+    pub amount: i128,
+    pub next_due: u64,
+    pub interval: u64,
+    pub recurring: bool,
+    pub active: bool,
+    pub created_at: u64,
+    pub last_executed: Option<u64>,
+    pub missed_count: u32,
+}
+
+#[contractclient(name = "SavingsGoalsClient")]
+pub trait SavingsGoalsTrait {
+    fn create_savings_schedule(
+        env: Env,
+        owner: Address,
+        goal_id: u32,
+        amount: i128,
+        next_due: u64,
+        interval: u64,
+    ) -> u32;
+    fn modify_savings_schedule(
+        env: Env,
+        caller: Address,
+        schedule_id: u32,
+        amount: i128,
+        next_due: u64,
+        interval: u64,
+    ) -> bool;
+    fn get_savings_schedule(env: Env, schedule_id: u32) -> Option<SavingsSchedule>;
+    fn get_portfolio(env: Env, owner: Address) -> Portfolio;
+}
+
+/// Goal summary as returned by the savings goals contract
+#[contracttype]
+#[derive(Clone)]
+pub struct Portfolio {
+    pub owner: Address,
+    pub goal_count: u32,
+    pub total_saved: i128,
+    pub total_target: i128,
+    pub completion_bps: u32,
+    pub locked_balance: i128,
+    pub unlocked_balance: i128,
+    pub schedules_with_missed_runs: u32,
+    pub next_contribution_at: Option<u64>,
+    pub next_contribution_goal: u32,
+    pub next_contribution_amount: i128,
+}
+
+#[contractclient(name = "BillPaymentsClient")]
+pub trait BillPaymentsTrait {
+    fn get_paid_total(env: Env, owner: Address, from_ts: u64, to_ts: u64) -> i128;
+}
+
+#[contractclient(name = "InsuranceClient")]
+pub trait InsuranceTrait {
+    fn get_total_monthly_premium(env: Env, owner: Address) -> i128;
+}
+
+/// Sanctions screening service of a regulated deployment, asked to approve
+/// each transfer of a distribution
+#[contractclient(name = "ComplianceClient")]
+pub trait ComplianceTrait {
+    fn check_transfer(env: Env, from: Address, to: Address, amount: i128, token: Address) -> bool;
+}
+
+/// Remittances that relayers may split on an owner's behalf with
+/// `settle_inbound`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InboundWatch {
+    pub expected_from: Address,
+    pub token: Address,
+    pub min_amount: i128,
+    pub accounts: AccountGroup,
+    pub purpose: PurposeCode,
+    pub settled_count: u32,
+    pub total_settled: i128,
+    /// Owner's balance of `token` when the watch was registered or last
+    /// settled. A settlement splits at most what the balance has grown by
+    /// since.
+    pub baseline_balance: i128,
+}
+
+/// A relayer's report of a payment received by a watched owner.
+/// `tx_hash` identifies the payment so that it is split only once.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct InboundProof {
+    pub from: Address,
+    pub amount: i128,
+    pub tx_hash: BytesN<32>,
+}
+
+/// One household of a `batch_distribute` call. The amount is split by
+/// `owner`'s split and paid to `accounts`.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchRecipient {
+    pub owner: Address,
+    pub accounts: AccountGroup,
+    pub amount: i128,
+}
+
+/// Outcome of one `batch_distribute` item
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchItemResult {
+    pub owner: Address,
+    pub amount: i128,
+    pub distributed: bool,
+    /// `RemittanceSplitError` code of a skipped item, 0 when distributed
+    pub error_code: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct BatchDistributionEvent {
+    pub sender: Address,
+    pub item_count: u32,
+    pub distributed_count: u32,
+    pub total_distributed: i128,
+}
+
+/// Category share held by the contract until its recipient claims it
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct EscrowedAllocation {
+    pub token: Address,
+    pub category: SplitCategory,
+    pub recipient: Address,
+    pub amount: i128,
+    /// After this time the recipient can no longer claim and the share can be
+    /// returned to the sender
+    pub expires_at: u64,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum ProposalStatus {
+    Pending = 0,
+    Committed = 1,
+    Cancelled = 2,
+}
+
+/// A large distribution awaiting `commit_distribution`.
+/// `memo` is all zeroes when no memo hash was supplied.
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct DistributionProposal {
+    pub id: u32,
+    pub from: Address,
+    pub token: Address,
+    pub accounts: AccountGroup,
+    pub total_amount: i128,
+    /// Amounts per category, fixed at proposal time
+    pub amounts: Vec<i128>,
+    /// Rounding remainder at proposal time; held as dust when it is not
+    /// part of `amounts`
+    pub remainder: i128,
+    pub memo: BytesN<32>,
+    pub purpose: PurposeCode,
+    pub created_at: u64,
+    pub execute_after: u64,
+    pub approved: bool,
+    pub status: ProposalStatus,
+}
+
 /// Schedule for automatic remittance splits
 #[contracttype]
 #[derive(Clone)]
@@ -139,7 +625,20 @@ pub enum ScheduleEvent {
 
 const SNAPSHOT_VERSION: u32 = 1;
 const MAX_AUDIT_ENTRIES: u32 = 100;
+const MAX_REMITTANCE_HISTORY: u32 = 200;
+/// Per-owner log of configuration changes and distributions
+const AUDIT_LOG: AuditLog = AuditLog::new(symbol_short!("AUD_LOG"));
+/// Maximum number of households in one `batch_distribute` call
+pub const MAX_BATCH_SIZE: u32 = 50;
 const CONTRACT_VERSION: u32 = 1;
+/// How far back `suggest_rebalance` looks, widened to whole calendar months
+pub const REBALANCE_WINDOW_SECS: u64 = 90 * 86400;
+/// Months `suggest_rebalance` allows for closing the shortfall of an owner's
+/// savings goals
+pub const GOAL_CATCH_UP_MONTHS: u32 = 12;
+/// Time between a request to deactivate an emergency redirect, or to change
+/// its safe address, and the request taking effect
+pub const EMERGENCY_REDIRECT_COOLDOWN_SECS: u64 = 2 * 86400;
 
 #[contract]
 pub struct RemittanceSplit;
@@ -163,20 +662,22 @@ impl RemittanceSplit {
         }
     }
 
+    /// Set the contract-wide pause admin, who also manages the token allow-list.
+    ///
+    /// The first admin claims the role by naming themselves; afterwards only
+    /// the current admin can hand it over.
     pub fn set_pause_admin(
         env: Env,
         caller: Address,
         new_admin: Address,
     ) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        if config.owner != caller {
-            return Err(RemittanceSplitError::Unauthorized);
+        match Self::get_pause_admin(&env) {
+            None if caller != new_admin => return Err(RemittanceSplitError::Unauthorized),
+            Some(admin) if admin != caller => return Err(RemittanceSplitError::Unauthorized),
+            _ => {}
         }
+        Self::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .set(&symbol_short!("PAUSE_ADM"), &new_admin);
@@ -184,15 +685,7 @@ impl RemittanceSplit {
     }
     pub fn pause(env: Env, caller: Address) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        let admin = Self::get_pause_admin(&env).unwrap_or(config.owner);
-        if admin != caller {
-            return Err(RemittanceSplitError::Unauthorized);
-        }
+        Self::require_admin(&env, &caller)?;
         env.storage()
             .instance()
             .set(&symbol_short!("PAUSED"), &true);
@@ -202,15 +695,7 @@ impl RemittanceSplit {
     }
     pub fn unpause(env: Env, caller: Address) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        let admin = Self::get_pause_admin(&env).unwrap_or(config.owner);
-        if admin != caller {
-            return Err(RemittanceSplitError::Unauthorized);
-        }
+        Self::require_admin(&env, &caller)?;
         env.storage()
             .instance()
             .set(&symbol_short!("PAUSED"), &false);
@@ -227,6 +712,12 @@ impl RemittanceSplit {
             .get(&symbol_short!("VERSION"))
             .unwrap_or(CONTRACT_VERSION)
     }
+    pub fn get_pause_admin_public(env: Env) -> Option<Address> {
+        Self::get_pause_admin(&env)
+    }
+    pub fn get_upgrade_admin_public(env: Env) -> Option<Address> {
+        Self::get_upgrade_admin(&env)
+    }
     fn get_upgrade_admin(env: &Env) -> Option<Address> {
         env.storage().instance().get(&symbol_short!("UPG_ADM"))
     }
@@ -236,14 +727,12 @@ impl RemittanceSplit {
         new_admin: Address,
     ) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        if config.owner != caller {
-            return Err(RemittanceSplitError::Unauthorized);
+        match Self::get_upgrade_admin(&env) {
+            None if caller != new_admin => return Err(RemittanceSplitError::Unauthorized),
+            Some(admin) if admin != caller => return Err(RemittanceSplitError::Unauthorized),
+            _ => {}
         }
+        Self::extend_instance_ttl(&env);
         env.storage()
             .instance()
             .set(&symbol_short!("UPG_ADM"), &new_admin);
@@ -255,13 +744,7 @@ impl RemittanceSplit {
         new_version: u32,
     ) -> Result<(), RemittanceSplitError> {
         caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        let admin = Self::get_upgrade_admin(&env).unwrap_or(config.owner);
-        if admin != caller {
+        if Self::get_upgrade_admin(&env) != Some(caller.clone()) {
             return Err(RemittanceSplitError::Unauthorized);
         }
         let prev = Self::get_version(env.clone());
@@ -292,7 +775,7 @@ impl RemittanceSplit {
     /// - If owner doesn't authorize the transaction
     /// - If nonce is invalid (replay)
     /// - If percentages don't sum to 100
-    /// - If split is already initialized (use update_split instead)
+    /// - If the owner's split is already initialized (use update_split instead)
     pub fn initialize_split(
         env: Env,
         owner: Address,
@@ -306,8 +789,7 @@ impl RemittanceSplit {
         Self::require_not_paused(&env)?;
         Self::require_nonce(&env, &owner, nonce)?;
 
-        let existing: Option<SplitConfig> = env.storage().instance().get(&symbol_short!("CONFIG"));
-        if existing.is_some() {
+        if Self::get_config(env.clone(), owner.clone()).is_some() {
             Self::append_audit(&env, symbol_short!("init"), &owner, false);
             return Err(RemittanceSplitError::AlreadyInitialized);
         }
@@ -329,22 +811,18 @@ impl RemittanceSplit {
             timestamp: env.ledger().timestamp(),
             initialized: true,
         };
-
-        env.storage()
-            .instance()
-            .set(&symbol_short!("CONFIG"), &config);
-        env.storage().instance().set(
-            &symbol_short!("SPLIT"),
-            &vec![
-                &env,
-                spending_percent,
-                savings_percent,
-                bills_percent,
-                insurance_percent,
-            ],
+        Self::set_owned(&env, KEY_CONFIG, &owner, &config);
+        AUDIT_LOG.append(
+            &env,
+            &owner,
+            &owner,
+            symbol_short!("init"),
+            AuditLog::empty_digest(&env),
+            AuditLog::digest(&env, config),
         );
 
         Self::increment_nonce(&env, &owner)?;
+        Self::extend_owner_ttl(&env, &owner);
         Self::append_audit(&env, symbol_short!("init"), &owner, true);
         env.events()
             .publish((symbol_short!("split"), SplitEvent::Initialized), owner);
@@ -352,6 +830,11 @@ impl RemittanceSplit {
         Ok(true)
     }
 
+    /// Change the caller's split percentages.
+    ///
+    /// Returns `false` without applying the change when the caller has a
+    /// guardian and the change moves more than their `max_shift`; it is then
+    /// held until the guardian calls `accept_split_change`.
     pub fn update_split(
         env: Env,
         caller: Address,
@@ -365,16 +848,7 @@ impl RemittanceSplit {
         Self::require_not_paused(&env)?;
         Self::require_nonce(&env, &caller, nonce)?;
 
-        let mut config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-
-        if config.owner != caller {
-            Self::append_audit(&env, symbol_short!("update"), &caller, false);
-            return Err(RemittanceSplitError::Unauthorized);
-        }
+        let mut config = Self::load_config(&env, &caller)?;
 
         let total = spending_percent + savings_percent + bills_percent + insurance_percent;
         if total != 100 {
@@ -384,92 +858,1499 @@ impl RemittanceSplit {
 
         Self::extend_instance_ttl(&env);
 
-        config.spending_percent = spending_percent;
-        config.savings_percent = savings_percent;
-        config.bills_percent = bills_percent;
-        config.insurance_percent = insurance_percent;
-
-        env.storage()
-            .instance()
-            .set(&symbol_short!("CONFIG"), &config);
-        env.storage().instance().set(
-            &symbol_short!("SPLIT"),
-            &vec![
-                &env,
-                spending_percent,
-                savings_percent,
-                bills_percent,
-                insurance_percent,
-            ],
-        );
-
-        let event = SplitInitializedEvent {
+        let percents = [
             spending_percent,
             savings_percent,
             bills_percent,
             insurance_percent,
-            timestamp: env.ledger().timestamp(),
-        };
-        env.events().publish((SPLIT_INITIALIZED,), event);
-        env.events()
-            .publish((symbol_short!("split"), SplitEvent::Updated), caller);
+        ];
+        if Self::stage_if_guarded(&env, &caller, &config, percents) {
+            return Ok(false);
+        }
+        Self::apply_split(&env, &caller, &caller, &mut config, percents);
 
         Ok(true)
     }
 
-    pub fn get_split(env: &Env) -> Vec<u32> {
-        env.storage()
-            .instance()
-            .get(&symbol_short!("SPLIT"))
-            .unwrap_or_else(|| vec![&env, 50, 30, 15, 5])
+    /// Get `owner`'s split percentages, or the default 50/30/15/5 before they
+    /// have initialized one.
+    pub fn get_split(env: &Env, owner: Address) -> Vec<u32> {
+        match Self::get_config(env.clone(), owner) {
+            Some(config) => vec![
+                env,
+                config.spending_percent,
+                config.savings_percent,
+                config.bills_percent,
+                config.insurance_percent,
+            ],
+            None => vec![env, 50, 30, 15, 5],
+        }
     }
 
-    pub fn get_config(env: Env) -> Option<SplitConfig> {
-        env.storage().instance().get(&symbol_short!("CONFIG"))
+    pub fn get_config(env: Env, owner: Address) -> Option<SplitConfig> {
+        Self::get_owned(&env, KEY_CONFIG, &owner)
     }
 
-    pub fn calculate_split(
-        env: Env,
-        total_amount: i128,
-    ) -> Result<Vec<i128>, RemittanceSplitError> {
-        let amounts = Self::calculate_split_amounts(&env, total_amount, true)?;
-        Ok(vec![&env, amounts[0], amounts[1], amounts[2], amounts[3]])
-    }
+    // -----------------------------------------------------------------------
+    // Guardian co-signature
+    // -----------------------------------------------------------------------
 
-    pub fn distribute_usdc(
+    /// Name a guardian who must approve split changes moving more than
+    /// `max_shift` percentage points between categories, or remove it with
+    /// `None`. While a guardian is set, replacing or removing it needs their
+    /// authorization too, so a compromised sender key cannot drop it.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the caller has not initialized a split
+    /// * `InvalidAmount` - If max_shift is above 100
+    pub fn set_guardian(
         env: Env,
-        usdc_contract: Address,
-        from: Address,
-        nonce: u64,
-        accounts: AccountGroup,
-        total_amount: i128,
-    ) -> Result<bool, RemittanceSplitError> {
-        if total_amount <= 0 {
-            Self::append_audit(&env, symbol_short!("distrib"), &from, false);
-            return Err(RemittanceSplitError::InvalidAmount);
+        caller: Address,
+        policy: Option<GuardianPolicy>,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        Self::load_config(&env, &caller)?;
+        let current = Self::get_guardian(env.clone(), caller.clone());
+        if let Some(current) = &current {
+            current.guardian.require_auth();
         }
 
-        from.require_auth();
-        Self::require_nonce(&env, &from, nonce)?;
-
-        let amounts = Self::calculate_split_amounts(&env, total_amount, false)?;
-        let token = TokenClient::new(&env, &usdc_contract);
-
-        if amounts[0] > 0 {
-            token.transfer(&from, &accounts.spending, &amounts[0]);
-        }
-        if amounts[1] > 0 {
-            token.transfer(&from, &accounts.savings, &amounts[1]);
-        }
-        if amounts[2] > 0 {
-            token.transfer(&from, &accounts.bills, &amounts[2]);
-        }
-        if amounts[3] > 0 {
-            token.transfer(&from, &accounts.insurance, &amounts[3]);
+        match &policy {
+            Some(policy) => {
+                if policy.max_shift > 100 {
+                    return Err(RemittanceSplitError::InvalidAmount);
+                }
+                Self::set_owned(&env, KEY_GUARDIAN, &caller, policy);
+            }
+            None => {
+                Self::remove_owned(&env, KEY_GUARDIAN, &caller);
+                Self::remove_owned(&env, KEY_PENDING_SPLIT, &caller);
+            }
         }
+        AUDIT_LOG.append(
+            &env,
+            &caller,
+            &caller,
+            symbol_short!("guardian"),
+            AuditLog::digest(&env, current),
+            AuditLog::digest(&env, policy),
+        );
+        Self::extend_owner_ttl(&env, &caller);
 
-        Self::increment_nonce(&env, &from)?;
-        Self::append_audit(&env, symbol_short!("distrib"), &from, true);
+        Self::append_audit(&env, symbol_short!("guardian"), &caller, true);
+        Ok(())
+    }
+
+    pub fn get_guardian(env: Env, owner: Address) -> Option<GuardianPolicy> {
+        Self::get_owned(&env, KEY_GUARDIAN, &owner)
+    }
+
+    pub fn get_pending_split_change(env: Env, owner: Address) -> Option<PendingSplitChange> {
+        Self::get_owned(&env, KEY_PENDING_SPLIT, &owner)
+    }
+
+    /// Apply `owner`'s pending split change (guardian only).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the owner's guardian
+    /// * `NoPendingChange` - If no change is waiting for approval
+    pub fn accept_split_change(
+        env: Env,
+        guardian: Address,
+        owner: Address,
+    ) -> Result<bool, RemittanceSplitError> {
+        guardian.require_auth();
+        Self::require_not_paused(&env)?;
+        match Self::get_guardian(env.clone(), owner.clone()) {
+            Some(policy) if policy.guardian == guardian => {}
+            _ => return Err(RemittanceSplitError::Unauthorized),
+        }
+        let pending = Self::get_pending_split_change(env.clone(), owner.clone())
+            .ok_or(RemittanceSplitError::NoPendingChange)?;
+        let mut config = Self::load_config(&env, &owner)?;
+
+        Self::extend_instance_ttl(&env);
+        Self::remove_owned(&env, KEY_PENDING_SPLIT, &owner);
+        let percents = [
+            pending.spending_percent,
+            pending.savings_percent,
+            pending.bills_percent,
+            pending.insurance_percent,
+        ];
+        Self::apply_split(&env, &guardian, &owner, &mut config, percents);
+
+        Self::append_audit(&env, symbol_short!("accept"), &guardian, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::ChangeAccepted),
+            (owner, guardian),
+        );
+        Ok(true)
+    }
+
+    /// Drop `owner`'s pending split change. Either the owner (withdrawing
+    /// it) or the guardian (rejecting it) may cancel.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is neither the owner nor their guardian
+    /// * `NoPendingChange` - If no change is waiting for approval
+    pub fn cancel_split_change(
+        env: Env,
+        caller: Address,
+        owner: Address,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        let is_guardian = Self::get_guardian(env.clone(), owner.clone())
+            .map(|policy| policy.guardian == caller)
+            .unwrap_or(false);
+        if caller != owner && !is_guardian {
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+        if Self::get_pending_split_change(env.clone(), owner.clone()).is_none() {
+            return Err(RemittanceSplitError::NoPendingChange);
+        }
+
+        Self::remove_owned(&env, KEY_PENDING_SPLIT, &owner);
+        Self::append_audit(&env, symbol_short!("cncl_chg"), &caller, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::ChangeCancelled),
+            (owner, caller),
+        );
+        Ok(())
+    }
+
+    // -----------------------------------------------------------------------
+    // Savings auto-invest
+    // -----------------------------------------------------------------------
+
+    /// Turn each distribution's savings allocation into a savings schedule on
+    /// the caller's goal, or stop doing so with `None`.
+    ///
+    /// The first distribution creates the schedule; later ones top it up. Each
+    /// allocation is spread evenly over the runs left until `target_date`.
+    /// Changing the config starts a new schedule on the next distribution.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the caller has not initialized a split
+    /// * `InvalidAmount` - If interval is zero
+    /// * `InvalidDueDate` - If target_date is not in the future
+    pub fn set_auto_invest(
+        env: Env,
+        caller: Address,
+        config: Option<AutoInvestConfig>,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        Self::load_config(&env, &caller)?;
+
+        let before = AuditLog::digest(&env, Self::get_auto_invest(env.clone(), caller.clone()));
+        match &config {
+            Some(config) => {
+                if config.interval == 0 {
+                    return Err(RemittanceSplitError::InvalidAmount);
+                }
+                if config.target_date <= env.ledger().timestamp() {
+                    return Err(RemittanceSplitError::InvalidDueDate);
+                }
+                Self::set_owned(&env, KEY_AUTO_INVEST, &caller, config);
+            }
+            None => Self::remove_owned(&env, KEY_AUTO_INVEST, &caller),
+        }
+        Self::remove_owned(&env, KEY_AUTO_SCHEDULE, &caller);
+        AUDIT_LOG.append(
+            &env,
+            &caller,
+            &caller,
+            symbol_short!("auto_inv"),
+            before,
+            AuditLog::digest(&env, config),
+        );
+        Self::extend_owner_ttl(&env, &caller);
+
+        Self::append_audit(&env, symbol_short!("auto_inv"), &caller, true);
+        Ok(())
+    }
+
+    pub fn get_auto_invest(env: Env, owner: Address) -> Option<AutoInvestConfig> {
+        Self::get_owned(&env, KEY_AUTO_INVEST, &owner)
+    }
+
+    /// ID of the savings schedule that auto-invest is topping up, if one has
+    /// been created since the config was last set.
+    pub fn get_auto_invest_schedule(env: Env, owner: Address) -> Option<u32> {
+        Self::get_owned(&env, KEY_AUTO_SCHEDULE, &owner)
+    }
+
+    /// Switch the caller's distributions to the claim model, or back to
+    /// direct transfers with `None`.
+    ///
+    /// In the claim model each category share is held by the contract, and its
+    /// recipient pulls it with `claim_allocation` within `window` seconds.
+    /// Shares left unclaimed after that go back to the sender with
+    /// `reclaim_expired`. A new share for a leg whose previous share is
+    /// still unclaimed joins it, and the window restarts for both, so a
+    /// sender holds at most one share per leg waiting to be claimed. Shares
+    /// already escrowed keep their expiry when the window changes.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the caller has not initialized a split
+    /// * `InvalidAmount` - If window is zero
+    pub fn set_claim_window(
+        env: Env,
+        caller: Address,
+        window: Option<u64>,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        Self::load_config(&env, &caller)?;
+
+        let before = AuditLog::digest(&env, Self::get_claim_window(env.clone(), caller.clone()));
+        match window {
+            Some(0) => return Err(RemittanceSplitError::InvalidAmount),
+            Some(window) => Self::set_owned(&env, KEY_CLAIM_WINDOW, &caller, &window),
+            None => Self::remove_owned(&env, KEY_CLAIM_WINDOW, &caller),
+        }
+        AUDIT_LOG.append(
+            &env,
+            &caller,
+            &caller,
+            symbol_short!("clm_win"),
+            before,
+            AuditLog::digest(&env, window),
+        );
+        Self::extend_owner_ttl(&env, &caller);
+
+        Self::append_audit(&env, symbol_short!("clm_win"), &caller, true);
+        Ok(())
+    }
+
+    pub fn get_claim_window(env: Env, owner: Address) -> Option<u64> {
+        Self::get_owned(&env, KEY_CLAIM_WINDOW, &owner)
+    }
+
+    /// Shares of `sender`'s distributions that are waiting to be claimed or
+    /// returned.
+    pub fn get_escrowed_allocations(env: Env, sender: Address) -> Vec<EscrowedAllocation> {
+        Self::get_escrow(&env, &sender)
+    }
+
+    /// Transfer to `recipient` every unexpired `category` share of `token`
+    /// that `sender` escrowed for them. Returns the amount claimed.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If the contract is paused
+    /// * `InvalidAmount` - If there is nothing to claim
+    pub fn claim_allocation(
+        env: Env,
+        recipient: Address,
+        sender: Address,
+        token: Address,
+        category: SplitCategory,
+    ) -> Result<i128, RemittanceSplitError> {
+        recipient.require_auth();
+        Self::require_not_paused(&env)?;
+        let _guard = ReentrancyGuard::enter(&env);
+
+        let now = env.ledger().timestamp();
+        let (claimed, remaining) = Self::take_escrow(&env, &sender, |entry| {
+            entry.recipient == recipient
+                && entry.token == token
+                && entry.category == category
+                && now <= entry.expires_at
+        });
+        if claimed <= 0 {
+            Self::append_audit(&env, symbol_short!("claim"), &recipient, false);
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+        Self::set_escrow(&env, &sender, &remaining);
+        TokenClient::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &recipient,
+            &claimed,
+        );
+
+        Self::append_audit(&env, symbol_short!("claim"), &recipient, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::AllocationClaimed),
+            (sender, recipient, category, claimed),
+        );
+        Ok(claimed)
+    }
+
+    /// Return to `sender` every expired share of `token` they escrowed.
+    /// Anyone can call this; the funds only ever go back to the sender.
+    /// Returns the amount returned.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If the contract is paused
+    /// * `InvalidAmount` - If no share has expired
+    pub fn reclaim_expired(
+        env: Env,
+        sender: Address,
+        token: Address,
+    ) -> Result<i128, RemittanceSplitError> {
+        Self::require_not_paused(&env)?;
+        let _guard = ReentrancyGuard::enter(&env);
+
+        let now = env.ledger().timestamp();
+        let (returned, remaining) = Self::take_escrow(&env, &sender, |entry| {
+            entry.token == token && now > entry.expires_at
+        });
+        if returned <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+        Self::set_escrow(&env, &sender, &remaining);
+        TokenClient::new(&env, &token).transfer(
+            &env.current_contract_address(),
+            &sender,
+            &returned,
+        );
+
+        Self::append_audit(&env, symbol_short!("reclaim"), &sender, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::AllocationReturned),
+            (sender, token, returned),
+        );
+        Ok(returned)
+    }
+
+    pub fn calculate_split(
+        env: Env,
+        owner: Address,
+        total_amount: i128,
+    ) -> Result<Vec<i128>, RemittanceSplitError> {
+        let (amounts, _) = Self::calculate_split_amounts(&env, &owner, total_amount, true)?;
+        Ok(vec![&env, amounts[0], amounts[1], amounts[2], amounts[3]])
+    }
+
+    /// Transfer `total_amount` from `from` to the category accounts according
+    /// to `from`'s split.
+    ///
+    /// The optional `memo` (e.g. a hash of an off-chain reference) and the
+    /// `purpose` code are recorded in the remittance history.
+    #[allow(clippy::too_many_arguments)]
+    pub fn distribute_usdc(
+        env: Env,
+        usdc_contract: Address,
+        from: Address,
+        nonce: u64,
+        accounts: AccountGroup,
+        total_amount: i128,
+        memo: Option<BytesN<32>>,
+        purpose: PurposeCode,
+    ) -> Result<bool, RemittanceSplitError> {
+        if total_amount <= 0 {
+            Self::append_audit(&env, symbol_short!("distrib"), &from, false);
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        from.require_auth();
+        let _guard = ReentrancyGuard::enter(&env);
+        Self::require_nonce(&env, &from, nonce)?;
+
+        if !Self::get_supported(&env).contains(&usdc_contract) {
+            Self::append_audit(&env, symbol_short!("distrib"), &from, false);
+            return Err(RemittanceSplitError::UnsupportedToken);
+        }
+        if let Some(policy) = Self::get_large_transfer_policy(env.clone(), from.clone()) {
+            if total_amount >= policy.threshold {
+                Self::append_audit(&env, symbol_short!("distrib"), &from, false);
+                return Err(RemittanceSplitError::ProposalRequired);
+            }
+        }
+
+        let (amounts, remainder) =
+            Self::distribution_amounts(&env, &from, &usdc_contract, &accounts, total_amount)?;
+        if let Err(err) =
+            Self::screen_transfers(&env, &usdc_contract, &from, &from, &accounts, &amounts)
+        {
+            Self::append_audit(&env, symbol_short!("distrib"), &from, false);
+            return Err(err);
+        }
+        let distribution_id = Self::transfer_amounts(
+            &env,
+            &usdc_contract,
+            &from,
+            &from,
+            &accounts,
+            &amounts,
+            total_amount,
+            remainder,
+            false,
+        );
+        Self::auto_invest(&env, &from, amounts[1]);
+
+        Self::increment_nonce(&env, &from)?;
+        Self::extend_owner_ttl(&env, &from);
+        Self::append_audit(&env, symbol_short!("distrib"), &from, true);
+        Self::append_remittance(
+            &env,
+            &from,
+            &from,
+            RemittanceRecord {
+                sender: from.clone(),
+                total_amount,
+                memo: memo.unwrap_or_else(|| BytesN::from_array(&env, &[0u8; 32])),
+                purpose,
+                timestamp: env.ledger().timestamp(),
+                amounts: vec![&env, amounts[0], amounts[1], amounts[2], amounts[3]],
+                distribution_id,
+            },
+        );
+        Ok(true)
+    }
+
+    /// Distribute to many households at once, e.g. for agents and NGOs.
+    ///
+    /// Each item is split by its owner's split and paid to its accounts,
+    /// following that owner's emergency redirect and claim window.
+    /// Items that cannot be split (owner without a split, non-positive
+    /// amount) or that the compliance checker rejects are skipped and
+    /// reported in the results, in input order; the others are paid. One
+    /// `BatchDistributed` event sums up the batch.
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If the batch is empty
+    /// * `BatchTooLarge` - If the batch has more than `MAX_BATCH_SIZE` items
+    /// * `InvalidNonce` - If nonce is not the sender's current nonce
+    /// * `UnsupportedToken` - If the token is not on the allow-list
+    /// * `InsufficientBalance` - If the sender's balance does not cover the
+    ///   total of the items
+    /// * `ProposalRequired` - If the total reaches the sender's large-transfer
+    ///   threshold
+    pub fn batch_distribute(
+        env: Env,
+        token: Address,
+        from: Address,
+        nonce: u64,
+        items: Vec<BatchRecipient>,
+        purpose: PurposeCode,
+    ) -> Result<Vec<BatchItemResult>, RemittanceSplitError> {
+        from.require_auth();
+        let _guard = ReentrancyGuard::enter(&env);
+        if items.is_empty() {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+        if items.len() > MAX_BATCH_SIZE {
+            return Err(RemittanceSplitError::BatchTooLarge);
+        }
+        Self::require_nonce(&env, &from, nonce)?;
+        if !Self::get_supported(&env).contains(&token) {
+            Self::append_audit(&env, symbol_short!("batch"), &from, false);
+            return Err(RemittanceSplitError::UnsupportedToken);
+        }
+
+        let mut total = 0i128;
+        for item in items.iter() {
+            if item.amount > 0 {
+                total = total
+                    .checked_add(item.amount)
+                    .ok_or(RemittanceSplitError::Overflow)?;
+            }
+        }
+        if TokenClient::new(&env, &token).balance(&from) < total {
+            Self::append_audit(&env, symbol_short!("batch"), &from, false);
+            return Err(RemittanceSplitError::InsufficientBalance);
+        }
+        if let Some(policy) = Self::get_large_transfer_policy(env.clone(), from.clone()) {
+            if total >= policy.threshold {
+                Self::append_audit(&env, symbol_short!("batch"), &from, false);
+                return Err(RemittanceSplitError::ProposalRequired);
+            }
+        }
+
+        let mut results = Vec::new(&env);
+        let mut distributed_count = 0u32;
+        let mut total_distributed = 0i128;
+        for item in items.iter() {
+            let split = Self::load_config(&env, &item.owner)
+                .and_then(|_| {
+                    Self::distribution_amounts(
+                        &env,
+                        &item.owner,
+                        &token,
+                        &item.accounts,
+                        item.amount,
+                    )
+                })
+                .and_then(|split| {
+                    Self::screen_transfers(
+                        &env,
+                        &token,
+                        &from,
+                        &item.owner,
+                        &item.accounts,
+                        &split.0,
+                    )
+                    .map(|_| split)
+                });
+            let (amounts, remainder) = match split {
+                Ok(split) => split,
+                Err(err) => {
+                    results.push_back(BatchItemResult {
+                        owner: item.owner,
+                        amount: item.amount,
+                        distributed: false,
+                        error_code: err as u32,
+                    });
+                    continue;
+                }
+            };
+            let distribution_id = Self::transfer_amounts(
+                &env,
+                &token,
+                &from,
+                &item.owner,
+                &item.accounts,
+                &amounts,
+                item.amount,
+                remainder,
+                false,
+            );
+            Self::append_remittance(
+                &env,
+                &from,
+                &item.owner,
+                RemittanceRecord {
+                    sender: from.clone(),
+                    total_amount: item.amount,
+                    memo: BytesN::from_array(&env, &[0u8; 32]),
+                    purpose,
+                    timestamp: env.ledger().timestamp(),
+                    amounts: vec![&env, amounts[0], amounts[1], amounts[2], amounts[3]],
+                    distribution_id,
+                },
+            );
+            distributed_count += 1;
+            total_distributed += item.amount;
+            results.push_back(BatchItemResult {
+                owner: item.owner,
+                amount: item.amount,
+                distributed: true,
+                error_code: 0,
+            });
+        }
+
+        Self::increment_nonce(&env, &from)?;
+        Self::extend_owner_ttl(&env, &from);
+        Self::append_audit(&env, symbol_short!("batch"), &from, true);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::BatchDistributed),
+            BatchDistributionEvent {
+                sender: from,
+                item_count: items.len(),
+                distributed_count,
+                total_distributed,
+            },
+        );
+        Ok(results)
+    }
+
+    // -----------------------------------------------------------------------
+    // Two-phase distribution for large transfers
+    // -----------------------------------------------------------------------
+
+    /// Require the caller's distributions of at least `threshold` to go through
+    /// `propose_distribution` / `commit_distribution`. Pass `None` to disable
+    /// the two-phase flow.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the caller has not initialized a split
+    /// * `InvalidAmount` - If the threshold is not positive
+    pub fn set_large_transfer_policy(
+        env: Env,
+        caller: Address,
+        policy: Option<LargeTransferPolicy>,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        Self::load_config(&env, &caller)?;
+
+        let before = AuditLog::digest(
+            &env,
+            Self::get_large_transfer_policy(env.clone(), caller.clone()),
+        );
+        match &policy {
+            Some(policy) => {
+                if policy.threshold <= 0 {
+                    return Err(RemittanceSplitError::InvalidAmount);
+                }
+                Self::set_owned(&env, KEY_LARGE_POLICY, &caller, policy);
+            }
+            None => Self::remove_owned(&env, KEY_LARGE_POLICY, &caller),
+        }
+        AUDIT_LOG.append(
+            &env,
+            &caller,
+            &caller,
+            symbol_short!("lrg_pol"),
+            before,
+            AuditLog::digest(&env, policy),
+        );
+        Self::extend_owner_ttl(&env, &caller);
+
+        Self::append_audit(&env, symbol_short!("lrg_pol"), &caller, true);
+        Ok(())
+    }
+
+    pub fn get_large_transfer_policy(env: Env, owner: Address) -> Option<LargeTransferPolicy> {
+        Self::get_owned(&env, KEY_LARGE_POLICY, &owner)
+    }
+
+    /// Record a large distribution for later execution.
+    ///
+    /// The category amounts are computed now and fixed on the proposal. The
+    /// sender can cancel until it is committed, giving a window against
+    /// fat-finger errors.
+    ///
+    /// # Returns
+    /// The proposal ID
+    ///
+    /// # Errors
+    /// * `InvalidAmount` - If total_amount is not positive
+    /// * `InvalidNonce` - If nonce is not the sender's current nonce
+    /// * `UnsupportedToken` - If the token is not on the allow-list
+    #[allow(clippy::too_many_arguments)]
+    pub fn propose_distribution(
+        env: Env,
+        usdc_contract: Address,
+        from: Address,
+        nonce: u64,
+        accounts: AccountGroup,
+        total_amount: i128,
+        memo: Option<BytesN<32>>,
+        purpose: PurposeCode,
+    ) -> Result<u32, RemittanceSplitError> {
+        if total_amount <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        from.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::require_nonce(&env, &from, nonce)?;
+
+        if !Self::get_supported(&env).contains(&usdc_contract) {
+            Self::append_audit(&env, symbol_short!("propose"), &from, false);
+            return Err(RemittanceSplitError::UnsupportedToken);
+        }
+
+        let (amounts, remainder) =
+            Self::distribution_amounts(&env, &from, &usdc_contract, &accounts, total_amount)?;
+        let delay = Self::get_large_transfer_policy(env.clone(), from.clone())
+            .map(|p| p.delay_secs)
+            .unwrap_or(0);
+        let now = env.ledger().timestamp();
+
+        Self::extend_instance_ttl(&env);
+        let id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_PROP"))
+            .unwrap_or(0u32)
+            + 1;
+        Self::store_proposal(
+            &env,
+            &DistributionProposal {
+                id,
+                from: from.clone(),
+                token: usdc_contract,
+                accounts,
+                total_amount,
+                amounts: vec![&env, amounts[0], amounts[1], amounts[2], amounts[3]],
+                remainder,
+                memo: memo.unwrap_or_else(|| BytesN::from_array(&env, &[0u8; 32])),
+                purpose,
+                created_at: now,
+                execute_after: now.saturating_add(delay),
+                approved: false,
+                status: ProposalStatus::Pending,
+            },
+        );
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_PROP"), &id);
+
+        Self::increment_nonce(&env, &from)?;
+        Self::extend_owner_ttl(&env, &from);
+        Self::append_audit(&env, symbol_short!("propose"), &from, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("proposed")),
+            (id, from, total_amount),
+        );
+        Ok(id)
+    }
+
+    /// Co-signer approval, allowing the proposal to be committed before its
+    /// delay has elapsed.
+    ///
+    /// # Errors
+    /// * `ProposalNotFound` - If the proposal does not exist
+    /// * `ProposalNotPending` - If it was already committed or cancelled
+    /// * `Unauthorized` - If caller is not the sender's configured co-signer
+    pub fn approve_distribution(
+        env: Env,
+        caller: Address,
+        proposal_id: u32,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+
+        let mut proposal = Self::pending_proposal(&env, proposal_id)?;
+        let co_signer = Self::get_large_transfer_policy(env.clone(), proposal.from.clone())
+            .and_then(|p| p.co_signer);
+        if co_signer != Some(caller.clone()) {
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+
+        proposal.approved = true;
+        Self::store_proposal(&env, &proposal);
+
+        Self::append_audit(&env, symbol_short!("approve"), &caller, true);
+        Ok(())
+    }
+
+    /// Execute a proposed distribution once its delay has elapsed or the
+    /// co-signer has approved it. Only the sender can commit.
+    ///
+    /// # Errors
+    /// * `ProposalNotFound` - If the proposal does not exist
+    /// * `ProposalNotPending` - If it was already committed or cancelled
+    /// * `Unauthorized` - If caller is not the sender, or the contract is paused
+    /// * `ProposalNotReady` - If the delay is still running and it is not approved
+    /// * `UnsupportedToken` - If the token was removed from the allow-list since
+    pub fn commit_distribution(
+        env: Env,
+        caller: Address,
+        proposal_id: u32,
+    ) -> Result<bool, RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_not_paused(&env)?;
+        let _guard = ReentrancyGuard::enter(&env);
+
+        let mut proposal = Self::pending_proposal(&env, proposal_id)?;
+        if proposal.from != caller {
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+        if !proposal.approved && env.ledger().timestamp() < proposal.execute_after {
+            return Err(RemittanceSplitError::ProposalNotReady);
+        }
+        if !Self::get_supported(&env).contains(&proposal.token) {
+            return Err(RemittanceSplitError::UnsupportedToken);
+        }
+
+        let amounts = [
+            proposal.amounts.get(0).unwrap_or(0),
+            proposal.amounts.get(1).unwrap_or(0),
+            proposal.amounts.get(2).unwrap_or(0),
+            proposal.amounts.get(3).unwrap_or(0),
+        ];
+        Self::screen_transfers(
+            &env,
+            &proposal.token,
+            &proposal.from,
+            &proposal.from,
+            &proposal.accounts,
+            &amounts,
+        )?;
+        let distribution_id = Self::transfer_amounts(
+            &env,
+            &proposal.token,
+            &proposal.from,
+            &proposal.from,
+            &proposal.accounts,
+            &amounts,
+            proposal.total_amount,
+            proposal.remainder,
+            false,
+        );
+        Self::auto_invest(&env, &proposal.from, amounts[1]);
+
+        proposal.status = ProposalStatus::Committed;
+        Self::store_proposal(&env, &proposal);
+
+        Self::append_audit(&env, symbol_short!("commit"), &caller, true);
+        Self::append_remittance(
+            &env,
+            &caller,
+            &caller,
+            RemittanceRecord {
+                sender: proposal.from,
+                total_amount: proposal.total_amount,
+                memo: proposal.memo,
+                purpose: proposal.purpose,
+                timestamp: env.ledger().timestamp(),
+                amounts: proposal.amounts,
+                distribution_id,
+            },
+        );
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("committed")),
+            proposal_id,
+        );
+        Ok(true)
+    }
+
+    /// Cancel a pending proposal (sender only).
+    ///
+    /// # Errors
+    /// * `ProposalNotFound` - If the proposal does not exist
+    /// * `ProposalNotPending` - If it was already committed or cancelled
+    /// * `Unauthorized` - If caller is not the sender
+    pub fn cancel_distribution(
+        env: Env,
+        caller: Address,
+        proposal_id: u32,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+
+        let mut proposal = Self::pending_proposal(&env, proposal_id)?;
+        if proposal.from != caller {
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+
+        proposal.status = ProposalStatus::Cancelled;
+        Self::store_proposal(&env, &proposal);
+
+        Self::append_audit(&env, symbol_short!("cancel"), &caller, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("cancelled")),
+            proposal_id,
+        );
+        Ok(())
+    }
+
+    /// A proposal and its status. Committed and cancelled proposals stay
+    /// readable until their storage entry expires.
+    pub fn get_distribution_proposal(env: Env, proposal_id: u32) -> Option<DistributionProposal> {
+        env.storage().persistent().get(&(KEY_PROPOSAL, proposal_id))
+    }
+
+    /// Get the recorded distributions of `sender`, oldest first.
+    pub fn get_remittance_history(
+        env: Env,
+        sender: Address,
+        from_index: u32,
+        limit: u32,
+    ) -> Vec<RemittanceRecord> {
+        let history = Self::get_remittances(&env, &sender);
+        let end = from_index
+            .saturating_add(MAX_REMITTANCE_HISTORY.min(limit))
+            .min(history.len());
+        let mut out = Vec::new(&env);
+        for i in from_index..end {
+            if let Some(record) = history.get(i) {
+                out.push_back(record);
+            }
+        }
+        out
+    }
+
+    /// Line items of a distribution, one per category that received funds.
+    /// The ID is on the `Distributed` event and the remittance history.
+    pub fn get_distribution_receipts(env: Env, distribution_id: u64) -> Vec<DistributionReceipt> {
+        env.storage()
+            .persistent()
+            .get(&(KEY_RECEIPTS, distribution_id))
+            .unwrap_or_else(|| Vec::new(&env))
+    }
+
+    /// Aggregate the recorded distributions of `sender` by purpose code over
+    /// `[from_ts, to_ts]`.
+    ///
+    /// Only the most recent `MAX_REMITTANCE_HISTORY` distributions of each
+    /// sender are kept, so older remittances fall out of the aggregation.
+    pub fn get_purpose_totals(
+        env: Env,
+        sender: Address,
+        from_ts: u64,
+        to_ts: u64,
+    ) -> Vec<PurposeTotal> {
+        let mut totals: Vec<PurposeTotal> = Vec::new(&env);
+        for record in Self::get_remittances(&env, &sender).iter() {
+            if record.timestamp < from_ts || record.timestamp > to_ts {
+                continue;
+            }
+            let purpose = record.purpose;
+            match totals.iter().position(|t| t.purpose == purpose) {
+                Some(i) => {
+                    let mut total = totals.get(i as u32).unwrap();
+                    total.count += 1;
+                    total.total_amount = total.total_amount.saturating_add(record.total_amount);
+                    totals.set(i as u32, total);
+                }
+                None => totals.push_back(PurposeTotal {
+                    purpose,
+                    count: 1,
+                    total_amount: record.total_amount,
+                }),
+            }
+        }
+        totals
+    }
+
+    /// Compute how `sender`'s distributions over `[from_ts, to_ts]` were
+    /// actually allocated, after frozen categories were redirected.
+    ///
+    /// Percentages are 0 when there were no distributions in the window.
+    /// Like `get_purpose_totals`, only the retained history is covered.
+    pub fn get_effective_split(
+        env: Env,
+        sender: Address,
+        from_ts: u64,
+        to_ts: u64,
+    ) -> EffectiveSplit {
+        let mut count = 0u32;
+        let mut total = 0i128;
+        let mut amounts = [0i128; 4];
+        for record in Self::get_remittances(&env, &sender).iter() {
+            if record.timestamp < from_ts || record.timestamp > to_ts {
+                continue;
+            }
+            count += 1;
+            total = total.saturating_add(record.total_amount);
+            for (i, amount) in record.amounts.iter().take(4).enumerate() {
+                amounts[i] = amounts[i].saturating_add(amount);
+            }
+        }
+
+        let mut percentages_bps = Vec::new(&env);
+        for amount in amounts {
+            let bps = if total > 0 {
+                amount * 10_000 / total
+            } else {
+                0
+            };
+            percentages_bps.push_back(bps as u32);
+        }
+
+        EffectiveSplit {
+            remittance_count: count,
+            total_amount: total,
+            amounts: vec![&env, amounts[0], amounts[1], amounts[2], amounts[3]],
+            percentages_bps,
+            configured_percentages: Self::get_split(&env, sender),
+        }
+    }
+
+    /// Allow distributions in `token` (admin only).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    pub fn add_supported_token(
+        env: Env,
+        caller: Address,
+        token: Address,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        let mut tokens = Self::get_supported(&env);
+        if !tokens.contains(&token) {
+            tokens.push_back(token.clone());
+            Self::extend_instance_ttl(&env);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("TOKENS"), &tokens);
+        }
+
+        Self::append_audit(&env, symbol_short!("add_tok"), &caller, true);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("tok_add")), token);
+        Ok(())
+    }
+
+    /// Stop accepting distributions in `token` (admin only).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    /// * `UnsupportedToken` - If the token is not on the list
+    pub fn remove_supported_token(
+        env: Env,
+        caller: Address,
+        token: Address,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        let mut tokens = Self::get_supported(&env);
+        let index = tokens
+            .first_index_of(&token)
+            .ok_or(RemittanceSplitError::UnsupportedToken)?;
+        tokens.remove(index);
+
+        Self::extend_instance_ttl(&env);
+        env.storage()
+            .instance()
+            .set(&symbol_short!("TOKENS"), &tokens);
+
+        Self::append_audit(&env, symbol_short!("rm_tok"), &caller, true);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("tok_rm")), token);
+        Ok(())
+    }
+
+    /// Tokens accepted by `distribute_usdc`
+    pub fn get_supported_tokens(env: Env) -> Vec<Address> {
+        Self::get_supported(&env)
+    }
+
+    // -----------------------------------------------------------------------
+    // Rebalancing suggestions
+    // -----------------------------------------------------------------------
+
+    /// Set (or clear with `None`) the contracts `suggest_rebalance` reads
+    /// commitments from (admin only).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    pub fn set_rebalance_sources(
+        env: Env,
+        caller: Address,
+        sources: Option<RebalanceSources>,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        Self::extend_instance_ttl(&env);
+        match &sources {
+            Some(sources) => env.storage().instance().set(&KEY_REBALANCE, sources),
+            None => env.storage().instance().remove(&KEY_REBALANCE),
+        }
+        Self::append_audit(&env, symbol_short!("rebal_src"), &caller, true);
+        Ok(())
+    }
+
+    pub fn get_rebalance_sources(env: Env) -> Option<RebalanceSources> {
+        env.storage().instance().get(&KEY_REBALANCE)
+    }
+
+    /// Suggest new split percentages for `owner` from what their remittances
+    /// actually have to cover.
+    ///
+    /// Over the calendar months touched by the last `REBALANCE_WINDOW_SECS`,
+    /// the remittances distributed to the owner are compared with:
+    /// - the bills they paid, from the bill payments contract;
+    /// - their monthly premiums for each month, from the insurance contract;
+    /// - the shortfall of their savings goals, spread over
+    ///   `GOAL_CATCH_UP_MONTHS`, from the savings goals contract.
+    ///
+    /// Bills and insurance get the share they need, rounded up. Savings is
+    /// only raised, never lowered. If the shares add up to more than 100 they
+    /// are scaled down in proportion; otherwise spending takes the rest.
+    /// Categories whose source is not set or cannot be read keep their
+    /// configured percentage.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the owner has not initialized a split
+    pub fn suggest_rebalance(
+        env: Env,
+        owner: Address,
+    ) -> Result<RebalanceSuggestion, RemittanceSplitError> {
+        let config = Self::load_config(&env, &owner)?;
+        let mut suggestion = RebalanceSuggestion {
+            spending_percent: config.spending_percent,
+            savings_percent: config.savings_percent,
+            bills_percent: config.bills_percent,
+            insurance_percent: config.insurance_percent,
+            reasons: Vec::new(&env),
+        };
+
+        let now = env.ledger().timestamp();
+        let from_ts = now.saturating_sub(REBALANCE_WINDOW_SECS);
+        let first_month = month_index(from_ts);
+        let months = (month_index(now) - first_month + 1) as i128;
+        let received = Self::get_remittances(&env, &owner)
+            .iter()
+            .filter(|record| month_index(record.timestamp) >= first_month)
+            .fold(0i128, |total, record| {
+                total.saturating_add(record.total_amount)
+            });
+        if received <= 0 {
+            suggestion
+                .reasons
+                .push_back(RebalanceReason::NoRecentRemittances);
+            return Ok(suggestion);
+        }
+        let percent_of = |need: i128| -> u32 {
+            let percent = need.saturating_mul(100).saturating_add(received - 1) / received;
+            percent.clamp(0, 100) as u32
+        };
+
+        let sources = match Self::get_rebalance_sources(env.clone()) {
+            Some(sources) => sources,
+            None => return Ok(suggestion),
+        };
+        if let Some(bills) = &sources.bills {
+            if let Ok(Ok(paid)) =
+                BillPaymentsClient::new(&env, bills).try_get_paid_total(&owner, &from_ts, &now)
+            {
+                suggestion.bills_percent = percent_of(paid);
+            }
+        }
+        if let Some(insurance) = &sources.insurance {
+            if let Ok(Ok(premium)) =
+                InsuranceClient::new(&env, insurance).try_get_total_monthly_premium(&owner)
+            {
+                suggestion.insurance_percent = percent_of(premium.saturating_mul(months));
+            }
+        }
+        if let Some(savings) = &sources.savings {
+            if let Ok(Ok(portfolio)) =
+                SavingsGoalsClient::new(&env, savings).try_get_portfolio(&owner)
+            {
+                let shortfall = (portfolio.total_target - portfolio.total_saved).max(0);
+                let need = shortfall.saturating_mul(months) / GOAL_CATCH_UP_MONTHS as i128;
+                suggestion.savings_percent = percent_of(need).max(config.savings_percent);
+            }
+        }
+
+        let committed =
+            suggestion.savings_percent + suggestion.bills_percent + suggestion.insurance_percent;
+        if committed > 100 {
+            suggestion.savings_percent = suggestion.savings_percent * 100 / committed;
+            suggestion.bills_percent = suggestion.bills_percent * 100 / committed;
+            suggestion.insurance_percent = suggestion.insurance_percent * 100 / committed;
+        }
+        suggestion.spending_percent = 100
+            - suggestion.savings_percent
+            - suggestion.bills_percent
+            - suggestion.insurance_percent;
+
+        let reasons = &mut suggestion.reasons;
+        if suggestion.bills_percent > config.bills_percent {
+            reasons.push_back(RebalanceReason::BillsAboveSplit);
+        } else if suggestion.bills_percent < config.bills_percent {
+            reasons.push_back(RebalanceReason::BillsBelowSplit);
+        }
+        if suggestion.insurance_percent > config.insurance_percent {
+            reasons.push_back(RebalanceReason::PremiumsAboveSplit);
+        } else if suggestion.insurance_percent < config.insurance_percent {
+            reasons.push_back(RebalanceReason::PremiumsBelowSplit);
+        }
+        if suggestion.savings_percent > config.savings_percent {
+            reasons.push_back(RebalanceReason::GoalShortfall);
+        }
+        if committed > 100 {
+            reasons.push_back(RebalanceReason::NeedsExceedRemittances);
+        }
+        Ok(suggestion)
+    }
+
+    // -----------------------------------------------------------------------
+    // Compliance screening
+    // -----------------------------------------------------------------------
+
+    /// Set (or clear with `None`) the compliance checker contract (admin only).
+    ///
+    /// While set, every transfer of `distribute_usdc`, `commit_distribution`,
+    /// `batch_distribute` and `settle_inbound` must pass its `check_transfer`.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    pub fn set_compliance_checker(
+        env: Env,
+        caller: Address,
+        checker: Option<Address>,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        Self::extend_instance_ttl(&env);
+        match &checker {
+            Some(checker) => env.storage().instance().set(&KEY_COMPLIANCE, checker),
+            None => env.storage().instance().remove(&KEY_COMPLIANCE),
+        }
+        Self::append_audit(&env, symbol_short!("comply"), &caller, true);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("comply")), checker);
+        Ok(())
+    }
+
+    pub fn get_compliance_checker(env: Env) -> Option<Address> {
+        env.storage().instance().get(&KEY_COMPLIANCE)
+    }
+
+    /// Let transfers from `from` to `to` skip the compliance check, or remove
+    /// that override (admin only). Meant for false positives of the checker.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    pub fn set_compliance_override(
+        env: Env,
+        caller: Address,
+        from: Address,
+        to: Address,
+        allowed: bool,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        let mut overrides = Self::get_compliance_overrides(&env);
+        let pair = (from.clone(), to.clone());
+        if allowed {
+            overrides.set(pair, true);
+        } else {
+            overrides.remove(pair);
+        }
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(&KEY_OVERRIDES, &overrides);
+
+        Self::append_audit(&env, symbol_short!("override"), &caller, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("override")),
+            (from, to, allowed),
+        );
+        Ok(())
+    }
+
+    pub fn has_compliance_override(env: Env, from: Address, to: Address) -> bool {
+        Self::get_compliance_overrides(&env).contains_key((from, to))
+    }
+
+    // -----------------------------------------------------------------------
+    // Inbound remittance triggers
+    // -----------------------------------------------------------------------
+
+    /// Allow or disallow a relayer to report inbound remittances with
+    /// `settle_inbound` (admin only).
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is not the pause admin
+    pub fn set_inbound_relayer(
+        env: Env,
+        caller: Address,
+        relayer: Address,
+        allowed: bool,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        Self::require_admin(&env, &caller)?;
+
+        let mut relayers = Self::get_relayers(&env);
+        if allowed {
+            relayers.set(relayer.clone(), true);
+        } else {
+            relayers.remove(relayer.clone());
+        }
+        Self::extend_instance_ttl(&env);
+        env.storage().instance().set(&KEY_RELAYERS, &relayers);
+
+        Self::append_audit(&env, symbol_short!("relayer"), &caller, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("relayer")),
+            (relayer, allowed),
+        );
+        Ok(())
+    }
+
+    pub fn is_inbound_relayer(env: Env, relayer: Address) -> bool {
+        Self::get_relayers(&env).contains_key(relayer)
+    }
+
+    /// Have remittances of at least `min_amount` of `token` from
+    /// `expected_from` split automatically into `accounts` when they reach the
+    /// caller's account, replacing any previous watch.
+    ///
+    /// A relayer reports each arrival with `settle_inbound`, which moves the
+    /// tokens under an allowance the owner grants this contract on `token`.
+    /// The owner's current balance becomes the watch's baseline; registering
+    /// again resets it, for example after spending from the account.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the owner has not initialized a split
+    /// * `InvalidAmount` - If min_amount is not positive
+    /// * `UnsupportedToken` - If the token is not on the allow-list
+    pub fn register_inbound_watch(
+        env: Env,
+        owner: Address,
+        expected_from: Address,
+        token: Address,
+        min_amount: i128,
+        accounts: AccountGroup,
+        purpose: PurposeCode,
+    ) -> Result<(), RemittanceSplitError> {
+        owner.require_auth();
+        Self::load_config(&env, &owner)?;
+        if min_amount <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+        if !Self::get_supported(&env).contains(&token) {
+            return Err(RemittanceSplitError::UnsupportedToken);
+        }
+
+        let before = AuditLog::digest(&env, Self::get_inbound_watch(env.clone(), owner.clone()));
+        let baseline_balance = TokenClient::new(&env, &token).balance(&owner);
+        let watch = InboundWatch {
+            expected_from,
+            token,
+            min_amount,
+            accounts,
+            purpose,
+            settled_count: 0,
+            total_settled: 0,
+            baseline_balance,
+        };
+        Self::set_owned(&env, KEY_INBOUND, &owner, &watch);
+        AUDIT_LOG.append(
+            &env,
+            &owner,
+            &owner,
+            symbol_short!("watch"),
+            before,
+            AuditLog::digest(&env, watch.clone()),
+        );
+        Self::extend_owner_ttl(&env, &owner);
+
+        Self::append_audit(&env, symbol_short!("watch"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("watch")),
+            (owner, watch.expected_from, watch.min_amount),
+        );
+        Ok(())
+    }
+
+    /// Stop splitting inbound remittances automatically.
+    ///
+    /// # Errors
+    /// * `InboundWatchNotFound` - If the owner has no watch
+    pub fn cancel_inbound_watch(env: Env, owner: Address) -> Result<(), RemittanceSplitError> {
+        owner.require_auth();
+        let Some(watch) = Self::get_inbound_watch(env.clone(), owner.clone()) else {
+            return Err(RemittanceSplitError::InboundWatchNotFound);
+        };
+        Self::remove_owned(&env, KEY_INBOUND, &owner);
+        AUDIT_LOG.append(
+            &env,
+            &owner,
+            &owner,
+            symbol_short!("unwatch"),
+            AuditLog::digest(&env, watch),
+            AuditLog::empty_digest(&env),
+        );
+        Self::append_audit(&env, symbol_short!("unwatch"), &owner, true);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("unwatch")), owner);
+        Ok(())
+    }
+
+    pub fn get_inbound_watch(env: Env, owner: Address) -> Option<InboundWatch> {
+        Self::get_owned(&env, KEY_INBOUND, &owner)
+    }
+
+    /// Split a remittance that has reached `owner`'s account, as reported by
+    /// an allowed relayer, according to the owner's inbound watch.
+    ///
+    /// Each inbound payment is split once: `proof.tx_hash` is remembered for
+    /// `INBOUND_REF_WINDOW`, and the hash is also recorded as the memo. The
+    /// owner's nonce is not used. Large transfers still need the two-phase
+    /// flow.
+    ///
+    /// The proof itself is taken on the relayer's word. To bound what a
+    /// dishonest relayer can move, the amount may not exceed the growth of
+    /// the owner's balance since the watch's `baseline_balance`, which is
+    /// then reset to the balance after the split.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If the contract is paused or relayer is not allowed
+    /// * `InboundWatchNotFound` - If the owner has no watch
+    /// * `InboundNotEligible` - If the sender or amount does not match the watch
+    /// * `InboundAlreadySettled` - If the payment has already been split
+    /// * `UnsupportedToken` - If the watched token has left the allow-list
+    /// * `ProposalRequired` - If the amount reaches the owner's large-transfer
+    ///   threshold
+    /// * `InsufficientBalance` - If the owner's balance does not cover the amount
+    /// * `InboundExceedsReceived` - If the amount is more than the owner's
+    ///   balance has grown since the baseline
+    /// * `ComplianceRejected` - If the compliance checker rejects a transfer
+    pub fn settle_inbound(
+        env: Env,
+        relayer: Address,
+        owner: Address,
+        proof: InboundProof,
+    ) -> Result<bool, RemittanceSplitError> {
+        relayer.require_auth();
+        Self::require_not_paused(&env)?;
+        let _guard = ReentrancyGuard::enter(&env);
+        if !Self::get_relayers(&env).contains_key(relayer.clone()) {
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+
+        let mut watch = Self::get_inbound_watch(env.clone(), owner.clone())
+            .ok_or(RemittanceSplitError::InboundWatchNotFound)?;
+        if proof.from != watch.expected_from || proof.amount < watch.min_amount {
+            Self::append_audit(&env, symbol_short!("inbound"), &relayer, false);
+            return Err(RemittanceSplitError::InboundNotEligible);
+        }
+        if INBOUND_REFS.record_ref(&env, &proof.tx_hash).is_err() {
+            Self::append_audit(&env, symbol_short!("inbound"), &relayer, false);
+            return Err(RemittanceSplitError::InboundAlreadySettled);
+        }
+        if !Self::get_supported(&env).contains(&watch.token) {
+            Self::append_audit(&env, symbol_short!("inbound"), &relayer, false);
+            return Err(RemittanceSplitError::UnsupportedToken);
+        }
+        if let Some(policy) = Self::get_large_transfer_policy(env.clone(), owner.clone()) {
+            if proof.amount >= policy.threshold {
+                Self::append_audit(&env, symbol_short!("inbound"), &relayer, false);
+                return Err(RemittanceSplitError::ProposalRequired);
+            }
+        }
+        let token_client = TokenClient::new(&env, &watch.token);
+        let balance = token_client.balance(&owner);
+        if balance < proof.amount {
+            Self::append_audit(&env, symbol_short!("inbound"), &relayer, false);
+            return Err(RemittanceSplitError::InsufficientBalance);
+        }
+        if proof.amount > balance.saturating_sub(watch.baseline_balance) {
+            Self::append_audit(&env, symbol_short!("inbound"), &relayer, false);
+            return Err(RemittanceSplitError::InboundExceedsReceived);
+        }
+
+        let (amounts, remainder) =
+            Self::distribution_amounts(&env, &owner, &watch.token, &watch.accounts, proof.amount)?;
+        if let Err(err) = Self::screen_transfers(
+            &env,
+            &watch.token,
+            &owner,
+            &owner,
+            &watch.accounts,
+            &amounts,
+        ) {
+            Self::append_audit(&env, symbol_short!("inbound"), &relayer, false);
+            return Err(err);
+        }
+        let distribution_id = Self::transfer_amounts(
+            &env,
+            &watch.token,
+            &owner,
+            &owner,
+            &watch.accounts,
+            &amounts,
+            proof.amount,
+            remainder,
+            true,
+        );
+        Self::auto_invest(&env, &owner, amounts[1]);
+
+        watch.settled_count += 1;
+        watch.total_settled = watch.total_settled.saturating_add(proof.amount);
+        watch.baseline_balance = token_client.balance(&owner);
+        Self::set_owned(&env, KEY_INBOUND, &owner, &watch);
+        Self::extend_owner_ttl(&env, &owner);
+
+        Self::append_audit(&env, symbol_short!("inbound"), &relayer, true);
+        Self::append_remittance(
+            &env,
+            &relayer,
+            &owner,
+            RemittanceRecord {
+                sender: owner.clone(),
+                total_amount: proof.amount,
+                memo: proof.tx_hash.clone(),
+                purpose: watch.purpose,
+                timestamp: env.ledger().timestamp(),
+                amounts: vec![&env, amounts[0], amounts[1], amounts[2], amounts[3]],
+                distribution_id,
+            },
+        );
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("inbound")),
+            (owner, proof.from, proof.amount, proof.tx_hash),
+        );
         Ok(true)
     }
 
@@ -479,9 +2360,10 @@ impl RemittanceSplit {
 
     pub fn get_split_allocations(
         env: &Env,
+        owner: Address,
         total_amount: i128,
     ) -> Result<Vec<Allocation>, RemittanceSplitError> {
-        let amounts = Self::calculate_split(env.clone(), total_amount)?;
+        let amounts = Self::calculate_split(env.clone(), owner, total_amount)?;
         let categories = [
             symbol_short!("SPENDING"),
             symbol_short!("SAVINGS"),
@@ -489,11 +2371,436 @@ impl RemittanceSplit {
             symbol_short!("INSURANCE"),
         ];
 
-        let mut result = Vec::new(env);
-        for (category, amount) in categories.into_iter().zip(amounts.into_iter()) {
-            result.push_back(Allocation { category, amount });
+        let mut result = Vec::new(env);
+        for (category, amount) in categories.into_iter().zip(amounts) {
+            result.push_back(Allocation { category, amount });
+        }
+        Ok(result)
+    }
+
+    /// Set the category that receives the share of any of the owner's frozen
+    /// categories.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the owner has not initialized a split
+    /// * `Unauthorized` - If the contract is paused
+    /// * `InvalidFallback` - If the requested fallback is currently frozen
+    pub fn set_fallback_category(
+        env: Env,
+        owner: Address,
+        category: SplitCategory,
+    ) -> Result<(), RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::load_config(&env, &owner)?;
+
+        if Self::get_frozen(&env, &owner).contains(category) {
+            Self::append_audit(&env, symbol_short!("fallback"), &owner, false);
+            return Err(RemittanceSplitError::InvalidFallback);
+        }
+
+        let before = AuditLog::digest(
+            &env,
+            Self::get_fallback_category(env.clone(), owner.clone()),
+        );
+        Self::set_owned(&env, KEY_FALLBACK, &owner, &category);
+        AUDIT_LOG.append(
+            &env,
+            &owner,
+            &owner,
+            symbol_short!("fallback"),
+            before,
+            AuditLog::digest(&env, category),
+        );
+        Self::extend_owner_ttl(&env, &owner);
+
+        Self::append_audit(&env, symbol_short!("fallback"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("fallback")),
+            category,
+        );
+        Ok(())
+    }
+
+    pub fn get_fallback_category(env: Env, owner: Address) -> Option<SplitCategory> {
+        Self::get_owned(&env, KEY_FALLBACK, &owner)
+    }
+
+    /// Temporarily suspend an allocation, e.g. when its recipient address is
+    /// compromised. While frozen, the category's share is paid to the
+    /// fallback category instead.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the owner has not initialized a split
+    /// * `Unauthorized` - If the contract is paused
+    /// * `FallbackNotConfigured` - If no fallback category has been set
+    /// * `InvalidFallback` - If `category` is the fallback category itself
+    pub fn freeze_category(
+        env: Env,
+        owner: Address,
+        category: SplitCategory,
+    ) -> Result<(), RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::load_config(&env, &owner)?;
+
+        let fallback = Self::get_fallback_category(env.clone(), owner.clone())
+            .ok_or(RemittanceSplitError::FallbackNotConfigured)?;
+        if fallback == category {
+            Self::append_audit(&env, symbol_short!("freeze"), &owner, false);
+            return Err(RemittanceSplitError::InvalidFallback);
+        }
+
+        let mut frozen = Self::get_frozen(&env, &owner);
+        let before = AuditLog::digest(&env, frozen.clone());
+        if !frozen.contains(category) {
+            frozen.push_back(category);
+        }
+        Self::set_owned(&env, KEY_FROZEN, &owner, &frozen);
+        AUDIT_LOG.append(
+            &env,
+            &owner,
+            &owner,
+            symbol_short!("freeze"),
+            before,
+            AuditLog::digest(&env, frozen),
+        );
+        Self::extend_owner_ttl(&env, &owner);
+
+        Self::append_audit(&env, symbol_short!("freeze"), &owner, true);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("frozen")), category);
+        Ok(())
+    }
+
+    /// Resume normal allocation for a previously frozen category.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the owner has not initialized a split
+    /// * `Unauthorized` - If the contract is paused
+    /// * `CategoryNotFrozen` - If the category is not frozen
+    pub fn unfreeze_category(
+        env: Env,
+        owner: Address,
+        category: SplitCategory,
+    ) -> Result<(), RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::load_config(&env, &owner)?;
+
+        let mut frozen = Self::get_frozen(&env, &owner);
+        let before = AuditLog::digest(&env, frozen.clone());
+        let index = frozen
+            .first_index_of(category)
+            .ok_or(RemittanceSplitError::CategoryNotFrozen)?;
+        frozen.remove(index);
+        Self::set_owned(&env, KEY_FROZEN, &owner, &frozen);
+        AUDIT_LOG.append(
+            &env,
+            &owner,
+            &owner,
+            symbol_short!("unfreeze"),
+            before,
+            AuditLog::digest(&env, frozen),
+        );
+        Self::extend_owner_ttl(&env, &owner);
+
+        Self::append_audit(&env, symbol_short!("unfreeze"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("unfrozen")),
+            category,
+        );
+        Ok(())
+    }
+
+    pub fn get_frozen_categories(env: Env, owner: Address) -> Vec<SplitCategory> {
+        Self::get_frozen(&env, &owner)
+    }
+
+    /// Switch the owner's distributions to top-up mode, or back to their
+    /// percentages with `None`.
+    ///
+    /// In top-up mode each distribution fills the category accounts in
+    /// `priority` order up to their target balances, e.g. keeping the bills
+    /// account at 200 USDC, and sends what is left to savings. Frozen
+    /// categories are not topped up. `calculate_split` still previews the
+    /// percentages, since balances are only known at distribution time.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the owner has not initialized a split
+    /// * `Unauthorized` - If the contract is paused
+    /// * `InvalidTopUpTargets` - If a target is negative or a category
+    ///   repeats in the priority order
+    pub fn set_top_up_targets(
+        env: Env,
+        owner: Address,
+        targets: Option<TopUpTargets>,
+    ) -> Result<(), RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::load_config(&env, &owner)?;
+
+        if let Some(targets) = &targets {
+            let mut seen = Vec::new(&env);
+            for category in targets.priority.iter() {
+                if seen.contains(category) {
+                    Self::append_audit(&env, symbol_short!("top_up"), &owner, false);
+                    return Err(RemittanceSplitError::InvalidTopUpTargets);
+                }
+                seen.push_back(category);
+            }
+            if [
+                targets.spending,
+                targets.savings,
+                targets.bills,
+                targets.insurance,
+            ]
+            .iter()
+            .any(|target| *target < 0)
+            {
+                Self::append_audit(&env, symbol_short!("top_up"), &owner, false);
+                return Err(RemittanceSplitError::InvalidTopUpTargets);
+            }
+        }
+
+        let before = AuditLog::digest(&env, Self::get_top_up_targets(env.clone(), owner.clone()));
+        match &targets {
+            Some(targets) => Self::set_owned(&env, KEY_TOP_UP, &owner, targets),
+            None => Self::remove_owned(&env, KEY_TOP_UP, &owner),
+        }
+        AUDIT_LOG.append(
+            &env,
+            &owner,
+            &owner,
+            symbol_short!("top_up"),
+            before,
+            AuditLog::digest(&env, targets.clone()),
+        );
+        Self::extend_owner_ttl(&env, &owner);
+
+        Self::append_audit(&env, symbol_short!("top_up"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("top_up")),
+            targets.is_some(),
+        );
+        Ok(())
+    }
+
+    pub fn get_top_up_targets(env: Env, owner: Address) -> Option<TopUpTargets> {
+        Self::get_owned(&env, KEY_TOP_UP, &owner)
+    }
+
+    /// Route 100% of `owner`'s distributions to `safe_address`, for when a
+    /// category account is compromised. The owner or their guardian may
+    /// activate it; the split configuration is left as it is.
+    ///
+    /// Every leg of a distribution, including committed proposals and
+    /// inbound settlements, is paid straight to the safe address, bypassing
+    /// the claim window. Activating again cancels a pending deactivation.
+    /// A different safe address only replaces the one in force after
+    /// `EMERGENCY_REDIRECT_COOLDOWN_SECS`, so a stolen key cannot point the
+    /// redirect at itself; activating with the address in force cancels
+    /// such a change.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the owner has not initialized a split
+    /// * `Unauthorized` - If caller is neither the owner nor their guardian
+    pub fn activate_emergency_redirect(
+        env: Env,
+        caller: Address,
+        owner: Address,
+        safe_address: Address,
+    ) -> Result<(), RemittanceSplitError> {
+        caller.require_auth();
+        Self::load_config(&env, &owner)?;
+        if !Self::is_owner_or_guardian(&env, &caller, &owner) {
+            Self::append_audit(&env, symbol_short!("emergency"), &caller, false);
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+
+        let current = Self::get_emergency_redirect(env.clone(), owner.clone());
+        let before = AuditLog::digest(&env, current.clone());
+        let now = env.ledger().timestamp();
+        let redirect = match current {
+            Some(mut redirect) => {
+                if redirect.safe_address == safe_address {
+                    redirect.pending_address = None;
+                    redirect.change_at = None;
+                } else {
+                    redirect.pending_address = Some(safe_address.clone());
+                    redirect.change_at = Some(now.saturating_add(EMERGENCY_REDIRECT_COOLDOWN_SECS));
+                }
+                redirect.ends_at = None;
+                redirect
+            }
+            None => EmergencyRedirect {
+                safe_address: safe_address.clone(),
+                activated_by: caller.clone(),
+                activated_at: now,
+                ends_at: None,
+                pending_address: None,
+                change_at: None,
+            },
+        };
+        Self::set_owned(&env, KEY_EMERGENCY, &owner, &redirect);
+        AUDIT_LOG.append(
+            &env,
+            &owner,
+            &caller,
+            symbol_short!("emergency"),
+            before,
+            AuditLog::digest(&env, Some(redirect)),
+        );
+        Self::extend_owner_ttl(&env, &owner);
+
+        Self::append_audit(&env, symbol_short!("emergency"), &caller, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("emergency")),
+            (owner, caller, safe_address),
+        );
+        Ok(())
+    }
+
+    /// Start winding down `owner`'s emergency redirect (owner or guardian).
+    /// Distributions keep going to the safe address for
+    /// `EMERGENCY_REDIRECT_COOLDOWN_SECS`, then follow the split again.
+    ///
+    /// # Returns
+    /// When normal splits resume
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If caller is neither the owner nor their guardian
+    /// * `RedirectNotActive` - If the owner has no active redirect
+    pub fn deactivate_emergency_redirect(
+        env: Env,
+        caller: Address,
+        owner: Address,
+    ) -> Result<u64, RemittanceSplitError> {
+        caller.require_auth();
+        if !Self::is_owner_or_guardian(&env, &caller, &owner) {
+            Self::append_audit(&env, symbol_short!("emerg_off"), &caller, false);
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+        let mut redirect = Self::get_emergency_redirect(env.clone(), owner.clone())
+            .ok_or(RemittanceSplitError::RedirectNotActive)?;
+        if let Some(ends_at) = redirect.ends_at {
+            return Ok(ends_at);
+        }
+
+        let before = AuditLog::digest(&env, Some(redirect.clone()));
+        let ends_at = env
+            .ledger()
+            .timestamp()
+            .saturating_add(EMERGENCY_REDIRECT_COOLDOWN_SECS);
+        redirect.ends_at = Some(ends_at);
+        Self::set_owned(&env, KEY_EMERGENCY, &owner, &redirect);
+        AUDIT_LOG.append(
+            &env,
+            &owner,
+            &caller,
+            symbol_short!("emerg_off"),
+            before,
+            AuditLog::digest(&env, Some(redirect)),
+        );
+        Self::extend_owner_ttl(&env, &owner);
+
+        Self::append_audit(&env, symbol_short!("emerg_off"), &caller, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("emerg_off")),
+            (owner, caller, ends_at),
+        );
+        Ok(ends_at)
+    }
+
+    /// `owner`'s emergency redirect, while it is in force, with a requested
+    /// address change applied once its cooldown has passed
+    pub fn get_emergency_redirect(env: Env, owner: Address) -> Option<EmergencyRedirect> {
+        let now = env.ledger().timestamp();
+        let mut redirect = Self::get_owned::<EmergencyRedirect>(&env, KEY_EMERGENCY, &owner)
+            .filter(|redirect| redirect.ends_at.is_none_or(|ends_at| now < ends_at))?;
+        if redirect.change_at.is_some_and(|change_at| now >= change_at) {
+            if let Some(address) = redirect.pending_address.take() {
+                redirect.safe_address = address;
+            }
+            redirect.change_at = None;
+        }
+        Some(redirect)
+    }
+
+    /// Choose where the rounding remainder of each split goes: a category, or
+    /// `Dust` to hold it in the contract until claimed. Defaults to the
+    /// insurance category.
+    ///
+    /// # Errors
+    /// * `NotInitialized` - If the owner has not initialized a split
+    /// * `Unauthorized` - If the contract is paused
+    pub fn set_remainder_sink(
+        env: Env,
+        owner: Address,
+        sink: RemainderSink,
+    ) -> Result<(), RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        Self::load_config(&env, &owner)?;
+
+        let before = AuditLog::digest(&env, Self::remainder_sink(&env, &owner));
+        Self::set_owned(&env, KEY_REMAINDER_SINK, &owner, &sink);
+        AUDIT_LOG.append(
+            &env,
+            &owner,
+            &owner,
+            symbol_short!("rem_sink"),
+            before,
+            AuditLog::digest(&env, sink),
+        );
+        Self::extend_owner_ttl(&env, &owner);
+
+        Self::append_audit(&env, symbol_short!("rem_sink"), &owner, true);
+        env.events()
+            .publish((symbol_short!("split"), symbol_short!("rem_sink")), sink);
+        Ok(())
+    }
+
+    pub fn get_remainder_sink(env: Env, owner: Address) -> RemainderSink {
+        Self::remainder_sink(&env, &owner)
+    }
+
+    /// Dust of `token` held for `owner`
+    pub fn get_dust_balance(env: Env, owner: Address, token: Address) -> i128 {
+        Self::get_dust(&env, &owner).get(token).unwrap_or(0)
+    }
+
+    /// Transfer the dust of `token` held for `owner` to `to`.
+    ///
+    /// # Errors
+    /// * `Unauthorized` - If the contract is paused
+    /// * `InvalidAmount` - If no dust of `token` is held for the owner
+    pub fn claim_dust(
+        env: Env,
+        owner: Address,
+        token: Address,
+        to: Address,
+    ) -> Result<i128, RemittanceSplitError> {
+        owner.require_auth();
+        Self::require_not_paused(&env)?;
+        let _guard = ReentrancyGuard::enter(&env);
+
+        let mut balances = Self::get_dust(&env, &owner);
+        let amount = balances.get(token.clone()).unwrap_or(0);
+        if amount <= 0 {
+            Self::append_audit(&env, symbol_short!("dust"), &owner, false);
+            return Err(RemittanceSplitError::InvalidAmount);
         }
-        Ok(result)
+        balances.remove(token.clone());
+        Self::set_owned(&env, KEY_DUST, &owner, &balances);
+        TokenClient::new(&env, &token).transfer(&env.current_contract_address(), &to, &amount);
+
+        Self::append_audit(&env, symbol_short!("dust"), &owner, true);
+        env.events().publish(
+            (symbol_short!("split"), symbol_short!("dust_clm")),
+            (owner, token, amount),
+        );
+        Ok(amount)
     }
 
     pub fn get_nonce(env: Env, address: Address) -> u64 {
@@ -501,12 +2808,7 @@ impl RemittanceSplit {
     }
 
     fn get_nonce_value(env: &Env, address: &Address) -> u64 {
-        let nonces: Option<Map<Address, u64>> =
-            env.storage().instance().get(&symbol_short!("NONCES"));
-        nonces
-            .as_ref()
-            .and_then(|m: &Map<Address, u64>| m.get(address.clone()))
-            .unwrap_or(0)
+        Self::get_owned(env, KEY_NONCE, address).unwrap_or(0)
     }
 
     pub fn export_snapshot(
@@ -514,14 +2816,7 @@ impl RemittanceSplit {
         caller: Address,
     ) -> Result<Option<ExportSnapshot>, RemittanceSplitError> {
         caller.require_auth();
-        let config: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        if config.owner != caller {
-            return Err(RemittanceSplitError::Unauthorized);
-        }
+        let config = Self::load_config(&env, &caller)?;
         let checksum = Self::compute_checksum(SNAPSHOT_VERSION, &config);
         Ok(Some(ExportSnapshot {
             version: SNAPSHOT_VERSION,
@@ -549,12 +2844,8 @@ impl RemittanceSplit {
             return Err(RemittanceSplitError::ChecksumMismatch);
         }
 
-        let existing: SplitConfig = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("CONFIG"))
-            .ok_or(RemittanceSplitError::NotInitialized)?;
-        if existing.owner != caller {
+        let current = Self::load_config(&env, &caller)?;
+        if snapshot.config.owner != caller {
             Self::append_audit(&env, symbol_short!("import"), &caller, false);
             return Err(RemittanceSplitError::Unauthorized);
         }
@@ -569,25 +2860,34 @@ impl RemittanceSplit {
         }
 
         Self::extend_instance_ttl(&env);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("CONFIG"), &snapshot.config);
-        env.storage().instance().set(
-            &symbol_short!("SPLIT"),
-            &vec![
-                &env,
-                snapshot.config.spending_percent,
-                snapshot.config.savings_percent,
-                snapshot.config.bills_percent,
-                snapshot.config.insurance_percent,
-            ],
+        let percents = [
+            snapshot.config.spending_percent,
+            snapshot.config.savings_percent,
+            snapshot.config.bills_percent,
+            snapshot.config.insurance_percent,
+        ];
+        if Self::stage_if_guarded(&env, &caller, &current, percents) {
+            Self::increment_nonce(&env, &caller)?;
+            return Ok(false);
+        }
+        Self::set_owned(&env, KEY_CONFIG, &caller, &snapshot.config);
+        AUDIT_LOG.append(
+            &env,
+            &caller,
+            &caller,
+            symbol_short!("import"),
+            AuditLog::digest(&env, current),
+            AuditLog::digest(&env, snapshot.config),
         );
 
         Self::increment_nonce(&env, &caller)?;
+        Self::extend_owner_ttl(&env, &caller);
         Self::append_audit(&env, symbol_short!("import"), &caller, true);
         Ok(true)
     }
 
+    /// Rotating log of the last `MAX_AUDIT_ENTRIES` calls across all owners,
+    /// failed ones included.
     pub fn get_audit_log(env: Env, from_index: u32, limit: u32) -> Vec<AuditEntry> {
         let log: Option<Vec<AuditEntry>> = env.storage().instance().get(&symbol_short!("AUDIT"));
         let log = log.unwrap_or_else(|| Vec::new(&env));
@@ -606,6 +2906,570 @@ impl RemittanceSplit {
         out
     }
 
+    /// Page of `owner`'s append-only audit log, oldest first: every change
+    /// to their split or frozen categories and every distribution by their
+    /// split, with who made it and digests of the state before and after.
+    /// The limit is clamped to 1..=50 (0 means 20).
+    pub fn get_owner_audit_log(
+        env: Env,
+        owner: Address,
+        offset: u32,
+        limit: u32,
+    ) -> Vec<AuditRecord> {
+        AUDIT_LOG.page(&env, &owner, offset, limit)
+    }
+
+    fn load_config(env: &Env, owner: &Address) -> Result<SplitConfig, RemittanceSplitError> {
+        Self::get_owned(env, KEY_CONFIG, owner).ok_or(RemittanceSplitError::NotInitialized)
+    }
+
+    /// Store `percents` as `owner`'s pending change instead of applying it
+    /// when they have a guardian and the change moves more than the
+    /// guardian's `max_shift`. Returns whether the change was staged.
+    fn stage_if_guarded(
+        env: &Env,
+        owner: &Address,
+        config: &SplitConfig,
+        percents: [u32; 4],
+    ) -> bool {
+        let Some(policy) = Self::get_guardian(env.clone(), owner.clone()) else {
+            return false;
+        };
+        let current = [
+            config.spending_percent,
+            config.savings_percent,
+            config.bills_percent,
+            config.insurance_percent,
+        ];
+        // Percentages sum to 100 on both sides, so half the absolute
+        // differences is what moved from some categories to others.
+        let shift = current
+            .iter()
+            .zip(percents.iter())
+            .map(|(a, b)| a.abs_diff(*b))
+            .sum::<u32>()
+            / 2;
+        if shift <= policy.max_shift {
+            return false;
+        }
+
+        let pending = PendingSplitChange {
+            spending_percent: percents[0],
+            savings_percent: percents[1],
+            bills_percent: percents[2],
+            insurance_percent: percents[3],
+            shift,
+            proposed_at: env.ledger().timestamp(),
+        };
+        Self::set_owned(env, KEY_PENDING_SPLIT, owner, &pending);
+        Self::extend_owner_ttl(env, owner);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::ChangePending),
+            (owner.clone(), policy.guardian, shift),
+        );
+        true
+    }
+
+    /// Apply `percents` to `owner`'s split on behalf of `actor`
+    fn apply_split(
+        env: &Env,
+        actor: &Address,
+        owner: &Address,
+        config: &mut SplitConfig,
+        percents: [u32; 4],
+    ) {
+        let before = AuditLog::digest(env, config.clone());
+        let [spending_percent, savings_percent, bills_percent, insurance_percent] = percents;
+        config.spending_percent = spending_percent;
+        config.savings_percent = savings_percent;
+        config.bills_percent = bills_percent;
+        config.insurance_percent = insurance_percent;
+        Self::set_owned(env, KEY_CONFIG, owner, config);
+        AUDIT_LOG.append(
+            env,
+            owner,
+            actor,
+            symbol_short!("update"),
+            before,
+            AuditLog::digest(env, config.clone()),
+        );
+        Self::extend_owner_ttl(env, owner);
+
+        let event = SplitInitializedEvent {
+            spending_percent,
+            savings_percent,
+            bills_percent,
+            insurance_percent,
+            timestamp: env.ledger().timestamp(),
+        };
+        env.events().publish((SPLIT_INITIALIZED,), event);
+        env.events()
+            .publish((symbol_short!("split"), SplitEvent::Updated), owner.clone());
+    }
+
+    fn require_admin(env: &Env, caller: &Address) -> Result<(), RemittanceSplitError> {
+        if Self::get_pause_admin(env) != Some(caller.clone()) {
+            return Err(RemittanceSplitError::Unauthorized);
+        }
+        Ok(())
+    }
+
+    fn get_owned<V: TryFromVal<Env, Val>>(env: &Env, tag: Symbol, owner: &Address) -> Option<V> {
+        env.storage().persistent().get(&(tag, owner.clone()))
+    }
+
+    fn set_owned<V: IntoVal<Env, Val>>(env: &Env, tag: Symbol, owner: &Address, value: &V) {
+        let key = (tag, owner.clone());
+        env.storage().persistent().set(&key, value);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, OWNER_LIFETIME_THRESHOLD, OWNER_BUMP_AMOUNT);
+    }
+
+    fn remove_owned(env: &Env, tag: Symbol, owner: &Address) {
+        env.storage().persistent().remove(&(tag, owner.clone()));
+    }
+
+    /// Extend the TTL of every persistent entry held for `owner`.
+    fn extend_owner_ttl(env: &Env, owner: &Address) {
+        let storage = env.storage().persistent();
+        for tag in [
+            KEY_CONFIG,
+            KEY_NONCE,
+            KEY_FALLBACK,
+            KEY_FROZEN,
+            KEY_LARGE_POLICY,
+            KEY_HISTORY,
+            KEY_REMAINDER_SINK,
+            KEY_DUST,
+            KEY_GUARDIAN,
+            KEY_PENDING_SPLIT,
+            KEY_AUTO_INVEST,
+            KEY_AUTO_SCHEDULE,
+            KEY_CLAIM_WINDOW,
+            KEY_ESCROW,
+            KEY_INBOUND,
+            KEY_TOP_UP,
+            KEY_EMERGENCY,
+            KEY_SCHEDULES,
+        ] {
+            let key = (tag, owner.clone());
+            if storage.has(&key) {
+                storage.extend_ttl(&key, OWNER_LIFETIME_THRESHOLD, OWNER_BUMP_AMOUNT);
+            }
+        }
+    }
+
+    /// Ask the compliance checker, if one is set, to approve each transfer of
+    /// a distribution from `from` to `owner`'s routed accounts. Pairs with an
+    /// admin override are not checked.
+    fn screen_transfers(
+        env: &Env,
+        token: &Address,
+        from: &Address,
+        owner: &Address,
+        accounts: &AccountGroup,
+        amounts: &[i128; 4],
+    ) -> Result<(), RemittanceSplitError> {
+        let checker: Address = match env.storage().instance().get(&KEY_COMPLIANCE) {
+            Some(checker) => checker,
+            None => return Ok(()),
+        };
+        let client = ComplianceClient::new(env, &checker);
+        let overrides = Self::get_compliance_overrides(env);
+        let accounts = Self::routed_accounts(env, owner, accounts);
+        let recipients = [
+            &accounts.spending,
+            &accounts.savings,
+            &accounts.bills,
+            &accounts.insurance,
+        ];
+        for (recipient, amount) in recipients.into_iter().zip(amounts) {
+            if *amount <= 0 || overrides.contains_key((from.clone(), recipient.clone())) {
+                continue;
+            }
+            if !client.check_transfer(from, recipient, amount, token) {
+                return Err(RemittanceSplitError::ComplianceRejected);
+            }
+        }
+        Ok(())
+    }
+
+    fn get_compliance_overrides(env: &Env) -> Map<(Address, Address), bool> {
+        env.storage()
+            .instance()
+            .get(&KEY_OVERRIDES)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    fn get_relayers(env: &Env) -> Map<Address, bool> {
+        env.storage()
+            .instance()
+            .get(&KEY_RELAYERS)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    /// Pay out the category amounts from `from`, routed by the household
+    /// `owner` whose split they came from, or escrow them under `owner` when
+    /// the household uses the claim model. Whatever of `total_amount` they do
+    /// not cover is the dust remainder, moved into the contract and credited
+    /// to `owner`. Records a receipt per leg and publishes the `Distributed`
+    /// event.
+    ///
+    /// With `use_allowance` the tokens move under this contract's allowance
+    /// on `from` instead of `from`'s authorization.
+    ///
+    /// Returns the new distribution's ID.
+    #[allow(clippy::too_many_arguments)]
+    fn transfer_amounts(
+        env: &Env,
+        token_address: &Address,
+        from: &Address,
+        owner: &Address,
+        accounts: &AccountGroup,
+        amounts: &[i128; 4],
+        total_amount: i128,
+        remainder: i128,
+        use_allowance: bool,
+    ) -> u64 {
+        let token = TokenClient::new(env, token_address);
+        let pay = |to: &Address, amount: &i128| {
+            if use_allowance {
+                token.transfer_from(&env.current_contract_address(), from, to, amount);
+            } else {
+                token.transfer(from, to, amount);
+            }
+        };
+        let accounts = Self::routed_accounts(env, owner, accounts);
+        let shares = [
+            (SplitCategory::Spending, &accounts.spending, amounts[0]),
+            (SplitCategory::Savings, &accounts.savings, amounts[1]),
+            (SplitCategory::Bills, &accounts.bills, amounts[2]),
+            (SplitCategory::Insurance, &accounts.insurance, amounts[3]),
+        ];
+        // Redirected funds go straight to the safe address
+        let claim_window = match Self::get_emergency_redirect(env.clone(), owner.clone()) {
+            Some(_) => None,
+            None => Self::get_owned::<u64>(env, KEY_CLAIM_WINDOW, owner),
+        };
+        let distribution_id =
+            Self::record_receipts(env, token_address, from, &shares, claim_window.is_some());
+        match claim_window {
+            Some(window) => {
+                let escrowed: i128 = amounts.iter().filter(|a| **a > 0).sum();
+                if escrowed > 0 {
+                    pay(&env.current_contract_address(), &escrowed);
+                }
+                let now = env.ledger().timestamp();
+                let expires_at = now.saturating_add(window);
+                let mut escrow = Self::get_escrow(env, owner);
+                for (category, recipient, amount) in shares {
+                    if amount <= 0 {
+                        continue;
+                    }
+                    let entry = EscrowedAllocation {
+                        token: token_address.clone(),
+                        category,
+                        recipient: recipient.clone(),
+                        amount,
+                        expires_at,
+                    };
+                    escrow = Self::add_to_escrow(env, &escrow, entry.clone(), now);
+                    env.events().publish(
+                        (symbol_short!("split"), SplitEvent::AllocationEscrowed),
+                        entry,
+                    );
+                }
+                Self::set_escrow(env, owner, &escrow);
+            }
+            None => {
+                for (_, recipient, amount) in shares {
+                    if amount > 0 {
+                        pay(recipient, &amount);
+                    }
+                }
+            }
+        }
+
+        let dust = total_amount - amounts.iter().sum::<i128>();
+        if dust > 0 {
+            pay(&env.current_contract_address(), &dust);
+            let mut balances = Self::get_dust(env, owner);
+            let held = balances.get(token_address.clone()).unwrap_or(0);
+            balances.set(token_address.clone(), held + dust);
+            Self::set_owned(env, KEY_DUST, owner, &balances);
+        }
+
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::Distributed),
+            DistributionEvent {
+                distribution_id,
+                sender: from.clone(),
+                total_amount,
+                amounts: vec![env, amounts[0], amounts[1], amounts[2], amounts[3]],
+                remainder,
+                remainder_sink: Self::remainder_sink(env, owner),
+            },
+        );
+        distribution_id
+    }
+
+    /// Assign the next distribution ID and store a receipt for each leg with
+    /// a positive amount.
+    fn record_receipts(
+        env: &Env,
+        token: &Address,
+        from: &Address,
+        shares: &[(SplitCategory, &Address, i128); 4],
+        escrowed: bool,
+    ) -> u64 {
+        let distribution_id = env
+            .storage()
+            .instance()
+            .get(&symbol_short!("NEXT_DIST"))
+            .unwrap_or(0u64)
+            + 1;
+        env.storage()
+            .instance()
+            .set(&symbol_short!("NEXT_DIST"), &distribution_id);
+
+        let timestamp = env.ledger().timestamp();
+        let mut receipts = Vec::new(env);
+        for (category, recipient, amount) in shares.iter() {
+            if *amount <= 0 {
+                continue;
+            }
+            receipts.push_back(DistributionReceipt {
+                receipt_id: distribution_id * 4 + *category as u64,
+                distribution_id,
+                sender: from.clone(),
+                category: *category,
+                recipient: (*recipient).clone(),
+                token: token.clone(),
+                amount: *amount,
+                escrowed,
+                timestamp,
+            });
+        }
+        let key = (KEY_RECEIPTS, distribution_id);
+        env.storage().persistent().set(&key, &receipts);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, OWNER_LIFETIME_THRESHOLD, OWNER_BUMP_AMOUNT);
+        distribution_id
+    }
+
+    /// Create or top up `owner`'s auto-invest savings schedule with
+    /// `savings_amount`. A failing savings call does not block the
+    /// distribution; the allocation has already reached the savings account.
+    fn auto_invest(env: &Env, owner: &Address, savings_amount: i128) {
+        let config: AutoInvestConfig = match Self::get_owned(env, KEY_AUTO_INVEST, owner) {
+            Some(config) => config,
+            None => return,
+        };
+        // The savings share went to the safe address, not the savings account
+        if Self::get_emergency_redirect(env.clone(), owner.clone()).is_some() {
+            return;
+        }
+        let now = env.ledger().timestamp();
+        if savings_amount <= 0 || config.target_date <= now {
+            return;
+        }
+        let runs = (config.target_date - now).div_ceil(config.interval);
+        let per_run = savings_amount / runs as i128;
+        if per_run == 0 {
+            return;
+        }
+
+        let savings = SavingsGoalsClient::new(env, &config.savings_contract);
+        let current = Self::get_owned::<u32>(env, KEY_AUTO_SCHEDULE, owner)
+            .and_then(|id| savings.try_get_savings_schedule(&id).ok()?.ok()?)
+            .filter(|schedule| schedule.active);
+        let schedule_id = match current {
+            Some(schedule) => {
+                let next_due = schedule.next_due.max(now + 1);
+                let amount = schedule.amount.saturating_add(per_run);
+                match savings.try_modify_savings_schedule(
+                    owner,
+                    &schedule.id,
+                    &amount,
+                    &next_due,
+                    &config.interval,
+                ) {
+                    Ok(Ok(_)) => schedule.id,
+                    _ => return,
+                }
+            }
+            None => match savings.try_create_savings_schedule(
+                owner,
+                &config.goal_id,
+                &per_run,
+                &now.saturating_add(config.interval),
+                &config.interval,
+            ) {
+                Ok(Ok(id)) => id,
+                _ => return,
+            },
+        };
+
+        Self::set_owned(env, KEY_AUTO_SCHEDULE, owner, &schedule_id);
+        env.events().publish(
+            (symbol_short!("split"), SplitEvent::AutoInvested),
+            (owner.clone(), schedule_id, per_run),
+        );
+    }
+
+    fn remainder_sink(env: &Env, owner: &Address) -> RemainderSink {
+        Self::get_owned(env, KEY_REMAINDER_SINK, owner)
+            .unwrap_or(RemainderSink::Category(SplitCategory::Insurance))
+    }
+
+    fn get_dust(env: &Env, owner: &Address) -> Map<Address, i128> {
+        Self::get_owned(env, KEY_DUST, owner).unwrap_or_else(|| Map::new(env))
+    }
+
+    fn get_escrow(env: &Env, sender: &Address) -> Vec<EscrowedAllocation> {
+        Self::get_owned(env, KEY_ESCROW, sender).unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn set_escrow(env: &Env, sender: &Address, escrow: &Vec<EscrowedAllocation>) {
+        if escrow.is_empty() {
+            Self::remove_owned(env, KEY_ESCROW, sender);
+        } else {
+            Self::set_owned(env, KEY_ESCROW, sender, escrow);
+        }
+    }
+
+    /// Add `share` to an escrow, folding in the shares already held for the
+    /// same token, category and recipient: unexpired ones join `share` and
+    /// take its expiry, and expired ones are merged into one. The escrow thus
+    /// holds at most one unexpired and one expired share per leg.
+    fn add_to_escrow(
+        env: &Env,
+        escrow: &Vec<EscrowedAllocation>,
+        share: EscrowedAllocation,
+        now: u64,
+    ) -> Vec<EscrowedAllocation> {
+        let mut share = share;
+        let mut expired: Option<EscrowedAllocation> = None;
+        let mut out = Vec::new(env);
+        for entry in escrow.iter() {
+            if entry.token != share.token
+                || entry.category != share.category
+                || entry.recipient != share.recipient
+            {
+                out.push_back(entry);
+            } else if now <= entry.expires_at {
+                share.amount = share.amount.saturating_add(entry.amount);
+            } else {
+                expired = Some(match expired {
+                    Some(mut merged) => {
+                        merged.amount = merged.amount.saturating_add(entry.amount);
+                        merged
+                    }
+                    None => entry,
+                });
+            }
+        }
+        if let Some(merged) = expired {
+            out.push_back(merged);
+        }
+        out.push_back(share);
+        out
+    }
+
+    /// Split `sender`'s escrow into the total of the entries matching `take`
+    /// and the entries left.
+    fn take_escrow(
+        env: &Env,
+        sender: &Address,
+        take: impl Fn(&EscrowedAllocation) -> bool,
+    ) -> (i128, Vec<EscrowedAllocation>) {
+        let mut total = 0i128;
+        let mut remaining = Vec::new(env);
+        for entry in Self::get_escrow(env, sender).iter() {
+            if take(&entry) {
+                total = total.saturating_add(entry.amount);
+            } else {
+                remaining.push_back(entry);
+            }
+        }
+        (total, remaining)
+    }
+
+    fn store_proposal(env: &Env, proposal: &DistributionProposal) {
+        let key = (KEY_PROPOSAL, proposal.id);
+        env.storage().persistent().set(&key, proposal);
+        env.storage()
+            .persistent()
+            .extend_ttl(&key, OWNER_LIFETIME_THRESHOLD, OWNER_BUMP_AMOUNT);
+    }
+
+    fn pending_proposal(
+        env: &Env,
+        proposal_id: u32,
+    ) -> Result<DistributionProposal, RemittanceSplitError> {
+        let proposal = Self::get_distribution_proposal(env.clone(), proposal_id)
+            .ok_or(RemittanceSplitError::ProposalNotFound)?;
+        if proposal.status != ProposalStatus::Pending {
+            return Err(RemittanceSplitError::ProposalNotPending);
+        }
+        Ok(proposal)
+    }
+
+    fn get_supported(env: &Env) -> Vec<Address> {
+        env.storage()
+            .instance()
+            .get(&symbol_short!("TOKENS"))
+            .unwrap_or_else(|| Vec::new(env))
+    }
+
+    fn is_owner_or_guardian(env: &Env, caller: &Address, owner: &Address) -> bool {
+        caller == owner
+            || Self::get_guardian(env.clone(), owner.clone())
+                .is_some_and(|policy| policy.guardian == *caller)
+    }
+
+    /// The accounts a distribution from `owner` is actually paid to: all
+    /// legs go to the safe address while an emergency redirect is in force.
+    fn routed_accounts(env: &Env, owner: &Address, accounts: &AccountGroup) -> AccountGroup {
+        match Self::get_emergency_redirect(env.clone(), owner.clone()) {
+            Some(redirect) => AccountGroup {
+                spending: redirect.safe_address.clone(),
+                savings: redirect.safe_address.clone(),
+                bills: redirect.safe_address.clone(),
+                insurance: redirect.safe_address,
+            },
+            None => accounts.clone(),
+        }
+    }
+
+    fn get_frozen(env: &Env, owner: &Address) -> Vec<SplitCategory> {
+        Self::get_owned(env, KEY_FROZEN, owner).unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Move the share of every frozen category onto the owner's fallback category.
+    fn redirect_frozen(
+        env: &Env,
+        owner: &Address,
+        amounts: &mut [i128; 4],
+    ) -> Result<(), RemittanceSplitError> {
+        let frozen = Self::get_frozen(env, owner);
+        if frozen.is_empty() {
+            return Ok(());
+        }
+        let fallback: SplitCategory = Self::get_owned(env, KEY_FALLBACK, owner)
+            .ok_or(RemittanceSplitError::FallbackNotConfigured)?;
+        for category in frozen.iter() {
+            let share = amounts[category as usize];
+            amounts[category as usize] = 0;
+            amounts[fallback as usize] = amounts[fallback as usize]
+                .checked_add(share)
+                .ok_or(RemittanceSplitError::Overflow)?;
+        }
+        Ok(())
+    }
+
     fn require_nonce(
         env: &Env,
         address: &Address,
@@ -623,15 +3487,7 @@ impl RemittanceSplit {
         let next = current
             .checked_add(1)
             .ok_or(RemittanceSplitError::Overflow)?;
-        let mut nonces: Map<Address, u64> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("NONCES"))
-            .unwrap_or_else(|| Map::new(env));
-        nonces.set(address.clone(), next);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("NONCES"), &nonces);
+        Self::set_owned(env, KEY_NONCE, address, &next);
         Ok(())
     }
 
@@ -673,19 +3529,57 @@ impl RemittanceSplit {
         env.storage().instance().set(&symbol_short!("AUDIT"), &log);
     }
 
+    fn get_remittances(env: &Env, sender: &Address) -> Vec<RemittanceRecord> {
+        Self::get_owned(env, KEY_HISTORY, sender).unwrap_or_else(|| Vec::new(env))
+    }
+
+    /// Add a distribution to the sender's history and audit log. `actor`
+    /// triggered it and `split_owner` is whose split allocated it; the audit
+    /// entry digests that split before and the record after.
+    fn append_remittance(
+        env: &Env,
+        actor: &Address,
+        split_owner: &Address,
+        record: RemittanceRecord,
+    ) {
+        let split = Self::get_owned::<SplitConfig>(env, KEY_CONFIG, split_owner)
+            .map(|config| AuditLog::digest(env, config))
+            .unwrap_or_else(|| AuditLog::empty_digest(env));
+        AUDIT_LOG.append(
+            env,
+            &record.sender,
+            actor,
+            symbol_short!("distrib"),
+            split,
+            AuditLog::digest(env, record.clone()),
+        );
+        let mut history = Self::get_remittances(env, &record.sender);
+        if history.len() >= MAX_REMITTANCE_HISTORY {
+            history.pop_front();
+        }
+        let sender = record.sender.clone();
+        history.push_back(record);
+        Self::set_owned(env, KEY_HISTORY, &sender, &history);
+    }
+
+    /// Category amounts for `total_amount`, and the rounding remainder left
+    /// after flooring every share. The remainder is added to the owner's sink
+    /// category, or left out of the amounts when the sink is `Dust`.
     fn calculate_split_amounts(
         env: &Env,
+        owner: &Address,
         total_amount: i128,
         emit_events: bool,
-    ) -> Result<[i128; 4], RemittanceSplitError> {
+    ) -> Result<([i128; 4], i128), RemittanceSplitError> {
         if total_amount <= 0 {
             return Err(RemittanceSplitError::InvalidAmount);
         }
 
-        let split = Self::get_split(env);
+        let split = Self::get_split(env, owner.clone());
         let s0 = split.get(0).unwrap() as i128;
         let s1 = split.get(1).unwrap() as i128;
         let s2 = split.get(2).unwrap() as i128;
+        let s3 = split.get(3).unwrap() as i128;
 
         let spending = total_amount
             .checked_mul(s0)
@@ -700,11 +3594,25 @@ impl RemittanceSplit {
             .and_then(|n| n.checked_div(100))
             .ok_or(RemittanceSplitError::Overflow)?;
         let insurance = total_amount
+            .checked_mul(s3)
+            .and_then(|n| n.checked_div(100))
+            .ok_or(RemittanceSplitError::Overflow)?;
+        let remainder = total_amount
             .checked_sub(spending)
             .and_then(|n| n.checked_sub(savings))
             .and_then(|n| n.checked_sub(bills))
+            .and_then(|n| n.checked_sub(insurance))
             .ok_or(RemittanceSplitError::Overflow)?;
 
+        let mut amounts = [spending, savings, bills, insurance];
+        if let RemainderSink::Category(category) = Self::remainder_sink(env, owner) {
+            amounts[category as usize] = amounts[category as usize]
+                .checked_add(remainder)
+                .ok_or(RemittanceSplitError::Overflow)?;
+        }
+        Self::redirect_frozen(env, owner, &mut amounts)?;
+        let [spending, savings, bills, insurance] = amounts;
+
         if emit_events {
             let event = SplitCalculatedEvent {
                 total_amount,
@@ -712,6 +3620,7 @@ impl RemittanceSplit {
                 savings_amount: savings,
                 bills_amount: bills,
                 insurance_amount: insurance,
+                remainder,
                 timestamp: env.ledger().timestamp(),
             };
             env.events().publish((SPLIT_CALCULATED,), event);
@@ -721,7 +3630,54 @@ impl RemittanceSplit {
             );
         }
 
-        Ok([spending, savings, bills, insurance])
+        Ok(([spending, savings, bills, insurance], remainder))
+    }
+
+    /// Amounts of a distribution from `owner`'s funds to `accounts`: by the
+    /// owner's top-up targets when they have set them, otherwise by their
+    /// percentages.
+    fn distribution_amounts(
+        env: &Env,
+        owner: &Address,
+        token: &Address,
+        accounts: &AccountGroup,
+        total_amount: i128,
+    ) -> Result<([i128; 4], i128), RemittanceSplitError> {
+        let targets = match Self::get_top_up_targets(env.clone(), owner.clone()) {
+            Some(targets) => targets,
+            None => return Self::calculate_split_amounts(env, owner, total_amount, false),
+        };
+        if total_amount <= 0 {
+            return Err(RemittanceSplitError::InvalidAmount);
+        }
+
+        let token = TokenClient::new(env, token);
+        let frozen = Self::get_frozen(env, owner);
+        let mut amounts = [0i128; 4];
+        let mut left = total_amount;
+        for category in targets.priority.iter() {
+            if left == 0 {
+                break;
+            }
+            if frozen.contains(category) {
+                continue;
+            }
+            let account = match category {
+                SplitCategory::Spending => &accounts.spending,
+                SplitCategory::Savings => &accounts.savings,
+                SplitCategory::Bills => &accounts.bills,
+                SplitCategory::Insurance => &accounts.insurance,
+            };
+            let shortfall = targets
+                .target(category)
+                .saturating_sub(token.balance(account));
+            let fill = shortfall.clamp(0, left);
+            amounts[category as usize] = fill;
+            left -= fill;
+        }
+        amounts[SplitCategory::Savings as usize] += left;
+        Self::redirect_frozen(env, owner, &mut amounts)?;
+        Ok((amounts, 0))
     }
 
     /// Extend the TTL of instance storage
@@ -751,11 +3707,8 @@ impl RemittanceSplit {
 
         Self::extend_instance_ttl(&env);
 
-        let mut schedules: Map<u32, RemittanceSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("REM_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+        let mut schedules: Map<u32, RemittanceSchedule> =
+            Self::get_owned(&env, KEY_SCHEDULES, &owner).unwrap_or_else(|| Map::new(&env));
 
         let next_schedule_id = env
             .storage()
@@ -778,9 +3731,7 @@ impl RemittanceSplit {
         };
 
         schedules.set(next_schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("REM_SCH"), &schedules);
+        Self::set_owned(&env, KEY_SCHEDULES, &owner, &schedules);
         env.storage()
             .instance()
             .set(&symbol_short!("NEXT_RSCH"), &next_schedule_id);
@@ -814,29 +3765,20 @@ impl RemittanceSplit {
 
         Self::extend_instance_ttl(&env);
 
-        let mut schedules: Map<u32, RemittanceSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("REM_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+        let mut schedules: Map<u32, RemittanceSchedule> =
+            Self::get_owned(&env, KEY_SCHEDULES, &caller).unwrap_or_else(|| Map::new(&env));
 
         let mut schedule = schedules
             .get(schedule_id)
             .ok_or(RemittanceSplitError::ScheduleNotFound)?;
 
-        if schedule.owner != caller {
-            return Err(RemittanceSplitError::Unauthorized);
-        }
-
         schedule.amount = amount;
         schedule.next_due = next_due;
         schedule.interval = interval;
         schedule.recurring = interval > 0;
 
         schedules.set(schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("REM_SCH"), &schedules);
+        Self::set_owned(&env, KEY_SCHEDULES, &caller, &schedules);
 
         env.events().publish(
             (symbol_short!("schedule"), ScheduleEvent::Modified),
@@ -855,26 +3797,17 @@ impl RemittanceSplit {
 
         Self::extend_instance_ttl(&env);
 
-        let mut schedules: Map<u32, RemittanceSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("REM_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
+        let mut schedules: Map<u32, RemittanceSchedule> =
+            Self::get_owned(&env, KEY_SCHEDULES, &caller).unwrap_or_else(|| Map::new(&env));
 
         let mut schedule = schedules
             .get(schedule_id)
             .ok_or(RemittanceSplitError::ScheduleNotFound)?;
 
-        if schedule.owner != caller {
-            return Err(RemittanceSplitError::Unauthorized);
-        }
-
         schedule.active = false;
 
         schedules.set(schedule_id, schedule);
-        env.storage()
-            .instance()
-            .set(&symbol_short!("REM_SCH"), &schedules);
+        Self::set_owned(&env, KEY_SCHEDULES, &caller, &schedules);
 
         env.events().publish(
             (symbol_short!("schedule"), ScheduleEvent::Cancelled),
@@ -885,34 +3818,23 @@ impl RemittanceSplit {
     }
 
     pub fn get_remittance_schedules(env: Env, owner: Address) -> Vec<RemittanceSchedule> {
-        let schedules: Map<u32, RemittanceSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("REM_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-
-        let mut result = Vec::new(&env);
-        for (_, schedule) in schedules.iter() {
-            if schedule.owner == owner {
-                result.push_back(schedule);
-            }
-        }
-        result
+        let schedules: Map<u32, RemittanceSchedule> =
+            Self::get_owned(&env, KEY_SCHEDULES, &owner).unwrap_or_else(|| Map::new(&env));
+        schedules.values()
     }
 
-    pub fn get_remittance_schedule(env: Env, schedule_id: u32) -> Option<RemittanceSchedule> {
-        let schedules: Map<u32, RemittanceSchedule> = env
-            .storage()
-            .instance()
-            .get(&symbol_short!("REM_SCH"))
-            .unwrap_or_else(|| Map::new(&env));
-
+    pub fn get_remittance_schedule(
+        env: Env,
+        owner: Address,
+        schedule_id: u32,
+    ) -> Option<RemittanceSchedule> {
+        let schedules: Map<u32, RemittanceSchedule> = Self::get_owned(&env, KEY_SCHEDULES, &owner)?;
         schedules.get(schedule_id)
     }
 }
 
 #[cfg(test)]
-mod test {
+mod tests {
     use super::*;
     use soroban_sdk::testutils::storage::Instance as _;
     use soroban_sdk::testutils::{Address as _, Events, Ledger, LedgerInfo};
@@ -950,7 +3872,7 @@ mod test {
         let events_before = env.events().all().len();
 
         // Calculate split
-        let result = client.calculate_split(&1000);
+        let result = client.calculate_split(&owner, &1000);
         assert_eq!(result.len(), 4);
         assert_eq!(result.get(0).unwrap(), 400); // 40% of 1000
         assert_eq!(result.get(1).unwrap(), 300); // 30% of 1000
@@ -974,8 +3896,8 @@ mod test {
         client.initialize_split(&owner, &0, &50, &25, &15, &10);
 
         // Calculate split twice
-        client.calculate_split(&2000);
-        client.calculate_split(&3000);
+        client.calculate_split(&owner, &2000);
+        client.calculate_split(&owner, &3000);
 
         // Should have 5 events total (1 init + 2*2 calc)
         let events = env.events().all();
@@ -1135,11 +4057,11 @@ mod test {
         });
 
         // Calculate split to exercise read path
-        let result = client.calculate_split(&1000);
+        let result = client.calculate_split(&owner, &1000);
         assert_eq!(result.len(), 4);
 
         // Config should be accessible with updated values
-        let config = client.get_config();
+        let config = client.get_config(&owner);
         assert!(
             config.is_some(),
             "Config must persist across ledger advancements"
@@ -1162,6 +4084,7 @@ mod test {
     // ============================================================================
 
     /// 1. test_initialize_split_success
+    ///
     /// Owner authorizes the call, percentages sum to 100, config is stored correctly.
     #[test]
     fn test_initialize_split_success() {
@@ -1175,7 +4098,7 @@ mod test {
         assert!(result, "initialize_split should return true on success");
 
         let config = client
-            .get_config()
+            .get_config(&owner)
             .expect("config should be stored after init");
         assert_eq!(config.owner, owner);
         assert_eq!(config.spending_percent, 50);
@@ -1186,6 +4109,7 @@ mod test {
     }
 
     /// 2. test_initialize_split_requires_auth
+    ///
     /// Calling initialize_split without the owner authorizing should panic.
     #[test]
     #[should_panic]
@@ -1201,6 +4125,7 @@ mod test {
     }
 
     /// 3. test_initialize_split_percentages_must_sum_to_100
+    ///
     /// Percentages that do not sum to 100 must return PercentagesDoNotSumTo100.
     #[test]
     fn test_initialize_split_percentages_must_sum_to_100() {
@@ -1226,6 +4151,7 @@ mod test {
     }
 
     /// 4. test_initialize_split_already_initialized_panics
+    ///
     /// Calling initialize_split a second time should return AlreadyInitialized.
     #[test]
     fn test_initialize_split_already_initialized_panics() {
@@ -1244,7 +4170,9 @@ mod test {
     }
 
     /// 5. test_update_split_owner_only
-    /// Only the owner can call update_split; any other address must get Unauthorized.
+    ///
+    /// update_split only ever touches the caller's own split; another address
+    /// without a split gets NotInitialized and cannot change the owner's.
     #[test]
     fn test_update_split_owner_only() {
         let env = Env::default();
@@ -1256,9 +4184,9 @@ mod test {
 
         client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
-        // other address is not the owner — must fail
+        // other address has no split of its own — must fail
         let result = client.try_update_split(&other, &0, &40, &40, &10, &10);
-        assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+        assert_eq!(result, Err(Ok(RemittanceSplitError::NotInitialized)));
 
         // owner can update just fine
         let ok = client.update_split(&owner, &1, &40, &40, &10, &10);
@@ -1266,6 +4194,7 @@ mod test {
     }
 
     /// 6. test_update_split_percentages_must_sum_to_100
+    ///
     /// update_split must reject percentages that do not sum to 100.
     #[test]
     fn test_update_split_percentages_must_sum_to_100() {
@@ -1293,6 +4222,7 @@ mod test {
     }
 
     /// 7. test_get_split_returns_default_before_init
+    ///
     /// Before initialize_split is called, get_split must return the hardcoded
     /// default of [50, 30, 15, 5].
     #[test]
@@ -1300,8 +4230,9 @@ mod test {
         let env = Env::default();
         let contract_id = env.register_contract(None, RemittanceSplit);
         let client = RemittanceSplitClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
 
-        let split = client.get_split();
+        let split = client.get_split(&owner);
         assert_eq!(split.len(), 4);
         assert_eq!(split.get(0).unwrap(), 50);
         assert_eq!(split.get(1).unwrap(), 30);
@@ -1310,18 +4241,21 @@ mod test {
     }
 
     /// 8. test_get_config_returns_none_before_init
+    ///
     /// Before initialize_split is called, get_config must return None.
     #[test]
     fn test_get_config_returns_none_before_init() {
         let env = Env::default();
         let contract_id = env.register_contract(None, RemittanceSplit);
         let client = RemittanceSplitClient::new(&env, &contract_id);
+        let owner = Address::generate(&env);
 
-        let config = client.get_config();
+        let config = client.get_config(&owner);
         assert!(config.is_none(), "get_config should be None before init");
     }
 
     /// 9. test_get_config_returns_some_after_init
+    ///
     /// After initialize_split, get_config must return Some with correct owner.
     #[test]
     fn test_get_config_returns_some_after_init() {
@@ -1333,7 +4267,7 @@ mod test {
 
         client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
-        let config = client.get_config();
+        let config = client.get_config(&owner);
         assert!(config.is_some(), "get_config should be Some after init");
 
         let config = config.unwrap();
@@ -1348,6 +4282,7 @@ mod test {
     }
 
     /// 10. test_calculate_split_positive_amount
+    ///
     /// Correct amounts for a positive total; insurance receives the remainder.
     #[test]
     fn test_calculate_split_positive_amount() {
@@ -1360,7 +4295,7 @@ mod test {
         // 50 / 30 / 15 / 5
         client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
-        let amounts = client.calculate_split(&1000);
+        let amounts = client.calculate_split(&owner, &1000);
         assert_eq!(amounts.len(), 4);
         // spending: 50% of 1000 = 500
         assert_eq!(amounts.get(0).unwrap(), 500);
@@ -1373,6 +4308,7 @@ mod test {
     }
 
     /// 11. test_calculate_split_zero_or_negative_panics
+    ///
     /// total_amount of 0 or any negative value must return InvalidAmount.
     #[test]
     fn test_calculate_split_zero_or_negative_panics() {
@@ -1385,15 +4321,15 @@ mod test {
         client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
         // Zero
-        let result_zero = client.try_calculate_split(&0);
+        let result_zero = client.try_calculate_split(&owner, &0);
         assert_eq!(result_zero, Err(Ok(RemittanceSplitError::InvalidAmount)));
 
         // Negative
-        let result_neg = client.try_calculate_split(&-1);
+        let result_neg = client.try_calculate_split(&owner, &-1);
         assert_eq!(result_neg, Err(Ok(RemittanceSplitError::InvalidAmount)));
 
         // Large negative
-        let result_large_neg = client.try_calculate_split(&-9999);
+        let result_large_neg = client.try_calculate_split(&owner, &-9999);
         assert_eq!(
             result_large_neg,
             Err(Ok(RemittanceSplitError::InvalidAmount))
@@ -1401,6 +4337,7 @@ mod test {
     }
 
     /// 12. test_calculate_split_rounding
+    ///
     /// The sum of all split amounts must always equal total_amount exactly
     /// (insurance absorbs any integer division remainder).
     #[test]
@@ -1415,22 +4352,23 @@ mod test {
         client.initialize_split(&owner, &0, &33, &33, &33, &1);
 
         // total = 100: 33+33+33 = 99, insurance gets remainder = 1
-        let amounts = client.calculate_split(&100);
+        let amounts = client.calculate_split(&owner, &100);
         let sum: i128 = amounts.iter().sum();
         assert_eq!(sum, 100, "split amounts must sum to total_amount");
 
         // total = 7: each of 33% = 2 (floor), remainder = 7 - 2 - 2 - 2 = 1
-        let amounts2 = client.calculate_split(&7);
+        let amounts2 = client.calculate_split(&owner, &7);
         let sum2: i128 = amounts2.iter().sum();
         assert_eq!(sum2, 7, "split amounts must sum to total_amount");
 
         // total = 1000
-        let amounts3 = client.calculate_split(&1000);
+        let amounts3 = client.calculate_split(&owner, &1000);
         let sum3: i128 = amounts3.iter().sum();
         assert_eq!(sum3, 1000, "split amounts must sum to total_amount");
     }
 
     /// 13. test_event_emitted_on_initialize_and_update
+    ///
     /// Events must be published when initialize_split and update_split are called.
     #[test]
     fn test_event_emitted_on_initialize_and_update() {
@@ -3,7 +3,7 @@
 use super::*;
 use soroban_sdk::{
     testutils::{Address as AddressTrait, Events, Ledger, LedgerInfo},
-    Address, Env, IntoVal, Symbol, TryFromVal, Val, Vec,
+    Address, BytesN, Env, IntoVal, Symbol, TryFromVal, Vec,
 };
 
 fn set_time(env: &Env, timestamp: u64) {
@@ -38,9 +38,9 @@ fn test_initialize_split() {
         &5,  // insurance
     );
 
-    assert_eq!(success, true);
+    assert!(success);
 
-    let config = client.get_config().unwrap();
+    let config = client.get_config(&owner).unwrap();
     assert_eq!(config.owner, owner);
     assert_eq!(config.spending_percent, 50);
     assert_eq!(config.savings_percent, 30);
@@ -62,7 +62,10 @@ fn test_initialize_split_invalid_sum() {
         &50, &50, &10, // Sums to 110
         &0,
     );
-    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidPercentages)));
+    assert_eq!(
+        result,
+        Err(Ok(RemittanceSplitError::PercentagesDoNotSumTo100))
+    );
 }
 
 #[test]
@@ -92,9 +95,9 @@ fn test_update_split() {
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
     let success = client.update_split(&owner, &1, &40, &40, &10, &10);
-    assert_eq!(success, true);
+    assert!(success);
 
-    let config = client.get_config().unwrap();
+    let config = client.get_config(&owner).unwrap();
     assert_eq!(config.spending_percent, 40);
     assert_eq!(config.savings_percent, 40);
     assert_eq!(config.bills_percent, 10);
@@ -113,8 +116,10 @@ fn test_update_split_unauthorized() {
 
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
+    // Another user only ever addresses their own (missing) split
     let result = client.try_update_split(&other, &0, &40, &40, &10, &10);
-    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotInitialized)));
+    assert_eq!(client.get_config(&owner).unwrap().spending_percent, 50);
 }
 
 #[test]
@@ -129,7 +134,7 @@ fn test_calculate_split() {
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
     // Test with 1000 units
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
 
     // spending: 50% of 1000 = 500
     // savings: 30% of 1000 = 300
@@ -160,7 +165,7 @@ fn test_calculate_split_rounding() {
     // insurance = total - spending - savings - bills
     // 100 - 33 - 33 - 33 = 1. Correct.
 
-    let amounts = client.calculate_split(&100);
+    let amounts = client.calculate_split(&owner, &100);
     assert_eq!(amounts.get(0).unwrap(), 33);
     assert_eq!(amounts.get(1).unwrap(), 33);
     assert_eq!(amounts.get(2).unwrap(), 33);
@@ -177,7 +182,7 @@ fn test_calculate_split_zero_amount() {
     env.mock_all_auths();
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
-    let result = client.try_calculate_split(&0);
+    let result = client.try_calculate_split(&owner, &0);
     assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidAmount)));
 }
 
@@ -198,7 +203,7 @@ fn test_calculate_complex_rounding() {
     // 23% = 230
     // 41% = 410
     // Sum = 1000. Perfect.
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 170);
     assert_eq!(amounts.get(1).unwrap(), 190);
     assert_eq!(amounts.get(2).unwrap(), 230);
@@ -209,7 +214,7 @@ fn test_calculate_complex_rounding() {
     // 19% of 3 = 0
     // 23% of 3 = 0
     // Remainder = 3 - 0 - 0 - 0 = 3. All goes to insurance.
-    let tiny_amounts = client.calculate_split(&3);
+    let tiny_amounts = client.calculate_split(&owner, &3);
     assert_eq!(tiny_amounts.get(0).unwrap(), 0);
     assert_eq!(tiny_amounts.get(3).unwrap(), 3);
 }
@@ -229,7 +234,7 @@ fn test_create_remittance_schedule() {
     let schedule_id = client.create_remittance_schedule(&owner, &10000, &3000, &86400);
     assert_eq!(schedule_id, 1);
 
-    let schedule = client.get_remittance_schedule(&schedule_id);
+    let schedule = client.get_remittance_schedule(&owner, &schedule_id);
     assert!(schedule.is_some());
     let schedule = schedule.unwrap();
     assert_eq!(schedule.amount, 10000);
@@ -253,7 +258,9 @@ fn test_modify_remittance_schedule() {
     let schedule_id = client.create_remittance_schedule(&owner, &10000, &3000, &86400);
     client.modify_remittance_schedule(&owner, &schedule_id, &15000, &4000, &172800);
 
-    let schedule = client.get_remittance_schedule(&schedule_id).unwrap();
+    let schedule = client
+        .get_remittance_schedule(&owner, &schedule_id)
+        .unwrap();
     assert_eq!(schedule.amount, 15000);
     assert_eq!(schedule.next_due, 4000);
     assert_eq!(schedule.interval, 172800);
@@ -274,7 +281,9 @@ fn test_cancel_remittance_schedule() {
     let schedule_id = client.create_remittance_schedule(&owner, &10000, &3000, &86400);
     client.cancel_remittance_schedule(&owner, &schedule_id);
 
-    let schedule = client.get_remittance_schedule(&schedule_id).unwrap();
+    let schedule = client
+        .get_remittance_schedule(&owner, &schedule_id)
+        .unwrap();
     assert!(!schedule.active);
 }
 
@@ -297,6 +306,40 @@ fn test_get_remittance_schedules() {
     assert_eq!(schedules.len(), 2);
 }
 
+#[test]
+fn test_remittance_schedules_are_kept_per_owner() {
+    let env = Env::default();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = <soroban_sdk::Address as AddressTrait>::generate(&env);
+    let other = <soroban_sdk::Address as AddressTrait>::generate(&env);
+
+    env.mock_all_auths();
+    set_time(&env, 1000);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.initialize_split(&other, &0, &50, &30, &15, &5);
+
+    let schedule_id = client.create_remittance_schedule(&owner, &10000, &3000, &86400);
+    let other_id = client.create_remittance_schedule(&other, &5000, &4000, &86400);
+    assert_ne!(schedule_id, other_id);
+
+    assert_eq!(client.get_remittance_schedules(&owner).len(), 1);
+    assert_eq!(client.get_remittance_schedules(&other).len(), 1);
+    assert!(client
+        .get_remittance_schedule(&other, &schedule_id)
+        .is_none());
+
+    let result = client.try_cancel_remittance_schedule(&other, &schedule_id);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ScheduleNotFound)));
+    assert!(
+        client
+            .get_remittance_schedule(&owner, &schedule_id)
+            .unwrap()
+            .active
+    );
+}
+
 #[test]
 fn test_remittance_schedule_validation() {
     let env = Env::default();
@@ -397,7 +440,7 @@ fn test_calculate_split_events() {
     client.initialize_split(&owner, &0, &50, &30, &15, &5);
 
     let total_amount = 1000i128;
-    client.calculate_split(&total_amount);
+    client.calculate_split(&owner, &total_amount);
 
     let events = env.events().all();
     // calculate_split publishes two events:
@@ -463,14 +506,14 @@ fn test_split_boundary_100_0_0_0() {
     assert!(ok);
 
     // get_split must return the exact percentages
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 100);
     assert_eq!(split.get(1).unwrap(), 0);
     assert_eq!(split.get(2).unwrap(), 0);
     assert_eq!(split.get(3).unwrap(), 0);
 
     // calculate_split must allocate the entire amount to spending
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 1000);
     assert_eq!(amounts.get(1).unwrap(), 0);
     assert_eq!(amounts.get(2).unwrap(), 0);
@@ -490,13 +533,13 @@ fn test_split_boundary_0_100_0_0() {
     let ok = client.initialize_split(&owner, &0, &0, &100, &0, &0);
     assert!(ok);
 
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 0);
     assert_eq!(split.get(1).unwrap(), 100);
     assert_eq!(split.get(2).unwrap(), 0);
     assert_eq!(split.get(3).unwrap(), 0);
 
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 0);
     assert_eq!(amounts.get(1).unwrap(), 1000);
     assert_eq!(amounts.get(2).unwrap(), 0);
@@ -516,13 +559,13 @@ fn test_split_boundary_0_0_100_0() {
     let ok = client.initialize_split(&owner, &0, &0, &0, &100, &0);
     assert!(ok);
 
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 0);
     assert_eq!(split.get(1).unwrap(), 0);
     assert_eq!(split.get(2).unwrap(), 100);
     assert_eq!(split.get(3).unwrap(), 0);
 
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 0);
     assert_eq!(amounts.get(1).unwrap(), 0);
     assert_eq!(amounts.get(2).unwrap(), 1000);
@@ -542,14 +585,14 @@ fn test_split_boundary_0_0_0_100() {
     let ok = client.initialize_split(&owner, &0, &0, &0, &0, &100);
     assert!(ok);
 
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 0);
     assert_eq!(split.get(1).unwrap(), 0);
     assert_eq!(split.get(2).unwrap(), 0);
     assert_eq!(split.get(3).unwrap(), 100);
 
     // Insurance gets the remainder: 1000 - 0 - 0 - 0 = 1000
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 0);
     assert_eq!(amounts.get(1).unwrap(), 0);
     assert_eq!(amounts.get(2).unwrap(), 0);
@@ -569,14 +612,14 @@ fn test_split_boundary_25_25_25_25() {
     let ok = client.initialize_split(&owner, &0, &25, &25, &25, &25);
     assert!(ok);
 
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 25);
     assert_eq!(split.get(1).unwrap(), 25);
     assert_eq!(split.get(2).unwrap(), 25);
     assert_eq!(split.get(3).unwrap(), 25);
 
     // 25 % of 1000 = 250 for each category
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 250);
     assert_eq!(amounts.get(1).unwrap(), 250);
     assert_eq!(amounts.get(2).unwrap(), 250);
@@ -601,13 +644,13 @@ fn test_update_split_boundary_percentages() {
     let ok = client.update_split(&owner, &1, &100, &0, &0, &0);
     assert!(ok);
 
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 100);
     assert_eq!(split.get(1).unwrap(), 0);
     assert_eq!(split.get(2).unwrap(), 0);
     assert_eq!(split.get(3).unwrap(), 0);
 
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 1000);
     assert_eq!(amounts.get(1).unwrap(), 0);
     assert_eq!(amounts.get(2).unwrap(), 0);
@@ -617,13 +660,13 @@ fn test_update_split_boundary_percentages() {
     let ok = client.update_split(&owner, &1, &25, &25, &25, &25);
     assert!(ok);
 
-    let split = client.get_split();
+    let split = client.get_split(&owner);
     assert_eq!(split.get(0).unwrap(), 25);
     assert_eq!(split.get(1).unwrap(), 25);
     assert_eq!(split.get(2).unwrap(), 25);
     assert_eq!(split.get(3).unwrap(), 25);
 
-    let amounts = client.calculate_split(&1000);
+    let amounts = client.calculate_split(&owner, &1000);
     assert_eq!(amounts.get(0).unwrap(), 250);
     assert_eq!(amounts.get(1).unwrap(), 250);
     assert_eq!(amounts.get(2).unwrap(), 250);
@@ -641,12 +684,1703 @@ fn test_update_split_not_initialized() {
     let result = client.try_update_split(&caller, &0, &25, &25, &25, &25);
     assert_eq!(result, Err(Ok(RemittanceSplitError::NotInitialized)));
 
-    let config = client.get_config();
+    let config = client.get_config(&caller);
     assert!(config.is_none());
 
-    let split = client.get_split();
+    let split = client.get_split(&caller);
     assert_eq!(split.get(0).unwrap(), 50);
     assert_eq!(split.get(1).unwrap(), 30);
     assert_eq!(split.get(2).unwrap(), 15);
     assert_eq!(split.get(3).unwrap(), 5);
 }
+
+#[test]
+fn test_frozen_category_share_goes_to_fallback() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.set_fallback_category(&owner, &SplitCategory::Savings);
+    client.freeze_category(&owner, &SplitCategory::Spending);
+
+    let amounts = client.calculate_split(&owner, &1000);
+    assert_eq!(amounts.get(0).unwrap(), 0);
+    assert_eq!(amounts.get(1).unwrap(), 800);
+    assert_eq!(amounts.get(2).unwrap(), 150);
+    assert_eq!(amounts.get(3).unwrap(), 50);
+
+    client.unfreeze_category(&owner, &SplitCategory::Spending);
+    let amounts = client.calculate_split(&owner, &1000);
+    assert_eq!(amounts.get(0).unwrap(), 500);
+    assert_eq!(amounts.get(1).unwrap(), 300);
+    assert_eq!(client.get_frozen_categories(&owner).len(), 0);
+}
+
+#[test]
+fn test_freeze_category_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let other = Address::generate(&env);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let result = client.try_freeze_category(&owner, &SplitCategory::Bills);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::FallbackNotConfigured)));
+
+    client.set_fallback_category(&owner, &SplitCategory::Savings);
+    let result = client.try_freeze_category(&owner, &SplitCategory::Savings);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidFallback)));
+
+    let result = client.try_freeze_category(&other, &SplitCategory::Bills);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotInitialized)));
+
+    client.freeze_category(&owner, &SplitCategory::Bills);
+    let result = client.try_set_fallback_category(&owner, &SplitCategory::Bills);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidFallback)));
+
+    let result = client.try_unfreeze_category(&owner, &SplitCategory::Insurance);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::CategoryNotFrozen)));
+}
+
+fn setup_distribution(env: &Env) -> (RemittanceSplitClient<'_>, Address, Address, AccountGroup) {
+    let fixture = testutils::split_with_funded_token(env);
+    (
+        fixture.client,
+        fixture.owner,
+        fixture.token,
+        fixture.accounts,
+    )
+}
+
+#[test]
+fn test_distribute_usdc_records_memo_and_purpose() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let (client, owner, token, accounts) = setup_distribution(&env);
+    let memo = BytesN::from_array(&env, &[7u8; 32]);
+
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &1_000,
+        &Some(memo.clone()),
+        &PurposeCode::SchoolFees,
+    );
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &2,
+        &accounts,
+        &500,
+        &None,
+        &PurposeCode::Other,
+    );
+
+    let history = client.get_remittance_history(&owner, &0, &10);
+    assert_eq!(history.len(), 2);
+    let first = history.get(0).unwrap();
+    assert_eq!(first.total_amount, 1_000);
+    assert_eq!(first.memo, memo);
+    assert_eq!(first.purpose, PurposeCode::SchoolFees);
+    assert_eq!(first.timestamp, 1_000);
+    assert_eq!(
+        history.get(1).unwrap().memo,
+        BytesN::from_array(&env, &[0u8; 32])
+    );
+
+    let page = client.get_remittance_history(&owner, &1, &10);
+    assert_eq!(page.len(), 1);
+    assert_eq!(page.get(0).unwrap().total_amount, 500);
+}
+
+#[test]
+fn test_audit_log_chains_config_changes_and_distributions() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let (client, owner, token, accounts) = setup_distribution(&env);
+    client.update_split(&owner, &1, &40, &40, &15, &5);
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::Other,
+    );
+    client.set_fallback_category(&owner, &SplitCategory::Spending);
+    client.freeze_category(&owner, &SplitCategory::Bills);
+
+    let log = client.get_owner_audit_log(&owner, &0, &10);
+    let mut actions = Vec::new(&env);
+    for record in log.iter() {
+        actions.push_back(record.action);
+    }
+    assert_eq!(
+        actions,
+        vec![
+            &env,
+            symbol_short!("init"),
+            symbol_short!("update"),
+            symbol_short!("distrib"),
+            symbol_short!("fallback"),
+            symbol_short!("freeze"),
+        ]
+    );
+    let (init, update, distrib) = (
+        log.get(0).unwrap(),
+        log.get(1).unwrap(),
+        log.get(2).unwrap(),
+    );
+    assert_eq!(init.before, BytesN::from_array(&env, &[0u8; 32]));
+    assert_eq!(init.actor, owner);
+    // Each split change starts from the digest the previous one left, and
+    // the distribution names the split it was allocated by
+    assert_eq!(update.before, init.after);
+    assert_ne!(update.after, update.before);
+    assert_eq!(distrib.before, update.after);
+    assert_eq!((distrib.index, distrib.timestamp), (2, 1_000));
+
+    assert_eq!(client.get_owner_audit_log(&owner, &3, &10).len(), 2);
+    assert_eq!(
+        client
+            .get_owner_audit_log(&accounts.spending, &0, &10)
+            .len(),
+        0
+    );
+    assert!(!client.get_audit_log(&0, &10).is_empty());
+}
+
+#[test]
+fn test_audit_log_records_policy_setters() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let (client, owner, _token, _accounts) = setup_distribution(&env);
+    let savings_id = env.register_contract(None, savings_goals::MockSavingsGoals);
+    let start = client.get_owner_audit_log(&owner, &0, &50).len();
+
+    client.set_guardian(
+        &owner,
+        &Some(GuardianPolicy {
+            guardian: Address::generate(&env),
+            max_shift: 10,
+        }),
+    );
+    client.set_auto_invest(
+        &owner,
+        &Some(AutoInvestConfig {
+            savings_contract: savings_id,
+            goal_id: 1,
+            target_date: 100_000,
+            interval: 86_400,
+        }),
+    );
+    client.set_claim_window(&owner, &Some(3_600));
+    client.set_claim_window(&owner, &None);
+    client.set_large_transfer_policy(
+        &owner,
+        &Some(LargeTransferPolicy {
+            threshold: 5_000,
+            delay_secs: 3_600,
+            co_signer: None,
+        }),
+    );
+    client.set_remainder_sink(&owner, &RemainderSink::Dust);
+
+    let log = client.get_owner_audit_log(&owner, &start, &50);
+    let mut actions = Vec::new(&env);
+    for record in log.iter() {
+        actions.push_back(record.action);
+    }
+    assert_eq!(
+        actions,
+        vec![
+            &env,
+            symbol_short!("guardian"),
+            symbol_short!("auto_inv"),
+            symbol_short!("clm_win"),
+            symbol_short!("clm_win"),
+            symbol_short!("lrg_pol"),
+            symbol_short!("rem_sink"),
+        ]
+    );
+    // Setting and then clearing the claim window returns to the digest of
+    // no window
+    let (set, cleared) = (log.get(2).unwrap(), log.get(3).unwrap());
+    assert_eq!(cleared.before, set.after);
+    assert_eq!(cleared.after, set.before);
+}
+
+#[test]
+fn test_top_up_mode_fills_targets_in_priority_order() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner, token, accounts) = setup_distribution(&env);
+    let balance =
+        |account: &Address| soroban_sdk::token::Client::new(&env, &token).balance(account);
+    soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&accounts.bills, &150);
+
+    let mut targets = TopUpTargets {
+        spending: 0,
+        savings: 0,
+        bills: 200,
+        insurance: 50,
+        priority: vec![&env, SplitCategory::Bills, SplitCategory::Bills],
+    };
+    assert_eq!(
+        client.try_set_top_up_targets(&owner, &Some(targets.clone())),
+        Err(Ok(RemittanceSplitError::InvalidTopUpTargets))
+    );
+    targets.priority = vec![&env, SplitCategory::Bills, SplitCategory::Insurance];
+    client.set_top_up_targets(&owner, &Some(targets.clone()));
+    assert_eq!(client.get_top_up_targets(&owner), Some(targets));
+
+    // Bills is 50 short of its target and insurance 50; savings gets the rest
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &300,
+        &None,
+        &PurposeCode::Other,
+    );
+    assert_eq!(balance(&accounts.bills), 200);
+    assert_eq!(balance(&accounts.insurance), 50);
+    assert_eq!(balance(&accounts.savings), 200);
+    assert_eq!(balance(&accounts.spending), 0);
+
+    // With both targets met, everything overflows to savings
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &2,
+        &accounts,
+        &100,
+        &None,
+        &PurposeCode::Other,
+    );
+    assert_eq!(balance(&accounts.savings), 300);
+    assert_eq!(balance(&accounts.bills), 200);
+
+    // Back to the 50/30/15/5 percentages
+    client.set_top_up_targets(&owner, &None);
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &3,
+        &accounts,
+        &100,
+        &None,
+        &PurposeCode::Other,
+    );
+    assert_eq!(balance(&accounts.spending), 50);
+    assert_eq!(balance(&accounts.bills), 215);
+}
+
+#[test]
+fn test_emergency_redirect_sends_everything_to_safe_address() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let (client, owner, token, accounts) = setup_distribution(&env);
+    let balance =
+        |account: &Address| soroban_sdk::token::Client::new(&env, &token).balance(account);
+    let guardian = Address::generate(&env);
+    let safe = Address::generate(&env);
+    client.set_guardian(
+        &owner,
+        &Some(GuardianPolicy {
+            guardian: guardian.clone(),
+            max_shift: 10,
+        }),
+    );
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_activate_emergency_redirect(&stranger, &owner, &safe),
+        Err(Ok(RemittanceSplitError::Unauthorized))
+    );
+    assert_eq!(
+        client.try_deactivate_emergency_redirect(&owner, &owner),
+        Err(Ok(RemittanceSplitError::RedirectNotActive))
+    );
+    client.activate_emergency_redirect(&guardian, &owner, &safe);
+    let redirect = client.get_emergency_redirect(&owner).unwrap();
+    assert_eq!(redirect.safe_address, safe);
+    assert_eq!(redirect.activated_by, guardian);
+
+    // The split is untouched, but every leg lands on the safe address
+    client.set_claim_window(&owner, &Some(3_600));
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::Other,
+    );
+    assert_eq!(balance(&safe), 1_000);
+    assert_eq!(balance(&accounts.spending), 0);
+    assert_eq!(client.get_split(&owner), vec![&env, 50, 30, 15, 5]);
+
+    // Deactivating keeps the redirect through the cooldown
+    let ends_at = client.deactivate_emergency_redirect(&owner, &owner);
+    assert_eq!(ends_at, 1_000 + EMERGENCY_REDIRECT_COOLDOWN_SECS);
+    assert_eq!(
+        client.deactivate_emergency_redirect(&guardian, &owner),
+        ends_at
+    );
+    set_time(&env, ends_at - 1);
+    assert!(client.get_emergency_redirect(&owner).is_some());
+
+    set_time(&env, ends_at);
+    client.set_claim_window(&owner, &None);
+    assert_eq!(client.get_emergency_redirect(&owner), None);
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &2,
+        &accounts,
+        &100,
+        &None,
+        &PurposeCode::Other,
+    );
+    assert_eq!(balance(&safe), 1_000);
+    assert_eq!(balance(&accounts.spending), 50);
+}
+
+#[test]
+fn test_emergency_redirect_address_change_waits_for_cooldown() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let (client, owner, token, accounts) = setup_distribution(&env);
+    let balance =
+        |account: &Address| soroban_sdk::token::Client::new(&env, &token).balance(account);
+    let safe = Address::generate(&env);
+    let thief = Address::generate(&env);
+    let new_safe = Address::generate(&env);
+
+    client.activate_emergency_redirect(&owner, &owner, &safe);
+
+    // A stolen key re-activating with its own address does not catch the next payment
+    client.activate_emergency_redirect(&owner, &owner, &thief);
+    let redirect = client.get_emergency_redirect(&owner).unwrap();
+    assert_eq!(redirect.safe_address, safe);
+    assert_eq!(redirect.pending_address, Some(thief.clone()));
+    assert_eq!(
+        redirect.change_at,
+        Some(1_000 + EMERGENCY_REDIRECT_COOLDOWN_SECS)
+    );
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::Other,
+    );
+    assert_eq!(balance(&safe), 1_000);
+    assert_eq!(balance(&thief), 0);
+
+    // Activating with the address in force cancels the change
+    client.activate_emergency_redirect(&owner, &owner, &safe);
+    let redirect = client.get_emergency_redirect(&owner).unwrap();
+    assert_eq!(redirect.pending_address, None);
+    assert_eq!(redirect.change_at, None);
+
+    // A change that is left standing takes effect after the cooldown
+    client.activate_emergency_redirect(&owner, &owner, &new_safe);
+    set_time(&env, 1_000 + EMERGENCY_REDIRECT_COOLDOWN_SECS);
+    assert_eq!(
+        client.get_emergency_redirect(&owner).unwrap().safe_address,
+        new_safe
+    );
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &2,
+        &accounts,
+        &100,
+        &None,
+        &PurposeCode::Other,
+    );
+    assert_eq!(balance(&new_safe), 100);
+    assert_eq!(balance(&safe), 1_000);
+}
+
+#[test]
+fn test_purpose_totals_aggregate_within_window() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner, token, accounts) = setup_distribution(&env);
+
+    set_time(&env, 100);
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::Medical,
+    );
+    set_time(&env, 200);
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &2,
+        &accounts,
+        &2_000,
+        &None,
+        &PurposeCode::Medical,
+    );
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &3,
+        &accounts,
+        &300,
+        &None,
+        &PurposeCode::FamilySupport,
+    );
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &4,
+        &accounts,
+        &50,
+        &None,
+        &PurposeCode::Other,
+    );
+
+    let totals = client.get_purpose_totals(&owner, &0, &u64::MAX);
+    assert_eq!(totals.len(), 3);
+    let medical = totals.get(0).unwrap();
+    assert_eq!(medical.purpose, PurposeCode::Medical);
+    assert_eq!(medical.count, 2);
+    assert_eq!(medical.total_amount, 3_000);
+    assert_eq!(totals.get(1).unwrap().total_amount, 300);
+
+    let later = client.get_purpose_totals(&owner, &150, &u64::MAX);
+    assert_eq!(later.get(0).unwrap().total_amount, 2_000);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(client.get_purpose_totals(&stranger, &0, &u64::MAX).len(), 0);
+}
+
+#[test]
+fn test_distribute_rejects_unsupported_token() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner, token, accounts) = setup_distribution(&env);
+
+    let other_admin = Address::generate(&env);
+    let other = env.register_stellar_asset_contract_v2(other_admin);
+    soroban_sdk::token::StellarAssetClient::new(&env, &other.address()).mint(&owner, &1_000);
+
+    let result = client.try_distribute_usdc(
+        &other.address(),
+        &owner,
+        &1,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::Other,
+    );
+    assert_eq!(result, Err(Ok(RemittanceSplitError::UnsupportedToken)));
+
+    client.remove_supported_token(&owner, &token);
+    assert_eq!(client.get_supported_tokens().len(), 0);
+    let result = client.try_distribute_usdc(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::Other,
+    );
+    assert_eq!(result, Err(Ok(RemittanceSplitError::UnsupportedToken)));
+}
+
+#[test]
+fn test_supported_token_list_is_admin_managed() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner, token, _accounts) = setup_distribution(&env);
+    let stranger = Address::generate(&env);
+
+    client.add_supported_token(&owner, &token);
+    assert_eq!(client.get_supported_tokens().len(), 1);
+
+    let result = client.try_add_supported_token(&stranger, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+
+    let result = client.try_remove_supported_token(&owner, &Address::generate(&env));
+    assert_eq!(result, Err(Ok(RemittanceSplitError::UnsupportedToken)));
+}
+
+#[test]
+fn test_large_distribution_requires_proposal_and_delay() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let (client, owner, token, accounts) = setup_distribution(&env);
+    client.set_large_transfer_policy(
+        &owner,
+        &Some(LargeTransferPolicy {
+            threshold: 5_000,
+            delay_secs: 3_600,
+            co_signer: None,
+        }),
+    );
+
+    let result = client.try_distribute_usdc(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &5_000,
+        &None,
+        &PurposeCode::Medical,
+    );
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ProposalRequired)));
+
+    let id = client.propose_distribution(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &5_000,
+        &None,
+        &PurposeCode::Medical,
+    );
+    let proposal = client.get_distribution_proposal(&id).unwrap();
+    assert_eq!(proposal.execute_after, 4_600);
+    assert_eq!(proposal.status, ProposalStatus::Pending);
+    assert_eq!(client.get_nonce(&owner), 2);
+
+    let result = client.try_commit_distribution(&owner, &id);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ProposalNotReady)));
+
+    set_time(&env, 4_600);
+    client.commit_distribution(&owner, &id);
+
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&accounts.spending), 2_500);
+    assert_eq!(token_client.balance(&accounts.insurance), 250);
+    assert_eq!(token_client.balance(&owner), 5_000);
+    assert_eq!(
+        client.get_distribution_proposal(&id).unwrap().status,
+        ProposalStatus::Committed
+    );
+    assert_eq!(client.get_remittance_history(&owner, &0, &10).len(), 1);
+
+    let result = client.try_commit_distribution(&owner, &id);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ProposalNotPending)));
+}
+
+#[test]
+fn test_proposal_cancel_and_co_signer_approval() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let (client, owner, token, accounts) = setup_distribution(&env);
+    let co_signer = Address::generate(&env);
+    client.set_large_transfer_policy(
+        &owner,
+        &Some(LargeTransferPolicy {
+            threshold: 1_000,
+            delay_secs: 86_400,
+            co_signer: Some(co_signer.clone()),
+        }),
+    );
+
+    let first = client.propose_distribution(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &9_000,
+        &None,
+        &PurposeCode::Other,
+    );
+    client.cancel_distribution(&owner, &first);
+    assert_eq!(
+        client.get_distribution_proposal(&first).unwrap().status,
+        ProposalStatus::Cancelled
+    );
+    let result = client.try_commit_distribution(&owner, &first);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ProposalNotPending)));
+
+    let second = client.propose_distribution(
+        &token,
+        &owner,
+        &2,
+        &accounts,
+        &2_000,
+        &None,
+        &PurposeCode::FamilySupport,
+    );
+    let stranger = Address::generate(&env);
+    let result = client.try_approve_distribution(&stranger, &second);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+
+    client.approve_distribution(&co_signer, &second);
+    client.commit_distribution(&owner, &second);
+
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+    assert_eq!(token_client.balance(&owner), 8_000);
+}
+
+#[test]
+fn test_effective_split_reflects_realized_allocation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let (client, owner, token, accounts) = setup_distribution(&env);
+
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::Other,
+    );
+    client.set_fallback_category(&owner, &SplitCategory::Savings);
+    client.freeze_category(&owner, &SplitCategory::Spending);
+    set_time(&env, 2_000);
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &2,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::Other,
+    );
+
+    let effective = client.get_effective_split(&owner, &0, &u64::MAX);
+    assert_eq!(effective.remittance_count, 2);
+    assert_eq!(effective.total_amount, 2_000);
+    assert_eq!(effective.amounts, vec![&env, 500, 1_100, 300, 100]);
+    assert_eq!(
+        effective.percentages_bps,
+        vec![&env, 2_500, 5_500, 1_500, 500]
+    );
+    assert_eq!(effective.configured_percentages, vec![&env, 50, 30, 15, 5]);
+
+    let recent = client.get_effective_split(&owner, &1_500, &u64::MAX);
+    assert_eq!(recent.percentages_bps, vec![&env, 0, 8_000, 1_500, 500]);
+
+    let stranger = Address::generate(&env);
+    let empty = client.get_effective_split(&stranger, &0, &u64::MAX);
+    assert_eq!(empty.remittance_count, 0);
+    assert_eq!(empty.percentages_bps, vec![&env, 0, 0, 0, 0]);
+}
+
+#[test]
+fn test_owners_keep_independent_splits_and_history() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner, token, accounts) = setup_distribution(&env);
+    let second = Address::generate(&env);
+    soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&second, &10_000);
+
+    client.initialize_split(&second, &0, &10, &20, &30, &40);
+    client.set_fallback_category(&second, &SplitCategory::Savings);
+    client.freeze_category(&second, &SplitCategory::Spending);
+
+    assert_eq!(client.get_split(&owner), vec![&env, 50, 30, 15, 5]);
+    assert_eq!(client.get_split(&second), vec![&env, 10, 20, 30, 40]);
+    assert_eq!(client.get_frozen_categories(&owner).len(), 0);
+    assert_eq!(
+        client.calculate_split(&second, &1_000),
+        vec![&env, 0, 300, 300, 400]
+    );
+
+    client.distribute_usdc(
+        &token,
+        &second,
+        &1,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::Other,
+    );
+    assert_eq!(client.get_remittance_history(&second, &0, &10).len(), 1);
+    assert_eq!(client.get_remittance_history(&owner, &0, &10).len(), 0);
+}
+
+#[test]
+fn test_owner_entries_get_their_own_ttl() {
+    use soroban_sdk::testutils::storage::Persistent as _;
+
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    let ttl = env.as_contract(&contract_id, || {
+        env.storage()
+            .persistent()
+            .get_ttl(&(symbol_short!("CONFIG"), owner.clone()))
+    });
+    assert!(
+        ttl >= 518_400,
+        "owner config TTL ({}) should be bumped",
+        ttl
+    );
+}
+
+#[test]
+fn test_remainder_goes_to_configured_category() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+
+    // 999 floors to 499 + 299 + 149 + 49, leaving a remainder of 3
+    assert_eq!(
+        client.get_remainder_sink(&owner),
+        RemainderSink::Category(SplitCategory::Insurance)
+    );
+    assert_eq!(
+        client.calculate_split(&owner, &999),
+        vec![&env, 499, 299, 149, 52]
+    );
+
+    client.set_remainder_sink(&owner, &RemainderSink::Category(SplitCategory::Savings));
+    assert_eq!(
+        client.calculate_split(&owner, &999),
+        vec![&env, 499, 302, 149, 49]
+    );
+
+    let stranger = Address::generate(&env);
+    let result = client.try_set_remainder_sink(&stranger, &RemainderSink::Dust);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotInitialized)));
+}
+
+#[test]
+fn test_dust_remainder_is_held_and_claimable() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, owner, token, accounts) = setup_distribution(&env);
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+    client.set_remainder_sink(&owner, &RemainderSink::Dust);
+
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &999,
+        &None,
+        &PurposeCode::Other,
+    );
+    assert_eq!(token_client.balance(&accounts.insurance), 49);
+    assert_eq!(token_client.balance(&client.address), 3);
+    assert_eq!(client.get_dust_balance(&owner, &token), 3);
+
+    let event = env.events().all().last().unwrap();
+    let topic: SplitEvent = SplitEvent::try_from_val(&env, &event.1.get(1).unwrap()).unwrap();
+    assert_eq!(topic, SplitEvent::Distributed);
+    let data = DistributionEvent::try_from_val(&env, &event.2).unwrap();
+    assert_eq!(data.remainder, 3);
+    assert_eq!(data.remainder_sink, RemainderSink::Dust);
+    assert_eq!(data.amounts, vec![&env, 499, 299, 149, 49]);
+
+    let recipient = Address::generate(&env);
+    assert_eq!(client.claim_dust(&owner, &token, &recipient), 3);
+    assert_eq!(token_client.balance(&recipient), 3);
+    assert_eq!(client.get_dust_balance(&owner, &token), 0);
+    let result = client.try_claim_dust(&owner, &token, &recipient);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidAmount)));
+}
+
+#[test]
+fn test_claim_model_folds_unclaimed_shares_per_leg() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let (client, owner, token, accounts) = setup_distribution(&env);
+    client.set_claim_window(&owner, &Some(3_600));
+    let distribute = |nonce: u64| {
+        client.distribute_usdc(
+            &token,
+            &owner,
+            &nonce,
+            &accounts,
+            &1_000,
+            &None,
+            &PurposeCode::Other,
+        );
+    };
+
+    // Unclaimed shares of the same leg are held as one, with the latest expiry
+    distribute(1);
+    set_time(&env, 2_000);
+    distribute(2);
+    let escrow = client.get_escrowed_allocations(&owner);
+    assert_eq!(escrow.len(), 4);
+    let savings = escrow.get(1).unwrap();
+    assert_eq!(savings.category, SplitCategory::Savings);
+    assert_eq!(savings.amount, 600);
+    assert_eq!(savings.expires_at, 5_600);
+
+    // Expired shares are kept apart from new ones, and merged together
+    set_time(&env, 6_000);
+    distribute(3);
+    set_time(&env, 10_000);
+    distribute(4);
+    let escrow = client.get_escrowed_allocations(&owner);
+    assert_eq!(escrow.len(), 8);
+    assert_eq!(client.reclaim_expired(&owner, &token), 3_000);
+    assert_eq!(client.get_escrowed_allocations(&owner).len(), 4);
+    assert_eq!(
+        client.claim_allocation(&accounts.savings, &owner, &token, &SplitCategory::Savings),
+        300
+    );
+}
+
+#[test]
+fn test_claim_model_escrows_shares_until_claimed_or_expired() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let (client, owner, token, accounts) = setup_distribution(&env);
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+    client.set_claim_window(&owner, &Some(3_600));
+
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::Other,
+    );
+    assert_eq!(token_client.balance(&accounts.savings), 0);
+    assert_eq!(token_client.balance(&client.address), 1_000);
+    let escrow = client.get_escrowed_allocations(&owner);
+    assert_eq!(escrow.len(), 4);
+    assert_eq!(escrow.get(1).unwrap().category, SplitCategory::Savings);
+    assert_eq!(escrow.get(1).unwrap().expires_at, 4_600);
+
+    // Only the share's recipient can claim it
+    let result =
+        client.try_claim_allocation(&accounts.bills, &owner, &token, &SplitCategory::Savings);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidAmount)));
+    assert_eq!(
+        client.claim_allocation(&accounts.savings, &owner, &token, &SplitCategory::Savings),
+        300
+    );
+    assert_eq!(token_client.balance(&accounts.savings), 300);
+
+    // Nothing has expired yet
+    let result = client.try_reclaim_expired(&owner, &token);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidAmount)));
+
+    set_time(&env, 4_601);
+    let result =
+        client.try_claim_allocation(&accounts.bills, &owner, &token, &SplitCategory::Bills);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidAmount)));
+    let before = token_client.balance(&owner);
+    assert_eq!(client.reclaim_expired(&owner, &token), 700);
+    assert_eq!(token_client.balance(&owner), before + 700);
+    assert_eq!(client.get_escrowed_allocations(&owner).len(), 0);
+
+    // Back to direct transfers
+    client.set_claim_window(&owner, &None);
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &2,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::Other,
+    );
+    assert_eq!(token_client.balance(&accounts.savings), 600);
+}
+
+fn household(env: &Env) -> AccountGroup {
+    AccountGroup {
+        spending: Address::generate(env),
+        savings: Address::generate(env),
+        bills: Address::generate(env),
+        insurance: Address::generate(env),
+    }
+}
+
+#[test]
+fn test_batch_distribute_reports_per_household_results() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, agent, token, _) = setup_distribution(&env);
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+    let family_a = Address::generate(&env);
+    let family_b = Address::generate(&env);
+    let unregistered = Address::generate(&env);
+    client.initialize_split(&family_a, &0, &50, &30, &15, &5);
+    client.initialize_split(&family_b, &0, &100, &0, &0, &0);
+    let (accounts_a, accounts_b) = (household(&env), household(&env));
+
+    let items = vec![
+        &env,
+        BatchRecipient {
+            owner: family_a.clone(),
+            accounts: accounts_a.clone(),
+            amount: 1_000,
+        },
+        BatchRecipient {
+            owner: unregistered.clone(),
+            accounts: household(&env),
+            amount: 500,
+        },
+        BatchRecipient {
+            owner: family_b.clone(),
+            accounts: accounts_b.clone(),
+            amount: 2_000,
+        },
+    ];
+    let results = client.batch_distribute(&token, &agent, &1, &items, &PurposeCode::FamilySupport);
+    assert_eq!(results.len(), 3);
+    assert!(results.get(0).unwrap().distributed);
+    let skipped = results.get(1).unwrap();
+    assert!(!skipped.distributed);
+    assert_eq!(
+        skipped.error_code,
+        RemittanceSplitError::NotInitialized as u32
+    );
+    assert!(results.get(2).unwrap().distributed);
+
+    assert_eq!(token_client.balance(&accounts_a.savings), 300);
+    assert_eq!(token_client.balance(&accounts_b.spending), 2_000);
+    assert_eq!(token_client.balance(&agent), 10_000 - 3_000);
+    assert_eq!(client.get_nonce(&agent), 2);
+    assert_eq!(client.get_remittance_history(&agent, &0, &10).len(), 2);
+
+    let event = env.events().all().last().unwrap();
+    let topic: SplitEvent = SplitEvent::try_from_val(&env, &event.1.get(1).unwrap()).unwrap();
+    assert_eq!(topic, SplitEvent::BatchDistributed);
+    let data = BatchDistributionEvent::try_from_val(&env, &event.2).unwrap();
+    assert_eq!(data.distributed_count, 2);
+    assert_eq!(data.total_distributed, 3_000);
+
+    // The whole batch is refused up front when the balance cannot cover it
+    let too_much = vec![
+        &env,
+        BatchRecipient {
+            owner: family_a,
+            accounts: accounts_a,
+            amount: 7_001,
+        },
+    ];
+    let result =
+        client.try_batch_distribute(&token, &agent, &2, &too_much, &PurposeCode::FamilySupport);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InsufficientBalance)));
+}
+
+#[test]
+fn test_batch_distribute_routes_by_each_household() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let (client, agent, token, _) = setup_distribution(&env);
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+    let redirected = Address::generate(&env);
+    let claiming = Address::generate(&env);
+    let safe = Address::generate(&env);
+    client.initialize_split(&redirected, &0, &50, &30, &15, &5);
+    client.initialize_split(&claiming, &0, &50, &30, &15, &5);
+    client.activate_emergency_redirect(&redirected, &redirected, &safe);
+    client.set_claim_window(&claiming, &Some(3_600));
+    let (accounts_r, accounts_c) = (household(&env), household(&env));
+
+    let items = vec![
+        &env,
+        BatchRecipient {
+            owner: redirected.clone(),
+            accounts: accounts_r.clone(),
+            amount: 1_000,
+        },
+        BatchRecipient {
+            owner: claiming.clone(),
+            accounts: accounts_c.clone(),
+            amount: 1_000,
+        },
+    ];
+    client.batch_distribute(&token, &agent, &1, &items, &PurposeCode::FamilySupport);
+
+    // The redirected household's legs all reach its safe address
+    assert_eq!(token_client.balance(&safe), 1_000);
+    assert_eq!(token_client.balance(&accounts_r.spending), 0);
+
+    // The claiming household's shares wait in its own escrow
+    assert_eq!(token_client.balance(&accounts_c.savings), 0);
+    assert_eq!(client.get_escrowed_allocations(&claiming).len(), 4);
+    assert_eq!(client.get_escrowed_allocations(&agent).len(), 0);
+    assert_eq!(
+        client.claim_allocation(
+            &accounts_c.savings,
+            &claiming,
+            &token,
+            &SplitCategory::Savings
+        ),
+        300
+    );
+}
+
+#[test]
+fn test_guardian_must_accept_large_split_change() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.set_guardian(
+        &owner,
+        &Some(GuardianPolicy {
+            guardian: guardian.clone(),
+            max_shift: 10,
+        }),
+    );
+
+    // Moving 10 points is within the guardian's allowance
+    assert!(client.update_split(&owner, &1, &40, &40, &15, &5));
+    assert_eq!(client.get_split(&owner), vec![&env, 40, 40, 15, 5]);
+
+    // Moving 35 points is held for the guardian
+    assert!(!client.update_split(&owner, &1, &75, &5, &15, &5));
+    assert_eq!(client.get_split(&owner), vec![&env, 40, 40, 15, 5]);
+    let pending = client.get_pending_split_change(&owner).unwrap();
+    assert_eq!(pending.shift, 35);
+    assert_eq!(pending.spending_percent, 75);
+
+    let stranger = Address::generate(&env);
+    assert_eq!(
+        client.try_accept_split_change(&stranger, &owner),
+        Err(Ok(RemittanceSplitError::Unauthorized))
+    );
+    assert!(client.accept_split_change(&guardian, &owner));
+    assert_eq!(client.get_split(&owner), vec![&env, 75, 5, 15, 5]);
+    assert_eq!(client.get_pending_split_change(&owner), None);
+    assert_eq!(
+        client.try_accept_split_change(&guardian, &owner),
+        Err(Ok(RemittanceSplitError::NoPendingChange))
+    );
+}
+
+#[test]
+fn test_guardian_can_reject_pending_split_change() {
+    let env = Env::default();
+    env.mock_all_auths();
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    let client = RemittanceSplitClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let guardian = Address::generate(&env);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    client.set_guardian(
+        &owner,
+        &Some(GuardianPolicy {
+            guardian: guardian.clone(),
+            max_shift: 0,
+        }),
+    );
+
+    assert!(!client.update_split(&owner, &1, &49, &31, &15, &5));
+    client.cancel_split_change(&guardian, &owner);
+    assert_eq!(client.get_pending_split_change(&owner), None);
+    assert_eq!(client.get_split(&owner), vec![&env, 50, 30, 15, 5]);
+
+    // Removing the guardian needs the guardian's signature as well
+    client.set_guardian(&owner, &None);
+    let auths = env.auths();
+    assert!(auths.iter().any(|(addr, _)| *addr == guardian));
+    assert_eq!(client.get_guardian(&owner), None);
+}
+
+mod savings_goals {
+    use crate::SavingsSchedule;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env, Map};
+
+    /// Keeps savings schedules the way the savings goals contract does,
+    /// without goals or execution
+    #[contract]
+    pub struct MockSavingsGoals;
+
+    #[contractimpl]
+    impl MockSavingsGoals {
+        pub fn create_savings_schedule(
+            env: Env,
+            owner: Address,
+            goal_id: u32,
+            amount: i128,
+            next_due: u64,
+            interval: u64,
+        ) -> u32 {
+            owner.require_auth();
+            let mut schedules = Self::schedules(&env);
+            let id = schedules.len() + 1;
+            schedules.set(
+                id,
+                SavingsSchedule {
+                    id,
+                    owner,
+                    goal_id,
+                    amount,
+                    next_due,
+                    interval,
+                    recurring: interval > 0,
+                    active: true,
+                    created_at: env.ledger().timestamp(),
+                    last_executed: None,
+                    missed_count: 0,
+                },
+            );
+            env.storage()
+                .instance()
+                .set(&symbol_short!("SAV_SCH"), &schedules);
+            id
+        }
+
+        pub fn modify_savings_schedule(
+            env: Env,
+            caller: Address,
+            schedule_id: u32,
+            amount: i128,
+            next_due: u64,
+            interval: u64,
+        ) -> bool {
+            caller.require_auth();
+            let mut schedules = Self::schedules(&env);
+            let mut schedule = schedules.get(schedule_id).expect("Schedule not found");
+            schedule.amount = amount;
+            schedule.next_due = next_due;
+            schedule.interval = interval;
+            schedules.set(schedule_id, schedule);
+            env.storage()
+                .instance()
+                .set(&symbol_short!("SAV_SCH"), &schedules);
+            true
+        }
+
+        pub fn get_savings_schedule(env: Env, schedule_id: u32) -> Option<SavingsSchedule> {
+            Self::schedules(&env).get(schedule_id)
+        }
+
+        fn schedules(env: &Env) -> Map<u32, SavingsSchedule> {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("SAV_SCH"))
+                .unwrap_or_else(|| Map::new(env))
+        }
+    }
+}
+
+#[test]
+fn test_distribution_creates_and_tops_up_auto_invest_schedule() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let (client, owner, token, accounts) = setup_distribution(&env);
+    let savings_id = env.register_contract(None, savings_goals::MockSavingsGoals);
+    let savings = savings_goals::MockSavingsGoalsClient::new(&env, &savings_id);
+    let day = 86_400u64;
+
+    client.set_auto_invest(
+        &owner,
+        &Some(AutoInvestConfig {
+            savings_contract: savings_id.clone(),
+            goal_id: 7,
+            target_date: 1_000 + 10 * day,
+            interval: day,
+        }),
+    );
+
+    // Savings share is 30%: 300 spread over 10 daily runs
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::Other,
+    );
+    let schedule_id = client.get_auto_invest_schedule(&owner).unwrap();
+    let schedule = savings.get_savings_schedule(&schedule_id).unwrap();
+    assert_eq!(schedule.goal_id, 7);
+    assert_eq!(schedule.owner, owner);
+    assert_eq!(schedule.amount, 30);
+    assert_eq!(schedule.next_due, 1_000 + day);
+    assert_eq!(schedule.interval, day);
+
+    // Halfway to the target, the next 300 is spread over the 5 runs left
+    set_time(&env, 1_000 + 5 * day);
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &2,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::Other,
+    );
+    assert_eq!(client.get_auto_invest_schedule(&owner), Some(schedule_id));
+    let schedule = savings.get_savings_schedule(&schedule_id).unwrap();
+    assert_eq!(schedule.amount, 90);
+    assert_eq!(schedule.next_due, 1_000 + 5 * day + 1);
+
+    // Past the target date, distributions no longer touch the schedule
+    set_time(&env, 1_000 + 10 * day);
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &3,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::Other,
+    );
+    assert_eq!(
+        savings.get_savings_schedule(&schedule_id).unwrap().amount,
+        90
+    );
+}
+
+#[test]
+fn test_auto_invest_config_validation() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let (client, owner, token, accounts) = setup_distribution(&env);
+    let savings_id = env.register_contract(None, savings_goals::MockSavingsGoals);
+    let mut config = AutoInvestConfig {
+        savings_contract: savings_id,
+        goal_id: 1,
+        target_date: 1_000,
+        interval: 86_400,
+    };
+
+    let result = client.try_set_auto_invest(&owner, &Some(config.clone()));
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidDueDate)));
+    config.target_date = 100_000;
+    config.interval = 0;
+    let result = client.try_set_auto_invest(&owner, &Some(config.clone()));
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InvalidAmount)));
+    let result = client.try_set_auto_invest(&Address::generate(&env), &Some(config.clone()));
+    assert_eq!(result, Err(Ok(RemittanceSplitError::NotInitialized)));
+
+    config.interval = 86_400;
+    client.set_auto_invest(&owner, &Some(config.clone()));
+    assert_eq!(client.get_auto_invest(&owner), Some(config));
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::Other,
+    );
+    assert!(client.get_auto_invest_schedule(&owner).is_some());
+
+    // Clearing the config also forgets the schedule
+    client.set_auto_invest(&owner, &None);
+    assert_eq!(client.get_auto_invest(&owner), None);
+    assert_eq!(client.get_auto_invest_schedule(&owner), None);
+}
+
+mod compliance {
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    /// Denies every transfer to one blocked address
+    #[contract]
+    pub struct MockCompliance;
+
+    #[contractimpl]
+    impl MockCompliance {
+        pub fn block(env: Env, to: Address) {
+            env.storage().instance().set(&symbol_short!("BLOCKED"), &to);
+        }
+
+        pub fn check_transfer(
+            env: Env,
+            _from: Address,
+            to: Address,
+            _amount: i128,
+            _token: Address,
+        ) -> bool {
+            env.storage()
+                .instance()
+                .get::<_, Address>(&symbol_short!("BLOCKED"))
+                != Some(to)
+        }
+    }
+}
+
+#[test]
+fn test_compliance_checker_rejects_distribution_unless_overridden() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let (client, owner, token, accounts) = setup_distribution(&env);
+    let checker_id = env.register_contract(None, compliance::MockCompliance);
+    let checker = compliance::MockComplianceClient::new(&env, &checker_id);
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+
+    let outsider = Address::generate(&env);
+    let result = client.try_set_compliance_checker(&outsider, &Some(checker_id.clone()));
+    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+    client.set_compliance_checker(&owner, &Some(checker_id.clone()));
+    assert_eq!(client.get_compliance_checker(), Some(checker_id));
+    checker.block(&accounts.bills);
+
+    let result = client.try_distribute_usdc(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::FamilySupport,
+    );
+    assert_eq!(result, Err(Ok(RemittanceSplitError::ComplianceRejected)));
+    assert_eq!(token_client.balance(&owner), 10_000);
+
+    // Rejected items of a batch are skipped, the others are paid
+    let other = Address::generate(&env);
+    client.initialize_split(&other, &0, &25, &25, &25, &25);
+    let clean = AccountGroup {
+        spending: Address::generate(&env),
+        savings: Address::generate(&env),
+        bills: Address::generate(&env),
+        insurance: Address::generate(&env),
+    };
+    let items = Vec::from_array(
+        &env,
+        [
+            BatchRecipient {
+                owner: owner.clone(),
+                accounts: accounts.clone(),
+                amount: 1_000,
+            },
+            BatchRecipient {
+                owner: other,
+                accounts: clean.clone(),
+                amount: 400,
+            },
+        ],
+    );
+    let results = client.batch_distribute(&token, &owner, &1, &items, &PurposeCode::FamilySupport);
+    assert_eq!(
+        results.get(0).unwrap().error_code,
+        RemittanceSplitError::ComplianceRejected as u32
+    );
+    assert!(results.get(1).unwrap().distributed);
+    assert_eq!(token_client.balance(&clean.bills), 100);
+
+    // The admin clears a false positive for the pair
+    client.set_compliance_override(&owner, &owner, &accounts.bills, &true);
+    assert!(client.has_compliance_override(&owner, &accounts.bills));
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &2,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::FamilySupport,
+    );
+    assert_eq!(token_client.balance(&accounts.bills), 150);
+}
+
+#[test]
+fn test_settle_inbound_splits_watched_remittance_once() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let (client, owner, token, accounts) = setup_distribution(&env);
+    let token_client = soroban_sdk::token::TokenClient::new(&env, &token);
+    let sender = Address::generate(&env);
+    let relayer = Address::generate(&env);
+
+    client.register_inbound_watch(
+        &owner,
+        &sender,
+        &token,
+        &500,
+        &accounts,
+        &PurposeCode::FamilySupport,
+    );
+    token_client.approve(&owner, &client.address, &10_000, &1_000);
+    let proof = InboundProof {
+        from: sender.clone(),
+        amount: 1_000,
+        tx_hash: BytesN::from_array(&env, &[3u8; 32]),
+    };
+
+    let result = client.try_settle_inbound(&relayer, &owner, &proof);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::Unauthorized)));
+    client.set_inbound_relayer(&owner, &relayer, &true);
+
+    let small = InboundProof {
+        amount: 100,
+        ..proof.clone()
+    };
+    let result = client.try_settle_inbound(&relayer, &owner, &small);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InboundNotEligible)));
+
+    // Nothing has arrived since the watch was registered
+    let result = client.try_settle_inbound(&relayer, &owner, &proof);
+    assert_eq!(
+        result,
+        Err(Ok(RemittanceSplitError::InboundExceedsReceived))
+    );
+
+    soroban_sdk::token::StellarAssetClient::new(&env, &token).mint(&owner, &1_000);
+    client.settle_inbound(&relayer, &owner, &proof);
+    assert_eq!(token_client.balance(&accounts.savings), 300);
+    assert_eq!(token_client.balance(&accounts.bills), 150);
+    assert_eq!(token_client.balance(&owner), 10_000);
+    let watch = client.get_inbound_watch(&owner).unwrap();
+    assert_eq!(watch.settled_count, 1);
+    assert_eq!(watch.total_settled, 1_000);
+    assert_eq!(watch.baseline_balance, 10_000);
+    // The owner's nonce is untouched
+    assert_eq!(client.get_nonce(&owner), 1);
+
+    let result = client.try_settle_inbound(&relayer, &owner, &proof);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InboundAlreadySettled)));
+
+    // A made-up payment cannot split the rest of the owner's balance
+    let made_up = InboundProof {
+        tx_hash: BytesN::from_array(&env, &[5u8; 32]),
+        ..proof.clone()
+    };
+    let result = client.try_settle_inbound(&relayer, &owner, &made_up);
+    assert_eq!(
+        result,
+        Err(Ok(RemittanceSplitError::InboundExceedsReceived))
+    );
+
+    // Settled payments are remembered for the whole replay window
+    env.ledger()
+        .set_timestamp(env.ledger().timestamp() + INBOUND_REF_WINDOW - 1);
+    let result = client.try_settle_inbound(&relayer, &owner, &proof);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InboundAlreadySettled)));
+
+    client.cancel_inbound_watch(&owner);
+    let next = InboundProof {
+        tx_hash: BytesN::from_array(&env, &[4u8; 32]),
+        ..proof
+    };
+    let result = client.try_settle_inbound(&relayer, &owner, &next);
+    assert_eq!(result, Err(Ok(RemittanceSplitError::InboundWatchNotFound)));
+}
+
+#[test]
+fn test_distribution_receipts_per_leg() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let (client, owner, token, accounts) = setup_distribution(&env);
+
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::FamilySupport,
+    );
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &2,
+        &accounts,
+        &200,
+        &None,
+        &PurposeCode::Other,
+    );
+
+    let history = client.get_remittance_history(&owner, &0, &10);
+    assert_eq!(history.get(0).unwrap().distribution_id, 1);
+    let distribution_id = history.get(1).unwrap().distribution_id;
+    assert_eq!(distribution_id, 2);
+
+    let receipts = client.get_distribution_receipts(&distribution_id);
+    assert_eq!(receipts.len(), 4);
+    let bills = receipts.get(2).unwrap();
+    assert_eq!(bills.receipt_id, 10);
+    assert_eq!(bills.category, SplitCategory::Bills);
+    assert_eq!(bills.recipient, accounts.bills);
+    assert_eq!(bills.token, token);
+    assert_eq!(bills.amount, 30);
+    assert!(!bills.escrowed);
+    let total: i128 = receipts.iter().map(|r| r.amount).sum();
+    assert_eq!(total, 200);
+
+    assert_eq!(client.get_distribution_receipts(&99).len(), 0);
+}
+
+mod household {
+    use crate::Portfolio;
+    use soroban_sdk::{contract, contractimpl, symbol_short, Address, Env};
+
+    /// Answers the reads of `suggest_rebalance` for the bill payments,
+    /// insurance and savings goals contracts with fixed amounts
+    #[contract]
+    pub struct MockHousehold;
+
+    #[contractimpl]
+    impl MockHousehold {
+        pub fn set(env: Env, bills_paid: i128, monthly_premium: i128, shortfall: i128) {
+            env.storage().instance().set(
+                &symbol_short!("AMOUNTS"),
+                &(bills_paid, monthly_premium, shortfall),
+            );
+        }
+
+        pub fn get_paid_total(env: Env, _owner: Address, _from_ts: u64, _to_ts: u64) -> i128 {
+            Self::amounts(&env).0
+        }
+
+        pub fn get_total_monthly_premium(env: Env, _owner: Address) -> i128 {
+            Self::amounts(&env).1
+        }
+
+        pub fn get_portfolio(env: Env, owner: Address) -> Portfolio {
+            Portfolio {
+                owner,
+                goal_count: 1,
+                total_saved: 0,
+                total_target: Self::amounts(&env).2,
+                completion_bps: 0,
+                locked_balance: 0,
+                unlocked_balance: 0,
+                schedules_with_missed_runs: 0,
+                next_contribution_at: None,
+                next_contribution_goal: 0,
+                next_contribution_amount: 0,
+            }
+        }
+
+        fn amounts(env: &Env) -> (i128, i128, i128) {
+            env.storage()
+                .instance()
+                .get(&symbol_short!("AMOUNTS"))
+                .unwrap_or((0, 0, 0))
+        }
+    }
+}
+
+#[test]
+fn test_suggest_rebalance_weighs_commitments_against_remittances() {
+    let env = Env::default();
+    env.mock_all_auths();
+    set_time(&env, 1_000);
+    let (client, owner, token, accounts) = setup_distribution(&env);
+    let household_id = env.register_contract(None, household::MockHousehold);
+    let household = household::MockHouseholdClient::new(&env, &household_id);
+
+    // Split is 50/30/15/5; nothing distributed yet
+    let suggestion = client.suggest_rebalance(&owner);
+    assert_eq!(
+        suggestion.reasons,
+        soroban_sdk::vec![&env, RebalanceReason::NoRecentRemittances]
+    );
+    assert_eq!(suggestion.bills_percent, 15);
+
+    client.distribute_usdc(
+        &token,
+        &owner,
+        &1,
+        &accounts,
+        &1_000,
+        &None,
+        &PurposeCode::FamilySupport,
+    );
+    // Without sources the configured split stands
+    assert_eq!(client.suggest_rebalance(&owner).reasons.len(), 0);
+
+    client.set_rebalance_sources(
+        &owner,
+        &Some(RebalanceSources {
+            bills: Some(household_id.clone()),
+            insurance: Some(household_id.clone()),
+            savings: Some(household_id.clone()),
+        }),
+    );
+    // Bills take 25%, premiums 2%, and closing the goal takes 10% a month,
+    // below the 30% already saved
+    household.set(&250, &20, &1_200);
+    let suggestion = client.suggest_rebalance(&owner);
+    assert_eq!(
+        (
+            suggestion.spending_percent,
+            suggestion.savings_percent,
+            suggestion.bills_percent,
+            suggestion.insurance_percent
+        ),
+        (43, 30, 25, 2)
+    );
+    assert_eq!(
+        suggestion.reasons,
+        soroban_sdk::vec![
+            &env,
+            RebalanceReason::BillsAboveSplit,
+            RebalanceReason::PremiumsBelowSplit
+        ]
+    );
+
+    // 90% + 2% + 60% is more than was sent, so the shares are scaled down
+    household.set(&900, &20, &7_200);
+    let suggestion = client.suggest_rebalance(&owner);
+    assert_eq!(
+        (
+            suggestion.spending_percent,
+            suggestion.savings_percent,
+            suggestion.bills_percent,
+            suggestion.insurance_percent
+        ),
+        (1, 39, 59, 1)
+    );
+    assert_eq!(
+        suggestion.reasons,
+        soroban_sdk::vec![
+            &env,
+            RebalanceReason::BillsAboveSplit,
+            RebalanceReason::PremiumsBelowSplit,
+            RebalanceReason::GoalShortfall,
+            RebalanceReason::NeedsExceedRemittances
+        ]
+    );
+}
@@ -0,0 +1,60 @@
+//! Setup helpers for tests of this contract and of crates built on it,
+//! enabled by the `testutils` feature.
+
+use crate::{AccountGroup, RemittanceSplit, RemittanceSplitClient};
+use soroban_sdk::{testutils::Address as _, token::StellarAssetClient, Address, Env};
+
+/// Balance `split_with_funded_token` mints to the owner
+pub const FIXTURE_BALANCE: i128 = 10_000;
+
+/// A split configured for `owner` with a token they hold and may distribute
+pub struct SplitFixture<'a> {
+    pub client: RemittanceSplitClient<'a>,
+    pub owner: Address,
+    pub token: Address,
+    pub accounts: AccountGroup,
+}
+
+/// Register the contract
+pub fn register(env: &Env) -> RemittanceSplitClient<'_> {
+    let contract_id = env.register_contract(None, RemittanceSplit);
+    RemittanceSplitClient::new(env, &contract_id)
+}
+
+/// Four new category accounts
+pub fn default_accounts(env: &Env) -> AccountGroup {
+    AccountGroup {
+        spending: Address::generate(env),
+        savings: Address::generate(env),
+        bills: Address::generate(env),
+        insurance: Address::generate(env),
+    }
+}
+
+/// Deploy a Stellar asset and mint `amount` of it to each holder
+pub fn funded_token(env: &Env, holders: &[&Address], amount: i128) -> Address {
+    let token = env.register_stellar_asset_contract_v2(Address::generate(env));
+    let admin = StellarAssetClient::new(env, &token.address());
+    for holder in holders {
+        admin.mint(holder, &amount);
+    }
+    token.address()
+}
+
+/// Register the contract and set up a new owner with a 50/30/15/5 split and
+/// `FIXTURE_BALANCE` of a token on the supported list. The owner is also the
+/// pause admin. Auths must be mocked.
+pub fn split_with_funded_token(env: &Env) -> SplitFixture<'_> {
+    let client = register(env);
+    let owner = Address::generate(env);
+    client.initialize_split(&owner, &0, &50, &30, &15, &5);
+    let token = funded_token(env, &[&owner], FIXTURE_BALANCE);
+    client.set_pause_admin(&owner, &owner);
+    client.add_supported_token(&owner, &token);
+    SplitFixture {
+        client,
+        owner,
+        token,
+        accounts: default_accounts(env),
+    }
+}
@@ -1,4 +1,4 @@
-use remittance_split::{AccountGroup, RemittanceSplit, RemittanceSplitClient};
+use remittance_split::{AccountGroup, PurposeCode, RemittanceSplit, RemittanceSplitClient};
 use soroban_sdk::testutils::{Address as AddressTrait, EnvTestConfig, Ledger, LedgerInfo};
 use soroban_sdk::token::StellarAssetClient;
 use soroban_sdk::{Address, Env};
@@ -49,6 +49,9 @@ fn bench_distribute_usdc_worst_case() {
     let payer = <Address as AddressTrait>::generate(&env);
     let amount = 10_000i128;
     StellarAssetClient::new(&env, &token_contract.address()).mint(&payer, &amount);
+    client.initialize_split(&payer, &0, &50, &30, &15, &5);
+    client.set_pause_admin(&admin, &admin);
+    client.add_supported_token(&admin, &token_contract.address());
 
     let accounts = AccountGroup {
         spending: <Address as AddressTrait>::generate(&env),
@@ -57,7 +60,7 @@ fn bench_distribute_usdc_worst_case() {
         insurance: <Address as AddressTrait>::generate(&env),
     };
 
-    let _nonce = 0u64;
+    let _nonce = 1u64;
     let (cpu, mem, distributed) = measure(&env, || {
         client.distribute_usdc(
             &token_contract.address(),
@@ -65,6 +68,8 @@ fn bench_distribute_usdc_worst_case() {
             &_nonce,
             &accounts,
             &amount,
+            &None,
+            &PurposeCode::FamilySupport,
         )
     });
     assert!(distributed);
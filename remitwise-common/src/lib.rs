@@ -1,6 +1,11 @@
 #![no_std]
+#[cfg(any(test, feature = "testutils"))]
+pub mod testutils;
 
-use soroban_sdk::{contracttype, symbol_short, Symbol};
+use soroban_sdk::{
+    contractclient, contracttype, symbol_short, xdr::ToXdr, Address, BytesN, Env, IntoVal, Map,
+    String, Symbol, Val, Vec,
+};
 
 /// Financial categories for remittance allocation
 #[contracttype]
@@ -91,6 +96,16 @@ pub const CONTRACT_VERSION: u32 = 1;
 /// Maximum batch size for operations
 pub const MAX_BATCH_SIZE: u32 = 50;
 
+/// Fixed-point scale of oracle rates (7 decimals, matching Stellar amounts)
+pub const RATE_SCALE: i128 = 10_000_000;
+
+/// FX oracle quoting how many token units one unit of a currency is worth,
+/// scaled by `RATE_SCALE`
+#[contractclient(name = "PriceOracleClient")]
+pub trait PriceOracleTrait {
+    fn get_rate(env: Env, currency: String) -> Option<i128>;
+}
+
 /// Helper function to clamp limit
 pub fn clamp_limit(limit: u32) -> u32 {
     if limit == 0 {
@@ -102,6 +117,560 @@ pub fn clamp_limit(limit: u32) -> u32 {
     }
 }
 
+/// Months since January 1970 of the UTC calendar month containing `ts`,
+/// for keying per-month totals
+pub fn month_index(ts: u64) -> u32 {
+    // Howard Hinnant's `civil_from_days`, keeping only the year and month
+    let z = (ts / 86400) as i64 + 719_468;
+    let era = z.div_euclid(146_097);
+    let doe = z - era * 146_097;
+    let yoe = (doe - doe / 1460 + doe / 36_524 - doe / 146_096) / 365;
+    let doy = doe - (365 * yoe + yoe / 4 - yoe / 100);
+    let mp = (5 * doy + 2) / 153;
+    let month = if mp < 10 { mp + 3 } else { mp - 9 };
+    let year = yoe + era * 400 + if month <= 2 { 1 } else { 0 };
+    ((year - 1970) * 12 + month - 1) as u32
+}
+
+/// Instance storage flag set while a guarded entrypoint is running
+const REENTRANCY_LOCK: Symbol = symbol_short!("RE_LOCK");
+
+/// Reentrancy guard for entrypoints that call out to token contracts.
+///
+/// Take it at the top of the entrypoint and keep it alive until the end:
+///
+/// ```ignore
+/// let _guard = ReentrancyGuard::enter(&env);
+/// ```
+///
+/// A nested `enter` while the flag is set panics. Dropping the guard clears
+/// the flag, so early returns release it; a panic rolls it back together with
+/// the rest of the invocation.
+#[must_use = "the lock is released as soon as the guard is dropped"]
+pub struct ReentrancyGuard {
+    env: Env,
+}
+
+impl ReentrancyGuard {
+    pub fn enter(env: &Env) -> Self {
+        let storage = env.storage().instance();
+        if storage.has(&REENTRANCY_LOCK) {
+            panic!("Reentrant call");
+        }
+        storage.set(&REENTRANCY_LOCK, &true);
+        ReentrancyGuard { env: env.clone() }
+    }
+}
+
+impl Drop for ReentrancyGuard {
+    fn drop(&mut self) {
+        self.env.storage().instance().remove(&REENTRANCY_LOCK);
+    }
+}
+
+/// Approximate ledger close time, for turning a replay window into a TTL
+const SECS_PER_LEDGER: u64 = 5;
+
+/// Registry of idempotency references (e.g. a hash of a payment or
+/// distribution reference) for suppressing duplicate submissions within a
+/// replay window.
+///
+/// Each reference is its own temporary entry, keyed by `prefix` and the
+/// reference, holding the ledger time it was recorded. Its TTL covers the
+/// window, capped at the network's maximum TTL, after which the entry is
+/// deleted rather than archived, so storage stays bounded by the references
+/// of the last `window` seconds and recording one costs the same however
+/// many are held. A reference older than the window no longer counts as
+/// recorded; `prune_refs` drops such entries before their TTL runs out.
+///
+/// ```ignore
+/// const PAYMENT_REFS: RefRegistry = RefRegistry::new(symbol_short!("PAY_REF"), 90 * 86400);
+/// ```
+pub struct RefRegistry {
+    prefix: Symbol,
+    window: u64,
+}
+
+/// Why `RefRegistry::record_ref` refused a reference. Contracts map these
+/// onto their own error enums.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RefError {
+    /// The reference was recorded within the replay window
+    Duplicate,
+}
+
+impl RefRegistry {
+    pub const fn new(prefix: Symbol, window: u64) -> Self {
+        RefRegistry { prefix, window }
+    }
+
+    fn key(&self, reference: &BytesN<32>) -> (Symbol, BytesN<32>) {
+        (self.prefix.clone(), reference.clone())
+    }
+
+    fn recorded_at(&self, env: &Env, reference: &BytesN<32>) -> Option<u64> {
+        env.storage().temporary().get(&self.key(reference))
+    }
+
+    /// Whether `reference` was recorded within the replay window
+    pub fn check_ref(&self, env: &Env, reference: &BytesN<32>) -> bool {
+        self.recorded_at(env, reference).is_some_and(|recorded_at| {
+            env.ledger().timestamp() < recorded_at.saturating_add(self.window)
+        })
+    }
+
+    /// Record `reference` at the current ledger time, leaving the registry
+    /// unchanged if it is refused.
+    pub fn record_ref(&self, env: &Env, reference: &BytesN<32>) -> Result<(), RefError> {
+        if self.check_ref(env, reference) {
+            return Err(RefError::Duplicate);
+        }
+        let key = self.key(reference);
+        let ledgers = u32::try_from(self.window / SECS_PER_LEDGER + 1)
+            .unwrap_or(u32::MAX)
+            .min(env.storage().max_ttl());
+        env.storage()
+            .temporary()
+            .set(&key, &env.ledger().timestamp());
+        env.storage().temporary().extend_ttl(&key, ledgers, ledgers);
+        Ok(())
+    }
+
+    /// Remove those of `references` recorded at least `max_age` seconds ago.
+    /// `max_age` is raised to the replay window, so pruning never lets a
+    /// duplicate through. Returns how many were removed.
+    pub fn prune_refs(&self, env: &Env, references: &Vec<BytesN<32>>, max_age: u64) -> u32 {
+        let max_age = max_age.max(self.window);
+        let now = env.ledger().timestamp();
+        let mut pruned = 0;
+        for reference in references.iter() {
+            if self
+                .recorded_at(env, &reference)
+                .is_some_and(|recorded_at| now >= recorded_at.saturating_add(max_age))
+            {
+                env.storage().temporary().remove(&self.key(&reference));
+                pruned += 1;
+            }
+        }
+        pruned
+    }
+}
+
+/// Instance storage keys of the pause admin and pause switches
+const PAUSE_ADMIN_KEY: Symbol = symbol_short!("PAUSE_ADM");
+const PAUSED_KEY: Symbol = symbol_short!("PAUSED");
+const PAUSED_FN_KEY: Symbol = symbol_short!("PAUSED_FN");
+const UNPAUSE_AT_KEY: Symbol = symbol_short!("UNP_AT");
+
+/// Pause switches of a contract as reported by `get_pause_state`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct PauseState {
+    pub paused: bool,
+    /// Functions paused on their own, by their `pause_functions` symbol
+    pub paused_functions: Vec<Symbol>,
+    /// Time before which `unpause` is refused, if set
+    pub unpause_at: Option<u64>,
+    pub pause_admin: Option<Address>,
+}
+
+/// Pause admin and pause switches, kept under the instance keys the
+/// contracts share (`PAUSE_ADM`, `PAUSED`, `PAUSED_FN`, `UNP_AT`).
+///
+/// Only storage lives here: contracts authorize the caller against
+/// `admin` themselves and fail with their own error types.
+pub struct Pausable;
+
+impl Pausable {
+    pub fn admin(env: &Env) -> Option<Address> {
+        env.storage().instance().get(&PAUSE_ADMIN_KEY)
+    }
+
+    pub fn set_admin(env: &Env, admin: &Address) {
+        env.storage().instance().set(&PAUSE_ADMIN_KEY, admin);
+    }
+
+    pub fn is_paused(env: &Env) -> bool {
+        env.storage().instance().get(&PAUSED_KEY).unwrap_or(false)
+    }
+
+    pub fn set_paused(env: &Env, paused: bool) {
+        env.storage().instance().set(&PAUSED_KEY, &paused);
+    }
+
+    fn paused_functions(env: &Env) -> Map<Symbol, bool> {
+        env.storage()
+            .instance()
+            .get(&PAUSED_FN_KEY)
+            .unwrap_or_else(|| Map::new(env))
+    }
+
+    pub fn is_function_paused(env: &Env, func: Symbol) -> bool {
+        Self::paused_functions(env).get(func).unwrap_or(false)
+    }
+
+    pub fn set_function_paused(env: &Env, func: Symbol, paused: bool) {
+        let mut functions = Self::paused_functions(env);
+        functions.set(func, paused);
+        env.storage().instance().set(&PAUSED_FN_KEY, &functions);
+    }
+
+    /// Time before which `unpause` is refused, if one was scheduled
+    pub fn unpause_at(env: &Env) -> Option<u64> {
+        env.storage().instance().get(&UNPAUSE_AT_KEY)
+    }
+
+    pub fn clear_unpause_at(env: &Env) {
+        env.storage().instance().remove(&UNPAUSE_AT_KEY);
+    }
+
+    pub fn state(env: &Env) -> PauseState {
+        let mut paused_functions = Vec::new(env);
+        for (func, paused) in Self::paused_functions(env).iter() {
+            if paused {
+                paused_functions.push_back(func);
+            }
+        }
+        PauseState {
+            paused: Self::is_paused(env),
+            paused_functions,
+            unpause_at: Self::unpause_at(env),
+            pause_admin: Self::admin(env),
+        }
+    }
+}
+
+/// Most recovery guardians an owner can register
+pub const MAX_RECOVERY_GUARDIANS: u32 = 10;
+
+/// Time the owner has to veto a recovery once enough guardians approved it
+pub const RECOVERY_DELAY_SECS: u64 = 3 * 86400;
+
+/// Guardians who can together move an owner's holdings to a new address
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryConfig {
+    pub guardians: Vec<Address>,
+    /// Guardian approvals needed to start the waiting period
+    pub threshold: u32,
+}
+
+/// Recovery of an owner's holdings to `new_owner`, in progress
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct RecoveryRequest {
+    pub new_owner: Address,
+    pub approvals: Vec<Address>,
+    pub initiated_at: u64,
+    /// Set once `threshold` guardians approve; the owner can veto until then
+    pub executable_at: Option<u64>,
+}
+
+/// Why a `GuardianRecovery` step was refused. Contracts map these onto their
+/// own error enums.
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+pub enum RecoveryError {
+    /// Guardians repeat, include the owner or exceed `MAX_RECOVERY_GUARDIANS`,
+    /// the threshold is 0 or above their number, or the new owner is the owner
+    InvalidConfig,
+    /// The address is not one of the owner's guardians
+    NotGuardian,
+    /// The owner already has a recovery in progress
+    Pending,
+    NotFound,
+    /// The recovery lacks guardian approvals or its waiting period is running
+    NotReady,
+}
+
+/// M-of-N guardian recovery of an owner's holdings.
+///
+/// An owner registers guardians and a threshold. One guardian starts a
+/// recovery to a new address, the others approve it, and once `threshold`
+/// have approved the owner has `RECOVERY_DELAY_SECS` to veto before it can
+/// be completed. Each step checks the authorization of the address acting;
+/// moving the holdings themselves is left to the contract.
+///
+/// Each owner's config and request are kept in their own persistent entry,
+/// keyed by `prefix` and the owner, so the cost of a recovery step does not
+/// grow with the number of owners and one contract can run separate
+/// recoveries side by side. Each write extends the entry for
+/// `ARCHIVE_BUMP_AMOUNT` ledgers.
+///
+/// ```ignore
+/// const RECOVERY: GuardianRecovery = GuardianRecovery::new(symbol_short!("RECOVERY"));
+/// ```
+pub struct GuardianRecovery {
+    prefix: Symbol,
+}
+
+impl GuardianRecovery {
+    pub const fn new(prefix: Symbol) -> Self {
+        GuardianRecovery { prefix }
+    }
+
+    fn config_key(&self, owner: &Address) -> (Symbol, Symbol, Address) {
+        (self.prefix.clone(), symbol_short!("config"), owner.clone())
+    }
+
+    fn request_key(&self, owner: &Address) -> (Symbol, Symbol, Address) {
+        (self.prefix.clone(), symbol_short!("request"), owner.clone())
+    }
+
+    fn store<K, V>(env: &Env, key: &K, value: &V)
+    where
+        K: IntoVal<Env, Val>,
+        V: IntoVal<Env, Val>,
+    {
+        let storage = env.storage().persistent();
+        storage.set(key, value);
+        storage.extend_ttl(key, ARCHIVE_LIFETIME_THRESHOLD, ARCHIVE_BUMP_AMOUNT);
+    }
+
+    pub fn config(&self, env: &Env, owner: &Address) -> Option<RecoveryConfig> {
+        env.storage().persistent().get(&self.config_key(owner))
+    }
+
+    pub fn request(&self, env: &Env, owner: &Address) -> Option<RecoveryRequest> {
+        env.storage().persistent().get(&self.request_key(owner))
+    }
+
+    /// Register `owner`'s guardians and threshold; the owner must authorize.
+    /// An empty list turns recovery off. Any recovery in progress is dropped.
+    pub fn set_guardians(
+        &self,
+        env: &Env,
+        owner: &Address,
+        guardians: &Vec<Address>,
+        threshold: u32,
+    ) -> Result<(), RecoveryError> {
+        owner.require_auth();
+        if guardians.len() > MAX_RECOVERY_GUARDIANS
+            || (!guardians.is_empty() && (threshold == 0 || threshold > guardians.len()))
+        {
+            return Err(RecoveryError::InvalidConfig);
+        }
+        for (i, guardian) in guardians.iter().enumerate() {
+            if guardian == *owner || guardians.first_index_of(&guardian) != Some(i as u32) {
+                return Err(RecoveryError::InvalidConfig);
+            }
+        }
+
+        if guardians.is_empty() {
+            env.storage().persistent().remove(&self.config_key(owner));
+        } else {
+            Self::store(
+                env,
+                &self.config_key(owner),
+                &RecoveryConfig {
+                    guardians: guardians.clone(),
+                    threshold,
+                },
+            );
+        }
+        env.storage().persistent().remove(&self.request_key(owner));
+        Ok(())
+    }
+
+    /// Start recovering `owner`'s holdings to `new_owner`; the guardian must
+    /// authorize and their approval is counted.
+    pub fn initiate(
+        &self,
+        env: &Env,
+        guardian: &Address,
+        owner: &Address,
+        new_owner: &Address,
+    ) -> Result<RecoveryRequest, RecoveryError> {
+        guardian.require_auth();
+        let config = self.config(env, owner).ok_or(RecoveryError::NotGuardian)?;
+        if !config.guardians.contains(guardian) {
+            return Err(RecoveryError::NotGuardian);
+        }
+        if new_owner == owner {
+            return Err(RecoveryError::InvalidConfig);
+        }
+        if self.request(env, owner).is_some() {
+            return Err(RecoveryError::Pending);
+        }
+
+        let now = env.ledger().timestamp();
+        let request = RecoveryRequest {
+            new_owner: new_owner.clone(),
+            approvals: Vec::from_array(env, [guardian.clone()]),
+            initiated_at: now,
+            executable_at: (config.threshold <= 1).then(|| now.saturating_add(RECOVERY_DELAY_SECS)),
+        };
+        Self::store(env, &self.request_key(owner), &request);
+        // Keep the config alive for as long as the request it governs
+        Self::store(env, &self.config_key(owner), &config);
+        Ok(request)
+    }
+
+    /// Add `guardian`'s approval to the recovery of `owner`; the guardian
+    /// must authorize. Approving twice is a no-op.
+    ///
+    /// Returns the number of approvals so far.
+    pub fn approve(
+        &self,
+        env: &Env,
+        guardian: &Address,
+        owner: &Address,
+    ) -> Result<u32, RecoveryError> {
+        guardian.require_auth();
+        let mut request = self.request(env, owner).ok_or(RecoveryError::NotFound)?;
+        let config = self.config(env, owner).ok_or(RecoveryError::NotFound)?;
+        if !config.guardians.contains(guardian) {
+            return Err(RecoveryError::NotGuardian);
+        }
+        if request.approvals.contains(guardian) {
+            return Ok(request.approvals.len());
+        }
+
+        request.approvals.push_back(guardian.clone());
+        if request.executable_at.is_none() && request.approvals.len() >= config.threshold {
+            request.executable_at =
+                Some(env.ledger().timestamp().saturating_add(RECOVERY_DELAY_SECS));
+        }
+        let approvals = request.approvals.len();
+        Self::store(env, &self.request_key(owner), &request);
+        Ok(approvals)
+    }
+
+    /// Drop the recovery of `owner`; the owner must authorize. Returns the
+    /// vetoed request.
+    pub fn veto(&self, env: &Env, owner: &Address) -> Result<RecoveryRequest, RecoveryError> {
+        owner.require_auth();
+        let request = self.request(env, owner).ok_or(RecoveryError::NotFound)?;
+        env.storage().persistent().remove(&self.request_key(owner));
+        Ok(request)
+    }
+
+    /// Close the recovery of `owner` once its waiting period has passed and
+    /// return the new owner, who must authorize. The caller then moves the
+    /// owner's holdings.
+    pub fn complete(&self, env: &Env, owner: &Address) -> Result<Address, RecoveryError> {
+        let request = self.request(env, owner).ok_or(RecoveryError::NotFound)?;
+        request.new_owner.require_auth();
+        match request.executable_at {
+            Some(at) if env.ledger().timestamp() >= at => {}
+            _ => return Err(RecoveryError::NotReady),
+        }
+        env.storage().persistent().remove(&self.request_key(owner));
+        Ok(request.new_owner)
+    }
+}
+
+/// One action in an owner's `AuditLog`
+#[contracttype]
+#[derive(Clone, Debug, Eq, PartialEq)]
+pub struct AuditRecord {
+    /// Position in the owner's log, counting from 0
+    pub index: u32,
+    /// Address that performed the action, not necessarily the owner
+    pub actor: Address,
+    pub action: Symbol,
+    /// Digests of the state the action changed, before and after it; all
+    /// zeroes where there was no state
+    pub before: BytesN<32>,
+    pub after: BytesN<32>,
+    pub timestamp: u64,
+}
+
+/// Append-only per-owner log of actions, for proving what happened to an
+/// owner's configuration and funds.
+///
+/// Entries go to persistent storage, one entry per key under `prefix`, and
+/// are never rewritten or dropped, so the log grows without a cap. Each
+/// write extends the entry for `ARCHIVE_BUMP_AMOUNT` ledgers.
+///
+/// ```ignore
+/// const AUDIT_LOG: AuditLog = AuditLog::new(symbol_short!("AUD_LOG"));
+/// ```
+pub struct AuditLog {
+    prefix: Symbol,
+}
+
+impl AuditLog {
+    pub const fn new(prefix: Symbol) -> Self {
+        AuditLog { prefix }
+    }
+
+    /// SHA-256 of the XDR encoding of `value`
+    pub fn digest<T: IntoVal<Env, Val>>(env: &Env, value: T) -> BytesN<32> {
+        env.crypto().sha256(&value.to_xdr(env)).into()
+    }
+
+    /// Digest standing for "no state"
+    pub fn empty_digest(env: &Env) -> BytesN<32> {
+        BytesN::from_array(env, &[0u8; 32])
+    }
+
+    fn len_key(&self, owner: &Address) -> (Symbol, Address) {
+        (self.prefix.clone(), owner.clone())
+    }
+
+    fn entry_key(&self, owner: &Address, index: u32) -> (Symbol, Address, u32) {
+        (self.prefix.clone(), owner.clone(), index)
+    }
+
+    /// Number of entries in `owner`'s log
+    pub fn len(&self, env: &Env, owner: &Address) -> u32 {
+        env.storage()
+            .persistent()
+            .get(&self.len_key(owner))
+            .unwrap_or(0)
+    }
+
+    /// Add an entry to `owner`'s log, returning its index
+    pub fn append(
+        &self,
+        env: &Env,
+        owner: &Address,
+        actor: &Address,
+        action: Symbol,
+        before: BytesN<32>,
+        after: BytesN<32>,
+    ) -> u32 {
+        let index = self.len(env, owner);
+        let storage = env.storage().persistent();
+        let entry_key = self.entry_key(owner, index);
+        storage.set(
+            &entry_key,
+            &AuditRecord {
+                index,
+                actor: actor.clone(),
+                action,
+                before,
+                after,
+                timestamp: env.ledger().timestamp(),
+            },
+        );
+        storage.extend_ttl(&entry_key, ARCHIVE_LIFETIME_THRESHOLD, ARCHIVE_BUMP_AMOUNT);
+        let len_key = self.len_key(owner);
+        storage.set(&len_key, &(index + 1));
+        storage.extend_ttl(&len_key, ARCHIVE_LIFETIME_THRESHOLD, ARCHIVE_BUMP_AMOUNT);
+        index
+    }
+
+    /// Up to `limit` entries of `owner`'s log from `offset`, oldest first.
+    /// The limit is clamped like other paginated queries.
+    pub fn page(&self, env: &Env, owner: &Address, offset: u32, limit: u32) -> Vec<AuditRecord> {
+        let end = offset
+            .saturating_add(clamp_limit(limit))
+            .min(self.len(env, owner));
+        let mut out = Vec::new(env);
+        for index in offset..end {
+            if let Some(record) = env
+                .storage()
+                .persistent()
+                .get(&self.entry_key(owner, index))
+            {
+                out.push_back(record);
+            }
+        }
+        out
+    }
+}
+
 /// Event emission helper
 pub struct RemitwiseEvents;
 
@@ -135,3 +704,351 @@ impl RemitwiseEvents {
         env.events().publish(topics, data);
     }
 }
+
+#[cfg(test)]
+mod test {
+    use super::*;
+    use soroban_sdk::{
+        contract, contracterror, contractimpl,
+        testutils::{Address as _, Ledger},
+    };
+
+    #[contract]
+    struct RefHost;
+
+    #[contractimpl]
+    impl RefHost {}
+
+    #[test]
+    fn test_month_index() {
+        assert_eq!(month_index(0), 0);
+        // 2024-02-29 23:59:59 and 2024-03-01 00:00:00 UTC
+        assert_eq!(month_index(1_709_251_199), 649);
+        assert_eq!(month_index(1_709_251_200), 650);
+    }
+
+    #[test]
+    #[should_panic(expected = "Reentrant call")]
+    fn test_reentrancy_guard_rejects_nested_enter() {
+        let env = Env::default();
+        let id = env.register_contract(None, RefHost);
+        env.as_contract(&id, || {
+            let _guard = ReentrancyGuard::enter(&env);
+            let _nested = ReentrancyGuard::enter(&env);
+        });
+    }
+
+    #[test]
+    fn test_reentrancy_guard_released_on_drop() {
+        let env = Env::default();
+        let id = env.register_contract(None, RefHost);
+        env.as_contract(&id, || {
+            let guard = ReentrancyGuard::enter(&env);
+            assert!(env.storage().instance().has(&REENTRANCY_LOCK));
+            drop(guard);
+            assert!(!env.storage().instance().has(&REENTRANCY_LOCK));
+
+            let _again = ReentrancyGuard::enter(&env);
+            assert!(env.storage().instance().has(&REENTRANCY_LOCK));
+        });
+    }
+
+    #[test]
+    fn test_pausable_state() {
+        let env = Env::default();
+        let id = env.register_contract(None, RefHost);
+        let admin = Address::generate(&env);
+        let create = symbol_short!("crt_goal");
+        let lock = symbol_short!("lock");
+
+        env.as_contract(&id, || {
+            assert_eq!(
+                Pausable::state(&env),
+                PauseState {
+                    paused: false,
+                    paused_functions: Vec::new(&env),
+                    unpause_at: None,
+                    pause_admin: None,
+                }
+            );
+            Pausable::set_admin(&env, &admin);
+            Pausable::set_function_paused(&env, create.clone(), true);
+            Pausable::set_function_paused(&env, lock.clone(), true);
+            Pausable::set_function_paused(&env, lock.clone(), false);
+            assert!(Pausable::is_function_paused(&env, create.clone()));
+            assert!(!Pausable::is_function_paused(&env, lock));
+            Pausable::set_paused(&env, true);
+
+            let state = Pausable::state(&env);
+            assert!(state.paused);
+            assert_eq!(state.paused_functions, Vec::from_array(&env, [create]));
+            assert_eq!(state.pause_admin, Some(admin.clone()));
+        });
+    }
+
+    #[test]
+    fn test_ref_registry_suppresses_duplicates_within_window() {
+        const REFS: RefRegistry = RefRegistry::new(symbol_short!("REFS"), 1_000);
+        const OTHER: RefRegistry = RefRegistry::new(symbol_short!("OTHER"), 1_000);
+        let env = Env::default();
+        env.budget().reset_unlimited();
+        let id = env.register_contract(None, RefHost);
+        let reference = |n: u32| {
+            let mut bytes = [0u8; 32];
+            bytes[..4].copy_from_slice(&n.to_be_bytes());
+            BytesN::from_array(&env, &bytes)
+        };
+
+        env.as_contract(&id, || {
+            env.ledger().set_timestamp(1_000);
+            assert_eq!(REFS.record_ref(&env, &reference(1)), Ok(()));
+            assert_eq!(
+                REFS.record_ref(&env, &reference(1)),
+                Err(RefError::Duplicate)
+            );
+            assert!(REFS.check_ref(&env, &reference(1)));
+            // Registries under other prefixes are independent
+            assert!(!OTHER.check_ref(&env, &reference(1)));
+            assert_eq!(OTHER.record_ref(&env, &reference(1)), Ok(()));
+
+            // There is no cap on the references held within the window
+            for n in 2..=600 {
+                assert_eq!(REFS.record_ref(&env, &reference(n)), Ok(()));
+            }
+
+            env.ledger().set_timestamp(1_999);
+            assert!(REFS.check_ref(&env, &reference(1)));
+            env.ledger().set_timestamp(2_000);
+            assert!(!REFS.check_ref(&env, &reference(1)));
+            assert_eq!(REFS.record_ref(&env, &reference(1)), Ok(()));
+            assert_eq!(
+                REFS.record_ref(&env, &reference(1)),
+                Err(RefError::Duplicate)
+            );
+        });
+    }
+
+    #[test]
+    fn test_prune_refs_only_drops_references_past_the_window() {
+        const REFS: RefRegistry = RefRegistry::new(symbol_short!("REFS"), 1_000);
+        let env = Env::default();
+        let id = env.register_contract(None, RefHost);
+        let reference = |n: u8| BytesN::from_array(&env, &[n; 32]);
+
+        env.as_contract(&id, || {
+            env.ledger().set_timestamp(1_000);
+            REFS.record_ref(&env, &reference(1)).unwrap();
+            REFS.record_ref(&env, &reference(2)).unwrap();
+            env.ledger().set_timestamp(1_500);
+            REFS.record_ref(&env, &reference(3)).unwrap();
+
+            let references = soroban_sdk::vec![&env, reference(1), reference(2), reference(3)];
+            // A max age below the window is raised to it
+            env.ledger().set_timestamp(1_999);
+            assert_eq!(REFS.prune_refs(&env, &references, 0), 0);
+            assert!(REFS.check_ref(&env, &reference(1)));
+
+            env.ledger().set_timestamp(2_000);
+            assert_eq!(REFS.prune_refs(&env, &references, 0), 2);
+            assert!(!env
+                .storage()
+                .temporary()
+                .has(&(symbol_short!("REFS"), reference(1))));
+            assert!(REFS.check_ref(&env, &reference(3)));
+            // Pruned references are gone, so pruning again removes nothing
+            assert_eq!(REFS.prune_refs(&env, &references, 0), 0);
+        });
+    }
+
+    #[test]
+    fn test_audit_log_appends_per_owner_and_pages() {
+        const LOG: AuditLog = AuditLog::new(symbol_short!("AUD_LOG"));
+        let env = Env::default();
+        let id = env.register_contract(None, RefHost);
+        let owner = Address::generate(&env);
+        let other = Address::generate(&env);
+        let actor = Address::generate(&env);
+
+        env.as_contract(&id, || {
+            env.ledger().set_timestamp(1_000);
+            let empty = AuditLog::empty_digest(&env);
+            let first = AuditLog::digest(&env, 1u32);
+            assert_eq!(first, AuditLog::digest(&env, 1u32));
+            assert_ne!(first, AuditLog::digest(&env, 2u32));
+
+            let action = symbol_short!("update");
+            assert_eq!(
+                LOG.append(
+                    &env,
+                    &owner,
+                    &owner,
+                    action.clone(),
+                    empty.clone(),
+                    first.clone()
+                ),
+                0
+            );
+            for n in 2..=30u32 {
+                LOG.append(
+                    &env,
+                    &owner,
+                    &actor,
+                    action.clone(),
+                    first.clone(),
+                    AuditLog::digest(&env, n),
+                );
+            }
+            LOG.append(
+                &env,
+                &other,
+                &other,
+                action.clone(),
+                empty.clone(),
+                first.clone(),
+            );
+            assert_eq!(LOG.len(&env, &owner), 30);
+            assert_eq!(LOG.len(&env, &other), 1);
+
+            let page = LOG.page(&env, &owner, 0, 2);
+            assert_eq!(page.len(), 2);
+            let record = page.get(0).unwrap();
+            assert_eq!(
+                (record.index, record.actor, record.timestamp),
+                (0, owner.clone(), 1_000)
+            );
+            assert_eq!((record.before, record.after), (empty, first));
+            assert_eq!(page.get(1).unwrap().actor, actor);
+            // The limit defaults and is clamped like other pages
+            assert_eq!(LOG.page(&env, &owner, 0, 0).len(), DEFAULT_PAGE_LIMIT);
+            assert_eq!(LOG.page(&env, &owner, 25, 10).len(), 5);
+            assert_eq!(LOG.page(&env, &owner, 30, 10).len(), 0);
+        });
+    }
+
+    const RECOVERY: GuardianRecovery = GuardianRecovery::new(symbol_short!("RECOVERY"));
+
+    #[contracterror]
+    #[derive(Copy, Clone, Debug, Eq, PartialEq)]
+    #[repr(u32)]
+    enum HostError {
+        InvalidConfig = 1,
+        NotGuardian = 2,
+        Pending = 3,
+        NotFound = 4,
+        NotReady = 5,
+    }
+
+    impl From<RecoveryError> for HostError {
+        fn from(err: RecoveryError) -> Self {
+            match err {
+                RecoveryError::InvalidConfig => HostError::InvalidConfig,
+                RecoveryError::NotGuardian => HostError::NotGuardian,
+                RecoveryError::Pending => HostError::Pending,
+                RecoveryError::NotFound => HostError::NotFound,
+                RecoveryError::NotReady => HostError::NotReady,
+            }
+        }
+    }
+
+    #[contract]
+    struct RecoveryHost;
+
+    #[contractimpl]
+    impl RecoveryHost {
+        pub fn set_guardians(
+            env: Env,
+            owner: Address,
+            guardians: Vec<Address>,
+            threshold: u32,
+        ) -> Result<(), HostError> {
+            Ok(RECOVERY.set_guardians(&env, &owner, &guardians, threshold)?)
+        }
+
+        pub fn initiate(
+            env: Env,
+            guardian: Address,
+            owner: Address,
+            new_owner: Address,
+        ) -> Result<RecoveryRequest, HostError> {
+            Ok(RECOVERY.initiate(&env, &guardian, &owner, &new_owner)?)
+        }
+
+        pub fn approve(env: Env, guardian: Address, owner: Address) -> Result<u32, HostError> {
+            Ok(RECOVERY.approve(&env, &guardian, &owner)?)
+        }
+
+        pub fn veto(env: Env, owner: Address) -> Result<RecoveryRequest, HostError> {
+            Ok(RECOVERY.veto(&env, &owner)?)
+        }
+
+        pub fn complete(env: Env, owner: Address) -> Result<Address, HostError> {
+            Ok(RECOVERY.complete(&env, &owner)?)
+        }
+
+        pub fn request(env: Env, owner: Address) -> Option<RecoveryRequest> {
+            RECOVERY.request(&env, &owner)
+        }
+
+        pub fn other_config(env: Env, owner: Address) -> Option<RecoveryConfig> {
+            GuardianRecovery::new(symbol_short!("OTHER")).config(&env, &owner)
+        }
+    }
+
+    #[test]
+    fn test_guardian_recovery_threshold_delay_and_veto() {
+        let env = Env::default();
+        env.mock_all_auths();
+        let id = env.register_contract(None, RecoveryHost);
+        let client = RecoveryHostClient::new(&env, &id);
+        let owner = Address::generate(&env);
+        let new_owner = Address::generate(&env);
+        let g1 = Address::generate(&env);
+        let g2 = Address::generate(&env);
+        let outsider = Address::generate(&env);
+        let guardians = Vec::from_array(&env, [g1.clone(), g2.clone()]);
+        env.ledger().set_timestamp(1_000);
+
+        assert_eq!(
+            client.try_set_guardians(&owner, &guardians, &3),
+            Err(Ok(HostError::InvalidConfig))
+        );
+        let with_owner = Vec::from_array(&env, [g1.clone(), owner.clone()]);
+        assert_eq!(
+            client.try_set_guardians(&owner, &with_owner, &1),
+            Err(Ok(HostError::InvalidConfig))
+        );
+        client.set_guardians(&owner, &guardians, &2);
+
+        assert_eq!(
+            client.try_initiate(&outsider, &owner, &new_owner),
+            Err(Ok(HostError::NotGuardian))
+        );
+        let request = client.initiate(&g1, &owner, &new_owner);
+        assert_eq!(request.executable_at, None);
+        assert_eq!(
+            client.try_initiate(&g2, &owner, &new_owner),
+            Err(Ok(HostError::Pending))
+        );
+        assert_eq!(client.try_complete(&owner), Err(Ok(HostError::NotReady)));
+
+        // The owner can veto, and a fresh recovery starts over
+        client.veto(&owner);
+        assert_eq!(client.request(&owner), None);
+        client.initiate(&g1, &owner, &new_owner);
+        assert_eq!(client.approve(&g1, &owner), 1);
+        assert_eq!(client.approve(&g2, &owner), 2);
+        assert_eq!(
+            client.request(&owner).unwrap().executable_at,
+            Some(1_000 + RECOVERY_DELAY_SECS)
+        );
+
+        env.ledger().set_timestamp(1_000 + RECOVERY_DELAY_SECS - 1);
+        assert_eq!(client.try_complete(&owner), Err(Ok(HostError::NotReady)));
+        env.ledger().set_timestamp(1_000 + RECOVERY_DELAY_SECS);
+        assert_eq!(client.complete(&owner), new_owner);
+        assert_eq!(client.try_complete(&owner), Err(Ok(HostError::NotFound)));
+
+        // Another prefix keeps its own guardians
+        assert_eq!(client.other_config(&owner), None);
+    }
+}
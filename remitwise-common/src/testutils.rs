@@ -0,0 +1,21 @@
+//! Mock contracts for tests of the contracts built on this crate, enabled by
+//! the `testutils` feature.
+
+use soroban_sdk::{contract, contractimpl, Env, String};
+
+/// Price oracle quoting the rates set with `set_rate`; other currencies are
+/// unquoted
+#[contract]
+pub struct MockPriceOracle;
+
+#[contractimpl]
+impl MockPriceOracle {
+    /// Quote `rate` (scaled by `RATE_SCALE`) for `currency`
+    pub fn set_rate(env: Env, currency: String, rate: i128) {
+        env.storage().instance().set(&currency, &rate);
+    }
+
+    pub fn get_rate(env: Env, currency: String) -> Option<i128> {
+        env.storage().instance().get(&currency)
+    }
+}
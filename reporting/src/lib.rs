@@ -1,9 +1,10 @@
 #![no_std]
 use soroban_sdk::{
-    contract, contractclient, contractimpl, contracttype, symbol_short, Address, Env, Map, Vec,
+    contract, contractclient, contractimpl, contracttype, symbol_short, Address, BytesN, Env, Map,
+    Vec,
 };
 
-use remitwise_common::Category;
+use remitwise_common::{Category, CoverageType, MAX_PAGE_LIMIT};
 
 // Storage TTL constants for active data
 const INSTANCE_LIFETIME_THRESHOLD: u32 = 17280; // ~1 day
@@ -208,8 +209,8 @@ pub struct StorageStats {
 
 #[contractclient(name = "RemittanceSplitClient")]
 pub trait RemittanceSplitTrait {
-    fn get_split(env: &Env) -> Vec<u32>;
-    fn calculate_split(env: Env, total_amount: i128) -> Vec<i128>;
+    fn get_split(env: &Env, owner: Address) -> Vec<u32>;
+    fn calculate_split(env: Env, owner: Address, total_amount: i128) -> Vec<i128>;
 }
 
 #[contractclient(name = "SavingsGoalsClient")]
@@ -220,9 +221,9 @@ pub trait SavingsGoalsTrait {
 
 #[contractclient(name = "BillPaymentsClient")]
 pub trait BillPaymentsTrait {
-    fn get_unpaid_bills(env: Env, owner: Address) -> Vec<Bill>;
+    fn get_unpaid_bills(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage;
     fn get_total_unpaid(env: Env, owner: Address) -> i128;
-    fn get_all_bills(env: Env) -> Vec<Bill>;
+    fn get_all_bills_for_owner(env: Env, owner: Address, cursor: u32, limit: u32) -> BillPage;
 }
 
 #[contractclient(name = "InsuranceClient")]
@@ -231,7 +232,9 @@ pub trait InsuranceTrait {
     fn get_total_monthly_premium(env: Env, owner: Address) -> i128;
 }
 
-// Data structures from other contracts (needed for client traits)
+// Data structures from other contracts (needed for client traits). They must
+// match the contracts' types field for field, or the cross-contract calls fail
+// to decode.
 
 #[contracttype]
 #[derive(Clone)]
@@ -244,6 +247,28 @@ pub struct SavingsGoal {
     pub target_date: u64,
     pub locked: bool,
     pub unlock_date: Option<u64>,
+    pub tags: Vec<soroban_sdk::String>,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum BillStatus {
+    Pending = 1,
+    PartiallyPaid = 2,
+    Paid = 3,
+    Overdue = 4,
+    Disputed = 5,
+    Cancelled = 6,
+    Archived = 7,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct EscalationConfig {
+    pub bps_per_cycle: u32,
+    pub step_amount: i128,
+    pub step_every_cycles: u32,
 }
 
 #[contracttype]
@@ -252,15 +277,55 @@ pub struct Bill {
     pub id: u32,
     pub owner: Address,
     pub name: soroban_sdk::String,
+    pub external_ref: Option<soroban_sdk::String>,
     pub amount: i128,
     pub due_date: u64,
     pub recurring: bool,
     pub frequency_days: u32,
     pub paid: bool,
+    pub status: BillStatus,
     pub created_at: u64,
     pub paid_at: Option<u64>,
     pub schedule_id: Option<u32>,
     pub currency: soroban_sdk::String,
+    pub fiat: bool,
+    pub escalation: EscalationConfig,
+    pub cycle: u32,
+    pub escalation_applied: i128,
+    pub payee: Option<Address>,
+    pub overdue_level: u32,
+    pub service_suspended: bool,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct BillPage {
+    pub items: Vec<Bill>,
+    pub next_cursor: u32,
+    pub count: u32,
+}
+
+#[contracttype]
+#[derive(Clone, Copy, Debug, Eq, PartialEq)]
+#[repr(u32)]
+pub enum Relationship {
+    Primary = 1,
+    Spouse = 2,
+    Child = 3,
+    Parent = 4,
+    Sibling = 5,
+    Other = 6,
+}
+
+#[contracttype]
+#[derive(Clone)]
+pub struct InsuredMember {
+    pub id: u32,
+    pub name_hash: BytesN<32>,
+    pub relationship: Relationship,
+    pub address: Option<Address>,
+    pub sub_limit: i128,
+    pub claimed: i128,
 }
 
 #[contracttype]
@@ -268,13 +333,19 @@ pub struct Bill {
 pub struct InsurancePolicy {
     pub id: u32,
     pub owner: Address,
+    pub co_owner: Option<Address>,
     pub name: soroban_sdk::String,
-    pub coverage_type: soroban_sdk::String,
+    pub external_ref: Option<soroban_sdk::String>,
+    pub coverage_type: CoverageType,
     pub monthly_premium: i128,
     pub coverage_amount: i128,
     pub active: bool,
     pub next_payment_date: u64,
     pub schedule_id: Option<u32>,
+    pub tags: Vec<soroban_sdk::String>,
+    pub created_at: u64,
+    pub members: Vec<InsuredMember>,
+    pub total_claimed: i128,
 }
 
 #[contracttype]
@@ -384,7 +455,7 @@ impl ReportingContract {
     /// Generate remittance summary report
     pub fn get_remittance_summary(
         env: Env,
-        _user: Address,
+        user: Address,
         total_amount: i128,
         period_start: u64,
         period_end: u64,
@@ -396,8 +467,8 @@ impl ReportingContract {
             .expect("Contract addresses not configured");
 
         let split_client = RemittanceSplitClient::new(&env, &addresses.remittance_split);
-        let split_percentages = split_client.get_split();
-        let split_amounts = split_client.calculate_split(&total_amount);
+        let split_percentages = split_client.get_split(&user);
+        let split_amounts = split_client.calculate_split(&user, &total_amount);
 
         let mut breakdown = Vec::new(&env);
         let categories = [
@@ -471,12 +542,17 @@ impl ReportingContract {
     }
 
     /// Generate bill payment compliance report
+    ///
+    /// `user` must authorize the call: the bills are read with the bill
+    /// contract's `get_all_bills_for_owner`, which only serves the owner.
     pub fn get_bill_compliance_report(
         env: Env,
         user: Address,
         period_start: u64,
         period_end: u64,
     ) -> BillComplianceReport {
+        user.require_auth();
+
         let addresses: ContractAddresses = env
             .storage()
             .instance()
@@ -484,7 +560,6 @@ impl ReportingContract {
             .expect("Contract addresses not configured");
 
         let bill_client = BillPaymentsClient::new(&env, &addresses.bill_payments);
-        let all_bills = bill_client.get_all_bills();
 
         let mut total_bills = 0u32;
         let mut paid_bills = 0u32;
@@ -496,36 +571,36 @@ impl ReportingContract {
 
         let current_time = env.ledger().timestamp();
 
-        for bill in all_bills.iter() {
-            if bill.owner != user {
-                continue;
-            }
-
-            // Filter by period
-            if bill.created_at < period_start || bill.created_at > period_end {
-                continue;
-            }
-
-            total_bills += 1;
-            total_amount += bill.amount;
+        let mut cursor = 0u32;
+        loop {
+            let page = bill_client.get_all_bills_for_owner(&user, &cursor, &MAX_PAGE_LIMIT);
+            for bill in page.items.iter() {
+                // Filter by period
+                if bill.created_at < period_start || bill.created_at > period_end {
+                    continue;
+                }
 
-            if bill.paid {
-                paid_bills += 1;
-                paid_amount += bill.amount;
-            } else {
-                unpaid_bills += 1;
-                unpaid_amount += bill.amount;
-                if bill.due_date < current_time {
-                    overdue_bills += 1;
+                total_bills += 1;
+                total_amount += bill.amount;
+
+                if bill.paid {
+                    paid_bills += 1;
+                    paid_amount += bill.amount;
+                } else {
+                    unpaid_bills += 1;
+                    unpaid_amount += bill.amount;
+                    if bill.due_date < current_time {
+                        overdue_bills += 1;
+                    }
                 }
             }
+            if page.next_cursor == 0 {
+                break;
+            }
+            cursor = page.next_cursor;
         }
 
-        let compliance_percentage = if total_bills > 0 {
-            (paid_bills * 100) / total_bills
-        } else {
-            100
-        };
+        let compliance_percentage = (paid_bills * 100).checked_div(total_bills).unwrap_or(100);
 
         BillComplianceReport {
             total_bills,
@@ -614,19 +689,27 @@ impl ReportingContract {
 
         // Bills score (0-40 points)
         let bill_client = BillPaymentsClient::new(&env, &addresses.bill_payments);
-        let unpaid_bills = bill_client.get_unpaid_bills(&user);
-        let bills_score = if unpaid_bills.is_empty() {
-            40
-        } else {
-            let overdue_count = unpaid_bills
+        let mut has_unpaid = false;
+        let mut has_overdue = false;
+        let mut cursor = 0u32;
+        loop {
+            let page = bill_client.get_unpaid_bills(&user, &cursor, &MAX_PAGE_LIMIT);
+            has_unpaid |= !page.items.is_empty();
+            has_overdue |= page
+                .items
                 .iter()
-                .filter(|b| b.due_date < env.ledger().timestamp())
-                .count();
-            if overdue_count == 0 {
-                35 // Has unpaid but none overdue
-            } else {
-                20 // Has overdue bills
+                .any(|b| b.due_date < env.ledger().timestamp());
+            if has_overdue || page.next_cursor == 0 {
+                break;
             }
+            cursor = page.next_cursor;
+        }
+        let bills_score = if !has_unpaid {
+            40
+        } else if !has_overdue {
+            35 // Has unpaid but none overdue
+        } else {
+            20 // Has overdue bills
         };
 
         // Insurance score (0-20 points)
@@ -644,7 +727,8 @@ impl ReportingContract {
         }
     }
 
-    /// Generate comprehensive financial health report
+    /// Generate comprehensive financial health report. `user` must authorize
+    /// the call, as for `get_bill_compliance_report`.
     pub fn get_financial_health_report(
         env: Env,
         user: Address,
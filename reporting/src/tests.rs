@@ -7,14 +7,14 @@ use soroban_sdk::{
 
 // Mock contracts for testing
 mod remittance_split {
-    use soroban_sdk::{contract, contractimpl, Env, Vec};
+    use soroban_sdk::{contract, contractimpl, Address, Env, Vec};
 
     #[contract]
     pub struct RemittanceSplit;
 
     #[contractimpl]
     impl RemittanceSplit {
-        pub fn get_split(env: &Env) -> Vec<u32> {
+        pub fn get_split(env: &Env, _owner: Address) -> Vec<u32> {
             let mut split = Vec::new(env);
             split.push_back(50);
             split.push_back(30);
@@ -23,7 +23,7 @@ mod remittance_split {
             split
         }
 
-        pub fn calculate_split(env: Env, total_amount: i128) -> Vec<i128> {
+        pub fn calculate_split(env: Env, _owner: Address, total_amount: i128) -> Vec<i128> {
             let mut amounts = Vec::new(&env);
             amounts.push_back(total_amount * 50 / 100);
             amounts.push_back(total_amount * 30 / 100);
@@ -55,6 +55,7 @@ mod savings_goals {
                 target_date: 1735689600,
                 locked: true,
                 unlock_date: None,
+                tags: Vec::new(&env),
             });
             goals.push_back(SavingsGoal {
                 id: 2,
@@ -65,6 +66,7 @@ mod savings_goals {
                 target_date: 1735689600,
                 locked: true,
                 unlock_date: None,
+                tags: Vec::new(&env),
             });
             goals
         }
@@ -76,79 +78,82 @@ mod savings_goals {
 }
 
 mod bill_payments {
-    use crate::{Bill, BillPaymentsTrait};
-    use soroban_sdk::{
-        contract, contractimpl, testutils::Address as _, Address, Env, String as SorobanString, Vec,
-    };
+    use crate::{Bill, BillPage, BillPaymentsTrait, BillStatus, EscalationConfig};
+    use soroban_sdk::{contract, contractimpl, Address, Env, String as SorobanString, Vec};
+
+    fn bill(env: &Env, id: u32, owner: &Address, name: &str, amount: i128, paid: bool) -> Bill {
+        Bill {
+            id,
+            owner: owner.clone(),
+            name: SorobanString::from_str(env, name),
+            external_ref: None,
+            amount,
+            due_date: 1735689600,
+            recurring: true,
+            frequency_days: 30,
+            paid,
+            status: if paid {
+                BillStatus::Paid
+            } else {
+                BillStatus::Pending
+            },
+            created_at: 1704067200,
+            paid_at: if paid { Some(1704153600) } else { None },
+            schedule_id: None,
+            currency: SorobanString::from_str(env, "XLM"),
+            fiat: false,
+            escalation: EscalationConfig {
+                bps_per_cycle: 0,
+                step_amount: 0,
+                step_every_cycles: 0,
+            },
+            cycle: 0,
+            escalation_applied: 0,
+            payee: None,
+            overdue_level: 0,
+            service_suspended: false,
+        }
+    }
+
+    fn page(items: Vec<Bill>) -> BillPage {
+        BillPage {
+            count: items.len(),
+            items,
+            next_cursor: 0,
+        }
+    }
 
     #[contract]
     pub struct BillPayments;
 
     #[contractimpl]
     impl BillPaymentsTrait for BillPayments {
-        fn get_unpaid_bills(_env: Env, _owner: Address) -> Vec<Bill> {
-            let env = _env;
+        fn get_unpaid_bills(env: Env, owner: Address, _cursor: u32, _limit: u32) -> BillPage {
             let mut bills = Vec::new(&env);
-            bills.push_back(Bill {
-                id: 1,
-                owner: _owner,
-                name: SorobanString::from_str(&env, "Electricity"),
-                amount: 100,
-                due_date: 1735689600,
-                recurring: true,
-                frequency_days: 30,
-                paid: false,
-                created_at: 1704067200,
-                paid_at: None,
-                schedule_id: None,
-                currency: SorobanString::from_str(&env, "XLM"),
-            });
-            bills
+            bills.push_back(bill(&env, 1, &owner, "Electricity", 100, false));
+            page(bills)
         }
 
         fn get_total_unpaid(_env: Env, _owner: Address) -> i128 {
             100
         }
 
-        fn get_all_bills(_env: Env) -> Vec<Bill> {
-            let env = _env;
-            let owner = Address::generate(&env);
+        fn get_all_bills_for_owner(
+            env: Env,
+            owner: Address,
+            _cursor: u32,
+            _limit: u32,
+        ) -> BillPage {
             let mut bills = Vec::new(&env);
-            bills.push_back(Bill {
-                id: 1,
-                owner: owner.clone(),
-                name: SorobanString::from_str(&env, "Electricity"),
-                amount: 100,
-                due_date: 1735689600,
-                recurring: true,
-                frequency_days: 30,
-                paid: false,
-                created_at: 1704067200,
-                paid_at: None,
-                schedule_id: None,
-                currency: SorobanString::from_str(&env, "XLM"),
-            });
-            bills.push_back(Bill {
-                id: 2,
-                owner,
-                name: SorobanString::from_str(&env, "Water"),
-                amount: 50,
-                due_date: 1735689600,
-                recurring: true,
-                frequency_days: 30,
-                paid: true,
-                created_at: 1704067200,
-                paid_at: Some(1704153600),
-                schedule_id: None,
-                currency: SorobanString::from_str(&env, "XLM"),
-            });
-            bills
+            bills.push_back(bill(&env, 1, &owner, "Electricity", 100, false));
+            bills.push_back(bill(&env, 2, &owner, "Water", 50, true));
+            page(bills)
         }
     }
 }
 
 mod insurance {
-    use crate::{InsurancePolicy, InsuranceTrait};
+    use crate::{CoverageType, InsurancePolicy, InsuranceTrait};
     use soroban_sdk::{contract, contractimpl, Address, Env, String as SorobanString, Vec};
 
     #[contract]
@@ -167,13 +172,19 @@ mod insurance {
             policies.push_back(InsurancePolicy {
                 id: 1,
                 owner: _owner,
+                co_owner: None,
                 name: SorobanString::from_str(&env, "Health Insurance"),
-                coverage_type: SorobanString::from_str(&env, "health"),
+                external_ref: None,
+                coverage_type: CoverageType::Health,
                 monthly_premium: 200,
                 coverage_amount: 50000,
                 active: true,
                 next_payment_date: 1735689600,
                 schedule_id: None,
+                tags: Vec::new(&env),
+                created_at: 1704067200,
+                members: Vec::new(&env),
+                total_claimed: 0,
             });
             crate::PolicyPage {
                 items: policies,
@@ -402,8 +413,13 @@ fn test_get_bill_compliance_report() {
 
     let report = client.get_bill_compliance_report(&user, &period_start, &period_end);
 
-    // Note: Mock returns bills for a generated address, so user-specific filtering will show 0
-    // This is expected behavior for the test
+    assert_eq!(report.total_bills, 2);
+    assert_eq!(report.paid_bills, 1);
+    assert_eq!(report.unpaid_bills, 1);
+    assert_eq!(report.overdue_bills, 0);
+    assert_eq!(report.paid_amount, 50);
+    assert_eq!(report.unpaid_amount, 100);
+    assert_eq!(report.compliance_percentage, 50);
     assert_eq!(report.period_start, period_start);
     assert_eq!(report.period_end, period_end);
 }
@@ -0,0 +1,30 @@
+//! Setup helpers for tests of this contract and of crates built on it,
+//! enabled by the `testutils` feature.
+
+use crate::{SavingsGoalContract, SavingsGoalContractClient};
+use soroban_sdk::{Address, Env, String};
+
+/// Register and initialize the contract
+pub fn register(env: &Env) -> SavingsGoalContractClient<'_> {
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(env, &contract_id);
+    client.init();
+    client
+}
+
+/// Create a goal of `target` for `owner`, due in a year, holding `funded`.
+/// Auths must be mocked.
+pub fn funded_goal(
+    env: &Env,
+    client: &SavingsGoalContractClient,
+    owner: &Address,
+    target: i128,
+    funded: i128,
+) -> u32 {
+    let target_date = env.ledger().timestamp() + 365 * 86400;
+    let goal_id = client.create_goal(owner, &String::from_str(env, "Goal"), &target, &target_date);
+    if funded > 0 {
+        client.add_to_goal(owner, &goal_id, &funded);
+    }
+    goal_id
+}
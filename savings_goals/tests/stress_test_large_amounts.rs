@@ -172,7 +172,7 @@ fn test_withdraw_from_goal_with_large_amount() {
     env.mock_all_auths();
     let remaining = client.withdraw_from_goal(&owner, &goal_id, &(large_amount / 2));
 
-    assert_eq!(remaining, large_amount / 2);
+    assert_eq!(remaining, large_amount - large_amount / 2);
 }
 #[test]
 fn test_goal_completion_with_large_amounts() {
@@ -412,7 +412,7 @@ fn test_sequential_large_operations() {
     env.mock_all_auths();
 
     // Test with progressively larger amounts
-    let amounts_to_test = vec![
+    let amounts_to_test = [
         i128::MAX / 1000,
         i128::MAX / 500,
         i128::MAX / 200,
@@ -520,7 +520,7 @@ fn test_export_import_snapshot_with_large_amounts() {
 
     // Export snapshot
     env.mock_all_auths();
-    let snapshot = client.export_snapshot(&owner);
+    let snapshot = client.export_snapshot(&owner, &0, &0);
 
     assert_eq!(snapshot.goals.len(), 2);
     assert_eq!(snapshot.goals.get(0).unwrap().target_amount, large_target);
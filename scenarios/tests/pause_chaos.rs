@@ -0,0 +1,181 @@
+//! Pausing contracts in the middle of scheduled flows.
+//!
+//! Each scenario pauses a single function and then the whole contract while
+//! payments are due, runs the keepers through the pause window, and unpauses.
+//! Calls made during the window must fail with the pause error of the
+//! contract, keepers must leave schedules untouched, and the first keeper run
+//! afterwards must execute each schedule exactly once and record the periods
+//! that passed as missed.
+
+use bill_payments::Error as BillError;
+use insurance::{pause_functions as insurance_pause, InsuranceError};
+use savings_goals::pause_functions as savings_pause;
+use soroban_sdk::{
+    testutils::{Address as _, Ledger},
+    Address, String,
+};
+
+const DAY: u64 = 86400;
+const MONTH: u64 = 30 * DAY;
+
+#[test]
+fn test_premium_schedule_survives_pause_window() {
+    let env = scenarios::tests::setup_env();
+    let start = env.ledger().timestamp();
+    let client = insurance::testutils::register(&env);
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    let keeper = Address::generate(&env);
+    client.set_pause_admin(&admin, &admin);
+
+    let policy_id = insurance::testutils::health_policy(&env, &client, &owner, 100, 10_000);
+    let first_due = start + DAY;
+    let schedule_id = client.create_premium_schedule(&owner, &policy_id, &first_due, &MONTH);
+
+    // Premium payments paused as the first premium falls due
+    client.pause_function(&admin, &insurance_pause::PAY_PREMIUM);
+    env.ledger().set_timestamp(first_due);
+    assert_eq!(
+        client.execute_due_premium_schedules(&keeper).executed.len(),
+        0
+    );
+    assert_eq!(
+        client.try_pay_premium(&owner, &policy_id),
+        Err(Ok(InsuranceError::FunctionPaused))
+    );
+    client.unpause_function(&admin, &insurance_pause::PAY_PREMIUM);
+
+    // Then the whole contract, across two more due dates
+    client.pause(&admin);
+    env.ledger().set_timestamp(first_due + 2 * MONTH);
+    assert_eq!(
+        client.execute_due_premium_schedules(&keeper).executed.len(),
+        0
+    );
+    assert_eq!(
+        client.try_pay_premium(&owner, &policy_id),
+        Err(Ok(InsuranceError::ContractPaused))
+    );
+    let held = client.get_premium_schedule(&schedule_id).unwrap();
+    assert_eq!((held.next_due, held.missed_count), (first_due, 0));
+    assert_eq!(client.get_premiums_paid(&policy_id), 0);
+    client.unpause(&admin);
+
+    // One payment for the run that executes, the rest counted as missed
+    assert_eq!(
+        client.execute_due_premium_schedules(&keeper).executed.len(),
+        1
+    );
+    assert_eq!(
+        client.execute_due_premium_schedules(&keeper).executed.len(),
+        0
+    );
+    let schedule = client.get_premium_schedule(&schedule_id).unwrap();
+    assert_eq!(schedule.missed_count, 2);
+    assert_eq!(schedule.next_due, first_due + 3 * MONTH);
+    assert_eq!(client.get_premiums_paid(&policy_id), 100);
+}
+
+#[test]
+fn test_savings_schedule_survives_pause_window() {
+    let env = scenarios::tests::setup_env();
+    let start = env.ledger().timestamp();
+    let client = savings_goals::testutils::register(&env);
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    client.set_pause_admin(&admin, &admin);
+
+    let goal_id = client.create_goal(
+        &owner,
+        &String::from_str(&env, "School Fees"),
+        &10_000,
+        &(start + 365 * DAY),
+    );
+    let first_due = start + DAY;
+    let schedule_id = client.create_savings_schedule(&owner, &goal_id, &250, &first_due, &MONTH);
+
+    client.pause_function(&admin, &savings_pause::ADD_TO_GOAL);
+    env.ledger().set_timestamp(first_due);
+    assert_eq!(client.execute_due_savings_schedules().len(), 0);
+    assert!(client.try_add_to_goal(&owner, &goal_id, &50).is_err());
+    client.unpause_function(&admin, &savings_pause::ADD_TO_GOAL);
+
+    client.pause(&admin);
+    env.ledger().set_timestamp(first_due + 2 * MONTH);
+    assert_eq!(client.execute_due_savings_schedules().len(), 0);
+    assert!(client.try_add_to_goal(&owner, &goal_id, &50).is_err());
+    assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 0);
+    client.unpause(&admin);
+
+    assert_eq!(client.execute_due_savings_schedules().len(), 1);
+    assert_eq!(client.execute_due_savings_schedules().len(), 0);
+    let schedule = client.get_savings_schedule(&schedule_id).unwrap();
+    assert_eq!(schedule.missed_count, 2);
+    assert_eq!(schedule.next_due, first_due + 3 * MONTH);
+    assert_eq!(client.get_goal(&goal_id).unwrap().current_amount, 250);
+}
+
+#[test]
+fn test_recurring_bill_paid_once_after_pause_window() {
+    let env = scenarios::tests::setup_env();
+    let start = env.ledger().timestamp();
+    let client = bill_payments::testutils::register(&env);
+    let admin = Address::generate(&env);
+    let owner = Address::generate(&env);
+    client.set_pause_admin(&admin, &admin);
+
+    let bill_id = client.create_bill(
+        &owner,
+        &String::from_str(&env, "Electricity"),
+        &150,
+        &(start + DAY),
+        &true,
+        &30,
+        &None,
+        &String::from_str(&env, "XLM"),
+    );
+
+    client.pause_function(&admin, &bill_payments::pause_functions::PAY_BILL);
+    env.ledger().set_timestamp(start + 2 * DAY);
+    assert_eq!(
+        client.try_pay_bill(&owner, &bill_id),
+        Err(Ok(BillError::FunctionPaused))
+    );
+    client.unpause_function(&admin, &bill_payments::pause_functions::PAY_BILL);
+
+    // The overdue keeper keeps running while paused but settles nothing
+    client.pause(&admin);
+    env.ledger().set_timestamp(start + 40 * DAY);
+    client.escalate_overdue(&0);
+    assert_eq!(
+        client.try_pay_bill(&owner, &bill_id),
+        Err(Ok(BillError::ContractPaused))
+    );
+    assert_eq!(
+        client.try_create_bill(
+            &owner,
+            &String::from_str(&env, "Water"),
+            &40,
+            &(start + 45 * DAY),
+            &false,
+            &0,
+            &None,
+            &String::from_str(&env, "XLM"),
+        ),
+        Err(Ok(BillError::ContractPaused))
+    );
+    let unpaid = client.get_unpaid_bills(&owner, &0, &10);
+    assert_eq!(unpaid.count, 1);
+    assert!(!client.get_bill(&bill_id).unwrap().is_paid());
+    client.unpause(&admin);
+
+    // Paying settles the bill and generates the next instance exactly once
+    client.pay_bill(&owner, &bill_id);
+    assert_eq!(
+        client.try_pay_bill(&owner, &bill_id),
+        Err(Ok(BillError::BillAlreadyPaid))
+    );
+    let unpaid = client.get_unpaid_bills(&owner, &0, &10);
+    assert_eq!(unpaid.count, 1);
+    assert_ne!(unpaid.items.get(0).unwrap().id, bill_id);
+}
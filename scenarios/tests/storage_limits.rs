@@ -0,0 +1,226 @@
+//! Where single-instance-Map storage breaks.
+//!
+//! Savings goals, bills and policies each live in one `Map` under a single
+//! instance storage key, so every entity lands in the contract's one instance
+//! ledger entry and every create call reads and rewrites the whole map. These
+//! scenarios create a thousand entities of each kind in one `Env`, measuring
+//! at checkpoints the size of the instance entry and the cost of the next
+//! create call, and derive two limits:
+//!
+//! - the entity count at which the instance entry outgrows `MAX_ENTRY_BYTES`,
+//!   after which the network rejects the write;
+//! - the count at which a create call would exhaust `MAX_TX_CPU`,
+//!   extrapolated from the measured per-entity growth because driving the
+//!   map that far takes minutes.
+//!
+//! Measured with soroban-sdk 21 (the entry limit is the first 50-entity
+//! checkpoint over it, the budget limit is extrapolated):
+//!
+//! | kind     | bytes each | CPU each | entry limit | budget limit |
+//! |----------|-----------:|---------:|------------:|-------------:|
+//! | goals    |        340 |   37,878 |         200 |       ~2,637 |
+//! | bills    |        736 |   85,553 |         100 |       ~1,167 |
+//! | policies |        504 |   61,334 |         150 |       ~1,628 |
+//!
+//! The scenarios assert the direction a fix has to take: the instance entry
+//! stays under `INSTANCE_ENTRY_BOUND` however many entities there are, and a
+//! create call stays within `MAX_TX_CPU`. The current layout fails that by
+//! design, so the scenarios are `#[ignore]`d as known failures; run them with
+//! `cargo test -p scenarios --test storage_limits -- --ignored` to see where
+//! each kind stands; a failure reports the figures in the table above. Once a
+//! kind moves to persistent per-entity keys its scenario should pass and lose
+//! the `#[ignore]`.
+
+use bill_payments::{BillPayments, BillPaymentsClient};
+use insurance::{Insurance, InsuranceClient};
+use remitwise_common::CoverageType;
+use savings_goals::{SavingsGoalContract, SavingsGoalContractClient};
+use soroban_sdk::testutils::storage::Instance as _;
+use soroban_sdk::testutils::{Address as _, EnvTestConfig, Ledger, LedgerInfo};
+use soroban_sdk::xdr::ToXdr;
+use soroban_sdk::{Address, Env, String};
+
+/// Network limit on the size of one contract data entry
+const MAX_ENTRY_BYTES: u32 = 64 * 1024;
+/// Network limit on CPU instructions per transaction, also the default
+/// budget of a test `Env`
+const MAX_TX_CPU: u64 = 100_000_000;
+/// Size the instance entry must stay under whatever the entity count: the
+/// contract's configuration and counters, not its collections
+const INSTANCE_ENTRY_BOUND: u32 = 16 * 1024;
+
+const ENTITIES: u32 = 1_000;
+const CHECKPOINT_EVERY: u32 = 50;
+
+/// State after `entities` creates, with the cost of the create that follows
+struct Checkpoint {
+    entities: u32,
+    entry_bytes: u32,
+    cpu: u64,
+    mem: u64,
+}
+
+struct Findings {
+    bytes_per_entity: u32,
+    cpu_per_entity: u64,
+    /// First checkpoint at which the instance entry is over `MAX_ENTRY_BYTES`
+    entry_limit_at: Option<u32>,
+    /// Entity count at which a create call would cost `MAX_TX_CPU`
+    budget_limit_at: u64,
+}
+
+fn stress_env() -> Env {
+    let env = Env::new_with_config(EnvTestConfig {
+        capture_snapshot_at_drop: false,
+    });
+    env.mock_all_auths();
+    env.ledger().set(LedgerInfo {
+        timestamp: 1704067200, // Jan 1, 2024
+        protocol_version: 20,
+        sequence_number: 1,
+        network_id: [0; 32],
+        base_reserve: 10,
+        min_temp_entry_ttl: 10,
+        min_persistent_entry_ttl: 10,
+        max_entry_ttl: 3110400,
+    });
+    env
+}
+
+/// Call `create` `ENTITIES` times, measuring every `CHECKPOINT_EVERY` calls
+fn probe(env: &Env, contract_id: &Address, mut create: impl FnMut()) -> Vec<Checkpoint> {
+    let mut checkpoints = Vec::new();
+    let mut budget = env.budget();
+    budget.reset_unlimited();
+    for entities in 0..ENTITIES {
+        if entities % CHECKPOINT_EVERY != 0 {
+            create();
+            continue;
+        }
+        let entry_bytes = env.as_contract(contract_id, || {
+            env.storage().instance().all().to_xdr(env).len()
+        });
+        budget.reset_unlimited();
+        budget.reset_tracker();
+        create();
+        checkpoints.push(Checkpoint {
+            entities,
+            entry_bytes,
+            cpu: budget.cpu_instruction_cost(),
+            mem: budget.memory_bytes_cost(),
+        });
+    }
+    checkpoints
+}
+
+fn findings(checkpoints: &[Checkpoint]) -> Findings {
+    let (first, last) = (&checkpoints[1], &checkpoints[checkpoints.len() - 1]);
+    let span = last.entities - first.entities;
+    let bytes_per_entity = last.entry_bytes.saturating_sub(first.entry_bytes) / span;
+    let cpu_per_entity = last.cpu.saturating_sub(first.cpu) / span as u64;
+    Findings {
+        bytes_per_entity,
+        cpu_per_entity,
+        entry_limit_at: checkpoints
+            .iter()
+            .find(|checkpoint| checkpoint.entry_bytes > MAX_ENTRY_BYTES)
+            .map(|checkpoint| checkpoint.entities),
+        budget_limit_at: last.entities as u64
+            + MAX_TX_CPU.saturating_sub(last.cpu) / cpu_per_entity.max(1),
+    }
+}
+
+/// Check that the instance entry stayed under `INSTANCE_ENTRY_BOUND` and
+/// create calls within `MAX_TX_CPU` at every checkpoint, reporting the
+/// findings when one did not
+fn assert_limits(kind: &str, checkpoints: &[Checkpoint]) {
+    let found = findings(checkpoints);
+    let report = format!(
+        "{} take {} bytes and {} cpu each; instance entry over {} bytes at {:?} entities, \
+         budget exhausted at ~{}",
+        kind,
+        found.bytes_per_entity,
+        found.cpu_per_entity,
+        MAX_ENTRY_BYTES,
+        found.entry_limit_at,
+        found.budget_limit_at
+    );
+
+    for checkpoint in checkpoints {
+        assert!(
+            checkpoint.entry_bytes < INSTANCE_ENTRY_BOUND,
+            "{} instance entry is {} bytes at {} entities ({})",
+            kind,
+            checkpoint.entry_bytes,
+            checkpoint.entities,
+            report
+        );
+        assert!(
+            checkpoint.cpu < MAX_TX_CPU,
+            "create at {} {} costs {} cpu / {} bytes mem ({})",
+            checkpoint.entities,
+            kind,
+            checkpoint.cpu,
+            checkpoint.mem,
+            report
+        );
+    }
+}
+
+#[test]
+#[ignore = "known failure: the collection still lives in one instance Map"]
+fn test_savings_goals_instance_map_limits() {
+    let env = stress_env();
+    let contract_id = env.register_contract(None, SavingsGoalContract);
+    let client = SavingsGoalContractClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let name = String::from_str(&env, "Goal");
+
+    let checkpoints = probe(&env, &contract_id, || {
+        client.create_goal(&owner, &name, &1_000, &2_000_000_000);
+    });
+    assert_limits("goals", &checkpoints);
+    assert_eq!(client.get_all_goals(&owner).len(), ENTITIES);
+}
+
+#[test]
+#[ignore = "known failure: the collection still lives in one instance Map"]
+fn test_bill_payments_instance_map_limits() {
+    let env = stress_env();
+    let contract_id = env.register_contract(None, BillPayments);
+    let client = BillPaymentsClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let name = String::from_str(&env, "Bill");
+    let currency = String::from_str(&env, "XLM");
+
+    let checkpoints = probe(&env, &contract_id, || {
+        client.create_bill(
+            &owner,
+            &name,
+            &100,
+            &2_000_000_000,
+            &false,
+            &0,
+            &None,
+            &currency,
+        );
+    });
+    assert_limits("bills", &checkpoints);
+    assert_eq!(client.get_total_unpaid(&owner), 100 * ENTITIES as i128);
+}
+
+#[test]
+#[ignore = "known failure: the collection still lives in one instance Map"]
+fn test_insurance_instance_map_limits() {
+    let env = stress_env();
+    let contract_id = env.register_contract(None, Insurance);
+    let client = InsuranceClient::new(&env, &contract_id);
+    let owner = Address::generate(&env);
+    let name = String::from_str(&env, "Policy");
+
+    let checkpoints = probe(&env, &contract_id, || {
+        client.create_policy(&owner, &name, &CoverageType::Health, &100, &10_000, &None);
+    });
+    assert_limits("policies", &checkpoints);
+    assert_eq!(client.get_active_policy_count(&owner), ENTITIES);
+}